@@ -9,6 +9,7 @@ from typing import Optional
 import logging
 
 from vault_manager import get_vault_manager
+from vault_tools import ASSET_ADDRESSES
 
 logger = logging.getLogger(__name__)
 
@@ -152,11 +153,17 @@ async def deposit_to_vault(request: DepositRequest):
         )
 
     try:
+        if request.asset not in ASSET_ADDRESSES:
+            raise HTTPException(
+                status_code=400, detail=f"Asset {request.asset} not supported"
+            )
+
         # Convert to stroops
         amount_stroops = int(request.amount * 1e7)
 
         result = await vault.deposit_to_vault(
             user_address=request.wallet_address,
+            asset=ASSET_ADDRESSES[request.asset],
             amount=amount_stroops,
             user_keypair=None,  # User will sign via wallet
         )
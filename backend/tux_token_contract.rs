@@ -1,5 +1,8 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, token, Address, Env, Map};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Map,
+    String,
+};
 
 /// TUX Token - Tuxedo Universal eXchange Token
 /// Governance, access control, and yield distribution token for the Tuxedo ecosystem
@@ -16,7 +19,18 @@ const BRONZE_TIER: i128 = 100_i128 * 10i128.pow(TOKEN_DECIMALS); // 100 TUX
 const SILVER_TIER: i128 = 1000_i128 * 10i128.pow(TOKEN_DECIMALS); // 1000 TUX
 const GOLD_TIER: i128 = 10000_i128 * 10i128.pow(TOKEN_DECIMALS); // 10000 TUX
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TuxError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientStake = 4,
+    InvalidThresholds = 5,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[contracttype]
 pub enum ParticipationTier {
     Free = 0,
@@ -32,7 +46,14 @@ impl TuxToken {
     /// # Arguments
     /// * `admin` - The admin address that can mint tokens
     /// * `initial_supply` - Initial supply to mint to admin
-    pub fn initialize(env: Env, admin: Address, initial_supply: i128) {
+    pub fn initialize(env: Env, admin: Address, initial_supply: i128) -> Result<(), TuxError> {
+        if env.storage().instance().has(&symbol!("admin")) {
+            return Err(TuxError::AlreadyInitialized);
+        }
+        if initial_supply < 0 {
+            return Err(TuxError::InvalidAmount);
+        }
+
         // Store admin
         env.storage().instance().set(&symbol!("admin"), &admin);
 
@@ -59,6 +80,13 @@ impl TuxToken {
             let token_client = token::Client::new(&env, &env.current_contract_address());
             token_client.mint(&admin, &initial_supply);
         }
+
+        env.events().publish(
+            (symbol_short!("tux"), symbol_short!("init")),
+            (admin, initial_supply),
+        );
+
+        Ok(())
     }
 
     /// Get the token name
@@ -83,23 +111,24 @@ impl TuxToken {
     }
 
     /// Get the current admin
-    pub fn get_admin(env: Env) -> Address {
+    pub fn get_admin(env: Env) -> Result<Address, TuxError> {
         env.storage().instance()
             .get(&symbol!("admin"))
-            .unwrap()
+            .ok_or(TuxError::NotInitialized)
     }
 
     /// Get user's current participation tier based on TUX balance
     pub fn get_user_tier(env: Env, user: Address) -> ParticipationTier {
         let token_client = token::Client::new(&env, &env.current_contract_address());
         let balance = token_client.balance(&user);
+        let (bronze, silver, gold) = Self::get_tier_thresholds(env);
 
         // Check balance against tier thresholds
-        if balance >= GOLD_TIER {
+        if balance >= gold {
             ParticipationTier::Gold
-        } else if balance >= SILVER_TIER {
+        } else if balance >= silver {
             ParticipationTier::Silver
-        } else if balance >= BRONZE_TIER {
+        } else if balance >= bronze {
             ParticipationTier::Bronze
         } else {
             ParticipationTier::Free
@@ -115,9 +144,14 @@ impl TuxToken {
     /// Stake TUX tokens for additional benefits
     ///
     /// # Arguments
+    /// * `user` - The staker, who must authorize the call
     /// * `amount` - Amount of TUX to stake
-    pub fn stake(env: Env, amount: i128) {
-        let user = env.current_contract_address();
+    pub fn stake(env: Env, user: Address, amount: i128) -> Result<(), TuxError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(TuxError::InvalidAmount);
+        }
+
         let token_client = token::Client::new(&env, &env.current_contract_address());
 
         // Transfer tokens to contract (staking)
@@ -128,32 +162,53 @@ impl TuxToken {
             .get(&symbol!("staking"))
             .unwrap_or(Map::new(&env));
 
-        let current_stake = staking.get(user).unwrap_or(0);
-        staking.set(user, current_stake + amount);
+        let new_stake = staking.get(user.clone()).unwrap_or(0) + amount;
+        staking.set(user.clone(), new_stake);
         env.storage().instance().set(&symbol!("staking"), &staking);
+
+        env.events().publish(
+            (symbol_short!("tux"), symbol_short!("stake")),
+            (user, amount, new_stake),
+        );
+
+        Ok(())
     }
 
     /// Unstake TUX tokens
     ///
     /// # Arguments
+    /// * `user` - The staker, who must authorize the call
     /// * `amount` - Amount of TUX to unstake
-    pub fn unstake(env: Env, amount: i128) {
-        let user = env.current_contract_address();
+    pub fn unstake(env: Env, user: Address, amount: i128) -> Result<(), TuxError> {
+        user.require_auth();
+        if amount <= 0 {
+            return Err(TuxError::InvalidAmount);
+        }
 
         // Update staking amount
         let mut staking: Map<Address, i128> = env.storage().instance()
             .get(&symbol!("staking"))
             .unwrap_or(Map::new(&env));
 
-        let current_stake = staking.get(user).unwrap_or(0);
-        require!(current_stake >= amount, "Insufficient staked amount");
+        let current_stake = staking.get(user.clone()).unwrap_or(0);
+        if current_stake < amount {
+            return Err(TuxError::InsufficientStake);
+        }
 
-        staking.set(user, current_stake - amount);
+        let new_stake = current_stake - amount;
+        staking.set(user.clone(), new_stake);
         env.storage().instance().set(&symbol!("staking"), &staking);
 
         // Transfer tokens back to user
         let token_client = token::Client::new(&env, &env.current_contract_address());
         token_client.transfer(&env.current_contract_address(), &user, &amount);
+
+        env.events().publish(
+            (symbol_short!("tux"), symbol_short!("unstake")),
+            (user, amount, new_stake),
+        );
+
+        Ok(())
     }
 
     /// Get user's staked amount
@@ -169,12 +224,49 @@ impl TuxToken {
     /// # Arguments
     /// * `to` - Address to mint tokens to
     /// * `amount` - Amount to mint
-    pub fn mint(env: Env, to: Address, amount: i128) {
-        let admin = Self::get_admin(env.clone());
-        require!(env.current_contract_address() == admin, "Admin only");
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), TuxError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        if amount <= 0 {
+            return Err(TuxError::InvalidAmount);
+        }
 
         let token_client = token::Client::new(&env, &env.current_contract_address());
         token_client.mint(&to, &amount);
+
+        Ok(())
+    }
+
+    /// Update the tier thresholds (admin only)
+    ///
+    /// # Arguments
+    /// * `bronze` - Minimum balance for the Bronze tier
+    /// * `silver` - Minimum balance for the Silver tier
+    /// * `gold` - Minimum balance for the Gold tier
+    pub fn set_tier_thresholds(
+        env: Env,
+        bronze: i128,
+        silver: i128,
+        gold: i128,
+    ) -> Result<(), TuxError> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+
+        // Thresholds must be positive and strictly increasing
+        if bronze <= 0 || silver <= bronze || gold <= silver {
+            return Err(TuxError::InvalidThresholds);
+        }
+
+        env.storage().instance().set(&symbol!("bronze_tier"), &bronze);
+        env.storage().instance().set(&symbol!("silver_tier"), &silver);
+        env.storage().instance().set(&symbol!("gold_tier"), &gold);
+
+        env.events().publish(
+            (symbol_short!("tux"), symbol_short!("tiers")),
+            (bronze, silver, gold),
+        );
+
+        Ok(())
     }
 
     /// Get tier thresholds
@@ -206,14 +298,102 @@ impl TuxToken {
 
     /// Check if this contract is the admin (useful for access control in other contracts)
     pub fn is_admin(env: Env, address: Address) -> bool {
-        let admin = Self::get_admin(env);
-        address == admin
+        Self::get_admin(env).map_or(false, |admin| address == admin)
     }
 }
 
-// Utility functions
-fn require<T: Into<bool>>(condition: T, message: &str) {
-    if !condition.into() {
-        panic!("{}", message);
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Events},
+        vec, IntoVal,
+    };
+
+    fn setup(env: &Env) -> (TuxTokenClient<'_>, Address) {
+        let contract_id = env.register(TuxToken, ());
+        let client = TuxTokenClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        (client, admin)
+    }
+
+    #[test]
+    fn test_initialize_emits_event() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+
+        client.initialize(&admin, &0);
+
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    client.address.clone(),
+                    (symbol_short!("tux"), symbol_short!("init")).into_val(&env),
+                    (admin.clone(), 0i128).into_val(&env),
+                ),
+            ]
+        );
+        assert_eq!(client.get_admin(), admin);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_initialize_errors() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+
+        assert_eq!(client.try_get_admin(), Err(Ok(TuxError::NotInitialized)));
+        assert_eq!(client.try_initialize(&admin, &-1), Err(Ok(TuxError::InvalidAmount)));
+
+        client.initialize(&admin, &0);
+        assert_eq!(
+            client.try_initialize(&admin, &0),
+            Err(Ok(TuxError::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_stake_errors() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, admin) = setup(&env);
+        let user = Address::generate(&env);
+        client.initialize(&admin, &0);
+
+        assert_eq!(client.try_stake(&user, &0), Err(Ok(TuxError::InvalidAmount)));
+        assert_eq!(client.try_unstake(&user, &-5), Err(Ok(TuxError::InvalidAmount)));
+        assert_eq!(client.try_unstake(&user, &1), Err(Ok(TuxError::InsufficientStake)));
+    }
+
+    #[test]
+    fn test_set_tier_thresholds() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, admin) = setup(&env);
+        client.initialize(&admin, &0);
+
+        assert_eq!(
+            client.try_set_tier_thresholds(&0, &SILVER_TIER, &GOLD_TIER),
+            Err(Ok(TuxError::InvalidThresholds))
+        );
+        assert_eq!(
+            client.try_set_tier_thresholds(&BRONZE_TIER, &BRONZE_TIER, &GOLD_TIER),
+            Err(Ok(TuxError::InvalidThresholds))
+        );
+
+        client.set_tier_thresholds(&1, &2, &3);
+        assert_eq!(client.get_tier_thresholds(), (1, 2, 3));
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    client.address.clone(),
+                    (symbol_short!("tux"), symbol_short!("tiers")).into_val(&env),
+                    (1i128, 2i128, 3i128).into_val(&env),
+                ),
+            ]
+        );
+    }
+}
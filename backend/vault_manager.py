@@ -41,15 +41,22 @@ class VaultManager:
         self.network = Network.PUBLIC_NETWORK_PASSPHRASE
 
     async def deposit_to_vault(
-        self, user_address: str, amount: int, user_keypair: Optional[Keypair] = None
+        self,
+        user_address: str,
+        asset: str,
+        amount: int,
+        user_keypair: Optional[Keypair] = None,
+        min_shares_out: int = 0,
     ) -> Dict:
         """
-        User deposits USDC to vault and receives TUX0 shares
+        User deposits an accepted asset to vault and receives TUX0 shares
 
         Args:
             user_address: User's Stellar address
-            amount: Amount of USDC to deposit (with 7 decimals)
+            asset: Asset contract address (USDC, or another accepted asset)
+            amount: Amount of the asset to deposit (with 7 decimals)
             user_keypair: User's keypair for signing (if available)
+            min_shares_out: Fewest shares to accept, 0 for no slippage bound
 
         Returns:
             dict: Transaction result with shares minted
@@ -70,7 +77,9 @@ class VaultManager:
                     function_name="deposit",
                     parameters=[
                         scval.to_address(user_address),  # user
+                        scval.to_address(asset),  # asset
                         scval.to_int128(amount),  # amount
+                        scval.to_int128(min_shares_out),  # min_shares_out
                     ],
                 )
                 .set_timeout(300)
@@ -102,7 +111,11 @@ class VaultManager:
             return {"status": "error", "message": str(e)}
 
     async def withdraw_from_vault(
-        self, user_address: str, shares: int, user_keypair: Optional[Keypair] = None
+        self,
+        user_address: str,
+        shares: int,
+        user_keypair: Optional[Keypair] = None,
+        min_assets_out: int = 0,
     ) -> Dict:
         """
         User burns TUX0 shares and withdraws USDC
@@ -111,6 +124,7 @@ class VaultManager:
             user_address: User's Stellar address
             shares: Number of shares to burn (with 7 decimals)
             user_keypair: User's keypair for signing (if available)
+            min_assets_out: Least USDC to accept, 0 for no slippage bound
 
         Returns:
             dict: Transaction result with assets withdrawn
@@ -131,6 +145,7 @@ class VaultManager:
                     parameters=[
                         scval.to_address(user_address),  # user
                         scval.to_int128(shares),  # shares
+                        scval.to_int128(min_assets_out),  # min_assets_out
                     ],
                 )
                 .set_timeout(300)
@@ -178,13 +193,15 @@ class VaultManager:
             # Build agent execute transaction
             source_account = self.server.load_account(self.agent.public_key)
 
-            # Create Strategy struct
+            # Create Strategy struct; Blend request type 2 supplies collateral,
+            # 3 withdraws it
             strategy_struct = scval.to_struct(
                 {
                     "action": scval.to_symbol(strategy),
                     "pool": scval.to_address(pool),
                     "asset": scval.to_address(asset),
                     "amount": scval.to_int128(amount),
+                    "request_type": scval.to_uint32(2 if strategy == "supply" else 3),
                 }
             )
 
@@ -197,7 +214,10 @@ class VaultManager:
                 .append_invoke_contract_function_op(
                     contract_id=self.contract_id,
                     function_name="agent_execute",
-                    parameters=[strategy_struct],
+                    parameters=[
+                        scval.to_address(self.agent.public_key),  # agent
+                        strategy_struct,
+                    ],
                 )
                 .set_timeout(300)
                 .build()
@@ -221,11 +241,13 @@ class VaultManager:
 
     async def distribute_yield(self, caller_keypair: Keypair) -> Dict:
         """
-        Distribute yield: 98% stays in vault, 2% to platform
+        Distribute yield: the caller earns the keeper reward, the platform
+        takes 2% of the rest and the remainder stays in the vault
         Anyone can call this function
 
         Args:
-            caller_keypair: Keypair of the address calling this function
+            caller_keypair: Keypair of the address calling this function,
+                which is paid the keeper reward
 
         Returns:
             dict: Transaction result
@@ -242,7 +264,9 @@ class VaultManager:
                 .append_invoke_contract_function_op(
                     contract_id=self.contract_id,
                     function_name="distribute_yield",
-                    parameters=[],
+                    parameters=[
+                        scval.to_address(caller_keypair.public_key),  # caller
+                    ],
                 )
                 .set_timeout(300)
                 .build()
@@ -47,6 +47,7 @@ async def deposit_to_vault(amount: float, asset: str = "USDC") -> str:
     # For now, return instructions for user to complete deposit
     result = await vault.deposit_to_vault(
         user_address="",  # Would be provided by connected wallet
+        asset=asset_address,
         amount=amount_stroops,
         user_keypair=None,  # User signs via wallet
     )
@@ -0,0 +1,242 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, Symbol, Vec,
+};
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const TOKEN: Symbol = symbol_short!("TOKEN");
+const MERKLE_ROOT: Symbol = symbol_short!("ROOT");
+const DEADLINE: Symbol = symbol_short!("DEADLINE");
+const TOTAL_CLAIMED: Symbol = symbol_short!("CLAIMED");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AirdropError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InvalidProof = 4,
+    AlreadyClaimed = 5,
+    ClaimWindowClosed = 6,
+    ClaimWindowOpen = 7,
+    NothingToSweep = 8,
+}
+
+/// Leaf committed to by the merkle root: `sha256(xdr(claimer) || amount as 16 big-endian bytes)`.
+/// Off-chain tooling must build the tree from the same encoding.
+pub fn leaf_hash(env: &Env, claimer: &Address, amount: i128) -> BytesN<32> {
+    let mut data = claimer.clone().to_xdr(env);
+    data.extend_from_array(&amount.to_be_bytes());
+    env.crypto().sha256(&data).into()
+}
+
+/// Hash a pair of nodes in sorted order so proofs don't need to carry left/right flags
+pub fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a.to_array() <= b.to_array() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let mut data = Bytes::from_array(env, &first.to_array());
+    data.extend_from_array(&second.to_array());
+    env.crypto().sha256(&data).into()
+}
+
+// ============ Airdrop Contract ============
+#[contract]
+pub struct TuxAirdrop;
+
+#[contractimpl]
+impl TuxAirdrop {
+    /// Initialize the airdrop. The contract pays claims from its own `token` balance,
+    /// which the admin funds after deployment.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        merkle_root: BytesN<32>,
+        deadline: u64,
+    ) -> Result<(), AirdropError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(AirdropError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&TOKEN, &token);
+        env.storage().instance().set(&MERKLE_ROOT, &merkle_root);
+        env.storage().instance().set(&DEADLINE, &deadline);
+        env.storage().instance().set(&TOTAL_CLAIMED, &0i128);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("airdrop"), symbol_short!("init")),
+            (token, merkle_root, deadline),
+        );
+
+        Ok(())
+    }
+
+    /// Replace the merkle root (admin only). Leaves already claimed stay claimed.
+    pub fn set_merkle_root(env: Env, merkle_root: BytesN<32>) -> Result<(), AirdropError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&MERKLE_ROOT, &merkle_root);
+
+        // Emit root update event
+        env.events().publish(
+            (symbol_short!("airdrop"), symbol_short!("root")),
+            merkle_root,
+        );
+
+        Ok(())
+    }
+
+    /// Claim `amount` for `claimer` by proving the leaf `sha256(claimer || amount)`
+    /// is part of the merkle tree. Each leaf can be claimed once, before the deadline.
+    pub fn claim(
+        env: Env,
+        claimer: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<i128, AirdropError> {
+        claimer.require_auth();
+
+        let deadline: u64 = env
+            .storage()
+            .instance()
+            .get(&DEADLINE)
+            .ok_or(AirdropError::NotInitialized)?;
+        if env.ledger().timestamp() > deadline {
+            return Err(AirdropError::ClaimWindowClosed);
+        }
+
+        if amount <= 0 {
+            return Err(AirdropError::InvalidAmount);
+        }
+
+        let leaf = leaf_hash(&env, &claimer, amount);
+        if Self::is_claimed(env.clone(), leaf.clone()) {
+            return Err(AirdropError::AlreadyClaimed);
+        }
+
+        // Walk the proof up to the root
+        let mut node = leaf.clone();
+        for sibling in proof.iter() {
+            node = hash_pair(&env, &node, &sibling);
+        }
+        let root: BytesN<32> = env.storage().instance().get(&MERKLE_ROOT).unwrap();
+        if node != root {
+            return Err(AirdropError::InvalidProof);
+        }
+
+        // Mark the leaf before paying out
+        env.storage()
+            .persistent()
+            .set(&Self::claimed_key(&leaf), &true);
+        let total_claimed = Self::get_total_claimed(env.clone());
+        env.storage()
+            .instance()
+            .set(&TOTAL_CLAIMED, &(total_claimed + amount));
+
+        let token: Address = env.storage().instance().get(&TOKEN).unwrap();
+        token::TokenClient::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &claimer,
+            &amount,
+        );
+
+        // Emit claim event
+        env.events().publish(
+            (symbol_short!("airdrop"), symbol_short!("claim")),
+            (claimer, amount),
+        );
+
+        Ok(amount)
+    }
+
+    /// Send the unclaimed remainder to `to` once the claim window has closed (admin only)
+    pub fn sweep(env: Env, to: Address) -> Result<i128, AirdropError> {
+        Self::require_admin(&env)?;
+
+        let deadline: u64 = env.storage().instance().get(&DEADLINE).unwrap();
+        if env.ledger().timestamp() <= deadline {
+            return Err(AirdropError::ClaimWindowOpen);
+        }
+
+        let token: Address = env.storage().instance().get(&TOKEN).unwrap();
+        let token_client = token::TokenClient::new(&env, &token);
+        let this = env.current_contract_address();
+        let remaining = token_client.balance(&this);
+
+        if remaining <= 0 {
+            return Err(AirdropError::NothingToSweep);
+        }
+
+        token_client.transfer(&this, &to, &remaining);
+
+        // Emit sweep event
+        env.events().publish(
+            (symbol_short!("airdrop"), symbol_short!("sweep")),
+            (to, remaining),
+        );
+
+        Ok(remaining)
+    }
+
+    /// Check whether the leaf has been claimed
+    pub fn is_claimed(env: Env, leaf: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Self::claimed_key(&leaf))
+            .unwrap_or(false)
+    }
+
+    /// Get the current merkle root
+    pub fn get_merkle_root(env: Env) -> BytesN<32> {
+        env.storage().instance().get(&MERKLE_ROOT).unwrap()
+    }
+
+    /// Get the claim deadline (ledger timestamp)
+    pub fn get_deadline(env: Env) -> u64 {
+        env.storage().instance().get(&DEADLINE).unwrap()
+    }
+
+    /// Get the total amount claimed so far
+    pub fn get_total_claimed(env: Env) -> i128 {
+        env.storage().instance().get(&TOTAL_CLAIMED).unwrap_or(0)
+    }
+
+    /// Get the airdropped token
+    pub fn get_token(env: Env) -> Address {
+        env.storage().instance().get(&TOKEN).unwrap()
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn require_admin(env: &Env) -> Result<(), AirdropError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(AirdropError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn claimed_key(leaf: &BytesN<32>) -> (Symbol, BytesN<32>) {
+        (symbol_short!("claimed"), leaf.clone())
+    }
+}
+
+mod test;
@@ -0,0 +1,158 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+
+const UNIT: i128 = 10_000_000;
+const DEADLINE: u64 = 30 * 86_400;
+
+struct AirdropTest<'a> {
+    env: Env,
+    airdrop: TuxAirdropClient<'a>,
+    tux: TuxTokenClient<'a>,
+    admin: Address,
+    claimers: [Address; 3],
+    amounts: [i128; 3],
+    leaves: [BytesN<32>; 3],
+}
+
+impl<'a> AirdropTest<'a> {
+    /// Hand-built tree over three leaves:
+    ///
+    ///            root
+    ///          /      \
+    ///       h01        l2
+    ///      /   \
+    ///    l0     l1
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let claimers = [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        let amounts = [100 * UNIT, 250 * UNIT, 50 * UNIT];
+        let leaves = [
+            leaf_hash(&env, &claimers[0], amounts[0]),
+            leaf_hash(&env, &claimers[1], amounts[1]),
+            leaf_hash(&env, &claimers[2], amounts[2]),
+        ];
+        let h01 = hash_pair(&env, &leaves[0], &leaves[1]);
+        let root = hash_pair(&env, &h01, &leaves[2]);
+
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &(1_000_000 * UNIT));
+
+        let airdrop = TuxAirdropClient::new(&env, &env.register(TuxAirdrop, ()));
+        airdrop.initialize(&admin, &tux.address, &root, &DEADLINE);
+        tux.transfer(&admin, &airdrop.address, &(1_000 * UNIT));
+
+        AirdropTest {
+            airdrop,
+            tux,
+            admin,
+            claimers,
+            amounts,
+            leaves,
+            env,
+        }
+    }
+
+    fn proof(&self, i: usize) -> Vec<BytesN<32>> {
+        match i {
+            0 => vec![&self.env, self.leaves[1].clone(), self.leaves[2].clone()],
+            1 => vec![&self.env, self.leaves[0].clone(), self.leaves[2].clone()],
+            _ => vec![
+                &self.env,
+                hash_pair(&self.env, &self.leaves[0], &self.leaves[1]),
+            ],
+        }
+    }
+}
+
+#[test]
+fn test_claim_with_valid_proofs() {
+    let t = AirdropTest::setup();
+
+    for i in 0..3 {
+        assert!(!t.airdrop.is_claimed(&t.leaves[i]));
+        let claimed = t.airdrop.claim(&t.claimers[i], &t.amounts[i], &t.proof(i));
+        assert_eq!(claimed, t.amounts[i]);
+        assert_eq!(t.tux.balance(&t.claimers[i]), t.amounts[i]);
+        assert!(t.airdrop.is_claimed(&t.leaves[i]));
+    }
+
+    assert_eq!(t.airdrop.get_total_claimed(), 400 * UNIT);
+    assert_eq!(t.tux.balance(&t.airdrop.address), 600 * UNIT);
+}
+
+#[test]
+fn test_claim_rejects_invalid_proof_and_double_claim() {
+    let t = AirdropTest::setup();
+
+    // Another claimer's proof doesn't fit this leaf
+    assert_eq!(
+        t.airdrop
+            .try_claim(&t.claimers[0], &t.amounts[0], &t.proof(2)),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+
+    // Inflating the amount changes the leaf
+    assert_eq!(
+        t.airdrop
+            .try_claim(&t.claimers[0], &(t.amounts[0] + 1), &t.proof(0)),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+
+    // A non-listed address can't reuse a valid proof
+    let outsider = Address::generate(&t.env);
+    assert_eq!(
+        t.airdrop.try_claim(&outsider, &t.amounts[0], &t.proof(0)),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+
+    t.airdrop.claim(&t.claimers[0], &t.amounts[0], &t.proof(0));
+    assert_eq!(
+        t.airdrop
+            .try_claim(&t.claimers[0], &t.amounts[0], &t.proof(0)),
+        Err(Ok(AirdropError::AlreadyClaimed))
+    );
+    assert_eq!(t.tux.balance(&t.claimers[0]), t.amounts[0]);
+}
+
+#[test]
+fn test_sweep_after_deadline() {
+    let t = AirdropTest::setup();
+    let treasury = Address::generate(&t.env);
+
+    t.airdrop.claim(&t.claimers[1], &t.amounts[1], &t.proof(1));
+
+    // Remainder stays put while the window is open
+    assert_eq!(
+        t.airdrop.try_sweep(&treasury),
+        Err(Ok(AirdropError::ClaimWindowOpen))
+    );
+
+    t.env.ledger().with_mut(|li| li.timestamp = DEADLINE + 1);
+
+    assert_eq!(
+        t.airdrop
+            .try_claim(&t.claimers[0], &t.amounts[0], &t.proof(0)),
+        Err(Ok(AirdropError::ClaimWindowClosed))
+    );
+
+    assert_eq!(t.airdrop.sweep(&treasury), 750 * UNIT);
+    assert_eq!(t.tux.balance(&treasury), 750 * UNIT);
+    assert_eq!(t.tux.balance(&t.airdrop.address), 0);
+    assert_eq!(
+        t.airdrop.try_sweep(&t.admin),
+        Err(Ok(AirdropError::NothingToSweep))
+    );
+}
@@ -0,0 +1,107 @@
+#![no_std]
+
+//! Typed adapter for Blend lending pools.
+//!
+//! Mirrors the parts of the Blend pool interface the vault relies on
+//! (`submit`, `claim`, `get_positions`) so callers speak the pool's real
+//! request format instead of moving tokens by hand.
+
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contractclient, contracttype, symbol_short, vec, Address, Env, IntoVal, Map, Vec,
+};
+
+// ============ Request Types ============
+/// Request types accepted by `Pool::submit`, matching Blend's numbering
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RequestType {
+    Supply = 0,
+    Withdraw = 1,
+    SupplyCollateral = 2,
+    WithdrawCollateral = 3,
+    Borrow = 4,
+    Repay = 5,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Request {
+    pub request_type: u32,
+    pub address: Address, // asset address for supply/withdraw requests
+    pub amount: i128,
+}
+
+/// Positions held by an address, keyed by reserve index
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Positions {
+    pub liabilities: Map<u32, i128>,
+    pub collateral: Map<u32, i128>,
+    pub supply: Map<u32, i128>,
+}
+
+// ============ Pool Interface ============
+#[contractclient(name = "PoolClient")]
+pub trait Pool {
+    /// Submit a batch of requests, pulling funds from `spender` and sending
+    /// withdrawals to `to`. Returns the resulting positions of `from`.
+    fn submit(env: Env, from: Address, spender: Address, to: Address, requests: Vec<Request>)
+        -> Positions;
+
+    /// Claim emissions for the given reserve token ids, returning the amount claimed
+    fn claim(env: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128;
+
+    /// Get the positions held by an address
+    fn get_positions(env: Env, address: Address) -> Positions;
+}
+
+// ============ Request Helpers ============
+/// Build a request supplying `amount` of `asset` as collateral
+pub fn supply_collateral(asset: &Address, amount: i128) -> Request {
+    Request {
+        request_type: RequestType::SupplyCollateral as u32,
+        address: asset.clone(),
+        amount,
+    }
+}
+
+/// Build a request withdrawing `amount` of `asset` from collateral
+pub fn withdraw_collateral(asset: &Address, amount: i128) -> Request {
+    Request {
+        request_type: RequestType::WithdrawCollateral as u32,
+        address: asset.clone(),
+        amount,
+    }
+}
+
+/// Supply plus collateral across every reserve: what the holder has lent out
+pub fn lent_total(positions: &Positions) -> i128 {
+    positions.supply.values().iter().sum::<i128>()
+        + positions.collateral.values().iter().sum::<i128>()
+}
+
+/// Reserve token id used by `claim` for the supply side of a reserve
+pub fn claim_supply_token_id(reserve_index: u32) -> u32 {
+    reserve_index * 2 + 1
+}
+
+/// Pre-authorize the pool pulling `amount` of `asset` from the current contract.
+///
+/// `submit` transfers supplied funds from the spender inside the pool's own
+/// invocation, which is not a direct call from us, so the transfer must be
+/// authorized explicitly before calling the pool.
+pub fn authorize_supply(env: &Env, pool: &Address, asset: &Address, amount: i128) {
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: asset.clone(),
+                fn_name: symbol_short!("transfer"),
+                args: (env.current_contract_address(), pool.clone(), amount).into_val(env),
+            },
+            sub_invocations: vec![env],
+        }),
+    ]);
+}
@@ -0,0 +1,260 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+use tuxedo_dex_adapter as dex;
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const KEEPER: Symbol = symbol_short!("KEEPER");
+const USDC: Symbol = symbol_short!("USDC");
+const TUX_TOKEN: Symbol = symbol_short!("TUX_TKN");
+const AMM: Symbol = symbol_short!("AMM");
+const DAILY_LIMIT: Symbol = symbol_short!("DAY_LIM");
+const WINDOW: Symbol = symbol_short!("WINDOW");
+const TOTAL_SPENT: Symbol = symbol_short!("T_SPENT");
+const TOTAL_BURNED: Symbol = symbol_short!("T_BURNED");
+
+const DAY_IN_SECONDS: u64 = 86_400;
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BuybackError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    InvalidAmount = 4,
+    InsufficientBalance = 5,
+    RateLimited = 6,
+    DeadlinePassed = 7,
+    SlippageExceeded = 8,
+}
+
+// ============ Data Structures ============
+/// USDC spent in the current 24h rate-limit window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendWindow {
+    pub start: u64,
+    pub spent: i128,
+}
+
+// ============ TUX Buyback Contract ============
+#[contract]
+pub struct TuxBuyback;
+
+#[contractimpl]
+impl TuxBuyback {
+    /// Initialize the buyback contract.
+    ///
+    /// Arguments:
+    /// - admin: Admin address that manages the keeper and limits
+    /// - keeper: Address allowed to trigger buybacks
+    /// - usdc_asset: Fee income asset held by this contract
+    /// - tux_token: TUX token that is bought and burned
+    /// - amm: AMM router used for the swap
+    /// - daily_limit: Maximum USDC spent per 24h window
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        keeper: Address,
+        usdc_asset: Address,
+        tux_token: Address,
+        amm: Address,
+        daily_limit: i128,
+    ) -> Result<(), BuybackError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(BuybackError::AlreadyInitialized);
+        }
+
+        if daily_limit <= 0 {
+            return Err(BuybackError::InvalidAmount);
+        }
+
+        // Set initial state
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&KEEPER, &keeper);
+        env.storage().instance().set(&USDC, &usdc_asset);
+        env.storage().instance().set(&TUX_TOKEN, &tux_token);
+        env.storage().instance().set(&AMM, &amm);
+        env.storage().instance().set(&DAILY_LIMIT, &daily_limit);
+        env.storage().instance().set(&TOTAL_SPENT, &0i128);
+        env.storage().instance().set(&TOTAL_BURNED, &0i128);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("buyback"), symbol_short!("init")),
+            (admin, keeper, daily_limit),
+        );
+
+        Ok(())
+    }
+
+    /// Swap `amount_in` of the held USDC into TUX and burn it (keeper only).
+    /// Returns the amount of TUX burned.
+    pub fn execute_buyback(
+        env: Env,
+        caller: Address,
+        amount_in: i128,
+        min_tux_out: i128,
+        deadline: u64,
+    ) -> Result<i128, BuybackError> {
+        let keeper: Address = Self::read(&env, &KEEPER)?;
+        if caller != keeper {
+            return Err(BuybackError::NotAuthorized);
+        }
+
+        caller.require_auth();
+
+        // Validate amounts
+        if amount_in <= 0 || min_tux_out < 0 {
+            return Err(BuybackError::InvalidAmount);
+        }
+
+        if env.ledger().timestamp() > deadline {
+            return Err(BuybackError::DeadlinePassed);
+        }
+
+        let usdc_asset: Address = Self::read(&env, &USDC)?;
+        let tux_token: Address = Self::read(&env, &TUX_TOKEN)?;
+        let amm: Address = Self::read(&env, &AMM)?;
+        let this = env.current_contract_address();
+
+        let usdc_client = token::TokenClient::new(&env, &usdc_asset);
+        if usdc_client.balance(&this) < amount_in {
+            return Err(BuybackError::InsufficientBalance);
+        }
+
+        // Enforce the rolling daily limit
+        let window = Self::spend(&env, amount_in)?;
+
+        // Check the quote first so a bad rate fails with a typed error
+        if dex::quote_exact_in(&env, &amm, &usdc_asset, &tux_token, amount_in) < min_tux_out {
+            return Err(BuybackError::SlippageExceeded);
+        }
+
+        // Swap, measuring the TUX that actually arrived
+        let tux_client = token::TokenClient::new(&env, &tux_token);
+        let balance_before = tux_client.balance(&this);
+        dex::swap_exact_in(&env, &amm, &usdc_asset, &tux_token, amount_in, min_tux_out, deadline);
+        let tux_bought = tux_client.balance(&this) - balance_before;
+
+        if tux_bought < min_tux_out {
+            return Err(BuybackError::SlippageExceeded);
+        }
+
+        // Burn everything bought
+        tux_client.burn(&this, &tux_bought);
+
+        // Update running totals
+        let total_spent: i128 = env.storage().instance().get(&TOTAL_SPENT).unwrap_or(0) + amount_in;
+        let total_burned: i128 = env.storage().instance().get(&TOTAL_BURNED).unwrap_or(0) + tux_bought;
+        env.storage().instance().set(&TOTAL_SPENT, &total_spent);
+        env.storage().instance().set(&TOTAL_BURNED, &total_burned);
+        env.storage().instance().set(&WINDOW, &window);
+
+        // Emit buyback event
+        env.events().publish(
+            (symbol_short!("buyback"), symbol_short!("burn")),
+            (amount_in, tux_bought, total_burned),
+        );
+
+        Ok(tux_bought)
+    }
+
+    /// Set the keeper allowed to trigger buybacks (admin only)
+    pub fn set_keeper(env: Env, keeper: Address) -> Result<(), BuybackError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&KEEPER, &keeper);
+
+        // Emit keeper update event
+        env.events().publish(
+            (symbol_short!("buyback"), symbol_short!("keeper")),
+            keeper,
+        );
+
+        Ok(())
+    }
+
+    /// Set the maximum USDC spent per 24h window (admin only)
+    pub fn set_daily_limit(env: Env, daily_limit: i128) -> Result<(), BuybackError> {
+        Self::require_admin(&env)?;
+
+        if daily_limit <= 0 {
+            return Err(BuybackError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&DAILY_LIMIT, &daily_limit);
+
+        // Emit limit update event
+        env.events().publish(
+            (symbol_short!("buyback"), symbol_short!("limit")),
+            daily_limit,
+        );
+
+        Ok(())
+    }
+
+    /// Get total TUX burned by buybacks
+    pub fn get_total_burned(env: Env) -> i128 {
+        env.storage().instance().get(&TOTAL_BURNED).unwrap_or(0)
+    }
+
+    /// Get total USDC spent on buybacks
+    pub fn get_total_spent(env: Env) -> i128 {
+        env.storage().instance().get(&TOTAL_SPENT).unwrap_or(0)
+    }
+
+    /// Get the USDC that can still be spent in the current 24h window
+    pub fn get_remaining_daily(env: Env) -> i128 {
+        let limit: i128 = env.storage().instance().get(&DAILY_LIMIT).unwrap_or(0);
+        let window = Self::current_window(&env);
+        limit - window.spent
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Result<Address, BuybackError> {
+        Self::read(&env, &ADMIN)
+    }
+
+    // ============ Internal Helper Functions ============
+
+    /// Read a required instance entry
+    fn read(env: &Env, key: &Symbol) -> Result<Address, BuybackError> {
+        env.storage().instance().get(key).ok_or(BuybackError::NotInitialized)
+    }
+
+    /// Require authorization from the stored admin
+    fn require_admin(env: &Env) -> Result<Address, BuybackError> {
+        let admin = Self::read(env, &ADMIN)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Current rate-limit window, reset once 24h have passed since it opened
+    fn current_window(env: &Env) -> SpendWindow {
+        let now = env.ledger().timestamp();
+        match env.storage().instance().get::<_, SpendWindow>(&WINDOW) {
+            Some(window) if now < window.start + DAY_IN_SECONDS => window,
+            _ => SpendWindow { start: now, spent: 0 },
+        }
+    }
+
+    /// Account `amount` against the daily limit, returning the updated window
+    fn spend(env: &Env, amount: i128) -> Result<SpendWindow, BuybackError> {
+        let limit: i128 = env.storage().instance().get(&DAILY_LIMIT).unwrap_or(0);
+        let mut window = Self::current_window(env);
+        if window.spent + amount > limit {
+            return Err(BuybackError::RateLimited);
+        }
+        window.spent += amount;
+        Ok(window)
+    }
+}
+
+mod test;
@@ -0,0 +1,112 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+use tuxedo_testutils::dex::{MockRouter, MockRouterClient, RATE_SCALE};
+
+const UNIT: i128 = 10_000_000;
+
+struct BuybackTest<'a> {
+    env: Env,
+    buyback: TuxBuybackClient<'a>,
+    usdc: TokenClient<'a>,
+    tux: TuxTokenClient<'a>,
+    keeper: Address,
+}
+
+impl<'a> BuybackTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let keeper = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &(1_000_000 * UNIT));
+
+        // 1 USDC buys 4 TUX
+        let amm = MockRouterClient::new(&env, &env.register(MockRouter, ()));
+        amm.set_rate(&(4 * RATE_SCALE));
+        tux.transfer(&admin, &amm.address, &(100_000 * UNIT));
+
+        let buyback = TuxBuybackClient::new(&env, &env.register(TuxBuyback, ()));
+        buyback.initialize(&admin, &keeper, &usdc_id, &tux.address, &amm.address, &(1_000 * UNIT));
+
+        // Fee income has been routed to the buyback contract
+        StellarAssetClient::new(&env, &usdc_id).mint(&buyback.address, &(5_000 * UNIT));
+
+        BuybackTest {
+            usdc: TokenClient::new(&env, &usdc_id),
+            buyback,
+            tux,
+            keeper,
+            env,
+        }
+    }
+}
+
+#[test]
+fn test_execute_buyback_burns_proceeds() {
+    let t = BuybackTest::setup();
+    let deadline = t.env.ledger().timestamp();
+
+    let burned = t.buyback.execute_buyback(&t.keeper, &(100 * UNIT), &(400 * UNIT), &deadline);
+
+    assert_eq!(burned, 400 * UNIT);
+    assert_eq!(t.tux.balance(&t.buyback.address), 0);
+    assert_eq!(t.usdc.balance(&t.buyback.address), 4_900 * UNIT);
+    assert_eq!(t.buyback.get_total_burned(), 400 * UNIT);
+    assert_eq!(t.buyback.get_total_spent(), 100 * UNIT);
+
+    // Running total accumulates across buybacks
+    t.buyback.execute_buyback(&t.keeper, &(50 * UNIT), &0, &deadline);
+    assert_eq!(t.buyback.get_total_burned(), 600 * UNIT);
+}
+
+#[test]
+fn test_daily_limit_resets_after_window() {
+    let t = BuybackTest::setup();
+    let start = t.env.ledger().timestamp();
+
+    t.buyback.execute_buyback(&t.keeper, &(800 * UNIT), &0, &(start + DAY_IN_SECONDS));
+    assert_eq!(t.buyback.get_remaining_daily(), 200 * UNIT);
+    assert_eq!(
+        t.buyback.try_execute_buyback(&t.keeper, &(200 * UNIT + 1), &0, &(start + DAY_IN_SECONDS)),
+        Err(Ok(BuybackError::RateLimited))
+    );
+
+    // A full day later the window reopens
+    t.env.ledger().with_mut(|li| li.timestamp = start + DAY_IN_SECONDS);
+    assert_eq!(t.buyback.get_remaining_daily(), 1_000 * UNIT);
+    t.buyback.execute_buyback(&t.keeper, &(1_000 * UNIT), &0, &(start + DAY_IN_SECONDS));
+    assert_eq!(t.buyback.get_total_spent(), 1_800 * UNIT);
+}
+
+#[test]
+fn test_buyback_guards() {
+    let t = BuybackTest::setup();
+    let deadline = t.env.ledger().timestamp();
+    let stranger = Address::generate(&t.env);
+
+    assert_eq!(
+        t.buyback.try_execute_buyback(&stranger, &UNIT, &0, &deadline),
+        Err(Ok(BuybackError::NotAuthorized))
+    );
+    assert_eq!(
+        t.buyback.try_execute_buyback(&t.keeper, &UNIT, &(4 * UNIT + 1), &deadline),
+        Err(Ok(BuybackError::SlippageExceeded))
+    );
+    t.env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    assert_eq!(
+        t.buyback.try_execute_buyback(&t.keeper, &UNIT, &0, &deadline),
+        Err(Ok(BuybackError::DeadlinePassed))
+    );
+    assert_eq!(t.buyback.get_total_burned(), 0);
+}
@@ -0,0 +1,10 @@
+//! Scaling constants shared across contracts.
+
+/// 100% expressed in basis points
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Fixed-point scale of 7-decimal values (USDC, TUX, share prices)
+pub const SCALAR_7: i128 = 10_000_000;
+
+/// Fixed-point scale of per-unit reward accumulators
+pub const REWARD_PRECISION: i128 = 1_000_000_000_000;
@@ -0,0 +1,24 @@
+//! Event publishing with per-contract sequence numbers.
+
+use soroban_sdk::{symbol_short, Env, IntoVal, Symbol, Val};
+
+const EVENT_SEQ: Symbol = symbol_short!("EVT_SEQ");
+
+/// Publish `data` under the topics `(contract, action, seq)`.
+///
+/// `seq` counts every event the calling contract has published through this
+/// helper, so indexers can order events and detect gaps. Returns `seq`.
+pub fn publish<D>(env: &Env, contract: Symbol, action: Symbol, data: D) -> u64
+where
+    D: IntoVal<Env, Val>,
+{
+    let seq = last_sequence(env) + 1;
+    env.storage().instance().set(&EVENT_SEQ, &seq);
+    env.events().publish((contract, action, seq), data);
+    seq
+}
+
+/// Sequence number of the last event the calling contract published
+pub fn last_sequence(env: &Env) -> u64 {
+    env.storage().instance().get(&EVENT_SEQ).unwrap_or(0)
+}
@@ -0,0 +1,14 @@
+#![no_std]
+
+//! Shared building blocks for the Tuxedo contracts.
+//!
+//! Keeps fixed-point math, basis-point constants, event publishing, and the
+//! tier-oracle interface in one place so the vault, farming, and token
+//! contracts round, report, and rank users the same way.
+
+pub mod constants;
+pub mod events;
+pub mod math;
+pub mod tiers;
+
+mod test;
@@ -0,0 +1,32 @@
+//! Checked fixed-point arithmetic. Helpers round down unless named `_ceil`.
+
+use crate::constants::BPS_DENOMINATOR;
+
+/// `a * b / denominator` rounded down.
+/// Returns `None` if the product overflows or `denominator` is not positive.
+pub fn muldiv(a: i128, b: i128, denominator: i128) -> Option<i128> {
+    if denominator <= 0 {
+        return None;
+    }
+    a.checked_mul(b)?.checked_div_euclid(denominator)
+}
+
+/// `a * b / denominator` rounded up, for amounts a user must cover in full.
+/// Returns `None` if the result overflows or `denominator` is not positive.
+pub fn muldiv_ceil(a: i128, b: i128, denominator: i128) -> Option<i128> {
+    if denominator <= 0 {
+        return None;
+    }
+    let product = a.checked_mul(b)?;
+    let quotient = product.div_euclid(denominator);
+    if product.rem_euclid(denominator) == 0 {
+        Some(quotient)
+    } else {
+        quotient.checked_add(1)
+    }
+}
+
+/// `bps` basis points of `amount`, rounded down
+pub fn bps_of(amount: i128, bps: u32) -> Option<i128> {
+    muldiv(amount, bps as i128, BPS_DENOMINATOR as i128)
+}
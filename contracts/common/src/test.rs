@@ -0,0 +1,172 @@
+#![cfg(test)]
+
+use crate::{
+    constants::{BPS_DENOMINATOR, SCALAR_7},
+    events,
+    math::{bps_of, muldiv, muldiv_ceil},
+    tiers::{self, TierOracle},
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Events as _},
+    vec, Address, Env, IntoVal,
+};
+
+/// Small deterministic generator so the property checks are reproducible
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Non-negative amount up to 10^8 whole units of a 7-decimal token
+    fn amount(&mut self) -> i128 {
+        (self.next() % (100_000_000 * SCALAR_7 as u64)) as i128
+    }
+
+    fn bps(&mut self) -> u32 {
+        (self.next() % (BPS_DENOMINATOR as u64 + 1)) as u32
+    }
+}
+
+#[test]
+fn test_muldiv_matches_exact_floor() {
+    let mut rng = XorShift(0x5eed);
+    for _ in 0..2_000 {
+        let (a, b) = (rng.amount(), rng.amount() % 1_000_000_000);
+        let d = rng.amount().max(1);
+        let result = muldiv(a, b, d).unwrap();
+
+        // result is the largest integer with result * d <= a * b
+        assert!(result * d <= a * b);
+        assert!((result + 1) * d > a * b);
+    }
+}
+
+#[test]
+fn test_muldiv_identities_and_monotonicity() {
+    let mut rng = XorShift(42);
+    for _ in 0..2_000 {
+        let a = rng.amount();
+        let d = rng.amount().max(1);
+        assert_eq!(muldiv(a, d, d), Some(a));
+        assert_eq!(muldiv(a, 0, d), Some(0));
+
+        let b = rng.amount() % 1_000_000;
+        assert!(muldiv(a, b, d).unwrap() <= muldiv(a + 1, b, d).unwrap());
+        assert!(muldiv(a, b, d + 1).unwrap() <= muldiv(a, b, d).unwrap());
+    }
+}
+
+#[test]
+fn test_muldiv_rejects_overflow_and_bad_denominator() {
+    assert_eq!(muldiv(i128::MAX, 2, 1), None);
+    assert_eq!(muldiv(1, 1, 0), None);
+    assert_eq!(muldiv(1, 1, -1), None);
+    // Negative products round down, not toward zero
+    assert_eq!(muldiv(-3, 1, 2), Some(-2));
+}
+
+#[test]
+fn test_muldiv_ceil_matches_exact_ceiling() {
+    let mut rng = XorShift(0xce11);
+    for _ in 0..2_000 {
+        let (a, b) = (rng.amount(), rng.amount() % 1_000_000_000);
+        let d = rng.amount().max(1);
+        let result = muldiv_ceil(a, b, d).unwrap();
+
+        // result is the smallest integer with result * d >= a * b
+        assert!(result * d >= a * b);
+        assert!((result - 1) * d < a * b);
+        assert!(result - muldiv(a, b, d).unwrap() <= 1);
+    }
+    assert_eq!(muldiv_ceil(i128::MAX, 2, 1), None);
+    assert_eq!(muldiv_ceil(1, 1, 0), None);
+    assert_eq!(muldiv_ceil(-3, 1, 2), Some(-1));
+}
+
+#[test]
+fn test_bps_of_properties() {
+    let mut rng = XorShift(7);
+    for _ in 0..2_000 {
+        let amount = rng.amount();
+        let (x, y) = (rng.bps() / 2, rng.bps() / 2);
+
+        assert_eq!(bps_of(amount, 0), Some(0));
+        assert_eq!(bps_of(amount, BPS_DENOMINATOR), Some(amount));
+        assert!(bps_of(amount, x).unwrap() <= amount);
+
+        // Splitting a fee never pays out more than charging it at once
+        let split = bps_of(amount, x).unwrap() + bps_of(amount, y).unwrap();
+        let whole = bps_of(amount, x + y).unwrap();
+        assert!(split <= whole && whole - split <= 1);
+    }
+}
+
+#[contract]
+struct Emitter;
+
+#[contractimpl]
+impl Emitter {
+    pub fn emit(env: Env, value: u32) -> u64 {
+        events::publish(&env, symbol_short!("test"), symbol_short!("emit"), value)
+    }
+}
+
+#[test]
+fn test_publish_attaches_sequence() {
+    let env = Env::default();
+    let emitter = EmitterClient::new(&env, &env.register(Emitter, ()));
+
+    assert_eq!(emitter.emit(&7), 1);
+    assert_eq!(emitter.emit(&8), 2);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                emitter.address.clone(),
+                (symbol_short!("test"), symbol_short!("emit"), 2u64).into_val(&env),
+                8u32.into_val(&env),
+            ),
+        ]
+    );
+}
+
+/// Ranks every user at tier 2
+#[contract]
+struct FixedTierOracle;
+
+#[contractimpl]
+impl TierOracle for FixedTierOracle {
+    fn tier_of(_env: Env, _user: Address) -> u32 {
+        2
+    }
+
+    fn meets_tier(_env: Env, _user: Address, level: u32) -> bool {
+        level <= 2
+    }
+}
+
+#[test]
+fn test_tier_lookup_falls_back_to_tier_zero() {
+    let env = Env::default();
+    let user = Address::generate(&env);
+    let oracle = env.register(FixedTierOracle, ());
+
+    assert_eq!(tiers::tier_of(&env, Some(oracle.clone()), &user), 2);
+    assert!(tiers::meets_tier(&env, Some(oracle.clone()), &user, 2));
+    assert!(!tiers::meets_tier(&env, Some(oracle), &user, 3));
+
+    // No oracle, or one that can't answer, ranks everyone at tier 0
+    let not_an_oracle = env.register(Emitter, ());
+    for oracle in [None, Some(not_an_oracle)] {
+        assert_eq!(tiers::tier_of(&env, oracle.clone(), &user), 0);
+        assert!(tiers::meets_tier(&env, oracle.clone(), &user, 0));
+        assert!(!tiers::meets_tier(&env, oracle, &user, 1));
+    }
+}
@@ -0,0 +1,43 @@
+//! Tier lookups for contracts that gate or price features by user tier.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Implemented by contracts that rank users into tiers, 0 being the lowest
+#[contractclient(name = "TierOracleClient")]
+pub trait TierOracle {
+    fn tier_of(env: Env, user: Address) -> u32;
+    fn meets_tier(env: Env, user: Address, level: u32) -> bool;
+}
+
+/// `user`'s tier according to `oracle`.
+///
+/// Without an oracle, or if the oracle can't be read, every user is tier 0 so
+/// a broken oracle degrades perks instead of blocking the caller.
+pub fn tier_of(env: &Env, oracle: Option<Address>, user: &Address) -> u32 {
+    let oracle = match oracle {
+        Some(oracle) => oracle,
+        None => return 0,
+    };
+
+    match TierOracleClient::new(env, &oracle).try_tier_of(user) {
+        Ok(Ok(tier)) => tier,
+        _ => 0,
+    }
+}
+
+/// Whether `user` is at tier `level` or above according to `oracle`, with the
+/// same tier-0 fallback as [`tier_of`]
+pub fn meets_tier(env: &Env, oracle: Option<Address>, user: &Address, level: u32) -> bool {
+    if level == 0 {
+        return true;
+    }
+    let oracle = match oracle {
+        Some(oracle) => oracle,
+        None => return false,
+    };
+
+    matches!(
+        TierOracleClient::new(env, &oracle).try_meets_tier(user, &level),
+        Ok(Ok(true))
+    )
+}
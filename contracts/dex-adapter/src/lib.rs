@@ -0,0 +1,92 @@
+#![no_std]
+
+//! Typed adapter for Soroswap-style AMM routers.
+//!
+//! Exposes the router entrypoints Tuxedo contracts swap through plus a
+//! helper that approves the router and performs a single-hop exact-in swap.
+
+use soroban_sdk::{contractclient, token, vec, Address, Env, Vec};
+
+// ============ Router Interface ============
+#[contractclient(name = "RouterClient")]
+pub trait Router {
+    /// Swap an exact `amount_in` along `path`, sending the output to `to`.
+    /// Returns the amounts for every hop of the path.
+    fn swap_exact_tokens_for_tokens(
+        env: Env,
+        amount_in: i128,
+        amount_out_min: i128,
+        path: Vec<Address>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<i128>;
+
+    /// Quote the amounts for every hop of `path` when swapping `amount_in`
+    fn router_get_amounts_out(env: Env, amount_in: i128, path: Vec<Address>) -> Vec<i128>;
+}
+
+// ============ Swap Helpers ============
+/// Quote the output of swapping `amount_in` of `token_in` into `token_out`
+pub fn quote_exact_in(
+    env: &Env,
+    router: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+) -> i128 {
+    let path = vec![env, token_in.clone(), token_out.clone()];
+    quote_exact_in_path(env, router, &path, amount_in)
+}
+
+/// Quote the output of swapping `amount_in` along `path`
+pub fn quote_exact_in_path(env: &Env, router: &Address, path: &Vec<Address>, amount_in: i128) -> i128 {
+    let amounts = RouterClient::new(env, router).router_get_amounts_out(&amount_in, path);
+    amounts.last().unwrap_or(0)
+}
+
+/// Swap `amount_in` of `token_in` held by the current contract into
+/// `token_out`, returning the amount received.
+pub fn swap_exact_in(
+    env: &Env,
+    router: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+    min_out: i128,
+    deadline: u64,
+) -> i128 {
+    let path = vec![env, token_in.clone(), token_out.clone()];
+    swap_exact_in_path(env, router, &path, amount_in, min_out, deadline)
+}
+
+/// Swap `amount_in` of the first token of `path` held by the current
+/// contract along `path`, returning the amount of the last token received.
+///
+/// The router pulls the input with `transfer_from`, so it is approved for
+/// exactly `amount_in` for the current ledger only.
+pub fn swap_exact_in_path(
+    env: &Env,
+    router: &Address,
+    path: &Vec<Address>,
+    amount_in: i128,
+    min_out: i128,
+    deadline: u64,
+) -> i128 {
+    let this = env.current_contract_address();
+    let token_in = path.first().expect("empty swap path");
+    token::TokenClient::new(env, &token_in).approve(
+        &this,
+        router,
+        &amount_in,
+        &env.ledger().sequence(),
+    );
+
+    let amounts = RouterClient::new(env, router).swap_exact_tokens_for_tokens(
+        &amount_in,
+        &min_out,
+        path,
+        &this,
+        &deadline,
+    );
+    amounts.last().unwrap_or(0)
+}
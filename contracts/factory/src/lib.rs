@@ -0,0 +1,152 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, symbol_short, Address, BytesN, Env, Symbol, Vec,
+};
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const VAULT_WASM: Symbol = symbol_short!("VAULT_WSM");
+const ASSETS: Symbol = symbol_short!("ASSETS");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    VaultExists = 3,
+}
+
+// ============ Vault Factory Contract ============
+/// Deploys and initializes one Tuxedo vault per asset and keeps a registry of them
+#[contract]
+pub struct VaultFactory;
+
+#[contractimpl]
+impl VaultFactory {
+    /// Initialize the factory.
+    ///
+    /// Arguments:
+    /// - admin: Deploys vaults, updates the wasm, and becomes admin of every vault
+    /// - vault_wasm_hash: Hash of the uploaded vault wasm
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        vault_wasm_hash: BytesN<32>,
+    ) -> Result<(), FactoryError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(FactoryError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&VAULT_WASM, &vault_wasm_hash);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("factory"), symbol_short!("init")),
+            (admin, vault_wasm_hash),
+        );
+
+        Ok(())
+    }
+
+    /// Set the vault wasm used for future deployments (admin only).
+    /// Vaults already deployed keep their code.
+    pub fn set_vault_wasm(env: Env, vault_wasm_hash: BytesN<32>) -> Result<(), FactoryError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&VAULT_WASM, &vault_wasm_hash);
+
+        // Emit wasm update event
+        env.events().publish(
+            (symbol_short!("factory"), symbol_short!("wasm")),
+            vault_wasm_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Deploy a vault for `asset`, initialized by its constructor (admin only).
+    /// The vault's address is derived from the factory and `salt`. Returns it.
+    pub fn deploy_vault(
+        env: Env,
+        asset: Address,
+        agent: Address,
+        platform: Address,
+        salt: BytesN<32>,
+    ) -> Result<Address, FactoryError> {
+        let admin = Self::require_admin(&env)?;
+
+        let key = Self::vault_key(&asset);
+        if env.storage().persistent().has(&key) {
+            return Err(FactoryError::VaultExists);
+        }
+
+        let wasm_hash: BytesN<32> = env.storage().instance().get(&VAULT_WASM).unwrap();
+        let vault = env
+            .deployer()
+            .with_current_contract(salt)
+            .deploy_v2(
+                wasm_hash,
+                (admin, agent.clone(), platform.clone(), asset.clone(), None::<u32>),
+            );
+
+        // Record in the registry
+        env.storage().persistent().set(&key, &vault);
+        let mut assets = Self::get_assets(env.clone());
+        assets.push_back(asset.clone());
+        env.storage().instance().set(&ASSETS, &assets);
+
+        // Emit deployment event
+        env.events().publish(
+            (symbol_short!("factory"), symbol_short!("deploy")),
+            (asset, vault.clone(), agent, platform),
+        );
+
+        Ok(vault)
+    }
+
+    /// Get the vault deployed for an asset
+    pub fn get_vault(env: Env, asset: Address) -> Option<Address> {
+        env.storage().persistent().get(&Self::vault_key(&asset))
+    }
+
+    /// Get every asset with a deployed vault, in deployment order
+    pub fn get_assets(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&ASSETS)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the vault wasm hash used for new deployments
+    pub fn get_vault_wasm(env: Env) -> BytesN<32> {
+        env.storage().instance().get(&VAULT_WASM).unwrap()
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn require_admin(env: &Env) -> Result<Address, FactoryError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(FactoryError::NotInitialized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    fn vault_key(asset: &Address) -> (Symbol, Address) {
+        (symbol_short!("vault"), asset.clone())
+    }
+}
+
+mod test;
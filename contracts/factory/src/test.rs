@@ -0,0 +1,137 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env,
+};
+
+// Build the vault first: `stellar contract build --package tuxedo-vault`
+mod vault {
+    soroban_sdk::contractimport!(file = "../../target/wasm32v1-none/release/tuxedo_vault.wasm");
+}
+
+const UNIT: i128 = 10_000_000;
+// Shares each vault locks out of its first deposit
+const DEAD_SHARES: i128 = 1_000;
+
+struct FactoryTest<'a> {
+    env: Env,
+    factory: VaultFactoryClient<'a>,
+    admin: Address,
+    agent: Address,
+    platform: Address,
+}
+
+impl<'a> FactoryTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let agent = Address::generate(&env);
+        let platform = Address::generate(&env);
+
+        let wasm_hash = env.deployer().upload_contract_wasm(vault::WASM);
+        let factory = VaultFactoryClient::new(&env, &env.register(VaultFactory, ()));
+        factory.initialize(&admin, &wasm_hash);
+
+        FactoryTest {
+            env,
+            factory,
+            admin,
+            agent,
+            platform,
+        }
+    }
+
+    fn asset(&self) -> StellarAssetClient<'a> {
+        let asset = self
+            .env
+            .register_stellar_asset_contract_v2(self.admin.clone());
+        StellarAssetClient::new(&self.env, &asset.address())
+    }
+
+    fn deploy(&self, asset: &Address, salt: u8) -> vault::Client<'a> {
+        let vault = self.factory.deploy_vault(
+            asset,
+            &self.agent,
+            &self.platform,
+            &BytesN::from_array(&self.env, &[salt; 32]),
+        );
+        vault::Client::new(&self.env, &vault)
+    }
+}
+
+#[test]
+fn test_deployed_vaults_are_independent() {
+    let t = FactoryTest::setup();
+    let (usdc, eurc) = (t.asset(), t.asset());
+    let usdc_vault = t.deploy(&usdc.address, 1);
+    let eurc_vault = t.deploy(&eurc.address, 2);
+
+    // Each vault is initialized with the factory's roles and its own asset
+    assert_eq!(usdc_vault.get_admin(), t.admin);
+    assert_eq!(eurc_vault.get_agent(), t.agent);
+    assert_eq!(
+        t.factory.get_vault(&usdc.address),
+        Some(usdc_vault.address.clone())
+    );
+    assert_eq!(
+        t.factory.get_vault(&eurc.address),
+        Some(eurc_vault.address.clone())
+    );
+    assert_eq!(t.factory.get_assets().len(), 2);
+
+    let user = Address::generate(&t.env);
+    usdc.mint(&user, &(1_000 * UNIT));
+    eurc.mint(&user, &(500 * UNIT));
+    assert_eq!(
+        usdc_vault.deposit(&user, &usdc.address, &(1_000 * UNIT), &0),
+        1_000 * UNIT - DEAD_SHARES
+    );
+    assert_eq!(
+        eurc_vault.deposit(&user, &eurc.address, &(500 * UNIT), &0),
+        500 * UNIT - DEAD_SHARES
+    );
+    assert_eq!(usdc_vault.get_total_assets(), 1_000 * UNIT);
+    assert_eq!(eurc_vault.get_total_assets(), 500 * UNIT);
+
+    // Withdrawing from one leaves the other untouched
+    assert_eq!(
+        eurc_vault.withdraw(&user, &(500 * UNIT - DEAD_SHARES), &0),
+        500 * UNIT - DEAD_SHARES
+    );
+    assert_eq!(
+        TokenClient::new(&t.env, &eurc.address).balance(&user),
+        500 * UNIT - DEAD_SHARES
+    );
+    assert_eq!(usdc_vault.get_user_shares(&user), 1_000 * UNIT - DEAD_SHARES);
+}
+
+#[test]
+fn test_one_vault_per_asset() {
+    let t = FactoryTest::setup();
+    let usdc = t.asset();
+    t.deploy(&usdc.address, 1);
+
+    assert_eq!(
+        t.factory.try_deploy_vault(
+            &usdc.address,
+            &t.agent,
+            &t.platform,
+            &BytesN::from_array(&t.env, &[2; 32]),
+        ),
+        Err(Ok(FactoryError::VaultExists))
+    );
+    assert_eq!(t.factory.get_vault(&Address::generate(&t.env)), None);
+}
+
+#[test]
+fn test_set_vault_wasm() {
+    let t = FactoryTest::setup();
+    let hash = BytesN::from_array(&t.env, &[7; 32]);
+    t.factory.set_vault_wasm(&hash);
+    assert_eq!(t.factory.get_vault_wasm(), hash);
+}
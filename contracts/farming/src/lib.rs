@@ -1,12 +1,26 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, Address, Env, Symbol, symbol_short,
+    contract, contractclient, contracterror, contractimpl, contracttype, Address, Env, Symbol,
+    symbol_short,
+};
+use tuxedo_common::{
+    constants::{BPS_DENOMINATOR, REWARD_PRECISION, SCALAR_7},
+    events, math, tiers,
 };
 
 // ============ Constants ============
 const OWNER: Symbol = symbol_short!("OWNER");
 const TUX_TOKEN: Symbol = symbol_short!("TUX_TKN");
+const VAULT: Symbol = symbol_short!("VAULT");
+const TIER_ORACLE: Symbol = symbol_short!("TIER_ORCL");
+
+// Vault boost: +1% per 1,000 USDC deposited; tier boost: +5% per tier.
+// Combined boost is capped at +50%.
+const BOOST_STEP_VALUE: i128 = 1_000 * SCALAR_7; // 1,000 USDC (7 decimals)
+const BOOST_STEP_BPS: u32 = 100;
+const TIER_BOOST_BPS: u32 = 500;
+const MAX_BOOST_BPS: u32 = 5_000;
 
 // ============ Errors ============
 #[contracterror]
@@ -19,6 +33,45 @@ pub enum FarmingError {
     InvalidAmount = 4,
     InsufficientBalance = 5,
     TokenError = 6,
+    NothingToClaim = 7,
+    MathOverflow = 8,
+}
+
+// ============ Data Structures ============
+/// Reward emission state of a pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolRewards {
+    pub reward_rate: i128, // TUX per second
+    pub reward_per_share: i128, // scaled by REWARD_PRECISION
+    pub last_update: u64,
+    pub total_staked: i128,
+}
+
+/// A staker's reward position in a pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserRewards {
+    pub reward_per_share_paid: i128,
+    pub accrued: i128,
+}
+
+/// Payload of the `claim` event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimEvent {
+    pub user: Address,
+    pub pool_id: Symbol,
+    pub amount: i128,
+    pub multiplier_bps: u32, // 10,000 = 1x
+}
+
+// ============ External Contract Interfaces ============
+/// Read-only view of the vault used to size the farming boost
+#[contractclient(name = "VaultClient")]
+pub trait Vault {
+    fn get_user_shares(env: Env, user: Address) -> i128;
+    fn get_share_value(env: Env) -> i128;
 }
 
 // ============ TUX Farming Contract ============
@@ -43,8 +96,10 @@ impl TuxFarming {
         env.storage().instance().set(&TUX_TOKEN, &tux_token);
 
         // Emit initialization event
-        env.events().publish(
-            (symbol_short!("farm"), symbol_short!("init")),
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("init"),
             (admin, tux_token),
         );
 
@@ -70,8 +125,10 @@ impl TuxFarming {
         env.storage().instance().set(&pool_id, &staking_token);
 
         // Emit pool added event
-        env.events().publish(
-            (symbol_short!("farm"), symbol_short!("pool")),
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("pool"),
             (pool_id, staking_token),
         );
 
@@ -93,29 +150,32 @@ impl TuxFarming {
         }
 
         // Get pool token
-        let staking_token: Address = env.storage().instance().get(&pool_id).unwrap_or_else(|| {
-            // Return a dummy address and handle the error below
-            Address::from_str(&env, "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA")
-        });
-
-        // Verify pool exists by checking if it's the dummy address
-        let dummy_addr = Address::from_str(&env, "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA");
-        if staking_token == dummy_addr {
-            return Err(FarmingError::PoolNotFound);
-        }
+        let staking_token: Address = env
+            .storage()
+            .instance()
+            .get(&pool_id)
+            .ok_or(FarmingError::PoolNotFound)?;
 
         // Transfer staking tokens from user to contract
         let token_client = soroban_sdk::token::TokenClient::new(&env, &staking_token);
         token_client.transfer(&user, &env.current_contract_address(), &amount);
 
-        // Update user stake (simple counter)
+        // Settle rewards earned on the old stake
         let stake_key = (user.clone(), pool_id.clone());
         let current_stake: i128 = env.storage().persistent().get(&stake_key).unwrap_or(0);
+        let mut pool = Self::update_pool(&env, &pool_id);
+        Self::settle_user(&env, &user, &pool_id, &pool, current_stake);
+
+        // Update user stake and pool total
         env.storage().persistent().set(&stake_key, &(current_stake + amount));
+        pool.total_staked += amount;
+        Self::save_pool(&env, &pool_id, &pool);
 
         // Emit stake event
-        env.events().publish(
-            (symbol_short!("farm"), symbol_short!("stake")),
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("stake"),
             (user, pool_id, amount),
         );
 
@@ -145,16 +205,17 @@ impl TuxFarming {
         }
 
         // Get pool token
-        let staking_token: Address = env.storage().instance().get(&pool_id).unwrap_or_else(|| {
-            // Return a dummy address and handle the error below
-            Address::from_str(&env, "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA")
-        });
-
-        // Verify pool exists by checking if it's the dummy address
-        let dummy_addr = Address::from_str(&env, "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA");
-        if staking_token == dummy_addr {
-            return Err(FarmingError::PoolNotFound);
-        }
+        let staking_token: Address = env
+            .storage()
+            .instance()
+            .get(&pool_id)
+            .ok_or(FarmingError::PoolNotFound)?;
+
+        // Settle rewards earned on the old stake
+        let mut pool = Self::update_pool(&env, &pool_id);
+        Self::settle_user(&env, &user, &pool_id, &pool, current_stake);
+        pool.total_staked -= amount;
+        Self::save_pool(&env, &pool_id, &pool);
 
         // Update user stake
         let new_stake = current_stake - amount;
@@ -169,14 +230,157 @@ impl TuxFarming {
         token_client.transfer(&env.current_contract_address(), &user, &amount);
 
         // Emit unstake event
-        env.events().publish(
-            (symbol_short!("farm"), symbol_short!("unstake")),
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("unstake"),
             (user, pool_id, amount),
         );
 
         Ok(())
     }
 
+    /// Set the TUX emitted per second to a pool's stakers (admin only)
+    pub fn set_reward_rate(
+        env: Env,
+        admin: Address,
+        pool_id: Symbol,
+        reward_rate: i128,
+    ) -> Result<(), FarmingError> {
+        // Verify admin authorization
+        let owner: Address = env.storage().instance().get(&OWNER).unwrap();
+        if admin != owner {
+            return Err(FarmingError::NotAuthorized);
+        }
+
+        admin.require_auth();
+
+        if reward_rate < 0 {
+            return Err(FarmingError::InvalidAmount);
+        }
+
+        if !env.storage().instance().has(&pool_id) {
+            return Err(FarmingError::PoolNotFound);
+        }
+
+        // Accrue at the old rate before switching
+        let mut pool = Self::update_pool(&env, &pool_id);
+        pool.reward_rate = reward_rate;
+        Self::save_pool(&env, &pool_id, &pool);
+
+        // Emit rate update event
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("rate"),
+            (pool_id, reward_rate),
+        );
+
+        Ok(())
+    }
+
+    /// Set the vault whose deposits boost farming rewards (admin only)
+    pub fn set_vault(env: Env, admin: Address, vault: Address) -> Result<(), FarmingError> {
+        // Verify admin authorization
+        let owner: Address = env.storage().instance().get(&OWNER).unwrap();
+        if admin != owner {
+            return Err(FarmingError::NotAuthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&VAULT, &vault);
+
+        // Emit vault update event
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("vault"),
+            vault,
+        );
+
+        Ok(())
+    }
+
+    /// Set the tier oracle whose tiers boost farming rewards (admin only)
+    pub fn set_tier_oracle(env: Env, admin: Address, oracle: Address) -> Result<(), FarmingError> {
+        // Verify admin authorization
+        let owner: Address = env.storage().instance().get(&OWNER).unwrap();
+        if admin != owner {
+            return Err(FarmingError::NotAuthorized);
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&TIER_ORACLE, &oracle);
+
+        // Emit tier oracle update event
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("tiers"),
+            oracle,
+        );
+
+        Ok(())
+    }
+
+    /// Claim accrued TUX rewards from a pool, boosted by the user's vault position and tier.
+    /// The boost is paid from the contract's TUX reserve. Returns the amount paid.
+    pub fn claim_rewards(env: Env, user: Address, pool_id: Symbol) -> Result<i128, FarmingError> {
+        user.require_auth();
+
+        if !env.storage().instance().has(&pool_id) {
+            return Err(FarmingError::PoolNotFound);
+        }
+
+        // Bring the user's rewards up to date
+        let stake = Self::get_user_stake(env.clone(), user.clone(), pool_id.clone());
+        let pool = Self::update_pool(&env, &pool_id);
+        let mut rewards = Self::settle_user(&env, &user, &pool_id, &pool, stake);
+
+        let base = rewards.accrued;
+        if base <= 0 {
+            return Err(FarmingError::NothingToClaim);
+        }
+
+        let tux_token: Address = env.storage().instance().get(&TUX_TOKEN).unwrap();
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &tux_token);
+        let reserve = token_client.balance(&env.current_contract_address());
+        if reserve < base {
+            return Err(FarmingError::InsufficientBalance);
+        }
+
+        // Boost on top of the base reward, limited by what the reserve holds
+        let boost_bps = Self::get_boost_bps(env.clone(), user.clone());
+        let bonus = math::bps_of(base, boost_bps)
+            .ok_or(FarmingError::MathOverflow)?
+            .min(reserve - base);
+        let payout = base + bonus;
+
+        rewards.accrued = 0;
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("rewards"), user.clone(), pool_id.clone()), &rewards);
+
+        token_client.transfer(&env.current_contract_address(), &user, &payout);
+
+        // Emit claim event with the applied multiplier
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("claim"),
+            ClaimEvent {
+                user,
+                pool_id,
+                amount: payout,
+                multiplier_bps: BPS_DENOMINATOR + boost_bps,
+            },
+        );
+
+        Ok(payout)
+    }
+
     /// Mint TUX rewards (admin only, simplified reward distribution)
     pub fn mint_rewards(
         env: Env,
@@ -203,8 +407,10 @@ impl TuxFarming {
         token_client.transfer(&env.current_contract_address(), &to, &amount);
 
         // Emit reward event
-        env.events().publish(
-            (symbol_short!("farm"), symbol_short!("reward")),
+        events::publish(
+            &env,
+            symbol_short!("farm"),
+            symbol_short!("reward"),
             (admin, to, amount),
         );
 
@@ -232,6 +438,40 @@ impl TuxFarming {
             .unwrap_or(0)
     }
 
+    /// Get a user's unclaimed base rewards in a pool, before any boost
+    pub fn get_pending_rewards(env: Env, user: Address, pool_id: Symbol) -> i128 {
+        let stake = Self::get_user_stake(env.clone(), user.clone(), pool_id.clone());
+        let pool = Self::accrued_pool(&env, &pool_id);
+        let rewards = Self::load_user(&env, &user, &pool_id);
+        let delta = pool.reward_per_share - rewards.reward_per_share_paid;
+        rewards.accrued + math::muldiv(stake, delta, REWARD_PRECISION).unwrap()
+    }
+
+    /// Get a user's reward boost in bps from their vault position and tier.
+    /// A missing or unreadable vault or tier oracle contributes nothing.
+    pub fn get_boost_bps(env: Env, user: Address) -> u32 {
+        let tier = tiers::tier_of(&env, Self::get_tier_oracle(env.clone()), &user);
+        let tier_bps = tier.saturating_mul(TIER_BOOST_BPS);
+        Self::vault_boost_bps(&env, &user)
+            .saturating_add(tier_bps)
+            .min(MAX_BOOST_BPS)
+    }
+
+    /// Get a pool's reward emission state
+    pub fn get_pool_rewards(env: Env, pool_id: Symbol) -> PoolRewards {
+        Self::accrued_pool(&env, &pool_id)
+    }
+
+    /// Get the vault used for reward boosts, if configured
+    pub fn get_vault(env: Env) -> Option<Address> {
+        env.storage().instance().get(&VAULT)
+    }
+
+    /// Get the tier oracle used for reward boosts, if configured
+    pub fn get_tier_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&TIER_ORACLE)
+    }
+
     /// Get contract admin
     pub fn get_admin(env: Env) -> Address {
         env.storage().instance().get(&OWNER).unwrap()
@@ -241,4 +481,99 @@ impl TuxFarming {
     pub fn get_tux_token(env: Env) -> Address {
         env.storage().instance().get(&TUX_TOKEN).unwrap()
     }
-}
\ No newline at end of file
+
+    // ============ Internal Helper Functions ============
+
+    /// Pool state with rewards accrued up to now, without writing it
+    fn accrued_pool(env: &Env, pool_id: &Symbol) -> PoolRewards {
+        let now = env.ledger().timestamp();
+        let mut pool: PoolRewards = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("pool"), pool_id.clone()))
+            .unwrap_or(PoolRewards {
+                reward_rate: 0,
+                reward_per_share: 0,
+                last_update: now,
+                total_staked: 0,
+            });
+
+        if pool.total_staked > 0 && now > pool.last_update {
+            let emitted = (now - pool.last_update) as i128 * pool.reward_rate;
+            pool.reward_per_share +=
+                math::muldiv(emitted, REWARD_PRECISION, pool.total_staked).unwrap();
+        }
+        pool.last_update = now;
+        pool
+    }
+
+    /// Accrue a pool's rewards up to now and persist them
+    fn update_pool(env: &Env, pool_id: &Symbol) -> PoolRewards {
+        let pool = Self::accrued_pool(env, pool_id);
+        Self::save_pool(env, pool_id, &pool);
+        pool
+    }
+
+    fn save_pool(env: &Env, pool_id: &Symbol, pool: &PoolRewards) {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("pool"), pool_id.clone()), pool);
+    }
+
+    fn load_user(env: &Env, user: &Address, pool_id: &Symbol) -> UserRewards {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("rewards"), user.clone(), pool_id.clone()))
+            .unwrap_or(UserRewards {
+                reward_per_share_paid: 0,
+                accrued: 0,
+            })
+    }
+
+    /// Credit rewards earned by `stake` since the user's last settlement
+    fn settle_user(
+        env: &Env,
+        user: &Address,
+        pool_id: &Symbol,
+        pool: &PoolRewards,
+        stake: i128,
+    ) -> UserRewards {
+        let mut rewards = Self::load_user(env, user, pool_id);
+        let delta = pool.reward_per_share - rewards.reward_per_share_paid;
+        rewards.accrued += math::muldiv(stake, delta, REWARD_PRECISION).unwrap();
+        rewards.reward_per_share_paid = pool.reward_per_share;
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("rewards"), user.clone(), pool_id.clone()), &rewards);
+        rewards
+    }
+
+    /// Boost in bps from the user's vault position, 0 if the vault can't be read
+    fn vault_boost_bps(env: &Env, user: &Address) -> u32 {
+        let vault: Address = match env.storage().instance().get(&VAULT) {
+            Some(vault) => vault,
+            None => return 0,
+        };
+
+        // A failing vault must never block claims
+        let client = VaultClient::new(env, &vault);
+        let shares = match client.try_get_user_shares(user) {
+            Ok(Ok(shares)) => shares,
+            _ => return 0,
+        };
+        let share_value = match client.try_get_share_value() {
+            Ok(Ok(share_value)) => share_value,
+            _ => return 0,
+        };
+
+        let value = match math::muldiv(shares, share_value, SCALAR_7) {
+            Some(value) if value > 0 => value,
+            _ => return 0,
+        };
+
+        let steps = (value / BOOST_STEP_VALUE).min(MAX_BOOST_BPS as i128) as u32;
+        (steps * BOOST_STEP_BPS).min(MAX_BOOST_BPS)
+    }
+}
+
+mod test;
\ No newline at end of file
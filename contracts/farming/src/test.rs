@@ -0,0 +1,180 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+const UNIT: i128 = 10_000_000;
+
+struct FarmingTest<'a> {
+    env: Env,
+    farming: TuxFarmingClient<'a>,
+    tux: TuxTokenClient<'a>,
+    lp: StellarAssetClient<'a>,
+    usdc: StellarAssetClient<'a>,
+    vault: TuxedoVaultClient<'a>,
+    admin: Address,
+    pool_id: Symbol,
+}
+
+impl<'a> FarmingTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &(1_000_000 * UNIT));
+
+        let lp = StellarAssetClient::new(
+            &env,
+            &env.register_stellar_asset_contract_v2(admin.clone()).address(),
+        );
+        let usdc = StellarAssetClient::new(
+            &env,
+            &env.register_stellar_asset_contract_v2(admin.clone()).address(),
+        );
+
+        let vault = TuxedoVaultClient::new(
+            &env,
+            &env.register(
+                TuxedoVault,
+                (
+                    &admin,
+                    Address::generate(&env),
+                    Address::generate(&env),
+                    &usdc.address,
+                    None::<u32>,
+                ),
+            ),
+        );
+        // Seed the vault so the first deposit's dead shares aren't a user's
+        usdc.mint(&admin, &(10 * UNIT));
+        vault.deposit(&admin, &usdc.address, &(10 * UNIT), &0);
+
+        // 10 TUX per second, with a reserve funding rewards and boosts
+        let farming = TuxFarmingClient::new(&env, &env.register(TuxFarming, ()));
+        farming.initialize(&admin, &tux.address);
+        let pool_id = symbol_short!("TUX0");
+        farming.add_pool(&admin, &pool_id, &lp.address);
+        farming.set_reward_rate(&admin, &pool_id, &(10 * UNIT));
+        tux.transfer(&admin, &farming.address, &(100_000 * UNIT));
+
+        FarmingTest {
+            env,
+            farming,
+            tux,
+            lp,
+            usdc,
+            vault,
+            admin,
+            pool_id,
+        }
+    }
+
+    fn staker(&self, amount: i128) -> Address {
+        let user = Address::generate(&self.env);
+        self.lp.mint(&user, &amount);
+        self.farming.stake(&user, &self.pool_id, &amount);
+        user
+    }
+
+    fn advance(&self, seconds: u64) {
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + seconds);
+    }
+}
+
+#[test]
+fn test_rewards_split_by_stake() {
+    let t = FarmingTest::setup();
+    let alice = t.staker(400 * UNIT);
+    let bob = t.staker(100 * UNIT);
+
+    t.advance(100);
+    assert_eq!(t.farming.get_pending_rewards(&alice, &t.pool_id), 800 * UNIT);
+    assert_eq!(t.farming.get_pending_rewards(&bob, &t.pool_id), 200 * UNIT);
+
+    // Unstaking settles rewards before the stake changes
+    t.farming.unstake(&bob, &t.pool_id, &(100 * UNIT));
+    t.advance(100);
+    assert_eq!(t.farming.claim_rewards(&bob, &t.pool_id), 200 * UNIT);
+    assert_eq!(t.farming.claim_rewards(&alice, &t.pool_id), 1_800 * UNIT);
+    assert_eq!(TokenClient::new(&t.env, &t.lp.address).balance(&bob), 100 * UNIT);
+    assert_eq!(
+        t.farming.try_claim_rewards(&bob, &t.pool_id),
+        Err(Ok(FarmingError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_claim_without_vault_position_is_unboosted() {
+    let t = FarmingTest::setup();
+    t.farming.set_vault(&t.admin, &t.vault.address);
+    let user = t.staker(100 * UNIT);
+
+    t.advance(100);
+    assert_eq!(t.farming.get_boost_bps(&user), 0);
+    assert_eq!(t.farming.claim_rewards(&user, &t.pool_id), 1_000 * UNIT);
+}
+
+#[test]
+fn test_claim_boosted_by_vault_position() {
+    let t = FarmingTest::setup();
+    t.farming.set_vault(&t.admin, &t.vault.address);
+    let user = t.staker(100 * UNIT);
+
+    // 10,000 USDC in the vault earns +10%
+    t.usdc.mint(&user, &(10_000 * UNIT));
+    t.vault.deposit(&user, &t.usdc.address, &(10_000 * UNIT), &0);
+    assert_eq!(t.farming.get_boost_bps(&user), 1_000);
+
+    t.advance(100);
+    assert_eq!(t.farming.claim_rewards(&user, &t.pool_id), 1_100 * UNIT);
+    assert_eq!(t.tux.balance(&user), 1_100 * UNIT);
+
+    // The boost is capped at +50%
+    let whale = Address::generate(&t.env);
+    t.usdc.mint(&whale, &(1_000_000 * UNIT));
+    t.vault.deposit(&whale, &t.usdc.address, &(1_000_000 * UNIT), &0);
+    assert_eq!(t.farming.get_boost_bps(&whale), 5_000);
+}
+
+#[test]
+fn test_unreachable_vault_falls_back_to_base_rewards() {
+    let t = FarmingTest::setup();
+    let user = t.staker(100 * UNIT);
+
+    // Not a vault: the boost read fails and the claim proceeds at 1x
+    t.farming.set_vault(&t.admin, &t.tux.address);
+    t.advance(100);
+
+    assert_eq!(t.farming.get_boost_bps(&user), 0);
+    assert_eq!(t.farming.claim_rewards(&user, &t.pool_id), 1_000 * UNIT);
+}
+
+#[test]
+fn test_tier_boost_stacks_with_vault_boost() {
+    let t = FarmingTest::setup();
+    t.farming.set_vault(&t.admin, &t.vault.address);
+    t.farming.set_tier_oracle(&t.admin, &t.tux.address);
+    t.tux
+        .set_tier_thresholds(&t.admin, &vec![&t.env, 100 * UNIT, 500 * UNIT]);
+    let user = t.staker(100 * UNIT);
+
+    // Tier 2 (+10%) on top of 10,000 USDC in the vault (+10%)
+    t.tux.transfer(&t.admin, &user, &(500 * UNIT));
+    t.usdc.mint(&user, &(10_000 * UNIT));
+    t.vault.deposit(&user, &t.usdc.address, &(10_000 * UNIT), &0);
+    assert_eq!(t.farming.get_boost_bps(&user), 2_000);
+
+    t.advance(100);
+    assert_eq!(t.farming.claim_rewards(&user, &t.pool_id), 1_200 * UNIT);
+}
@@ -0,0 +1,105 @@
+//! Event schema of the farming contract: each operation's full event stream,
+//! decoded field by field. The indexer depends on these shapes, so changing
+//! one means updating the expectation here.
+
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, TryFromVal, Val};
+use tux_farming::{ClaimEvent, TuxFarming, TuxFarmingClient};
+use tuxedo_common::constants::BPS_DENOMINATOR;
+use tuxedo_fixtures::{events::EventLog, TuxedoFixture, TUX0_POOL, UNIT};
+
+/// Decode the only event the last invocation published
+fn single<D: TryFromVal<Env, Val>>(t: &TuxedoFixture, action: Symbol) -> D {
+    let mut log = EventLog::new(&t.env, &t.farming.address, symbol_short!("farm"));
+    let data = log.next(action);
+    log.finish();
+    data
+}
+
+#[test]
+fn test_initialize_event() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let tux = Address::generate(&env);
+    let farming = TuxFarmingClient::new(&env, &env.register(TuxFarming, ()));
+    farming.initialize(&admin, &tux);
+
+    let mut log = EventLog::new(&env, &farming.address, symbol_short!("farm"));
+    assert_eq!(
+        log.next::<(Address, Address)>(symbol_short!("init")),
+        (admin, tux)
+    );
+    log.finish();
+}
+
+#[test]
+fn test_staking_flow_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.fund_rewards(10_000 * UNIT);
+
+    t.farming.set_reward_rate(&t.admin, &TUX0_POOL, &UNIT);
+    assert_eq!(
+        single::<(Symbol, i128)>(&t, symbol_short!("rate")),
+        (TUX0_POOL, UNIT)
+    );
+
+    t.farming.stake(&user, &TUX0_POOL, &(100 * UNIT));
+    assert_eq!(
+        single::<(Address, Symbol, i128)>(&t, symbol_short!("stake")),
+        (user.clone(), TUX0_POOL, 100 * UNIT)
+    );
+
+    // Sole staker for 100 seconds at 1 TUX/s, boosted by the vault position
+    t.advance_time(100);
+    let boost_bps = t.farming.get_boost_bps(&user);
+    let base = 100 * UNIT;
+    let payout = t.farming.claim_rewards(&user, &TUX0_POOL);
+    assert_eq!(
+        payout,
+        base + base * boost_bps as i128 / BPS_DENOMINATOR as i128
+    );
+    assert_eq!(
+        single::<ClaimEvent>(&t, symbol_short!("claim")),
+        ClaimEvent {
+            user: user.clone(),
+            pool_id: TUX0_POOL,
+            amount: payout,
+            multiplier_bps: BPS_DENOMINATOR + boost_bps,
+        }
+    );
+
+    t.farming.unstake(&user, &TUX0_POOL, &(100 * UNIT));
+    assert_eq!(
+        single::<(Address, Symbol, i128)>(&t, symbol_short!("unstake")),
+        (user, TUX0_POOL, 100 * UNIT)
+    );
+}
+
+#[test]
+fn test_admin_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    t.fund_rewards(100 * UNIT);
+
+    let lp = Address::generate(&t.env);
+    t.farming.add_pool(&t.admin, &symbol_short!("LP"), &lp);
+    assert_eq!(
+        single::<(Symbol, Address)>(&t, symbol_short!("pool")),
+        (symbol_short!("LP"), lp)
+    );
+
+    let vault = Address::generate(&t.env);
+    t.farming.set_vault(&t.admin, &vault);
+    assert_eq!(single::<Address>(&t, symbol_short!("vault")), vault);
+
+    let oracle = Address::generate(&t.env);
+    t.farming.set_tier_oracle(&t.admin, &oracle);
+    assert_eq!(single::<Address>(&t, symbol_short!("tiers")), oracle);
+
+    let to = Address::generate(&t.env);
+    t.farming.mint_rewards(&t.admin, &to, &(10 * UNIT));
+    assert_eq!(
+        single::<(Address, Address, i128)>(&t, symbol_short!("reward")),
+        (t.admin.clone(), to, 10 * UNIT)
+    );
+}
@@ -0,0 +1,136 @@
+//! Randomized interleavings of stake/unstake/claim/rate changes checking that
+//! reward accounting neither over-pays nor strands emissions.
+//!
+//! Every run is driven by a seed. By default a fixed range of seeds runs; set
+//! `FARMING_FUZZ_SEED=<seed>` to replay the one a failure reports.
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::StellarAssetClient,
+    Address, Env, Symbol,
+};
+use tux_farming::{FarmingError, TuxFarming, TuxFarmingClient};
+use tux_token::{TuxToken, TuxTokenClient};
+
+const UNIT: i128 = 10_000_000;
+const USERS: usize = 4;
+const SEEDS: u64 = 12;
+const OPS_PER_SEED: usize = 120;
+const FUNDED: i128 = 50_000_000 * UNIT;
+const POOL: Symbol = symbol_short!("LP");
+
+/// Small deterministic PRNG (xorshift64*) so runs replay exactly from a seed
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in `0..n`
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+fn run(seed: u64) {
+    // Printed with the output of a failing test, naming the seed to replay
+    println!("farming fuzz seed {seed}");
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let mut rng = Rng::new(seed);
+
+    let admin = Address::generate(&env);
+    let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+    tux.initialize(&admin, &FUNDED);
+    let lp = StellarAssetClient::new(
+        &env,
+        &env.register_stellar_asset_contract_v2(admin.clone())
+            .address(),
+    );
+
+    let farming = TuxFarmingClient::new(&env, &env.register(TuxFarming, ()));
+    farming.initialize(&admin, &tux.address);
+    farming.add_pool(&admin, &POOL, &lp.address);
+    tux.transfer(&admin, &farming.address, &FUNDED);
+
+    let users: [Address; USERS] = core::array::from_fn(|_| Address::generate(&env));
+    let mut rate = 0i128;
+    let mut emitted = 0i128;
+    let mut claimed = 0i128;
+
+    for step in 0..OPS_PER_SEED {
+        let user = &users[rng.below(USERS as u64) as usize];
+        let stake = farming.get_user_stake(user, &POOL);
+
+        match rng.below(5) {
+            0 => {
+                let amount = 1 + rng.below(1_000 * UNIT as u64) as i128;
+                lp.mint(user, &amount);
+                farming.stake(user, &POOL, &amount);
+            }
+            1 if stake > 0 => {
+                let amount = 1 + rng.below(stake as u64) as i128;
+                farming.unstake(user, &POOL, &amount);
+            }
+            2 => match farming.try_claim_rewards(user, &POOL) {
+                Ok(Ok(payout)) => claimed += payout,
+                Err(Ok(FarmingError::NothingToClaim)) => {}
+                other => panic!("seed {seed} step {step}: claim failed: {other:?}"),
+            },
+            3 => {
+                rate = rng.below(20 * UNIT as u64) as i128;
+                farming.set_reward_rate(&admin, &POOL, &rate);
+            }
+            _ => {
+                // Rewards only flow while something is staked
+                let seconds = rng.below(3_600);
+                if farming.get_pool_rewards(&POOL).total_staked > 0 {
+                    emitted += rate * seconds as i128;
+                }
+                let now = env.ledger().timestamp();
+                env.ledger().set_timestamp(now + seconds);
+            }
+        }
+
+        let pending: i128 = users
+            .iter()
+            .map(|u| farming.get_pending_rewards(u, &POOL))
+            .sum();
+
+        assert!(
+            claimed <= FUNDED,
+            "seed {seed} step {step}: claimed {claimed} exceeds funded {FUNDED}"
+        );
+        assert_eq!(
+            tux.balance(&farming.address),
+            FUNDED - claimed,
+            "seed {seed} step {step}: reserve out of sync with claims"
+        );
+
+        // Rounding may only ever shave a few stroops off what was emitted
+        let epsilon = ((step + 1) * (USERS + 1)) as i128;
+        let accounted = claimed + pending;
+        assert!(
+            accounted <= emitted && emitted - accounted <= epsilon,
+            "seed {seed} step {step}: emitted {emitted}, claimed + pending {accounted}"
+        );
+    }
+}
+
+#[test]
+fn test_reward_accounting_under_random_interleavings() {
+    match std::env::var("FARMING_FUZZ_SEED") {
+        Ok(seed) => run(seed.parse().expect("FARMING_FUZZ_SEED must be a u64")),
+        Err(_) => (0..SEEDS).for_each(run),
+    }
+}
@@ -0,0 +1,281 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, symbol_short, token, Address, Env,
+    Symbol, Vec,
+};
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const RECIPIENTS: Symbol = symbol_short!("RECIPS");
+
+const BPS_DENOMINATOR: u32 = 10_000;
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SplitterError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidWeights = 3,
+    NoRecipients = 4,
+    NothingToDistribute = 5,
+    NothingToClaim = 6,
+    TransferFailed = 7,
+}
+
+// ============ External Contract Interfaces ============
+/// Reward contracts that account for tokens pushed to them (e.g. TUX revenue share)
+#[contractclient(name = "RewardNotifyClient")]
+pub trait RewardNotify {
+    fn notify_reward(env: Env, amount: i128);
+}
+
+// ============ Fee Splitter Contract ============
+#[contract]
+pub struct FeeSplitter;
+
+#[contractimpl]
+impl FeeSplitter {
+    /// Initialize the splitter with its admin
+    pub fn initialize(env: Env, admin: Address) -> Result<(), SplitterError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(SplitterError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage()
+            .instance()
+            .set(&RECIPIENTS, &Vec::<(Address, u32)>::new(&env));
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("init")),
+            admin,
+        );
+
+        Ok(())
+    }
+
+    /// Replace the recipient list (admin only). Weights are in bps and must sum to 10,000.
+    pub fn set_recipients(
+        env: Env,
+        recipients: Vec<(Address, u32)>,
+    ) -> Result<(), SplitterError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(SplitterError::NotInitialized)?;
+        admin.require_auth();
+
+        if recipients.is_empty() {
+            return Err(SplitterError::NoRecipients);
+        }
+
+        // Every recipient needs a weight and the total must be exactly 100%
+        let mut total_weight: u32 = 0;
+        for (_, weight) in recipients.iter() {
+            if weight == 0 {
+                return Err(SplitterError::InvalidWeights);
+            }
+            total_weight = total_weight.saturating_add(weight);
+        }
+        if total_weight != BPS_DENOMINATOR {
+            return Err(SplitterError::InvalidWeights);
+        }
+
+        env.storage().instance().set(&RECIPIENTS, &recipients);
+
+        // Emit recipients update event
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("recips")),
+            recipients,
+        );
+
+        Ok(())
+    }
+
+    /// Set whether `recipient` is told about each payment through `notify_reward` (admin only)
+    pub fn set_notify(env: Env, recipient: Address, notify: bool) -> Result<(), SplitterError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(SplitterError::NotInitialized)?;
+        admin.require_auth();
+
+        if notify {
+            env.storage()
+                .persistent()
+                .set(&Self::notify_key(&recipient), &true);
+        } else {
+            env.storage()
+                .persistent()
+                .remove(&Self::notify_key(&recipient));
+        }
+
+        // Emit notify update event
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("notify")),
+            (recipient, notify),
+        );
+
+        Ok(())
+    }
+
+    /// Split the contract's unreserved balance of `token` between the recipients.
+    /// Anyone may call this. A recipient whose transfer fails is credited instead
+    /// and can pull the amount later with `claim`. Returns the amount distributed.
+    pub fn distribute(env: Env, token: Address) -> Result<i128, SplitterError> {
+        let recipients: Vec<(Address, u32)> = env
+            .storage()
+            .instance()
+            .get(&RECIPIENTS)
+            .ok_or(SplitterError::NotInitialized)?;
+
+        if recipients.is_empty() {
+            return Err(SplitterError::NoRecipients);
+        }
+
+        // Balance already owed to recipients is not distributed again
+        let this = env.current_contract_address();
+        let token_client = token::TokenClient::new(&env, &token);
+        let reserved = Self::get_reserved(env.clone(), token.clone());
+        let available = token_client.balance(&this) - reserved;
+
+        if available <= 0 {
+            return Err(SplitterError::NothingToDistribute);
+        }
+
+        // Pay each recipient its share; rounding dust stays for the next round
+        let mut distributed: i128 = 0;
+        let mut newly_owed: i128 = 0;
+        for (recipient, weight) in recipients.iter() {
+            let share = available * weight as i128 / BPS_DENOMINATOR as i128;
+            if share <= 0 {
+                continue;
+            }
+
+            if token_client.try_transfer(&this, &recipient, &share).is_err() {
+                // Credit the recipient so one bad recipient can't block the rest
+                let key = Self::owed_key(&token, &recipient);
+                let owed: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+                env.storage().persistent().set(&key, &(owed + share));
+                newly_owed += share;
+
+                env.events().publish(
+                    (symbol_short!("split"), symbol_short!("owed")),
+                    (token.clone(), recipient.clone(), share),
+                );
+            } else if Self::get_notify(env.clone(), recipient.clone()) {
+                // A failed notification leaves the tokens with the recipient to account for later
+                let _ = RewardNotifyClient::new(&env, &recipient).try_notify_reward(&share);
+            }
+
+            distributed += share;
+        }
+
+        if newly_owed > 0 {
+            env.storage()
+                .persistent()
+                .set(&Self::reserved_key(&token), &(reserved + newly_owed));
+        }
+
+        // Emit distribution event
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("dist")),
+            (token, distributed),
+        );
+
+        Ok(distributed)
+    }
+
+    /// Pay out the amount of `token` credited to `recipient` by failed transfers
+    pub fn claim(env: Env, recipient: Address, token: Address) -> Result<i128, SplitterError> {
+        recipient.require_auth();
+
+        let key = Self::owed_key(&token, &recipient);
+        let owed: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+
+        if owed <= 0 {
+            return Err(SplitterError::NothingToClaim);
+        }
+
+        let this = env.current_contract_address();
+        let token_client = token::TokenClient::new(&env, &token);
+        if token_client.try_transfer(&this, &recipient, &owed).is_err() {
+            return Err(SplitterError::TransferFailed);
+        }
+
+        // Clear the credit and release the reservation
+        env.storage().persistent().remove(&key);
+        let reserved = Self::get_reserved(env.clone(), token.clone());
+        env.storage()
+            .persistent()
+            .set(&Self::reserved_key(&token), &(reserved - owed));
+
+        // Emit claim event
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("claim")),
+            (token, recipient, owed),
+        );
+
+        Ok(owed)
+    }
+
+    /// Get the configured recipients and their weights
+    pub fn get_recipients(env: Env) -> Vec<(Address, u32)> {
+        env.storage()
+            .instance()
+            .get(&RECIPIENTS)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the amount of `token` credited to `recipient` and awaiting claim
+    pub fn get_owed(env: Env, recipient: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Self::owed_key(&token, &recipient))
+            .unwrap_or(0)
+    }
+
+    /// Get the total amount of `token` held back for pending claims
+    pub fn get_reserved(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Self::reserved_key(&token))
+            .unwrap_or(0)
+    }
+
+    /// Check whether `recipient` is notified of payments
+    pub fn get_notify(env: Env, recipient: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Self::notify_key(&recipient))
+            .unwrap_or(false)
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn owed_key(token: &Address, recipient: &Address) -> (Symbol, Address, Address) {
+        (symbol_short!("owed"), token.clone(), recipient.clone())
+    }
+
+    fn reserved_key(token: &Address) -> (Symbol, Address) {
+        (symbol_short!("reserved"), token.clone())
+    }
+
+    fn notify_key(recipient: &Address) -> (Symbol, Address) {
+        (symbol_short!("notify"), recipient.clone())
+    }
+}
+
+mod test;
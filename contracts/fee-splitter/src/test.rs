@@ -0,0 +1,161 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, IssuerFlags},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+struct SplitterTest<'a> {
+    env: Env,
+    splitter: FeeSplitterClient<'a>,
+    usdc: TokenClient<'a>,
+    usdc_admin: StellarAssetClient<'a>,
+    admin: Address,
+    treasury: Address,
+    insurance: Address,
+    buyback: Address,
+}
+
+impl<'a> SplitterTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let insurance = Address::generate(&env);
+        let buyback = Address::generate(&env);
+
+        // Revocable so tests can block a recipient from receiving
+        let usdc_sac = env.register_stellar_asset_contract_v2(admin.clone());
+        usdc_sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+        let usdc_id = usdc_sac.address();
+
+        let splitter = FeeSplitterClient::new(&env, &env.register(FeeSplitter, ()));
+        splitter.initialize(&admin);
+        splitter.set_recipients(&vec![
+            &env,
+            (treasury.clone(), 5_000),
+            (insurance.clone(), 2_000),
+            (buyback.clone(), 3_000),
+        ]);
+
+        SplitterTest {
+            usdc: TokenClient::new(&env, &usdc_id),
+            usdc_admin: StellarAssetClient::new(&env, &usdc_id),
+            splitter,
+            admin,
+            treasury,
+            insurance,
+            buyback,
+            env,
+        }
+    }
+}
+
+#[test]
+fn test_distribute_splits_by_weight() {
+    let t = SplitterTest::setup();
+    t.usdc_admin.mint(&t.splitter.address, &1_001);
+
+    assert_eq!(t.splitter.distribute(&t.usdc.address), 1_000);
+    assert_eq!(t.usdc.balance(&t.treasury), 500);
+    assert_eq!(t.usdc.balance(&t.insurance), 200);
+    assert_eq!(t.usdc.balance(&t.buyback), 300);
+
+    // Rounding dust is left for the next round
+    assert_eq!(t.usdc.balance(&t.splitter.address), 1);
+}
+
+#[test]
+fn test_set_recipients_validates_weights() {
+    let t = SplitterTest::setup();
+
+    assert_eq!(
+        t.splitter.try_set_recipients(&vec![&t.env, (t.treasury.clone(), 9_999)]),
+        Err(Ok(SplitterError::InvalidWeights))
+    );
+    assert_eq!(
+        t.splitter.try_set_recipients(&vec![
+            &t.env,
+            (t.treasury.clone(), 10_000),
+            (t.buyback.clone(), 0),
+        ]),
+        Err(Ok(SplitterError::InvalidWeights))
+    );
+    assert_eq!(
+        t.splitter.try_set_recipients(&Vec::new(&t.env)),
+        Err(Ok(SplitterError::NoRecipients))
+    );
+    assert_eq!(
+        t.splitter.try_distribute(&t.usdc.address),
+        Err(Ok(SplitterError::NothingToDistribute))
+    );
+}
+
+#[test]
+fn test_failed_transfer_is_credited_for_claim() {
+    let t = SplitterTest::setup();
+
+    // Insurance can't receive USDC for now
+    t.usdc_admin.set_authorized(&t.insurance, &false);
+    t.usdc_admin.mint(&t.splitter.address, &1_000);
+
+    t.splitter.distribute(&t.usdc.address);
+    assert_eq!(t.usdc.balance(&t.treasury), 500);
+    assert_eq!(t.usdc.balance(&t.buyback), 300);
+    assert_eq!(t.splitter.get_owed(&t.insurance, &t.usdc.address), 200);
+    assert_eq!(t.splitter.get_reserved(&t.usdc.address), 200);
+
+    // Reserved funds are not split again
+    assert_eq!(
+        t.splitter.try_distribute(&t.usdc.address),
+        Err(Ok(SplitterError::NothingToDistribute))
+    );
+    assert_eq!(
+        t.splitter.try_claim(&t.insurance, &t.usdc.address),
+        Err(Ok(SplitterError::TransferFailed))
+    );
+
+    t.usdc_admin.set_authorized(&t.insurance, &true);
+    assert_eq!(t.splitter.claim(&t.insurance, &t.usdc.address), 200);
+    assert_eq!(t.usdc.balance(&t.insurance), 200);
+    assert_eq!(t.splitter.get_reserved(&t.usdc.address), 0);
+    assert_eq!(
+        t.splitter.try_claim(&t.insurance, &t.usdc.address),
+        Err(Ok(SplitterError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_vault_platform_fees_flow_through_splitter() {
+    let t = SplitterTest::setup();
+
+    // Vault sends its platform fee to the splitter
+    let agent = Address::generate(&t.env);
+    let vault = TuxedoVaultClient::new(
+        &t.env,
+        &t.env.register(
+            TuxedoVault,
+            (&t.admin, &agent, &t.splitter.address, &t.usdc.address, None::<u32>),
+        ),
+    );
+
+    let user = Address::generate(&t.env);
+    t.usdc_admin.mint(&user, &100_000_000);
+    vault.deposit(&user, &t.usdc.address, &100_000_000, &0);
+
+    // 10_000_000 of yield lands in the vault; the caller takes 0.1% and 2% of
+    // the rest goes to the platform
+    t.usdc_admin.mint(&vault.address, &10_000_000);
+    vault.distribute_yield(&user);
+    assert_eq!(t.usdc.balance(&t.splitter.address), 199_800);
+
+    t.splitter.distribute(&t.usdc.address);
+    assert_eq!(t.usdc.balance(&t.treasury), 99_900);
+    assert_eq!(t.usdc.balance(&t.insurance), 39_960);
+    assert_eq!(t.usdc.balance(&t.buyback), 59_940);
+}
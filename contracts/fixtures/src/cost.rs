@@ -0,0 +1,50 @@
+//! Resource measurements for budget regression tests.
+
+extern crate std;
+
+use soroban_sdk::Env;
+use std::println;
+
+/// Resources metered for one top-level contract invocation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cost {
+    pub instructions: i64,
+    pub mem_bytes: i64,
+    pub read_entries: u32,
+    pub write_entries: u32,
+}
+
+impl Cost {
+    /// Capture the resources of the last invocation made on `env` and print
+    /// them under `label`, so thresholds can be re-baselined from test output
+    pub fn last(env: &Env, label: &str) -> Self {
+        let resources = env.cost_estimate().resources();
+        let cost = Cost {
+            instructions: resources.instructions,
+            mem_bytes: resources.mem_bytes,
+            read_entries: resources.read_entries,
+            write_entries: resources.write_entries,
+        };
+        println!(
+            "{label}: {} instructions, {} mem bytes, {} reads, {} writes",
+            cost.instructions, cost.mem_bytes, cost.read_entries, cost.write_entries
+        );
+        cost
+    }
+
+    /// Panic naming every metric above its threshold in `limit`
+    pub fn assert_within(&self, label: &str, limit: &Cost) {
+        let over = [
+            ("instructions", self.instructions > limit.instructions),
+            ("mem_bytes", self.mem_bytes > limit.mem_bytes),
+            ("read_entries", self.read_entries > limit.read_entries),
+            ("write_entries", self.write_entries > limit.write_entries),
+        ];
+        for (metric, exceeded) in over {
+            assert!(
+                !exceeded,
+                "{label} exceeded its {metric} budget: {self:?} > {limit:?}"
+            );
+        }
+    }
+}
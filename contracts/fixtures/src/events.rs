@@ -0,0 +1,84 @@
+//! Decoding helpers for event schema tests.
+//!
+//! Tuxedo contracts publish through `tuxedo_common::events::publish`, so each
+//! event has the topics `(contract, action, seq)`. `EventLog` collects the
+//! events one contract published in the last invocation under its contract
+//! topic, and tests walk it in order, decoding every payload into the type the
+//! indexer expects.
+
+extern crate std;
+
+use soroban_sdk::{testutils::Events as _, Address, Env, Symbol, TryFromVal, Val, Vec};
+use std::vec::Vec as StdVec;
+use tuxedo_common::events;
+
+/// Events published by one contract in the last invocation, consumed in order
+pub struct EventLog {
+    env: Env,
+    name: Symbol,
+    events: StdVec<(Vec<Val>, Val)>,
+    pos: usize,
+    next_seq: u64,
+}
+
+impl EventLog {
+    /// Collect the events `contract` published under the `name` topic.
+    /// Events from other emitters or with other topics, such as SEP-41 token
+    /// events, are not part of the log.
+    pub fn new(env: &Env, contract: &Address, name: Symbol) -> Self {
+        let mut collected = StdVec::new();
+        for (emitter, topics, data) in env.events().all().iter() {
+            let topic = topics
+                .get(0)
+                .and_then(|topic| Symbol::try_from_val(env, &topic).ok());
+            if emitter == *contract && topic.as_ref() == Some(&name) {
+                collected.push((topics, data));
+            }
+        }
+
+        // The invocation's events end at the contract's latest sequence number
+        let last_seq = env.as_contract(contract, || events::last_sequence(env));
+
+        EventLog {
+            env: env.clone(),
+            name,
+            next_seq: last_seq + 1 - collected.len() as u64,
+            events: collected,
+            pos: 0,
+        }
+    }
+
+    /// Decode the next event's payload, asserting it is `action` and carries
+    /// the next sequence number
+    pub fn next<D: TryFromVal<Env, Val>>(&mut self, action: Symbol) -> D {
+        let (topics, data) = self
+            .events
+            .get(self.pos)
+            .unwrap_or_else(|| panic!("expected a {action:?} event, found none"));
+        let topics = <(Symbol, Symbol, u64)>::try_from_val(&self.env, &topics.to_val())
+            .unwrap_or_else(|_| {
+                panic!("event {} topics are not (contract, action, seq)", self.pos)
+            });
+        assert_eq!(
+            topics,
+            (self.name.clone(), action.clone(), self.next_seq),
+            "event {} topics",
+            self.pos
+        );
+
+        self.pos += 1;
+        self.next_seq += 1;
+        D::try_from_val(&self.env, data)
+            .unwrap_or_else(|_| panic!("{action:?} payload does not decode"))
+    }
+
+    /// Assert every collected event was checked
+    pub fn finish(self) {
+        assert_eq!(
+            self.pos,
+            self.events.len(),
+            "{} unchecked events",
+            self.events.len() - self.pos
+        );
+    }
+}
@@ -0,0 +1,145 @@
+#![no_std]
+
+//! One-call deployment of the whole Tuxedo protocol for tests.
+//!
+//! `TuxedoFixture::new(&env)` registers and initializes the TUX token, vault,
+//! farming contract, a mock USDC asset and a mock Blend pool in the right
+//! order, then hands back typed clients and pre-funded users. `Scenario`
+//! drives the same deployment through long multi-user flows.
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol,
+};
+use tux_farming::{TuxFarming, TuxFarmingClient};
+use tux_token::{TuxToken, TuxTokenClient};
+use tuxedo_testutils::blend::{MockBlendPool, MockBlendPoolClient};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+pub mod cost;
+pub mod events;
+pub mod scenario;
+
+/// One USDC / TUX / TUX0 with 7 decimals
+pub const UNIT: i128 = 10_000_000;
+
+/// TUX minted to the admin at deployment
+pub const TUX_SUPPLY: i128 = 100_000_000 * UNIT;
+
+/// USDC each pre-funded user starts with
+pub const USER_USDC: i128 = 10_000 * UNIT;
+
+/// Farming pool that stakes vault shares
+pub const TUX0_POOL: Symbol = symbol_short!("TUX0");
+
+/// Every Tuxedo contract deployed and wired together in one `Env`
+pub struct TuxedoFixture<'a> {
+    pub env: Env,
+    pub admin: Address,
+    pub agent: Address,
+    pub platform: Address,
+    /// Calls `distribute_yield` and collects its keeper reward
+    pub keeper: Address,
+    pub usdc: TokenClient<'a>,
+    pub usdc_admin: StellarAssetClient<'a>,
+    pub tux: TuxTokenClient<'a>,
+    pub vault: TuxedoVaultClient<'a>,
+    pub farming: TuxFarmingClient<'a>,
+    /// Mock Blend pool, approved for agent strategies
+    pub pool: Address,
+    /// Users holding `USER_USDC` each
+    pub users: [Address; 3],
+}
+
+impl<'a> TuxedoFixture<'a> {
+    /// Deploy and initialize the protocol with all auths mocked
+    pub fn new(env: &Env) -> Self {
+        let env = env.clone();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let agent = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let keeper = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let usdc = TokenClient::new(&env, &usdc_id);
+        let usdc_admin = StellarAssetClient::new(&env, &usdc_id);
+
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &TUX_SUPPLY);
+
+        let vault = TuxedoVaultClient::new(
+            &env,
+            &env.register(
+                TuxedoVault,
+                (&admin, &agent, &platform, &usdc_id, None::<u32>),
+            ),
+        );
+
+        // Vault shares are staked in the TUX0 pool and boost its rewards
+        let farming = TuxFarmingClient::new(&env, &env.register(TuxFarming, ()));
+        farming.initialize(&admin, &tux.address);
+        farming.add_pool(&admin, &TUX0_POOL, &vault.address);
+        farming.set_vault(&admin, &vault.address);
+
+        let pool = env.register(MockBlendPool, ());
+        vault.add_approved_pool(&pool);
+        vault.assign_pool(&agent, &pool);
+
+        let users = [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        for user in users.iter() {
+            usdc_admin.mint(user, &USER_USDC);
+        }
+
+        TuxedoFixture {
+            env,
+            admin,
+            agent,
+            platform,
+            keeper,
+            usdc,
+            usdc_admin,
+            tux,
+            vault,
+            farming,
+            pool,
+            users,
+        }
+    }
+
+    /// A fresh user holding exactly `amount` USDC
+    pub fn user_with_usdc(&self, amount: i128) -> Address {
+        let user = Address::generate(&self.env);
+        self.usdc_admin.mint(&user, &amount);
+        user
+    }
+
+    /// Credit `amount` USDC to the vault as if strategies had earned it
+    pub fn simulate_yield(&self, amount: i128) {
+        self.usdc_admin.mint(&self.vault.address, &amount);
+    }
+
+    /// Have the pool write off `loss_bps` of every position it holds, a loss
+    /// the vault only records once it next moves funds there
+    pub fn simulate_loss(&self, loss_bps: u32) {
+        MockBlendPoolClient::new(&self.env, &self.pool).write_down(&loss_bps);
+    }
+
+    /// Move the ledger clock forward
+    pub fn advance_time(&self, seconds: u64) {
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + seconds);
+    }
+
+    /// Send `amount` TUX from the admin to the farming reward reserve
+    pub fn fund_rewards(&self, amount: i128) {
+        self.tux.transfer(&self.admin, &self.farming.address, &amount);
+    }
+}
@@ -0,0 +1,271 @@
+//! Chainable builder for long, multi-user vault scenarios.
+//!
+//! A `Scenario` deploys a fresh protocol and runs one step per call, so a
+//! flow reads top to bottom:
+//!
+//! ```ignore
+//! Scenario::new()
+//!     .deposit("alice", 1_000 * UNIT)
+//!     .agent_supply(pct(80))
+//!     .advance_days(3)
+//!     .accrue_yield(bps(500))
+//!     .agent_withdraw(pct(100))
+//!     .distribute()
+//!     .withdraw_shares("alice", half())
+//!     .expect_usdc("alice", 5_196 * UNIT / 10);
+//! ```
+//!
+//! Users are named and created on first use; a deposit mints the user exactly
+//! the USDC it pays in. `expect_*` helpers check state after any step.
+//! `expect_event` decodes the vault events of the last invocation, so it must
+//! come straight after the step, before other expectations query the vault.
+
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, Symbol, TryFromVal, Val};
+use tuxedo_blend_adapter::{self as blend, RequestType};
+use tuxedo_testutils::blend::MockBlendPoolClient;
+use tuxedo_vault::Strategy;
+
+use crate::{events::EventLog, TuxedoFixture};
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// A share of some amount, in basis points
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Fraction(pub u32);
+
+impl Fraction {
+    /// This fraction of `amount`, rounded down
+    pub fn of(self, amount: i128) -> i128 {
+        amount * self.0 as i128 / 10_000
+    }
+}
+
+/// `percent` hundredths
+pub fn pct(percent: u32) -> Fraction {
+    Fraction(percent * 100)
+}
+
+/// `bps` ten-thousandths
+pub fn bps(bps: u32) -> Fraction {
+    Fraction(bps)
+}
+
+/// Fifty percent
+pub fn half() -> Fraction {
+    Fraction(5_000)
+}
+
+/// A protocol deployment driven step by step
+pub struct Scenario<'a> {
+    pub t: TuxedoFixture<'a>,
+    users: Map<Symbol, Address>,
+}
+
+impl Default for Scenario<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Scenario<'a> {
+    /// Deploy the protocol in a fresh `Env`
+    pub fn new() -> Self {
+        let t = TuxedoFixture::new(&Env::default());
+        let users = Map::new(&t.env);
+        Scenario { t, users }
+    }
+
+    /// The address behind `name`, generated on first use
+    pub fn user(&mut self, name: &str) -> Address {
+        let key = Symbol::new(&self.t.env, name);
+        if let Some(user) = self.users.get(key.clone()) {
+            return user;
+        }
+        let user = Address::generate(&self.t.env);
+        self.users.set(key, user.clone());
+        user
+    }
+
+    // ============ Steps ============
+
+    /// Mint `amount` USDC to `name` and deposit all of it
+    pub fn deposit(mut self, name: &str, amount: i128) -> Self {
+        let user = self.user(name);
+        self.t.usdc_admin.mint(&user, &amount);
+        self.t.vault.deposit(&user, &self.t.usdc.address, &amount, &0);
+        self
+    }
+
+    /// Redeem `fraction` of the shares `name` holds
+    pub fn withdraw_shares(mut self, name: &str, fraction: Fraction) -> Self {
+        let user = self.user(name);
+        let shares = fraction.of(self.t.vault.get_user_shares(&user));
+        self.t.vault.withdraw(&user, &shares, &0);
+        self
+    }
+
+    /// Have the agent supply `fraction` of the vault's idle USDC to the pool
+    pub fn agent_supply(self, fraction: Fraction) -> Self {
+        let amount = fraction.of(self.t.usdc.balance(&self.t.vault.address));
+        self.t.vault.agent_execute(
+            &self.t.agent,
+            &self.strategy("supply", RequestType::SupplyCollateral, amount),
+        );
+        self
+    }
+
+    /// Have the agent pull `fraction` of the vault's pool position back
+    pub fn agent_withdraw(self, fraction: Fraction) -> Self {
+        let amount = fraction.of(self.pool_position());
+        self.t.vault.agent_execute(
+            &self.t.agent,
+            &self.strategy("withdraw", RequestType::WithdrawCollateral, amount),
+        );
+        self
+    }
+
+    /// Move the ledger clock forward by whole days
+    pub fn advance_days(self, days: u64) -> Self {
+        self.t.advance_time(days * DAY);
+        self
+    }
+
+    /// Grow the pool position by `rate` and fund the interest into the pool
+    pub fn accrue_yield(self, rate: Fraction) -> Self {
+        let before = self.pool_position();
+        self.pool().accrue(&rate.0);
+        let interest = self.pool_position() - before;
+        self.t.usdc_admin.mint(&self.t.pool, &interest);
+        self
+    }
+
+    /// Write the pool position down by `loss`
+    pub fn write_down(self, loss: Fraction) -> Self {
+        self.pool().write_down(&loss.0);
+        self
+    }
+
+    /// Have the admin reopen a vault the circuit breaker paused
+    pub fn clear_breaker(self) -> Self {
+        assert!(self.t.vault.is_breaker_tripped(), "breaker not tripped");
+        self.t.vault.clear_breaker();
+        self
+    }
+
+    /// Credit `amount` USDC straight to the vault as earned yield
+    pub fn simulate_yield(self, amount: i128) -> Self {
+        self.t.simulate_yield(amount);
+        self
+    }
+
+    /// Have an outside payer, such as the insurance fund, pay `amount` back in
+    pub fn recover(self, amount: i128) -> Self {
+        let insurer = self.t.user_with_usdc(amount);
+        self.t.vault.recover_assets(&insurer, &amount);
+        self
+    }
+
+    /// Have the platform pay `amount` USDC in as yield, such as the proceeds
+    /// of emissions it converted
+    pub fn donate_yield(self, amount: i128) -> Self {
+        self.t.usdc_admin.mint(&self.t.platform, &amount);
+        self.t.vault.donate_yield(&amount);
+        self
+    }
+
+    /// Take the platform fee on yield earned so far
+    pub fn distribute(self) -> Self {
+        self.t.vault.distribute_yield(&self.t.keeper);
+        self
+    }
+
+    /// Run any other action against the deployment
+    pub fn then(self, step: impl FnOnce(&TuxedoFixture<'a>)) -> Self {
+        step(&self.t);
+        self
+    }
+
+    // ============ Expectations ============
+
+    #[track_caller]
+    pub fn expect_share_value(self, share_value: i128) -> Self {
+        assert_eq!(self.t.vault.get_share_value(), share_value, "share value");
+        self
+    }
+
+    /// Assets the vault holds itself, excluding its pool position
+    #[track_caller]
+    pub fn expect_total_assets(self, total_assets: i128) -> Self {
+        assert_eq!(
+            self.t.vault.get_total_assets(),
+            total_assets,
+            "total assets"
+        );
+        self
+    }
+
+    #[track_caller]
+    pub fn expect_pool_position(self, position: i128) -> Self {
+        assert_eq!(self.pool_position(), position, "pool position");
+        self
+    }
+
+    #[track_caller]
+    pub fn expect_shares(mut self, name: &str, shares: i128) -> Self {
+        let user = self.user(name);
+        assert_eq!(self.t.vault.get_user_shares(&user), shares, "{name} shares");
+        self
+    }
+
+    /// USDC in `name`'s wallet, outside the vault
+    #[track_caller]
+    pub fn expect_usdc(mut self, name: &str, balance: i128) -> Self {
+        let user = self.user(name);
+        assert_eq!(self.t.usdc.balance(&user), balance, "{name} USDC");
+        self
+    }
+
+    /// USDC the platform has collected in fees
+    #[track_caller]
+    pub fn expect_platform_fees(self, fees: i128) -> Self {
+        assert_eq!(self.t.usdc.balance(&self.t.platform), fees, "platform fees");
+        self
+    }
+
+    /// Assert the last step published exactly one vault event, `action`
+    /// carrying `data`
+    #[track_caller]
+    pub fn expect_event<D>(self, action: &str, data: D) -> Self
+    where
+        D: TryFromVal<Env, Val> + PartialEq + core::fmt::Debug,
+    {
+        let env = &self.t.env;
+        let mut log = EventLog::new(env, &self.t.vault.address, Symbol::new(env, "vault"));
+        assert_eq!(
+            log.next::<D>(Symbol::new(env, action)),
+            data,
+            "{action} event"
+        );
+        log.finish();
+        self
+    }
+
+    fn pool(&self) -> MockBlendPoolClient<'_> {
+        MockBlendPoolClient::new(&self.t.env, &self.t.pool)
+    }
+
+    /// Supply plus collateral the vault holds in the pool, across reserves
+    fn pool_position(&self) -> i128 {
+        blend::lent_total(&self.pool().get_positions(&self.t.vault.address))
+    }
+
+    fn strategy(&self, action: &str, request_type: RequestType, amount: i128) -> Strategy {
+        Strategy {
+            action: Symbol::new(&self.t.env, action),
+            pool: self.t.pool.clone(),
+            asset: self.t.usdc.address.clone(),
+            amount,
+            request_type: request_type as u32,
+        }
+    }
+}
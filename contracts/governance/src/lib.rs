@@ -0,0 +1,308 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address,
+    BytesN, Env, Symbol, Val, Vec,
+};
+
+// ============ Constants ============
+const TUX_TOKEN: Symbol = symbol_short!("TUX_TKN");
+const CONFIG: Symbol = symbol_short!("CONFIG");
+const PROPOSAL_COUNT: Symbol = symbol_short!("PROP_CNT");
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidConfig = 3,
+    BelowProposalThreshold = 4,
+    ProposalNotFound = 5,
+    NotActive = 6,
+    AlreadyVoted = 7,
+    NoVotingPower = 8,
+    NotSucceeded = 9,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfig {
+    /// Minimum TUX balance needed to create a proposal
+    pub proposal_threshold: i128,
+    /// Share of the snapshot supply that must vote, in basis points
+    pub quorum_bps: u32,
+    /// Ledgers between proposal creation and the start of voting
+    pub voting_delay: u32,
+    /// Ledgers voting stays open
+    pub voting_period: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub target: Address,
+    pub fn_name: Symbol,
+    pub args: Vec<Val>,
+    pub description_hash: BytesN<32>,
+    pub snapshot_ledger: u32,
+    pub vote_start: u32,
+    pub vote_end: u32,
+    pub for_votes: i128,
+    pub against_votes: i128,
+    pub executed: bool,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ProposalState {
+    Pending,
+    Active,
+    Succeeded,
+    Defeated,
+    Executed,
+}
+
+// ============ External Contract Interfaces ============
+/// Checkpointed voting power exposed by the TUX token
+#[contractclient(name = "VotesClient")]
+pub trait Votes {
+    fn balance(env: Env, id: Address) -> i128;
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> i128;
+    fn get_past_total_supply(env: Env, ledger: u32) -> i128;
+}
+
+// ============ Governance Contract ============
+#[contract]
+pub struct TuxGovernance;
+
+#[contractimpl]
+impl TuxGovernance {
+    /// Initialize governance over the TUX token
+    pub fn initialize(
+        env: Env,
+        tux_token: Address,
+        config: GovernanceConfig,
+    ) -> Result<(), GovernanceError> {
+        // Check if already initialized
+        if env.storage().instance().has(&TUX_TOKEN) {
+            return Err(GovernanceError::AlreadyInitialized);
+        }
+
+        if config.proposal_threshold < 0
+            || config.quorum_bps as i128 > BPS_DENOMINATOR
+            || config.voting_period == 0
+        {
+            return Err(GovernanceError::InvalidConfig);
+        }
+
+        env.storage().instance().set(&TUX_TOKEN, &tux_token);
+        env.storage().instance().set(&CONFIG, &config);
+        env.storage().instance().set(&PROPOSAL_COUNT, &0u32);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("init")),
+            (tux_token, config),
+        );
+
+        Ok(())
+    }
+
+    /// Create a proposal to call `fn_name(args)` on `target`. Returns the proposal id.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        target: Address,
+        fn_name: Symbol,
+        args: Vec<Val>,
+        description_hash: BytesN<32>,
+    ) -> Result<u32, GovernanceError> {
+        proposer.require_auth();
+
+        let config = Self::load_config(&env)?;
+        let votes = Self::votes_client(&env)?;
+
+        // Proposer must hold enough TUX
+        if votes.balance(&proposer) < config.proposal_threshold {
+            return Err(GovernanceError::BelowProposalThreshold);
+        }
+
+        // Voting power is read as of the creation ledger
+        let snapshot_ledger = env.ledger().sequence();
+        let vote_start = snapshot_ledger + config.voting_delay;
+        let vote_end = vote_start + config.voting_period;
+
+        let proposal_id: u32 = env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0) + 1;
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            target,
+            fn_name,
+            args,
+            description_hash,
+            snapshot_ledger,
+            vote_start,
+            vote_end,
+            for_votes: 0,
+            against_votes: 0,
+            executed: false,
+        };
+
+        env.storage().persistent().set(&(symbol_short!("prop"), proposal_id), &proposal);
+        env.storage().instance().set(&PROPOSAL_COUNT, &proposal_id);
+
+        // Emit proposal event
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("propose")),
+            (proposal_id, proposer, vote_start, vote_end),
+        );
+
+        Ok(proposal_id)
+    }
+
+    /// Vote on an active proposal, weighted by TUX held at its snapshot ledger.
+    /// Returns the weight applied.
+    pub fn vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u32,
+        support: bool,
+    ) -> Result<i128, GovernanceError> {
+        voter.require_auth();
+
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        if Self::state_of(&env, &proposal)? != ProposalState::Active {
+            return Err(GovernanceError::NotActive);
+        }
+
+        // One vote per address per proposal
+        let voted_key = (symbol_short!("voted"), proposal_id, voter.clone());
+        if env.storage().persistent().has(&voted_key) {
+            return Err(GovernanceError::AlreadyVoted);
+        }
+
+        let weight = Self::votes_client(&env)?.get_past_votes(&voter, &proposal.snapshot_ledger);
+        if weight <= 0 {
+            return Err(GovernanceError::NoVotingPower);
+        }
+
+        if support {
+            proposal.for_votes += weight;
+        } else {
+            proposal.against_votes += weight;
+        }
+
+        env.storage().persistent().set(&voted_key, &support);
+        env.storage().persistent().set(&(symbol_short!("prop"), proposal_id), &proposal);
+
+        // Emit vote event
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("vote")),
+            (proposal_id, voter, support, weight),
+        );
+
+        Ok(weight)
+    }
+
+    /// Execute a succeeded proposal by invoking its target. Anyone may call this.
+    pub fn execute(env: Env, proposal_id: u32) -> Result<(), GovernanceError> {
+        let mut proposal = Self::load_proposal(&env, proposal_id)?;
+        if Self::state_of(&env, &proposal)? != ProposalState::Succeeded {
+            return Err(GovernanceError::NotSucceeded);
+        }
+
+        // Mark executed before the external call
+        proposal.executed = true;
+        env.storage().persistent().set(&(symbol_short!("prop"), proposal_id), &proposal);
+
+        env.invoke_contract::<Val>(&proposal.target, &proposal.fn_name, proposal.args);
+
+        // Emit execution event
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("execute")),
+            proposal_id,
+        );
+
+        Ok(())
+    }
+
+    /// Get the lifecycle state of a proposal
+    pub fn state(env: Env, proposal_id: u32) -> Result<ProposalState, GovernanceError> {
+        let proposal = Self::load_proposal(&env, proposal_id)?;
+        Self::state_of(&env, &proposal)
+    }
+
+    /// Get a proposal
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, GovernanceError> {
+        Self::load_proposal(&env, proposal_id)
+    }
+
+    /// Get the number of proposals created
+    pub fn get_proposal_count(env: Env) -> u32 {
+        env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0)
+    }
+
+    /// Get governance parameters
+    pub fn get_config(env: Env) -> Result<GovernanceConfig, GovernanceError> {
+        Self::load_config(&env)
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn load_config(env: &Env) -> Result<GovernanceConfig, GovernanceError> {
+        env.storage()
+            .instance()
+            .get(&CONFIG)
+            .ok_or(GovernanceError::NotInitialized)
+    }
+
+    fn votes_client(env: &Env) -> Result<VotesClient<'_>, GovernanceError> {
+        let tux_token: Address = env
+            .storage()
+            .instance()
+            .get(&TUX_TOKEN)
+            .ok_or(GovernanceError::NotInitialized)?;
+        Ok(VotesClient::new(env, &tux_token))
+    }
+
+    fn load_proposal(env: &Env, proposal_id: u32) -> Result<Proposal, GovernanceError> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("prop"), proposal_id))
+            .ok_or(GovernanceError::ProposalNotFound)
+    }
+
+    /// Derive the state from the current ledger and tallies
+    fn state_of(env: &Env, proposal: &Proposal) -> Result<ProposalState, GovernanceError> {
+        if proposal.executed {
+            return Ok(ProposalState::Executed);
+        }
+
+        let ledger = env.ledger().sequence();
+        if ledger <= proposal.vote_start {
+            return Ok(ProposalState::Pending);
+        }
+        if ledger <= proposal.vote_end {
+            return Ok(ProposalState::Active);
+        }
+
+        // Voting closed: check quorum against the snapshot supply, then the majority
+        let config = Self::load_config(env)?;
+        let supply = Self::votes_client(env)?.get_past_total_supply(&proposal.snapshot_ledger);
+        let quorum = supply * config.quorum_bps as i128 / BPS_DENOMINATOR;
+        let turnout = proposal.for_votes + proposal.against_votes;
+
+        if turnout >= quorum && proposal.for_votes > proposal.against_votes {
+            Ok(ProposalState::Succeeded)
+        } else {
+            Ok(ProposalState::Defeated)
+        }
+    }
+}
+
+mod test;
@@ -0,0 +1,173 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env, IntoVal,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+const UNIT: i128 = 10_000_000;
+
+struct GovernanceTest<'a> {
+    env: Env,
+    gov: TuxGovernanceClient<'a>,
+    tux: TuxTokenClient<'a>,
+    vault: TuxedoVaultClient<'a>,
+    alice: Address,
+    bob: Address,
+    carol: Address,
+}
+
+impl<'a> GovernanceTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_sequence_number(100);
+
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+
+        // 1,000 TUX: alice 400, bob 300, carol 100, admin 200
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &(1_000 * UNIT));
+        tux.transfer(&admin, &alice, &(400 * UNIT));
+        tux.transfer(&admin, &bob, &(300 * UNIT));
+        tux.transfer(&admin, &carol, &(100 * UNIT));
+
+        let gov = TuxGovernanceClient::new(&env, &env.register(TuxGovernance, ()));
+        gov.initialize(
+            &tux.address,
+            &GovernanceConfig {
+                proposal_threshold: 100 * UNIT,
+                quorum_bps: 4_000,
+                voting_delay: 10,
+                voting_period: 100,
+            },
+        );
+
+        // The vault is administered by governance
+        let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let platform = Address::generate(&env);
+        let vault = TuxedoVaultClient::new(
+            &env,
+            &env.register(TuxedoVault, (&gov.address, &admin, &platform, &usdc, None::<u32>)),
+        );
+
+        env.ledger().set_sequence_number(101);
+
+        GovernanceTest {
+            env,
+            gov,
+            tux,
+            vault,
+            alice,
+            bob,
+            carol,
+        }
+    }
+
+    fn propose_fee(&self, fee_bps: u32) -> u32 {
+        self.gov.propose(
+            &self.alice,
+            &self.vault.address,
+            &Symbol::new(&self.env, "set_platform_fee_bps"),
+            &vec![&self.env, fee_bps.into_val(&self.env)],
+            &BytesN::from_array(&self.env, &[7; 32]),
+        )
+    }
+
+    fn advance_to(&self, ledger: u32) {
+        self.env.ledger().set_sequence_number(ledger);
+    }
+}
+
+#[test]
+fn test_proposal_changes_vault_fee() {
+    let t = GovernanceTest::setup();
+    let id = t.propose_fee(500);
+    assert_eq!(t.gov.state(&id), ProposalState::Pending);
+    assert_eq!(
+        t.gov.try_vote(&t.alice, &id, &true),
+        Err(Ok(GovernanceError::NotActive))
+    );
+
+    t.advance_to(112);
+    assert_eq!(t.gov.state(&id), ProposalState::Active);
+    assert_eq!(t.gov.vote(&t.alice, &id, &true), 400 * UNIT);
+    assert_eq!(t.gov.vote(&t.carol, &id, &false), 100 * UNIT);
+    assert_eq!(
+        t.gov.try_vote(&t.alice, &id, &true),
+        Err(Ok(GovernanceError::AlreadyVoted))
+    );
+    assert_eq!(
+        t.gov.try_execute(&id),
+        Err(Ok(GovernanceError::NotSucceeded))
+    );
+
+    t.advance_to(212);
+    assert_eq!(t.gov.state(&id), ProposalState::Succeeded);
+    t.gov.execute(&id);
+
+    assert_eq!(t.gov.state(&id), ProposalState::Executed);
+    assert_eq!(t.vault.get_platform_fee_bps(), 500);
+    assert_eq!(
+        t.gov.try_execute(&id),
+        Err(Ok(GovernanceError::NotSucceeded))
+    );
+}
+
+#[test]
+fn test_votes_use_snapshot_balance() {
+    let t = GovernanceTest::setup();
+    let id = t.propose_fee(300);
+
+    // Tokens moved after the snapshot carry no extra weight
+    t.advance_to(105);
+    t.tux.transfer(&t.bob, &t.carol, &(300 * UNIT));
+
+    t.advance_to(112);
+    assert_eq!(t.gov.vote(&t.carol, &id, &true), 100 * UNIT);
+    assert_eq!(t.gov.vote(&t.bob, &id, &false), 300 * UNIT);
+
+    // Quorum reached but the majority is against
+    t.advance_to(212);
+    assert_eq!(t.gov.state(&id), ProposalState::Defeated);
+    assert_eq!(t.vault.get_platform_fee_bps(), 200);
+}
+
+#[test]
+fn test_quorum_and_threshold() {
+    let t = GovernanceTest::setup();
+    let outsider = Address::generate(&t.env);
+
+    assert_eq!(
+        t.gov.try_propose(
+            &outsider,
+            &t.vault.address,
+            &Symbol::new(&t.env, "set_platform_fee_bps"),
+            &vec![&t.env, 0u32.into_val(&t.env)],
+            &BytesN::from_array(&t.env, &[0; 32]),
+        ),
+        Err(Ok(GovernanceError::BelowProposalThreshold))
+    );
+
+    // 300 of 1,000 TUX voting is short of the 40% quorum
+    let id = t.propose_fee(0);
+    t.advance_to(112);
+    t.gov.vote(&t.bob, &id, &true);
+    assert_eq!(
+        t.gov.try_vote(&outsider, &id, &true),
+        Err(Ok(GovernanceError::NoVotingPower))
+    );
+
+    t.advance_to(212);
+    assert_eq!(t.gov.state(&id), ProposalState::Defeated);
+    assert_eq!(
+        t.gov.try_state(&(id + 1)),
+        Err(Ok(GovernanceError::ProposalNotFound))
+    );
+}
@@ -0,0 +1,284 @@
+#![no_std]
+
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token, vec,
+    Address, Env, IntoVal, Symbol,
+};
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const USDC: Symbol = symbol_short!("USDC");
+const NOTIFIER: Symbol = symbol_short!("NOTIFIER");
+const CLAIM_COUNT: Symbol = symbol_short!("CLAIM_CNT");
+const CONTRIBUTED: Symbol = symbol_short!("CONTRIB");
+const PAID_OUT: Symbol = symbol_short!("PAID_OUT");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum InsuranceError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    ClaimNotFound = 4,
+    ClaimNotPending = 5,
+    InsufficientFunds = 6,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClaimStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// A vault's request to be made whole after a strategy loss
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub vault: Address,
+    pub amount: i128,
+    pub requested_at: u64,
+    pub status: ClaimStatus,
+}
+
+// ============ External Contract Interfaces ============
+#[contractclient(name = "VaultClient")]
+pub trait VaultInterface {
+    fn get_admin(env: Env) -> Address;
+    fn recover_assets(env: Env, from: Address, amount: i128);
+}
+
+// ============ Insurance Fund Contract ============
+/// First-loss buffer for the vaults, capitalized with a slice of platform fees.
+///
+/// The fee splitter transfers USDC here and reports it through
+/// `notify_contribution`. A vault admin files a claim after a strategy loss and,
+/// once the insurance admin approves it, the USDC goes back into the vault as
+/// recovered assets.
+#[contract]
+pub struct InsuranceFund;
+
+#[contractimpl]
+impl InsuranceFund {
+    /// Initialize the insurance fund.
+    ///
+    /// Arguments:
+    /// - admin: Approves or rejects claims
+    /// - usdc: Token the fund holds and pays out
+    /// - notifier: Address allowed to report contributions (the fee splitter)
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        usdc: Address,
+        notifier: Address,
+    ) -> Result<(), InsuranceError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(InsuranceError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&USDC, &usdc);
+        env.storage().instance().set(&NOTIFIER, &notifier);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("init")),
+            (admin, usdc, notifier),
+        );
+
+        Ok(())
+    }
+
+    /// Change the address allowed to report contributions (admin only)
+    pub fn set_notifier(env: Env, notifier: Address) -> Result<(), InsuranceError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&NOTIFIER, &notifier);
+
+        // Emit notifier update event
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("notifier")),
+            notifier,
+        );
+
+        Ok(())
+    }
+
+    /// Record `amount` USDC already transferred into the fund (notifier only)
+    pub fn notify_contribution(env: Env, amount: i128) -> Result<(), InsuranceError> {
+        let notifier: Address = env
+            .storage()
+            .instance()
+            .get(&NOTIFIER)
+            .ok_or(InsuranceError::NotInitialized)?;
+        notifier.require_auth();
+
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        let contributed = Self::get_total_contributed(env.clone()) + amount;
+        env.storage().instance().set(&CONTRIBUTED, &contributed);
+
+        // Emit contribution event
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("contrib")),
+            (amount, contributed),
+        );
+
+        Ok(())
+    }
+
+    /// The fee splitter's payment hook; same as `notify_contribution`
+    pub fn notify_reward(env: Env, amount: i128) -> Result<(), InsuranceError> {
+        Self::notify_contribution(env, amount)
+    }
+
+    /// File a claim for `amount` USDC on behalf of `vault` (vault admin only).
+    /// Returns the claim id.
+    pub fn request_coverage(env: Env, vault: Address, amount: i128) -> Result<u32, InsuranceError> {
+        VaultClient::new(&env, &vault).get_admin().require_auth();
+
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        let id = Self::get_claim_count(env.clone());
+        let claim = Claim {
+            vault: vault.clone(),
+            amount,
+            requested_at: env.ledger().timestamp(),
+            status: ClaimStatus::Pending,
+        };
+        env.storage().persistent().set(&Self::claim_key(id), &claim);
+        env.storage().instance().set(&CLAIM_COUNT, &(id + 1));
+
+        // Emit claim request event
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("request")),
+            (id, vault, amount),
+        );
+
+        Ok(id)
+    }
+
+    /// Pay a pending claim back into its vault (admin only)
+    pub fn approve_claim(env: Env, id: u32) -> Result<(), InsuranceError> {
+        Self::require_admin(&env)?;
+
+        let mut claim = Self::pending_claim(&env, id)?;
+        if Self::get_fund_balance(env.clone()) < claim.amount {
+            return Err(InsuranceError::InsufficientFunds);
+        }
+
+        claim.status = ClaimStatus::Approved;
+        env.storage().persistent().set(&Self::claim_key(id), &claim);
+        let paid_out = Self::get_total_paid_out(env.clone()) + claim.amount;
+        env.storage().instance().set(&PAID_OUT, &paid_out);
+
+        // The vault pulls the USDC from the fund inside its own invocation
+        let fund = env.current_contract_address();
+        let usdc: Address = env.storage().instance().get(&USDC).unwrap();
+        env.authorize_as_current_contract(vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: usdc,
+                    fn_name: symbol_short!("transfer"),
+                    args: (fund.clone(), claim.vault.clone(), claim.amount).into_val(&env),
+                },
+                sub_invocations: vec![&env],
+            }),
+        ]);
+        VaultClient::new(&env, &claim.vault).recover_assets(&fund, &claim.amount);
+
+        // Emit claim approval event
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("approve")),
+            (id, claim.vault, claim.amount),
+        );
+
+        Ok(())
+    }
+
+    /// Close a pending claim without paying it (admin only)
+    pub fn reject_claim(env: Env, id: u32) -> Result<(), InsuranceError> {
+        Self::require_admin(&env)?;
+
+        let mut claim = Self::pending_claim(&env, id)?;
+        claim.status = ClaimStatus::Rejected;
+        env.storage().persistent().set(&Self::claim_key(id), &claim);
+
+        // Emit claim rejection event
+        env.events()
+            .publish((symbol_short!("insure"), symbol_short!("reject")), id);
+
+        Ok(())
+    }
+
+    /// Get the USDC the fund currently holds
+    pub fn get_fund_balance(env: Env) -> i128 {
+        let usdc: Address = env.storage().instance().get(&USDC).unwrap();
+        token::TokenClient::new(&env, &usdc).balance(&env.current_contract_address())
+    }
+
+    /// Get a claim by id
+    pub fn get_claim(env: Env, id: u32) -> Result<Claim, InsuranceError> {
+        env.storage()
+            .persistent()
+            .get(&Self::claim_key(id))
+            .ok_or(InsuranceError::ClaimNotFound)
+    }
+
+    /// Get the number of claims ever filed; ids run from 0 to this minus one
+    pub fn get_claim_count(env: Env) -> u32 {
+        env.storage().instance().get(&CLAIM_COUNT).unwrap_or(0)
+    }
+
+    /// Get the total contributions reported by the notifier
+    pub fn get_total_contributed(env: Env) -> i128 {
+        env.storage().instance().get(&CONTRIBUTED).unwrap_or(0)
+    }
+
+    /// Get the total paid out on approved claims
+    pub fn get_total_paid_out(env: Env) -> i128 {
+        env.storage().instance().get(&PAID_OUT).unwrap_or(0)
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn require_admin(env: &Env) -> Result<(), InsuranceError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(InsuranceError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn pending_claim(env: &Env, id: u32) -> Result<Claim, InsuranceError> {
+        let claim = Self::get_claim(env.clone(), id)?;
+        if claim.status != ClaimStatus::Pending {
+            return Err(InsuranceError::ClaimNotPending);
+        }
+        Ok(claim)
+    }
+
+    fn claim_key(id: u32) -> (Symbol, u32) {
+        (symbol_short!("claim"), id)
+    }
+}
+
+mod test;
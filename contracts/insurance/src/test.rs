@@ -0,0 +1,107 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+const UNIT: i128 = 10_000_000;
+
+struct InsuranceTest<'a> {
+    fund: InsuranceFundClient<'a>,
+    vault: TuxedoVaultClient<'a>,
+    usdc: TokenClient<'a>,
+    usdc_admin: StellarAssetClient<'a>,
+}
+
+impl<'a> InsuranceTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let usdc_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+
+        let vault = TuxedoVaultClient::new(
+            &env,
+            &env.register(
+                TuxedoVault,
+                (
+                    &admin,
+                    Address::generate(&env),
+                    Address::generate(&env),
+                    &usdc_id,
+                    None::<u32>,
+                ),
+            ),
+        );
+
+        let fund = InsuranceFundClient::new(&env, &env.register(InsuranceFund, ()));
+        fund.initialize(&admin, &usdc_id, &Address::generate(&env));
+
+        InsuranceTest {
+            fund,
+            vault,
+            usdc: TokenClient::new(&env, &usdc_id),
+            usdc_admin: StellarAssetClient::new(&env, &usdc_id),
+        }
+    }
+
+    /// Transfer and report a contribution the way the fee splitter does
+    fn contribute(&self, amount: i128) {
+        self.usdc_admin.mint(&self.fund.address, &amount);
+        self.fund.notify_contribution(&amount);
+    }
+}
+
+#[test]
+fn test_approved_claim_returns_usdc_to_vault() {
+    let t = InsuranceTest::setup();
+    t.contribute(500 * UNIT);
+    assert_eq!(t.fund.get_fund_balance(), 500 * UNIT);
+    assert_eq!(t.fund.get_total_contributed(), 500 * UNIT);
+
+    let id = t.fund.request_coverage(&t.vault.address, &(200 * UNIT));
+    assert_eq!(t.fund.get_claim(&id).status, ClaimStatus::Pending);
+
+    t.fund.approve_claim(&id);
+    assert_eq!(t.usdc.balance(&t.vault.address), 200 * UNIT);
+    assert_eq!(t.fund.get_fund_balance(), 300 * UNIT);
+    assert_eq!(t.fund.get_total_paid_out(), 200 * UNIT);
+    assert_eq!(t.fund.get_claim(&id).status, ClaimStatus::Approved);
+
+    // A claim is paid at most once
+    assert_eq!(
+        t.fund.try_approve_claim(&id),
+        Err(Ok(InsuranceError::ClaimNotPending))
+    );
+}
+
+#[test]
+fn test_claims_limited_to_fund_balance() {
+    let t = InsuranceTest::setup();
+    t.contribute(100 * UNIT);
+
+    let id = t.fund.request_coverage(&t.vault.address, &(150 * UNIT));
+    assert_eq!(
+        t.fund.try_approve_claim(&id),
+        Err(Ok(InsuranceError::InsufficientFunds))
+    );
+
+    t.fund.reject_claim(&id);
+    assert_eq!(t.fund.get_claim(&id).status, ClaimStatus::Rejected);
+    assert_eq!(t.fund.get_claim_count(), 1);
+    assert_eq!(
+        t.fund.try_get_claim(&1),
+        Err(Ok(InsuranceError::ClaimNotFound))
+    );
+    assert_eq!(
+        t.fund.try_notify_contribution(&0),
+        Err(Ok(InsuranceError::InvalidAmount))
+    );
+}
@@ -0,0 +1,251 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+
+// ============ Constants ============
+const REWARD_TOKEN: Symbol = symbol_short!("RWD_TKN");
+const OWED: Symbol = symbol_short!("OWED");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum KeeperError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    TaskNotFound = 4,
+    TooSoon = 5,
+    Underfunded = 6,
+    NothingToClaim = 7,
+}
+
+// ============ Data Structures ============
+/// A maintenance call a protocol contract pays keepers to make
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Task {
+    pub reward_per_call: i128,
+    /// Seconds that must pass between rewarded executions
+    pub min_interval: u64,
+    pub last_executed: Option<u64>,
+    pub executions: u32,
+}
+
+// ============ Keeper Escrow Contract ============
+/// Holds platform-funded bounties for permissionless maintenance calls.
+/// Protocol contracts register their tasks and report each execution inline,
+/// crediting the caller, who claims the accumulated bounties later.
+#[contract]
+pub struct KeeperEscrow;
+
+#[contractimpl]
+impl KeeperEscrow {
+    /// Initialize the escrow with the token bounties are paid in
+    pub fn initialize(env: Env, reward_token: Address) -> Result<(), KeeperError> {
+        // Check if already initialized
+        if env.storage().instance().has(&REWARD_TOKEN) {
+            return Err(KeeperError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&REWARD_TOKEN, &reward_token);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("keeper"), symbol_short!("init")),
+            reward_token,
+        );
+
+        Ok(())
+    }
+
+    /// Add `amount` of the reward token to the bounty pool
+    pub fn fund(env: Env, from: Address, amount: i128) -> Result<(), KeeperError> {
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(KeeperError::InvalidAmount);
+        }
+
+        let reward_token: Address = env
+            .storage()
+            .instance()
+            .get(&REWARD_TOKEN)
+            .ok_or(KeeperError::NotInitialized)?;
+        token::TokenClient::new(&env, &reward_token).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        // Emit fund event
+        env.events().publish(
+            (symbol_short!("keeper"), symbol_short!("fund")),
+            (from, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Register or update one of `contract`'s tasks (called by the contract itself).
+    /// Re-registering keeps the execution history.
+    pub fn register_task(
+        env: Env,
+        contract: Address,
+        task_id: Symbol,
+        reward_per_call: i128,
+        min_interval: u64,
+    ) -> Result<(), KeeperError> {
+        contract.require_auth();
+
+        if reward_per_call <= 0 {
+            return Err(KeeperError::InvalidAmount);
+        }
+
+        let key = Self::task_key(&contract, &task_id);
+        let (last_executed, executions) = match env.storage().persistent().get::<_, Task>(&key) {
+            Some(task) => (task.last_executed, task.executions),
+            None => (None, 0),
+        };
+        let task = Task {
+            reward_per_call,
+            min_interval,
+            last_executed,
+            executions,
+        };
+        env.storage().persistent().set(&key, &task);
+
+        // Emit task registration event
+        env.events().publish(
+            (symbol_short!("keeper"), symbol_short!("task")),
+            (contract, task_id, reward_per_call, min_interval),
+        );
+
+        Ok(())
+    }
+
+    /// Credit `caller` with the task's bounty (called by `contract` inline, right
+    /// after doing the work). Fails if the interval hasn't passed since the last
+    /// rewarded execution or the escrow can't cover the bounty.
+    pub fn record_execution(
+        env: Env,
+        contract: Address,
+        task_id: Symbol,
+        caller: Address,
+    ) -> Result<i128, KeeperError> {
+        contract.require_auth();
+
+        let key = Self::task_key(&contract, &task_id);
+        let mut task: Task = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(KeeperError::TaskNotFound)?;
+
+        let now = env.ledger().timestamp();
+        if let Some(last) = task.last_executed {
+            if now < last + task.min_interval {
+                return Err(KeeperError::TooSoon);
+            }
+        }
+
+        // Bounties already credited stay reserved for their keepers
+        let reward_token: Address = env.storage().instance().get(&REWARD_TOKEN).unwrap();
+        let balance =
+            token::TokenClient::new(&env, &reward_token).balance(&env.current_contract_address());
+        let owed: i128 = env.storage().instance().get(&OWED).unwrap_or(0);
+        if balance < owed + task.reward_per_call {
+            return Err(KeeperError::Underfunded);
+        }
+
+        task.last_executed = Some(now);
+        task.executions += 1;
+        env.storage().persistent().set(&key, &task);
+        env.storage()
+            .instance()
+            .set(&OWED, &(owed + task.reward_per_call));
+
+        let claimable = Self::get_claimable(env.clone(), caller.clone()) + task.reward_per_call;
+        env.storage()
+            .persistent()
+            .set(&Self::claimable_key(&caller), &claimable);
+
+        // Emit execution event
+        env.events().publish(
+            (symbol_short!("keeper"), symbol_short!("executed")),
+            (contract, task_id, caller, task.reward_per_call),
+        );
+
+        Ok(task.reward_per_call)
+    }
+
+    /// Pay out a keeper's accumulated bounties. Returns the amount paid.
+    pub fn claim(env: Env, keeper: Address) -> Result<i128, KeeperError> {
+        keeper.require_auth();
+
+        let amount = Self::get_claimable(env.clone(), keeper.clone());
+        if amount <= 0 {
+            return Err(KeeperError::NothingToClaim);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&Self::claimable_key(&keeper));
+        let owed: i128 = env.storage().instance().get(&OWED).unwrap_or(0);
+        env.storage().instance().set(&OWED, &(owed - amount));
+
+        let reward_token: Address = env.storage().instance().get(&REWARD_TOKEN).unwrap();
+        token::TokenClient::new(&env, &reward_token).transfer(
+            &env.current_contract_address(),
+            &keeper,
+            &amount,
+        );
+
+        // Emit claim event
+        env.events().publish(
+            (symbol_short!("keeper"), symbol_short!("claim")),
+            (keeper, amount),
+        );
+
+        Ok(amount)
+    }
+
+    /// Get a registered task
+    pub fn get_task(env: Env, contract: Address, task_id: Symbol) -> Option<Task> {
+        env.storage()
+            .persistent()
+            .get(&Self::task_key(&contract, &task_id))
+    }
+
+    /// Get a keeper's unclaimed bounties
+    pub fn get_claimable(env: Env, keeper: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Self::claimable_key(&keeper))
+            .unwrap_or(0)
+    }
+
+    /// Get the total of credited but unclaimed bounties
+    pub fn get_owed(env: Env) -> i128 {
+        env.storage().instance().get(&OWED).unwrap_or(0)
+    }
+
+    /// Get the token bounties are paid in
+    pub fn get_reward_token(env: Env) -> Address {
+        env.storage().instance().get(&REWARD_TOKEN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn task_key(contract: &Address, task_id: &Symbol) -> (Symbol, Address, Symbol) {
+        (symbol_short!("task"), contract.clone(), task_id.clone())
+    }
+
+    fn claimable_key(keeper: &Address) -> (Symbol, Address) {
+        (symbol_short!("claim"), keeper.clone())
+    }
+}
+
+mod test;
@@ -0,0 +1,143 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::StellarAssetClient,
+    Address, Env,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+const UNIT: i128 = 10_000_000;
+const BOUNTY: i128 = 5 * UNIT;
+const INTERVAL: u64 = 86_400;
+
+struct KeeperTest<'a> {
+    env: Env,
+    escrow: KeeperEscrowClient<'a>,
+    tux: TuxTokenClient<'a>,
+    vault: TuxedoVaultClient<'a>,
+    usdc: StellarAssetClient<'a>,
+    keeper: Address,
+}
+
+impl<'a> KeeperTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let platform = Address::generate(&env);
+        let keeper = Address::generate(&env);
+
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&platform, &(1_000_000 * UNIT));
+
+        let escrow = KeeperEscrowClient::new(&env, &env.register(KeeperEscrow, ()));
+        escrow.initialize(&tux.address);
+        escrow.fund(&platform, &(100 * UNIT));
+
+        let usdc_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let vault = TuxedoVaultClient::new(
+            &env,
+            &env.register(
+                TuxedoVault,
+                (&admin, Address::generate(&env), &platform, &usdc_id, None::<u32>),
+            ),
+        );
+        vault.set_keeper(&escrow.address, &BOUNTY, &INTERVAL);
+
+        KeeperTest {
+            usdc: StellarAssetClient::new(&env, &usdc_id),
+            env,
+            escrow,
+            tux,
+            vault,
+            keeper,
+        }
+    }
+
+    /// Deposit and earn enough for `distribute_yield` to have work to do
+    fn accrue_yield(&self) {
+        let user = Address::generate(&self.env);
+        self.usdc.mint(&user, &(1_000 * UNIT));
+        self.vault.deposit(&user, &self.usdc.address, &(1_000 * UNIT), &0);
+        self.usdc.mint(&self.vault.address, &(100 * UNIT));
+    }
+}
+
+#[test]
+fn test_keeper_claims_distribution_bounty() {
+    let t = KeeperTest::setup();
+    let task = t
+        .escrow
+        .get_task(&t.vault.address, &symbol_short!("dist_yld"))
+        .unwrap();
+    assert_eq!(task.reward_per_call, BOUNTY);
+    assert_eq!(task.last_executed, None);
+
+    t.accrue_yield();
+    assert_eq!(t.vault.keeper_distribute_yield(&t.keeper), BOUNTY);
+    assert_eq!(t.escrow.get_claimable(&t.keeper), BOUNTY);
+
+    assert_eq!(t.escrow.claim(&t.keeper), BOUNTY);
+    assert_eq!(t.tux.balance(&t.keeper), BOUNTY);
+    assert_eq!(t.escrow.get_owed(), 0);
+    assert_eq!(
+        t.escrow.try_claim(&t.keeper),
+        Err(Ok(KeeperError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_second_execution_within_interval_earns_nothing() {
+    let t = KeeperTest::setup();
+    t.accrue_yield();
+    t.vault.keeper_distribute_yield(&t.keeper);
+
+    // The distribution itself still runs, but no bounty is credited
+    t.env.ledger().set_timestamp(1_000 + INTERVAL - 1);
+    t.accrue_yield();
+    assert_eq!(t.vault.keeper_distribute_yield(&t.keeper), 0);
+    assert_eq!(t.escrow.get_claimable(&t.keeper), BOUNTY);
+    assert_eq!(
+        t.escrow
+            .try_record_execution(&t.vault.address, &symbol_short!("dist_yld"), &t.keeper),
+        Err(Ok(KeeperError::TooSoon))
+    );
+
+    t.env.ledger().set_timestamp(1_000 + INTERVAL);
+    t.accrue_yield();
+    assert_eq!(t.vault.keeper_distribute_yield(&t.keeper), BOUNTY);
+    assert_eq!(t.escrow.get_claimable(&t.keeper), 2 * BOUNTY);
+}
+
+#[test]
+fn test_bounties_limited_to_funding() {
+    let t = KeeperTest::setup();
+    let task_id = symbol_short!("update");
+    let contract = Address::generate(&t.env);
+    t.escrow
+        .register_task(&contract, &task_id, &(60 * UNIT), &0);
+
+    assert_eq!(
+        t.escrow.record_execution(&contract, &task_id, &t.keeper),
+        60 * UNIT
+    );
+
+    // 40 TUX left unreserved can't cover another 60 TUX bounty
+    assert_eq!(
+        t.escrow
+            .try_record_execution(&contract, &task_id, &t.keeper),
+        Err(Ok(KeeperError::Underfunded))
+    );
+    assert_eq!(
+        t.escrow
+            .try_record_execution(&contract, &symbol_short!("other"), &t.keeper),
+        Err(Ok(KeeperError::TaskNotFound))
+    );
+}
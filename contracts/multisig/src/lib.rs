@@ -0,0 +1,303 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Symbol,
+    TryFromVal, Val, Vec,
+};
+
+// ============ Constants ============
+const MEMBERS: Symbol = symbol_short!("MEMBERS");
+const THRESHOLD: Symbol = symbol_short!("THRESHOLD");
+const PROPOSAL_TTL: Symbol = symbol_short!("TTL");
+const PROPOSAL_COUNT: Symbol = symbol_short!("PROP_CNT");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MultisigError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotMember = 3,
+    AlreadyMember = 4,
+    InvalidThreshold = 5,
+    ProposalNotFound = 6,
+    AlreadyConfirmed = 7,
+    AlreadyExecuted = 8,
+    ProposalExpired = 9,
+    ThresholdNotMet = 10,
+    InvalidCall = 11,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub target: Address,
+    pub fn_name: Symbol,
+    pub args: Vec<Val>,
+    pub confirmations: Vec<Address>,
+    pub expires_at: u64,
+    pub executed: bool,
+}
+
+// ============ Multisig Contract ============
+/// m-of-n multisig meant to hold admin roles on other protocol contracts.
+///
+/// Membership changes go through the multisig itself: propose a call with the
+/// multisig as `target` and `add_member`, `remove_member` or `set_threshold` as
+/// `fn_name`. Those calls are applied in place by `execute`, since a contract
+/// cannot invoke itself.
+#[contract]
+pub struct Multisig;
+
+#[contractimpl]
+impl Multisig {
+    /// Initialize the multisig.
+    ///
+    /// Arguments:
+    /// - members: Addresses allowed to propose and confirm
+    /// - threshold: Confirmations needed to execute a proposal
+    /// - proposal_ttl: Seconds a proposal stays open after it is created
+    pub fn initialize(
+        env: Env,
+        members: Vec<Address>,
+        threshold: u32,
+        proposal_ttl: u64,
+    ) -> Result<(), MultisigError> {
+        // Check if already initialized
+        if env.storage().instance().has(&MEMBERS) {
+            return Err(MultisigError::AlreadyInitialized);
+        }
+
+        // Reject duplicate members so they can't count twice
+        let mut unique = Vec::new(&env);
+        for member in members.iter() {
+            if unique.contains(&member) {
+                return Err(MultisigError::AlreadyMember);
+            }
+            unique.push_back(member);
+        }
+        Self::check_threshold(threshold, unique.len())?;
+
+        env.storage().instance().set(&MEMBERS, &unique);
+        env.storage().instance().set(&THRESHOLD, &threshold);
+        env.storage().instance().set(&PROPOSAL_TTL, &proposal_ttl);
+        env.storage().instance().set(&PROPOSAL_COUNT, &0u32);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("msig"), symbol_short!("init")),
+            (unique, threshold, proposal_ttl),
+        );
+
+        Ok(())
+    }
+
+    /// Propose a call (members only). The proposer's confirmation is counted.
+    /// Returns the proposal id.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        target: Address,
+        fn_name: Symbol,
+        args: Vec<Val>,
+    ) -> Result<u32, MultisigError> {
+        proposer.require_auth();
+        Self::require_member(&env, &proposer)?;
+
+        let ttl: u64 = env.storage().instance().get(&PROPOSAL_TTL).unwrap_or(0);
+        let proposal_id: u32 = env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0) + 1;
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            target: target.clone(),
+            fn_name: fn_name.clone(),
+            args,
+            confirmations: Vec::from_array(&env, [proposer.clone()]),
+            expires_at: env.ledger().timestamp() + ttl,
+            executed: false,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&Self::proposal_key(proposal_id), &proposal);
+        env.storage().instance().set(&PROPOSAL_COUNT, &proposal_id);
+
+        // Emit proposal event
+        env.events().publish(
+            (symbol_short!("msig"), symbol_short!("propose")),
+            (proposal_id, proposer, target, fn_name),
+        );
+
+        Ok(proposal_id)
+    }
+
+    /// Confirm an open proposal (members only)
+    pub fn confirm(env: Env, member: Address, proposal_id: u32) -> Result<u32, MultisigError> {
+        member.require_auth();
+        Self::require_member(&env, &member)?;
+
+        let mut proposal = Self::load_open_proposal(&env, proposal_id)?;
+        if proposal.confirmations.contains(&member) {
+            return Err(MultisigError::AlreadyConfirmed);
+        }
+
+        proposal.confirmations.push_back(member.clone());
+        env.storage()
+            .persistent()
+            .set(&Self::proposal_key(proposal_id), &proposal);
+
+        // Emit confirmation event
+        env.events().publish(
+            (symbol_short!("msig"), symbol_short!("confirm")),
+            (proposal_id, member),
+        );
+
+        Ok(proposal.confirmations.len())
+    }
+
+    /// Execute a proposal once enough current members have confirmed it.
+    /// Anyone may call this.
+    pub fn execute(env: Env, proposal_id: u32) -> Result<(), MultisigError> {
+        let mut proposal = Self::load_open_proposal(&env, proposal_id)?;
+
+        // Confirmations from removed members no longer count
+        let members = Self::get_members(env.clone());
+        let confirmed = proposal
+            .confirmations
+            .iter()
+            .filter(|member| members.contains(member))
+            .count() as u32;
+        if confirmed < Self::get_threshold(env.clone()) {
+            return Err(MultisigError::ThresholdNotMet);
+        }
+
+        // Mark executed before the external call
+        proposal.executed = true;
+        env.storage()
+            .persistent()
+            .set(&Self::proposal_key(proposal_id), &proposal);
+
+        if proposal.target == env.current_contract_address() {
+            Self::apply_self_call(&env, &proposal.fn_name, &proposal.args)?;
+        } else {
+            env.invoke_contract::<Val>(&proposal.target, &proposal.fn_name, proposal.args);
+        }
+
+        // Emit execution event
+        env.events().publish(
+            (symbol_short!("msig"), symbol_short!("execute")),
+            proposal_id,
+        );
+
+        Ok(())
+    }
+
+    /// Get a proposal
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, MultisigError> {
+        env.storage()
+            .persistent()
+            .get(&Self::proposal_key(proposal_id))
+            .ok_or(MultisigError::ProposalNotFound)
+    }
+
+    /// Get the number of proposals created
+    pub fn get_proposal_count(env: Env) -> u32 {
+        env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0)
+    }
+
+    /// Get the current members
+    pub fn get_members(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&MEMBERS)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the number of confirmations needed to execute
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage().instance().get(&THRESHOLD).unwrap_or(0)
+    }
+
+    /// Check whether `address` is a member
+    pub fn is_member(env: Env, address: Address) -> bool {
+        Self::get_members(env).contains(&address)
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn require_member(env: &Env, address: &Address) -> Result<(), MultisigError> {
+        if !env.storage().instance().has(&MEMBERS) {
+            return Err(MultisigError::NotInitialized);
+        }
+        if !Self::is_member(env.clone(), address.clone()) {
+            return Err(MultisigError::NotMember);
+        }
+        Ok(())
+    }
+
+    fn check_threshold(threshold: u32, member_count: u32) -> Result<(), MultisigError> {
+        if threshold == 0 || threshold > member_count {
+            return Err(MultisigError::InvalidThreshold);
+        }
+        Ok(())
+    }
+
+    fn load_open_proposal(env: &Env, proposal_id: u32) -> Result<Proposal, MultisigError> {
+        let proposal = Self::get_proposal(env.clone(), proposal_id)?;
+        if proposal.executed {
+            return Err(MultisigError::AlreadyExecuted);
+        }
+        if env.ledger().timestamp() > proposal.expires_at {
+            return Err(MultisigError::ProposalExpired);
+        }
+        Ok(proposal)
+    }
+
+    /// Apply a membership or threshold change the multisig proposed to itself
+    fn apply_self_call(env: &Env, fn_name: &Symbol, args: &Vec<Val>) -> Result<(), MultisigError> {
+        let arg = args.get(0).ok_or(MultisigError::InvalidCall)?;
+        let mut members = Self::get_members(env.clone());
+        let mut threshold = Self::get_threshold(env.clone());
+
+        if *fn_name == Symbol::new(env, "add_member") {
+            let member =
+                Address::try_from_val(env, &arg).map_err(|_| MultisigError::InvalidCall)?;
+            if members.contains(&member) {
+                return Err(MultisigError::AlreadyMember);
+            }
+            members.push_back(member);
+        } else if *fn_name == Symbol::new(env, "remove_member") {
+            let member =
+                Address::try_from_val(env, &arg).map_err(|_| MultisigError::InvalidCall)?;
+            let index = members
+                .first_index_of(&member)
+                .ok_or(MultisigError::NotMember)?;
+            members.remove(index);
+        } else if *fn_name == Symbol::new(env, "set_threshold") {
+            threshold = u32::try_from_val(env, &arg).map_err(|_| MultisigError::InvalidCall)?;
+        } else {
+            return Err(MultisigError::InvalidCall);
+        }
+
+        // Membership can never drop below the threshold
+        Self::check_threshold(threshold, members.len())?;
+        env.storage().instance().set(&MEMBERS, &members);
+        env.storage().instance().set(&THRESHOLD, &threshold);
+
+        // Emit membership update event
+        env.events().publish(
+            (symbol_short!("msig"), symbol_short!("members")),
+            (members, threshold),
+        );
+
+        Ok(())
+    }
+
+    fn proposal_key(proposal_id: u32) -> (Symbol, u32) {
+        (symbol_short!("prop"), proposal_id)
+    }
+}
+
+mod test;
@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env, IntoVal,
+};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+const TTL: u64 = 7 * 86_400;
+
+struct MultisigTest<'a> {
+    env: Env,
+    multisig: MultisigClient<'a>,
+    vault: TuxedoVaultClient<'a>,
+    members: [Address; 3],
+}
+
+impl<'a> MultisigTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let members = [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+
+        let multisig = MultisigClient::new(&env, &env.register(Multisig, ()));
+        multisig.initialize(
+            &vec![
+                &env,
+                members[0].clone(),
+                members[1].clone(),
+                members[2].clone(),
+            ],
+            &2,
+            &TTL,
+        );
+
+        // The vault's admin role points at the multisig
+        let usdc = env
+            .register_stellar_asset_contract_v2(members[0].clone())
+            .address();
+        let vault = TuxedoVaultClient::new(
+            &env,
+            &env.register(
+                TuxedoVault,
+                (&multisig.address, &members[0], &members[1], &usdc, None::<u32>),
+            ),
+        );
+
+        MultisigTest {
+            env,
+            multisig,
+            vault,
+            members,
+        }
+    }
+
+    fn propose_fee(&self, fee_bps: u32) -> u32 {
+        self.multisig.propose(
+            &self.members[0],
+            &self.vault.address,
+            &Symbol::new(&self.env, "set_platform_fee_bps"),
+            &vec![&self.env, fee_bps.into_val(&self.env)],
+        )
+    }
+
+    fn propose_self(&self, fn_name: &str, arg: Val) -> u32 {
+        self.multisig.propose(
+            &self.members[0],
+            &self.multisig.address,
+            &Symbol::new(&self.env, fn_name),
+            &vec![&self.env, arg],
+        )
+    }
+}
+
+#[test]
+fn test_execute_after_threshold() {
+    let t = MultisigTest::setup();
+    let id = t.propose_fee(500);
+
+    assert_eq!(
+        t.multisig.try_execute(&id),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+
+    assert_eq!(t.multisig.confirm(&t.members[1], &id), 2);
+    t.multisig.execute(&id);
+    assert_eq!(t.vault.get_platform_fee_bps(), 500);
+    assert!(t.multisig.get_proposal(&id).executed);
+    assert_eq!(
+        t.multisig.try_execute(&id),
+        Err(Ok(MultisigError::AlreadyExecuted))
+    );
+}
+
+#[test]
+fn test_confirm_guards() {
+    let t = MultisigTest::setup();
+    let id = t.propose_fee(500);
+
+    assert_eq!(
+        t.multisig.try_confirm(&t.members[0], &id),
+        Err(Ok(MultisigError::AlreadyConfirmed))
+    );
+
+    let outsider = Address::generate(&t.env);
+    assert_eq!(
+        t.multisig.try_confirm(&outsider, &id),
+        Err(Ok(MultisigError::NotMember))
+    );
+
+    // Expired proposals can't be confirmed or executed
+    t.env.ledger().set_timestamp(TTL + 1);
+    assert_eq!(
+        t.multisig.try_confirm(&t.members[1], &id),
+        Err(Ok(MultisigError::ProposalExpired))
+    );
+    assert_eq!(
+        t.multisig.try_execute(&id),
+        Err(Ok(MultisigError::ProposalExpired))
+    );
+}
+
+#[test]
+fn test_membership_is_self_governed() {
+    let t = MultisigTest::setup();
+    let newcomer = Address::generate(&t.env);
+
+    let id = t.propose_self("add_member", newcomer.into_val(&t.env));
+    t.multisig.confirm(&t.members[1], &id);
+    t.multisig.execute(&id);
+    assert!(t.multisig.is_member(&newcomer));
+
+    let id = t.propose_self("set_threshold", 3u32.into_val(&t.env));
+    t.multisig.confirm(&t.members[2], &id);
+    t.multisig.execute(&id);
+    assert_eq!(t.multisig.get_threshold(), 3);
+
+    // Removing members below the threshold is rejected
+    let id = t.propose_self("remove_member", t.members[2].into_val(&t.env));
+    t.multisig.confirm(&t.members[1], &id);
+    t.multisig.confirm(&newcomer, &id);
+    t.multisig.execute(&id);
+    assert_eq!(t.multisig.get_members().len(), 3);
+
+    let id = t.propose_self("remove_member", t.members[1].into_val(&t.env));
+    t.multisig.confirm(&t.members[1], &id);
+    t.multisig.confirm(&newcomer, &id);
+    assert_eq!(
+        t.multisig.try_execute(&id),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+}
+
+#[test]
+fn test_removed_member_confirmation_stops_counting() {
+    let t = MultisigTest::setup();
+    let id = t.propose_fee(500);
+    t.multisig.confirm(&t.members[1], &id);
+
+    let remove = t.propose_self("remove_member", t.members[1].into_val(&t.env));
+    t.multisig.confirm(&t.members[2], &remove);
+    t.multisig.execute(&remove);
+
+    assert_eq!(
+        t.multisig.try_execute(&id),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+    t.multisig.confirm(&t.members[2], &id);
+    t.multisig.execute(&id);
+    assert_eq!(t.vault.get_platform_fee_bps(), 500);
+}
+
+#[test]
+fn test_initialize_validates_threshold() {
+    let env = Env::default();
+    let member = Address::generate(&env);
+    let multisig = MultisigClient::new(&env, &env.register(Multisig, ()));
+
+    assert_eq!(
+        multisig.try_initialize(&vec![&env, member.clone()], &2, &TTL),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+    assert_eq!(
+        multisig.try_initialize(&vec![&env, member.clone(), member.clone()], &1, &TTL),
+        Err(Ok(MultisigError::AlreadyMember))
+    );
+}
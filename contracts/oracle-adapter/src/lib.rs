@@ -0,0 +1,114 @@
+#![no_std]
+
+//! Typed adapter for Reflector-compatible (SEP-40) price feeds.
+//!
+//! Wraps the feed's `lastprice`/`decimals` entrypoints with a staleness
+//! check, and keeps optional fixed fallback prices in the calling contract's
+//! storage for testnets where the feed does not list an asset.
+
+use soroban_sdk::{contractclient, contracttype, symbol_short, Address, Env, Symbol};
+
+// ============ Feed Interface ============
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contractclient(name = "ReflectorClient")]
+pub trait Reflector {
+    /// Most recent price of `asset`, if the feed tracks it
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+
+    /// Number of decimals prices are scaled by
+    fn decimals(env: Env) -> u32;
+}
+
+// ============ Errors ============
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OracleError {
+    /// Neither the feed nor a fallback has a price for the asset
+    PriceUnavailable,
+    /// The feed's latest price is older than the allowed age
+    StalePrice,
+}
+
+/// Fixed price used when the feed can't price an asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FixedPrice {
+    pub price: i128,
+    pub decimals: u32,
+}
+
+// ============ Price Helpers ============
+/// Price of `asset` as `(price, timestamp, decimals)`.
+///
+/// A fresh feed price wins. If the feed is unset, doesn't list the asset, or
+/// its price is older than `max_age` seconds, the asset's fallback price is
+/// used when one is configured.
+pub fn get_price(
+    env: &Env,
+    feed: Option<&Address>,
+    asset: &Address,
+    max_age: u64,
+) -> Result<(i128, u64, u32), OracleError> {
+    let feed_price = feed.map(|feed| read_feed(env, feed, asset, max_age));
+
+    match (feed_price, fallback_price(env, asset)) {
+        (Some(Ok(price)), _) => Ok(price),
+        (_, Some(fixed)) => Ok((fixed.price, env.ledger().timestamp(), fixed.decimals)),
+        (Some(Err(err)), None) => Err(err),
+        (None, None) => Err(OracleError::PriceUnavailable),
+    }
+}
+
+/// Set the fixed fallback price for `asset` in the calling contract's storage
+pub fn set_fallback_price(env: &Env, asset: &Address, price: FixedPrice) {
+    env.storage().persistent().set(&fallback_key(asset), &price);
+}
+
+/// Remove the fixed fallback price for `asset`
+pub fn remove_fallback_price(env: &Env, asset: &Address) {
+    env.storage().persistent().remove(&fallback_key(asset));
+}
+
+/// Fixed fallback price for `asset`, if configured
+pub fn fallback_price(env: &Env, asset: &Address) -> Option<FixedPrice> {
+    env.storage().persistent().get(&fallback_key(asset))
+}
+
+fn read_feed(
+    env: &Env,
+    feed: &Address,
+    asset: &Address,
+    max_age: u64,
+) -> Result<(i128, u64, u32), OracleError> {
+    let client = ReflectorClient::new(env, feed);
+    let data = client
+        .lastprice(&Asset::Stellar(asset.clone()))
+        .ok_or(OracleError::PriceUnavailable)?;
+
+    if data.price <= 0 {
+        return Err(OracleError::PriceUnavailable);
+    }
+
+    // Reject prices older than the allowed age
+    if env.ledger().timestamp().saturating_sub(data.timestamp) > max_age {
+        return Err(OracleError::StalePrice);
+    }
+
+    Ok((data.price, data.timestamp, client.decimals()))
+}
+
+fn fallback_key(asset: &Address) -> (Symbol, Address) {
+    (symbol_short!("fb_price"), asset.clone())
+}
@@ -0,0 +1,120 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol,
+};
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const ENTRIES: Symbol = symbol_short!("ENTRIES");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RegistryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub address: Address,
+    /// Starts at 1 and increases every time the key is pointed somewhere new
+    pub version: u32,
+    pub updated_at: u64,
+}
+
+// ============ Registry Contract ============
+/// Names the deployed Tuxedo contracts (vault, token, farming, ...) so
+/// frontends, the agent, and other contracts can look them up instead of
+/// hardcoding addresses per network
+#[contract]
+pub struct TuxedoRegistry;
+
+#[contractimpl]
+impl TuxedoRegistry {
+    /// Initialize the registry with the admin allowed to change entries
+    pub fn initialize(env: Env, admin: Address) -> Result<(), RegistryError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(RegistryError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+
+        // Emit initialization event
+        env.events()
+            .publish((symbol_short!("registry"), symbol_short!("init")), admin);
+
+        Ok(())
+    }
+
+    /// Point `key` at `addr` (admin only). Returns the entry's new version.
+    pub fn set(env: Env, key: Symbol, addr: Address) -> Result<u32, RegistryError> {
+        Self::require_admin(&env)?;
+
+        let mut entries = Self::list(env.clone());
+        let version = match entries.get(key.clone()) {
+            Some(entry) if entry.address == addr => return Ok(entry.version),
+            Some(entry) => entry.version + 1,
+            None => 1,
+        };
+        entries.set(
+            key.clone(),
+            Entry {
+                address: addr.clone(),
+                version,
+                updated_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage().instance().set(&ENTRIES, &entries);
+
+        // Emit entry update event
+        env.events().publish(
+            (symbol_short!("registry"), symbol_short!("set")),
+            (key, addr, version),
+        );
+
+        Ok(version)
+    }
+
+    /// Get the address registered under `key`
+    pub fn get(env: Env, key: Symbol) -> Option<Address> {
+        Self::get_entry(env, key).map(|entry| entry.address)
+    }
+
+    /// Get the entry registered under `key`, with its version
+    pub fn get_entry(env: Env, key: Symbol) -> Option<Entry> {
+        Self::list(env).get(key)
+    }
+
+    /// Get every entry by key
+    pub fn list(env: Env) -> Map<Symbol, Entry> {
+        env.storage()
+            .instance()
+            .get(&ENTRIES)
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn require_admin(env: &Env) -> Result<(), RegistryError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(RegistryError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+}
+
+mod test;
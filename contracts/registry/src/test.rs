@@ -0,0 +1,63 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+
+struct RegistryTest<'a> {
+    env: Env,
+    registry: TuxedoRegistryClient<'a>,
+}
+
+impl<'a> RegistryTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = TuxedoRegistryClient::new(&env, &env.register(TuxedoRegistry, ()));
+        registry.initialize(&Address::generate(&env));
+
+        RegistryTest { env, registry }
+    }
+}
+
+#[test]
+fn test_set_and_list() {
+    let t = RegistryTest::setup();
+    let (vault, token) = (Address::generate(&t.env), Address::generate(&t.env));
+
+    assert_eq!(t.registry.set(&symbol_short!("vault"), &vault), 1);
+    assert_eq!(t.registry.set(&symbol_short!("token"), &token), 1);
+    assert_eq!(t.registry.get(&symbol_short!("vault")), Some(vault.clone()));
+    assert_eq!(t.registry.get(&symbol_short!("farming")), None);
+
+    let entries = t.registry.list();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries.get(symbol_short!("token")).unwrap().address, token);
+}
+
+#[test]
+fn test_override_bumps_version() {
+    let t = RegistryTest::setup();
+    let key = symbol_short!("vault");
+    let (v1, v2) = (Address::generate(&t.env), Address::generate(&t.env));
+    t.registry.set(&key, &v1);
+
+    t.env.ledger().set_timestamp(500);
+    assert_eq!(t.registry.set(&key, &v2), 2);
+    let entry = t.registry.get_entry(&key).unwrap();
+    assert_eq!(
+        entry,
+        Entry {
+            address: v2.clone(),
+            version: 2,
+            updated_at: 500,
+        }
+    );
+
+    // Re-registering the same address is not an upgrade
+    assert_eq!(t.registry.set(&key, &v2), 2);
+    assert_eq!(t.registry.list().len(), 1);
+}
@@ -0,0 +1,375 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+use tuxedo_common::{constants::REWARD_PRECISION, events, math};
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const TUX_TOKEN: Symbol = symbol_short!("TUX_TKN");
+const REWARD_TOKEN: Symbol = symbol_short!("RWD_TKN");
+const NOTIFIER: Symbol = symbol_short!("NOTIFIER");
+const REWARDS: Symbol = symbol_short!("REWARDS");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RevenueShareError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    NothingToClaim = 5,
+    RewardNotFunded = 6,
+    MathOverflow = 7,
+}
+
+// ============ Data Structures ============
+/// Reward distribution state shared by all stakers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardState {
+    pub reward_per_share: i128, // scaled by REWARD_PRECISION
+    pub total_staked: i128,
+    /// Rewards notified while nobody was staked, credited to the next stake
+    pub queued: i128,
+    /// Rewards notified and not yet claimed
+    pub reserved: i128,
+}
+
+/// A staker's reward position
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserRewards {
+    pub reward_per_share_paid: i128,
+    pub accrued: i128,
+}
+
+// ============ Revenue Share Contract ============
+/// Pays platform fees, in USDC, to TUX stakers pro-rata.
+///
+/// The fee splitter transfers USDC here and calls `notify_reward`; every staker's
+/// share accrues with the same reward-per-share accounting as farming.
+#[contract]
+pub struct TuxRevenueShare;
+
+#[contractimpl]
+impl TuxRevenueShare {
+    /// Initialize the revenue share contract.
+    ///
+    /// Arguments:
+    /// - admin: Address allowed to change the notifier
+    /// - tux_token: Token that is staked
+    /// - reward_token: Token paid out (USDC)
+    /// - notifier: Address allowed to call `notify_reward` (the fee splitter)
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        tux_token: Address,
+        reward_token: Address,
+        notifier: Address,
+    ) -> Result<(), RevenueShareError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(RevenueShareError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&TUX_TOKEN, &tux_token);
+        env.storage().instance().set(&REWARD_TOKEN, &reward_token);
+        env.storage().instance().set(&NOTIFIER, &notifier);
+        env.storage().instance().set(
+            &REWARDS,
+            &RewardState {
+                reward_per_share: 0,
+                total_staked: 0,
+                queued: 0,
+                reserved: 0,
+            },
+        );
+
+        // Emit initialization event
+        events::publish(
+            &env,
+            symbol_short!("revshare"),
+            symbol_short!("init"),
+            (tux_token, reward_token, notifier),
+        );
+
+        Ok(())
+    }
+
+    /// Replace the address allowed to notify rewards (admin only)
+    pub fn set_notifier(env: Env, notifier: Address) -> Result<(), RevenueShareError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(RevenueShareError::NotInitialized)?;
+        admin.require_auth();
+
+        env.storage().instance().set(&NOTIFIER, &notifier);
+
+        // Emit notifier update event
+        events::publish(
+            &env,
+            symbol_short!("revshare"),
+            symbol_short!("notifier"),
+            notifier,
+        );
+
+        Ok(())
+    }
+
+    /// Stake TUX to earn a share of platform fees
+    pub fn stake(env: Env, user: Address, amount: i128) -> Result<(), RevenueShareError> {
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(RevenueShareError::InvalidAmount);
+        }
+
+        let mut state = Self::load_state(&env)?;
+        let tux_token: Address = env.storage().instance().get(&TUX_TOKEN).unwrap();
+        token::TokenClient::new(&env, &tux_token).transfer(
+            &user,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        // Settle rewards earned on the old stake
+        let stake = Self::get_user_stake(env.clone(), user.clone());
+        Self::settle_user(&env, &user, &state, stake);
+        Self::save_stake(&env, &user, stake + amount);
+
+        // Rewards that arrived while nobody was staked go to the first stakers
+        state.total_staked += amount;
+        if state.queued > 0 {
+            state.reward_per_share +=
+                math::muldiv(state.queued, REWARD_PRECISION, state.total_staked)
+                    .ok_or(RevenueShareError::MathOverflow)?;
+            state.queued = 0;
+        }
+        env.storage().instance().set(&REWARDS, &state);
+
+        // Emit stake event
+        events::publish(
+            &env,
+            symbol_short!("revshare"),
+            symbol_short!("stake"),
+            (user, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Unstake TUX. Accrued USDC stays claimable.
+    pub fn unstake(env: Env, user: Address, amount: i128) -> Result<(), RevenueShareError> {
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(RevenueShareError::InvalidAmount);
+        }
+
+        let mut state = Self::load_state(&env)?;
+        let stake = Self::get_user_stake(env.clone(), user.clone());
+        if stake < amount {
+            return Err(RevenueShareError::InsufficientBalance);
+        }
+
+        // Settle rewards earned on the old stake
+        Self::settle_user(&env, &user, &state, stake);
+        Self::save_stake(&env, &user, stake - amount);
+        state.total_staked -= amount;
+        env.storage().instance().set(&REWARDS, &state);
+
+        let tux_token: Address = env.storage().instance().get(&TUX_TOKEN).unwrap();
+        token::TokenClient::new(&env, &tux_token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &amount,
+        );
+
+        // Emit unstake event
+        events::publish(
+            &env,
+            symbol_short!("revshare"),
+            symbol_short!("unstake"),
+            (user, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Claim accrued USDC. Returns the amount paid.
+    pub fn claim(env: Env, user: Address) -> Result<i128, RevenueShareError> {
+        user.require_auth();
+
+        let mut state = Self::load_state(&env)?;
+        let stake = Self::get_user_stake(env.clone(), user.clone());
+        let mut rewards = Self::settle_user(&env, &user, &state, stake);
+
+        let payout = rewards.accrued;
+        if payout <= 0 {
+            return Err(RevenueShareError::NothingToClaim);
+        }
+
+        rewards.accrued = 0;
+        env.storage()
+            .persistent()
+            .set(&Self::rewards_key(&user), &rewards);
+        state.reserved -= payout;
+        env.storage().instance().set(&REWARDS, &state);
+
+        let reward_token: Address = env.storage().instance().get(&REWARD_TOKEN).unwrap();
+        token::TokenClient::new(&env, &reward_token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &payout,
+        );
+
+        // Emit claim event
+        events::publish(
+            &env,
+            symbol_short!("revshare"),
+            symbol_short!("claim"),
+            (user, payout),
+        );
+
+        Ok(payout)
+    }
+
+    /// Distribute `amount` of USDC already transferred in to current stakers
+    /// (notifier only). The contract must hold enough unreserved USDC to cover it.
+    pub fn notify_reward(env: Env, amount: i128) -> Result<(), RevenueShareError> {
+        let notifier: Address = env
+            .storage()
+            .instance()
+            .get(&NOTIFIER)
+            .ok_or(RevenueShareError::NotInitialized)?;
+        notifier.require_auth();
+
+        if amount <= 0 {
+            return Err(RevenueShareError::InvalidAmount);
+        }
+
+        // Only account for USDC that has actually arrived
+        let mut state = Self::load_state(&env)?;
+        let reward_token: Address = env.storage().instance().get(&REWARD_TOKEN).unwrap();
+        let balance =
+            token::TokenClient::new(&env, &reward_token).balance(&env.current_contract_address());
+        if balance < state.reserved + amount {
+            return Err(RevenueShareError::RewardNotFunded);
+        }
+
+        state.reserved += amount;
+        if state.total_staked > 0 {
+            let distributable = state.queued + amount;
+            state.reward_per_share +=
+                math::muldiv(distributable, REWARD_PRECISION, state.total_staked)
+                    .ok_or(RevenueShareError::MathOverflow)?;
+            state.queued = 0;
+        } else {
+            state.queued += amount;
+        }
+        env.storage().instance().set(&REWARDS, &state);
+
+        // Emit reward event
+        events::publish(
+            &env,
+            symbol_short!("revshare"),
+            symbol_short!("reward"),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Get a user's unclaimed USDC
+    pub fn get_pending_rewards(env: Env, user: Address) -> i128 {
+        let state = match Self::load_state(&env) {
+            Ok(state) => state,
+            Err(_) => return 0,
+        };
+        let stake = Self::get_user_stake(env.clone(), user.clone());
+        let rewards = Self::load_user(&env, &user);
+        let delta = state.reward_per_share - rewards.reward_per_share_paid;
+        rewards.accrued + math::muldiv(stake, delta, REWARD_PRECISION).unwrap()
+    }
+
+    /// Get a user's staked TUX
+    pub fn get_user_stake(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Self::stake_key(&user))
+            .unwrap_or(0)
+    }
+
+    /// Get the reward distribution state
+    pub fn get_reward_state(env: Env) -> Result<RewardState, RevenueShareError> {
+        Self::load_state(&env)
+    }
+
+    /// Get the address allowed to notify rewards
+    pub fn get_notifier(env: Env) -> Address {
+        env.storage().instance().get(&NOTIFIER).unwrap()
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn load_state(env: &Env) -> Result<RewardState, RevenueShareError> {
+        env.storage()
+            .instance()
+            .get(&REWARDS)
+            .ok_or(RevenueShareError::NotInitialized)
+    }
+
+    fn load_user(env: &Env, user: &Address) -> UserRewards {
+        env.storage()
+            .persistent()
+            .get(&Self::rewards_key(user))
+            .unwrap_or(UserRewards {
+                reward_per_share_paid: 0,
+                accrued: 0,
+            })
+    }
+
+    /// Credit rewards earned by `stake` since the user's last settlement
+    fn settle_user(env: &Env, user: &Address, state: &RewardState, stake: i128) -> UserRewards {
+        let mut rewards = Self::load_user(env, user);
+        let delta = state.reward_per_share - rewards.reward_per_share_paid;
+        rewards.accrued += math::muldiv(stake, delta, REWARD_PRECISION).unwrap();
+        rewards.reward_per_share_paid = state.reward_per_share;
+        env.storage()
+            .persistent()
+            .set(&Self::rewards_key(user), &rewards);
+        rewards
+    }
+
+    fn save_stake(env: &Env, user: &Address, stake: i128) {
+        if stake == 0 {
+            env.storage().persistent().remove(&Self::stake_key(user));
+        } else {
+            env.storage()
+                .persistent()
+                .set(&Self::stake_key(user), &stake);
+        }
+    }
+
+    fn stake_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("stake"), user.clone())
+    }
+
+    fn rewards_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("rewards"), user.clone())
+    }
+}
+
+mod test;
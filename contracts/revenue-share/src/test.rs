@@ -0,0 +1,116 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+
+const UNIT: i128 = 10_000_000;
+
+struct RevenueShareTest<'a> {
+    revshare: TuxRevenueShareClient<'a>,
+    usdc: TokenClient<'a>,
+    usdc_admin: StellarAssetClient<'a>,
+    alice: Address,
+    bob: Address,
+}
+
+impl<'a> RevenueShareTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let splitter = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        let usdc_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &(1_000_000 * UNIT));
+        tux.transfer(&admin, &alice, &(1_000 * UNIT));
+        tux.transfer(&admin, &bob, &(1_000 * UNIT));
+
+        let revshare = TuxRevenueShareClient::new(&env, &env.register(TuxRevenueShare, ()));
+        revshare.initialize(&admin, &tux.address, &usdc_id, &splitter);
+
+        RevenueShareTest {
+            usdc: TokenClient::new(&env, &usdc_id),
+            usdc_admin: StellarAssetClient::new(&env, &usdc_id),
+            revshare,
+            alice,
+            bob,
+        }
+    }
+
+    /// Transfer USDC in and notify it, as the fee splitter does
+    fn pay_fees(&self, amount: i128) {
+        self.usdc_admin.mint(&self.revshare.address, &amount);
+        self.revshare.notify_reward(&amount);
+    }
+}
+
+#[test]
+fn test_rewards_split_pro_rata() {
+    let t = RevenueShareTest::setup();
+    t.revshare.stake(&t.alice, &(400 * UNIT));
+    t.revshare.stake(&t.bob, &(100 * UNIT));
+
+    t.pay_fees(40 * UNIT);
+    assert_eq!(t.revshare.get_pending_rewards(&t.alice), 32 * UNIT);
+    assert_eq!(t.revshare.get_pending_rewards(&t.bob), 8 * UNIT);
+
+    // Bob leaves; only Alice earns the next payment but Bob keeps his share
+    t.revshare.unstake(&t.bob, &(100 * UNIT));
+    t.pay_fees(10 * UNIT);
+
+    assert_eq!(t.revshare.claim(&t.alice), 42 * UNIT);
+    assert_eq!(t.revshare.claim(&t.bob), 8 * UNIT);
+    assert_eq!(t.usdc.balance(&t.alice), 42 * UNIT);
+    assert_eq!(t.usdc.balance(&t.bob), 8 * UNIT);
+    assert_eq!(t.revshare.get_reward_state().reserved, 0);
+    assert_eq!(
+        t.revshare.try_claim(&t.bob),
+        Err(Ok(RevenueShareError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_rewards_before_first_stake_are_queued() {
+    let t = RevenueShareTest::setup();
+    t.pay_fees(5 * UNIT);
+    assert_eq!(t.revshare.get_reward_state().queued, 5 * UNIT);
+
+    t.revshare.stake(&t.alice, &(10 * UNIT));
+    assert_eq!(t.revshare.get_pending_rewards(&t.alice), 5 * UNIT);
+
+    // Later stakers don't share in the queued amount
+    t.revshare.stake(&t.bob, &(10 * UNIT));
+    assert_eq!(t.revshare.get_pending_rewards(&t.bob), 0);
+}
+
+#[test]
+fn test_notify_requires_funds() {
+    let t = RevenueShareTest::setup();
+    t.revshare.stake(&t.alice, &(10 * UNIT));
+    t.pay_fees(5 * UNIT);
+
+    // USDC already owed to stakers can't be notified twice
+    assert_eq!(
+        t.revshare.try_notify_reward(&(5 * UNIT)),
+        Err(Ok(RevenueShareError::RewardNotFunded))
+    );
+    assert_eq!(
+        t.revshare.try_notify_reward(&0),
+        Err(Ok(RevenueShareError::InvalidAmount))
+    );
+    assert_eq!(
+        t.revshare.try_unstake(&t.alice, &(11 * UNIT)),
+        Err(Ok(RevenueShareError::InsufficientBalance))
+    );
+}
@@ -0,0 +1,63 @@
+//! Event schema of the revenue share contract: each operation's full event
+//! stream, decoded field by field. The indexer depends on these shapes, so
+//! changing one means updating the expectation here.
+
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, TryFromVal, Val};
+use tuxedo_fixtures::{events::EventLog, TuxedoFixture, UNIT};
+use tuxedo_revenue_share::{TuxRevenueShare, TuxRevenueShareClient};
+
+/// Decode the only event the last invocation published
+fn single<D: TryFromVal<Env, Val>>(revshare: &TuxRevenueShareClient, action: Symbol) -> D {
+    let mut log = EventLog::new(&revshare.env, &revshare.address, symbol_short!("revshare"));
+    let data = log.next(action);
+    log.finish();
+    data
+}
+
+#[test]
+fn test_revenue_share_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let notifier = Address::generate(&t.env);
+    let user = Address::generate(&t.env);
+    t.tux.transfer(&t.admin, &user, &(100 * UNIT));
+
+    let revshare = TuxRevenueShareClient::new(&t.env, &t.env.register(TuxRevenueShare, ()));
+    revshare.initialize(&t.admin, &t.tux.address, &t.usdc.address, &notifier);
+    assert_eq!(
+        single::<(Address, Address, Address)>(&revshare, symbol_short!("init")),
+        (t.tux.address.clone(), t.usdc.address.clone(), notifier)
+    );
+
+    let notifier = Address::generate(&t.env);
+    revshare.set_notifier(&notifier);
+    assert_eq!(
+        single::<Address>(&revshare, symbol_short!("notifier")),
+        notifier
+    );
+
+    revshare.stake(&user, &(100 * UNIT));
+    assert_eq!(
+        single::<(Address, i128)>(&revshare, symbol_short!("stake")),
+        (user.clone(), 100 * UNIT)
+    );
+
+    // Fee splitter payment: USDC arrives, then the notifier reports it
+    t.usdc_admin.mint(&revshare.address, &(10 * UNIT));
+    revshare.notify_reward(&(10 * UNIT));
+    assert_eq!(
+        single::<i128>(&revshare, symbol_short!("reward")),
+        10 * UNIT
+    );
+
+    revshare.claim(&user);
+    assert_eq!(
+        single::<(Address, i128)>(&revshare, symbol_short!("claim")),
+        (user.clone(), 10 * UNIT)
+    );
+
+    revshare.unstake(&user, &(100 * UNIT));
+    assert_eq!(
+        single::<(Address, i128)>(&revshare, symbol_short!("unstake")),
+        (user, 100 * UNIT)
+    );
+}
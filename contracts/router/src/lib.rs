@@ -0,0 +1,349 @@
+#![no_std]
+
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractclient, contracterror, contractimpl, symbol_short, token, vec, Address, Env,
+    IntoVal, Symbol, Vec,
+};
+use tuxedo_dex_adapter as dex;
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const REGISTRY: Symbol = symbol_short!("REGISTRY");
+
+// Registry keys of the contracts the router calls
+pub const VAULT_KEY: Symbol = symbol_short!("vault");
+pub const FARMING_KEY: Symbol = symbol_short!("farming");
+pub const USDC_KEY: Symbol = symbol_short!("usdc");
+pub const AMM_KEY: Symbol = symbol_short!("amm");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RouterError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    AmmNotSet = 4,
+    InvalidPath = 5,
+    DeadlinePassed = 6,
+    SlippageExceeded = 7,
+}
+
+// ============ External Interfaces ============
+#[contractclient(name = "RegistryClient")]
+pub trait RegistryInterface {
+    fn get(env: Env, key: Symbol) -> Option<Address>;
+}
+
+#[contractclient(name = "VaultClient")]
+pub trait VaultInterface {
+    fn deposit_for(
+        env: Env,
+        payer: Address,
+        recipient: Address,
+        asset: Address,
+        amount: i128,
+    ) -> i128;
+    fn withdraw(env: Env, user: Address, shares: i128, min_assets_out: i128) -> i128;
+    fn transfer(env: Env, from: Address, to: Address, amount: i128);
+}
+
+#[contractclient(name = "FarmingClient")]
+pub trait FarmingInterface {
+    fn stake(env: Env, user: Address, pool_id: Symbol, amount: i128);
+    fn unstake(env: Env, user: Address, pool_id: Symbol, amount: i128);
+}
+
+// ============ Tuxedo Router Contract ============
+#[contract]
+pub struct TuxedoRouter;
+
+#[contractimpl]
+impl TuxedoRouter {
+    /// Initialize the router with the registry it resolves the vault, farming,
+    /// USDC, and AMM addresses from on every call
+    pub fn initialize(env: Env, admin: Address, registry: Address) -> Result<(), RouterError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(RouterError::AlreadyInitialized);
+        }
+
+        // Set initial state
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&REGISTRY, &registry);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("router"), symbol_short!("init")),
+            (admin, registry),
+        );
+
+        Ok(())
+    }
+
+    /// Deposit USDC into the vault and stake the minted TUX0 in a farming pool.
+    ///
+    /// The router pulls `amount` USDC using the allowance the user granted it,
+    /// deposits on the user's behalf, and stakes the shares for the user, so
+    /// it holds no balance once the call returns. Returns the shares staked.
+    pub fn deposit_and_stake(
+        env: Env,
+        user: Address,
+        amount: i128,
+        pool_id: Symbol,
+    ) -> Result<i128, RouterError> {
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+
+        let (vault, farming, usdc_asset) = Self::load_config(&env)?;
+        let router = env.current_contract_address();
+
+        // Pull USDC from the user via allowance
+        token::TokenClient::new(&env, &usdc_asset).transfer_from(&router, &user, &router, &amount);
+
+        let shares = Self::deposit_for_user(&env, &vault, &usdc_asset, &user, amount);
+
+        // Stake the user's new shares
+        FarmingClient::new(&env, &farming).stake(&user, &pool_id, &shares);
+
+        // Emit deposit and stake event
+        env.events().publish(
+            (symbol_short!("router"), symbol_short!("dep_stk")),
+            (user, amount, shares, pool_id),
+        );
+
+        Ok(shares)
+    }
+
+    /// Unstake TUX0 from a farming pool and redeem it from the vault for USDC.
+    /// Returns the USDC paid to the user.
+    pub fn unstake_and_withdraw(
+        env: Env,
+        user: Address,
+        shares: i128,
+        pool_id: Symbol,
+    ) -> Result<i128, RouterError> {
+        user.require_auth();
+
+        if shares <= 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+
+        let (vault, farming, _) = Self::load_config(&env)?;
+
+        // Unstaking returns the shares to the user, who then redeems them
+        FarmingClient::new(&env, &farming).unstake(&user, &pool_id, &shares);
+        let assets = VaultClient::new(&env, &vault).withdraw(&user, &shares, &0);
+
+        // Emit unstake and withdraw event
+        env.events().publish(
+            (symbol_short!("router"), symbol_short!("unstk_wd")),
+            (user, shares, assets, pool_id),
+        );
+
+        Ok(assets)
+    }
+
+    /// Swap any asset into USDC and deposit it into the vault for the user.
+    ///
+    /// `pool_path` is the AMM swap path and must start at `token_in` and end
+    /// at the vault's USDC. Slippage and deadline are checked on the swap
+    /// output before anything is deposited. Returns the shares minted.
+    pub fn zap_in(
+        env: Env,
+        user: Address,
+        token_in: Address,
+        amount_in: i128,
+        min_usdc_out: i128,
+        deadline: u64,
+        pool_path: Vec<Address>,
+    ) -> Result<i128, RouterError> {
+        user.require_auth();
+
+        if amount_in <= 0 || min_usdc_out < 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+
+        let (vault, _, usdc_asset) = Self::load_config(&env)?;
+        let amm = Self::resolve(&env, AMM_KEY).ok_or(RouterError::AmmNotSet)?;
+        if pool_path.len() < 2
+            || pool_path.first() != Some(token_in.clone())
+            || pool_path.last() != Some(usdc_asset.clone())
+        {
+            return Err(RouterError::InvalidPath);
+        }
+        if env.ledger().timestamp() > deadline {
+            return Err(RouterError::DeadlinePassed);
+        }
+
+        // Check the quote first so a bad rate fails before funds move
+        if dex::quote_exact_in_path(&env, &amm, &pool_path, amount_in) < min_usdc_out {
+            return Err(RouterError::SlippageExceeded);
+        }
+
+        let router = env.current_contract_address();
+
+        // Pull the input asset from the user via allowance
+        token::TokenClient::new(&env, &token_in).transfer_from(&router, &user, &router, &amount_in);
+
+        // Swap into USDC, measuring what actually arrived
+        let usdc_client = token::TokenClient::new(&env, &usdc_asset);
+        let balance_before = usdc_client.balance(&router);
+        dex::swap_exact_in_path(&env, &amm, &pool_path, amount_in, min_usdc_out, deadline);
+        let usdc_out = usdc_client.balance(&router) - balance_before;
+
+        if usdc_out < min_usdc_out {
+            return Err(RouterError::SlippageExceeded);
+        }
+
+        let shares = Self::deposit_for_user(&env, &vault, &usdc_asset, &user, usdc_out);
+
+        // Emit zap in event
+        env.events().publish(
+            (symbol_short!("router"), symbol_short!("zap_in")),
+            (user, token_in, amount_in, usdc_out, shares),
+        );
+
+        Ok(shares)
+    }
+
+    /// Redeem TUX0 from the vault and swap the USDC into any asset for the user.
+    ///
+    /// `pool_path` must start at the vault's USDC and end at `token_out`.
+    /// Returns the amount of `token_out` paid to the user.
+    pub fn zap_out(
+        env: Env,
+        user: Address,
+        shares: i128,
+        token_out: Address,
+        min_out: i128,
+        deadline: u64,
+        pool_path: Vec<Address>,
+    ) -> Result<i128, RouterError> {
+        user.require_auth();
+
+        if shares <= 0 || min_out < 0 {
+            return Err(RouterError::InvalidAmount);
+        }
+
+        let (vault, _, usdc_asset) = Self::load_config(&env)?;
+        let amm = Self::resolve(&env, AMM_KEY).ok_or(RouterError::AmmNotSet)?;
+        if pool_path.len() < 2
+            || pool_path.first() != Some(usdc_asset.clone())
+            || pool_path.last() != Some(token_out.clone())
+        {
+            return Err(RouterError::InvalidPath);
+        }
+        if env.ledger().timestamp() > deadline {
+            return Err(RouterError::DeadlinePassed);
+        }
+
+        let router = env.current_contract_address();
+
+        // Take the user's shares and redeem them to the router
+        let vault_client = VaultClient::new(&env, &vault);
+        vault_client.transfer(&user, &router, &shares);
+        let assets = vault_client.withdraw(&router, &shares, &0);
+
+        // Swap the USDC into the requested asset
+        let out_client = token::TokenClient::new(&env, &token_out);
+        let balance_before = out_client.balance(&router);
+        dex::swap_exact_in_path(&env, &amm, &pool_path, assets, min_out, deadline);
+        let amount_out = out_client.balance(&router) - balance_before;
+
+        if amount_out < min_out {
+            return Err(RouterError::SlippageExceeded);
+        }
+
+        out_client.transfer(&router, &user, &amount_out);
+
+        // Emit zap out event
+        env.events().publish(
+            (symbol_short!("router"), symbol_short!("zap_out")),
+            (user, token_out, shares, assets, amount_out),
+        );
+
+        Ok(amount_out)
+    }
+
+    /// Point the router at a different registry (admin only)
+    pub fn set_registry(env: Env, registry: Address) -> Result<(), RouterError> {
+        let admin: Address = env.storage().instance().get(&ADMIN).ok_or(RouterError::NotInitialized)?;
+        admin.require_auth();
+
+        env.storage().instance().set(&REGISTRY, &registry);
+
+        // Emit registry update event
+        env.events().publish((symbol_short!("router"), symbol_short!("registry")), registry);
+
+        Ok(())
+    }
+
+    /// Get the registry the router resolves its dependencies from
+    pub fn get_registry(env: Env) -> Result<Address, RouterError> {
+        env.storage().instance().get(&REGISTRY).ok_or(RouterError::NotInitialized)
+    }
+
+    /// Get the AMM router used for zaps, if registered
+    pub fn get_amm(env: Env) -> Option<Address> {
+        Self::resolve(&env, AMM_KEY)
+    }
+
+    /// Get the vault address currently registered
+    pub fn get_vault(env: Env) -> Result<Address, RouterError> {
+        Self::resolve(&env, VAULT_KEY).ok_or(RouterError::NotInitialized)
+    }
+
+    /// Get the farming contract address currently registered
+    pub fn get_farming(env: Env) -> Result<Address, RouterError> {
+        Self::resolve(&env, FARMING_KEY).ok_or(RouterError::NotInitialized)
+    }
+
+    // ============ Internal Helper Functions ============
+
+    /// Deposit USDC held by the router into the vault, crediting the user
+    fn deposit_for_user(
+        env: &Env,
+        vault: &Address,
+        usdc_asset: &Address,
+        user: &Address,
+        amount: i128,
+    ) -> i128 {
+        let router = env.current_contract_address();
+
+        // The vault pulls the USDC from the router inside its own invocation
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: usdc_asset.clone(),
+                    fn_name: symbol_short!("transfer"),
+                    args: (router.clone(), vault.clone(), amount).into_val(env),
+                },
+                sub_invocations: vec![env],
+            }),
+        ]);
+        VaultClient::new(env, vault).deposit_for(&router, user, usdc_asset, &amount)
+    }
+
+    /// Resolve the vault, farming, and USDC addresses through the registry
+    fn load_config(env: &Env) -> Result<(Address, Address, Address), RouterError> {
+        let vault = Self::resolve(env, VAULT_KEY).ok_or(RouterError::NotInitialized)?;
+        let farming = Self::resolve(env, FARMING_KEY).ok_or(RouterError::NotInitialized)?;
+        let usdc_asset = Self::resolve(env, USDC_KEY).ok_or(RouterError::NotInitialized)?;
+        Ok((vault, farming, usdc_asset))
+    }
+
+    /// Look up a registry key, `None` if the router or the key is not set up
+    fn resolve(env: &Env, key: Symbol) -> Option<Address> {
+        let registry: Address = env.storage().instance().get(&REGISTRY)?;
+        RegistryClient::new(env, &registry).get(&key)
+    }
+}
+
+mod test;
@@ -0,0 +1,314 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+use tux_farming::{TuxFarming, TuxFarmingClient};
+use tux_token::{TuxToken, TuxTokenClient};
+use tuxedo_registry::{TuxedoRegistry, TuxedoRegistryClient};
+use tuxedo_testutils::dex::{MockRouter, MockRouterClient, RATE_SCALE};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+const USDC_UNIT: i128 = 10_000_000;
+
+struct RouterTest<'a> {
+    env: Env,
+    router: TuxedoRouterClient<'a>,
+    registry: TuxedoRegistryClient<'a>,
+    vault: TuxedoVaultClient<'a>,
+    farming: TuxFarmingClient<'a>,
+    usdc: TokenClient<'a>,
+    usdc_admin: StellarAssetClient<'a>,
+    pool_id: Symbol,
+}
+
+impl<'a> RouterTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &0);
+
+        let vault = TuxedoVaultClient::new(
+            &env,
+            &env.register(
+                TuxedoVault,
+                (
+                    &admin,
+                    Address::generate(&env),
+                    Address::generate(&env),
+                    &usdc_id,
+                    None::<u32>,
+                ),
+            ),
+        );
+
+        // Seed the vault so the first deposit's dead shares aren't a user's
+        StellarAssetClient::new(&env, &usdc_id).mint(&admin, &(10 * USDC_UNIT));
+        vault.deposit(&admin, &usdc_id, &(10 * USDC_UNIT), &0);
+
+        // TUX0 shares are staked in a farming pool whose token is the vault itself
+        let pool_id = symbol_short!("TUX0");
+        let farming = TuxFarmingClient::new(&env, &env.register(TuxFarming, ()));
+        farming.initialize(&admin, &tux.address);
+        farming.add_pool(&admin, &pool_id, &vault.address);
+
+        let registry = TuxedoRegistryClient::new(&env, &env.register(TuxedoRegistry, ()));
+        registry.initialize(&admin);
+        registry.set(&VAULT_KEY, &vault.address);
+        registry.set(&FARMING_KEY, &farming.address);
+        registry.set(&USDC_KEY, &usdc_id);
+
+        let router = TuxedoRouterClient::new(&env, &env.register(TuxedoRouter, ()));
+        router.initialize(&admin, &registry.address);
+
+        RouterTest {
+            router,
+            registry,
+            vault,
+            farming,
+            usdc: TokenClient::new(&env, &usdc_id),
+            usdc_admin: StellarAssetClient::new(&env, &usdc_id),
+            pool_id,
+            env,
+        }
+    }
+
+    fn funded_user(&self, amount: i128) -> Address {
+        let user = Address::generate(&self.env);
+        self.usdc_admin.mint(&user, &amount);
+        self.usdc.approve(&user, &self.router.address, &amount, &1_000);
+        user
+    }
+
+    fn assert_router_empty(&self) {
+        assert_eq!(self.usdc.balance(&self.router.address), 0);
+        assert_eq!(self.vault.balance(&self.router.address), 0);
+    }
+}
+
+#[test]
+fn test_deposit_and_stake() {
+    let t = RouterTest::setup();
+    let user = t.funded_user(500 * USDC_UNIT);
+
+    let shares = t.router.deposit_and_stake(&user, &(500 * USDC_UNIT), &t.pool_id);
+
+    assert_eq!(shares, 500 * USDC_UNIT);
+    assert_eq!(t.usdc.balance(&user), 0);
+    assert_eq!(t.usdc.balance(&t.vault.address), 510 * USDC_UNIT);
+    assert_eq!(t.farming.get_user_stake(&user, &t.pool_id), shares);
+    assert_eq!(t.vault.get_user_shares(&user), 0);
+    assert_eq!(t.vault.get_user_shares(&t.farming.address), shares);
+    t.assert_router_empty();
+}
+
+#[test]
+fn test_unstake_and_withdraw_round_trip() {
+    let t = RouterTest::setup();
+    let user = t.funded_user(500 * USDC_UNIT);
+    let shares = t.router.deposit_and_stake(&user, &(500 * USDC_UNIT), &t.pool_id);
+
+    let assets = t.router.unstake_and_withdraw(&user, &shares, &t.pool_id);
+
+    assert_eq!(assets, 500 * USDC_UNIT);
+    assert_eq!(t.usdc.balance(&user), 500 * USDC_UNIT);
+    assert_eq!(t.farming.get_user_stake(&user, &t.pool_id), 0);
+    assert_eq!(t.vault.get_total_shares(), 10 * USDC_UNIT);
+    t.assert_router_empty();
+}
+
+#[test]
+fn test_dependencies_resolved_through_registry() {
+    let t = RouterTest::setup();
+    assert_eq!(t.router.get_vault(), t.vault.address);
+
+    // Register a replacement vault with its own farming pool
+    let admin = t.farming.get_admin();
+    let vault = TuxedoVaultClient::new(
+        &t.env,
+        &t.env.register(
+            TuxedoVault,
+            (
+                &admin,
+                Address::generate(&t.env),
+                Address::generate(&t.env),
+                &t.usdc.address,
+                None::<u32>,
+            ),
+        ),
+    );
+    let pool_id = symbol_short!("TUX0_V2");
+    t.farming.add_pool(&admin, &pool_id, &vault.address);
+    t.registry.set(&VAULT_KEY, &vault.address);
+    assert_eq!(t.router.get_vault(), vault.address);
+
+    // The next deposit goes to the new vault without touching the router
+    let user = t.funded_user(100 * USDC_UNIT);
+    t.router.deposit_and_stake(&user, &(100 * USDC_UNIT), &pool_id);
+    assert_eq!(t.usdc.balance(&vault.address), 100 * USDC_UNIT);
+    assert_eq!(t.usdc.balance(&t.vault.address), 10 * USDC_UNIT);
+
+    // Nothing registered under the AMM key yet
+    assert_eq!(
+        t.router.try_zap_in(
+            &user,
+            &t.usdc.address,
+            &USDC_UNIT,
+            &0,
+            &0,
+            &vec![&t.env, t.usdc.address.clone()],
+        ),
+        Err(Ok(RouterError::AmmNotSet))
+    );
+}
+
+#[test]
+fn test_invalid_amount() {
+    let t = RouterTest::setup();
+    let user = t.funded_user(USDC_UNIT);
+
+    assert_eq!(
+        t.router.try_deposit_and_stake(&user, &0, &t.pool_id),
+        Err(Ok(RouterError::InvalidAmount))
+    );
+    assert_eq!(
+        t.router.try_unstake_and_withdraw(&user, &0, &t.pool_id),
+        Err(Ok(RouterError::InvalidAmount))
+    );
+}
+
+struct ZapTest<'a> {
+    t: RouterTest<'a>,
+    xlm: TokenClient<'a>,
+    xlm_admin: StellarAssetClient<'a>,
+    amm: MockRouterClient<'a>,
+}
+
+impl<'a> ZapTest<'a> {
+    fn setup() -> Self {
+        let t = RouterTest::setup();
+        let xlm_id = t
+            .env
+            .register_stellar_asset_contract_v2(Address::generate(&t.env))
+            .address();
+        let amm = MockRouterClient::new(&t.env, &t.env.register(MockRouter, ()));
+        t.registry.set(&AMM_KEY, &amm.address);
+
+        // Fund the AMM on both sides
+        t.usdc_admin.mint(&amm.address, &(10_000 * USDC_UNIT));
+        let xlm_admin = StellarAssetClient::new(&t.env, &xlm_id);
+        xlm_admin.mint(&amm.address, &(100_000 * USDC_UNIT));
+
+        ZapTest {
+            xlm: TokenClient::new(&t.env, &xlm_id),
+            xlm_admin,
+            amm,
+            t,
+        }
+    }
+
+    fn xlm_user(&self, amount: i128) -> Address {
+        let user = Address::generate(&self.t.env);
+        self.xlm_admin.mint(&user, &amount);
+        self.xlm.approve(&user, &self.t.router.address, &amount, &1_000);
+        user
+    }
+
+    fn path(&self, from: &Address, to: &Address) -> Vec<Address> {
+        vec![&self.t.env, from.clone(), to.clone()]
+    }
+}
+
+#[test]
+fn test_zap_in_swaps_then_deposits() {
+    let z = ZapTest::setup();
+    z.amm.set_rate(&(RATE_SCALE / 10)); // 1 XLM = 0.1 USDC
+    let user = z.xlm_user(1_000 * USDC_UNIT);
+    let path = z.path(&z.xlm.address, &z.t.usdc.address);
+    let deadline = z.t.env.ledger().timestamp();
+
+    let shares = z.t.router.zap_in(
+        &user,
+        &z.xlm.address,
+        &(1_000 * USDC_UNIT),
+        &(100 * USDC_UNIT),
+        &deadline,
+        &path,
+    );
+
+    assert_eq!(shares, 100 * USDC_UNIT);
+    assert_eq!(z.t.vault.get_user_shares(&user), shares);
+    assert_eq!(z.xlm.balance(&user), 0);
+    assert_eq!(z.xlm.balance(&z.t.router.address), 0);
+    z.t.assert_router_empty();
+}
+
+#[test]
+fn test_zap_out_withdraws_then_swaps() {
+    let z = ZapTest::setup();
+    let user = z.t.funded_user(100 * USDC_UNIT);
+    z.t.vault.deposit(&user, &z.t.usdc.address, &(100 * USDC_UNIT), &0);
+    z.amm.set_rate(&(RATE_SCALE * 10)); // 1 USDC = 10 XLM
+    let path = z.path(&z.t.usdc.address, &z.xlm.address);
+    let deadline = z.t.env.ledger().timestamp();
+
+    let received = z.t.router.zap_out(
+        &user,
+        &(100 * USDC_UNIT),
+        &z.xlm.address,
+        &(1_000 * USDC_UNIT),
+        &deadline,
+        &path,
+    );
+
+    assert_eq!(received, 1_000 * USDC_UNIT);
+    assert_eq!(z.xlm.balance(&user), 1_000 * USDC_UNIT);
+    assert_eq!(z.t.vault.get_user_shares(&user), 0);
+    assert_eq!(z.xlm.balance(&z.t.router.address), 0);
+    z.t.assert_router_empty();
+}
+
+#[test]
+fn test_zap_in_guards() {
+    let z = ZapTest::setup();
+    z.amm.set_rate(&(RATE_SCALE / 10));
+    let user = z.xlm_user(1_000 * USDC_UNIT);
+    let path = z.path(&z.xlm.address, &z.t.usdc.address);
+    let deadline = z.t.env.ledger().timestamp();
+
+    // Quote below the minimum is rejected before any funds move
+    assert_eq!(
+        z.t.router.try_zap_in(
+            &user,
+            &z.xlm.address,
+            &(1_000 * USDC_UNIT),
+            &(100 * USDC_UNIT + 1),
+            &deadline,
+            &path,
+        ),
+        Err(Ok(RouterError::SlippageExceeded))
+    );
+
+    // Path must run from token_in to the vault's USDC
+    let wrong_path = z.path(&z.t.usdc.address, &z.xlm.address);
+    assert_eq!(
+        z.t.router.try_zap_in(&user, &z.xlm.address, &USDC_UNIT, &0, &deadline, &wrong_path),
+        Err(Ok(RouterError::InvalidPath))
+    );
+
+    // Expired deadline is rejected
+    z.t.env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    assert_eq!(
+        z.t.router.try_zap_in(&user, &z.xlm.address, &USDC_UNIT, &0, &deadline, &path),
+        Err(Ok(RouterError::DeadlinePassed))
+    );
+    assert_eq!(z.xlm.balance(&user), 1_000 * USDC_UNIT);
+}
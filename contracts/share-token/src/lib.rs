@@ -0,0 +1,138 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, panic_with_error, symbol_short,
+    token::TokenInterface, Address, Env, String, Symbol,
+};
+use stellar_tokens::fungible::Base;
+
+// ============ Constants ============
+const VAULT: Symbol = symbol_short!("VAULT");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ShareTokenError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+}
+
+// ============ TUX0 Share Token Contract ============
+/// Vault share token (TUX0). Freely transferable; only the vault can mint and burn.
+#[contract]
+pub struct ShareToken;
+
+#[contractimpl]
+impl ShareToken {
+    /// Initialize the share token at deployment. Deploying and initializing
+    /// in one transaction leaves no window for someone else to name the vault
+    /// first.
+    ///
+    /// Arguments:
+    /// - vault: Vault contract allowed to mint and burn
+    /// - decimals: Must match the vault asset's decimals
+    /// - name: Token name
+    /// - symbol: Token symbol
+    pub fn __constructor(env: Env, vault: Address, decimals: u32, name: String, symbol: String) {
+        Base::set_metadata(&env, decimals, name, symbol);
+        env.storage().instance().set(&VAULT, &vault);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("tux0"), symbol_short!("init")),
+            (vault, decimals),
+        );
+    }
+
+    /// Mint shares (vault only)
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), ShareTokenError> {
+        Self::require_vault(&env)?;
+
+        if amount <= 0 {
+            return Err(ShareTokenError::InvalidAmount);
+        }
+
+        Base::mint(&env, &to, amount);
+
+        Ok(())
+    }
+
+    /// Get total shares in circulation
+    pub fn total_supply(env: Env) -> i128 {
+        Base::total_supply(&env)
+    }
+
+    /// Get the vault allowed to mint and burn
+    pub fn get_vault(env: Env) -> Result<Address, ShareTokenError> {
+        env.storage()
+            .instance()
+            .get(&VAULT)
+            .ok_or(ShareTokenError::NotInitialized)
+    }
+
+    // ============ Internal Helper Functions ============
+
+    /// Require authorization from the configured vault
+    fn require_vault(env: &Env) -> Result<Address, ShareTokenError> {
+        let vault = Self::get_vault(env.clone())?;
+        vault.require_auth();
+        Ok(vault)
+    }
+}
+
+// ============ TokenInterface Implementation ============
+#[contractimpl]
+impl TokenInterface for ShareToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        Base::allowance(&env, &from, &spender)
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, live_until_ledger: u32) {
+        Base::approve(&env, &from, &spender, amount, live_until_ledger);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        Base::balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        Base::transfer(&env, &from, &to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        Base::transfer_from(&env, &spender, &from, &to, amount);
+    }
+
+    /// Burn shares on redemption (vault only)
+    fn burn(env: Env, from: Address, amount: i128) {
+        if let Err(err) = Self::require_vault(&env) {
+            panic_with_error!(&env, err);
+        }
+        Base::update(&env, Some(&from), None, amount);
+    }
+
+    /// Burn shares through an allowance; the spender must be the vault
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        if Self::get_vault(env.clone()).ok() != Some(spender.clone()) {
+            panic_with_error!(&env, ShareTokenError::Unauthorized);
+        }
+        Base::burn_from(&env, &spender, &from, amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        Base::decimals(&env)
+    }
+
+    fn name(env: Env) -> String {
+        Base::name(&env)
+    }
+
+    fn symbol(env: Env) -> String {
+        Base::symbol(&env)
+    }
+}
+
+mod test;
@@ -0,0 +1,57 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+fn setup(env: &Env) -> (ShareTokenClient<'_>, Address) {
+    env.mock_all_auths();
+    let vault = Address::generate(env);
+    let name = String::from_str(env, "Tuxedo Vault Share");
+    let symbol = String::from_str(env, "TUX0");
+    let token = ShareTokenClient::new(
+        env,
+        &env.register(ShareToken, (vault.clone(), 7u32, name, symbol)),
+    );
+    (token, vault)
+}
+
+#[test]
+fn test_vault_mints_and_burns() {
+    let env = Env::default();
+    let (token, vault) = setup(&env);
+    let user = Address::generate(&env);
+
+    token.mint(&user, &1_000);
+    assert_eq!(token.balance(&user), 1_000);
+    assert_eq!(token.total_supply(), 1_000);
+    assert_eq!(token.get_vault(), vault);
+    assert_eq!(token.decimals(), 7);
+
+    token.burn(&user, &400);
+    assert_eq!(token.balance(&user), 600);
+    assert_eq!(token.total_supply(), 600);
+}
+
+#[test]
+fn test_only_vault_can_mint() {
+    let env = Env::default();
+    let (token, _vault) = setup(&env);
+    let user = Address::generate(&env);
+
+    // Without mocked auths only the user has signed
+    env.set_auths(&[]);
+    assert!(token.try_mint(&user, &1_000).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_burn_from_requires_vault_spender() {
+    let env = Env::default();
+    let (token, _vault) = setup(&env);
+    let user = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    token.mint(&user, &1_000);
+    token.approve(&user, &spender, &1_000, &100);
+    token.burn_from(&spender, &user, &1_000);
+}
@@ -0,0 +1,288 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, Address,
+    Env, Symbol, Vec,
+};
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const REGISTRY: Symbol = symbol_short!("REGISTRY");
+const POOLS: Symbol = symbol_short!("POOLS");
+
+// Registry keys of the contracts the aggregator reads
+pub const VAULT_KEY: Symbol = symbol_short!("vault");
+pub const FACTORY_KEY: Symbol = symbol_short!("factory");
+pub const FARMING_KEY: Symbol = symbol_short!("farming");
+pub const TOKEN_KEY: Symbol = symbol_short!("token");
+
+// Bits of `ProtocolStats::ok`, set when every read behind the field succeeded
+pub const OK_VAULTS: u32 = 1 << 0;
+pub const OK_POOLS: u32 = 1 << 1;
+pub const OK_TUX_SUPPLY: u32 = 1 << 2;
+pub const OK_TUX_BURNED: u32 = 1 << 3;
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StatsError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaultTvl {
+    pub vault: Address,
+    pub total_assets: i128,
+    pub ok: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolStake {
+    pub pool_id: Symbol,
+    pub total_staked: i128,
+    pub ok: bool,
+}
+
+/// Protocol-wide totals. Unreachable contracts read as zero and clear their
+/// bit in `ok` instead of failing the whole call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolStats {
+    pub vaults: Vec<VaultTvl>,
+    pub total_value_locked: i128,
+    pub pools: Vec<PoolStake>,
+    pub tux_total_supply: i128,
+    pub tux_burned: i128,
+    pub ok: u32,
+}
+
+// ============ External Contract Interfaces ============
+#[contractclient(name = "RegistryClient")]
+pub trait RegistryInterface {
+    fn get(env: Env, key: Symbol) -> Option<Address>;
+}
+
+#[contractclient(name = "FactoryClient")]
+pub trait FactoryInterface {
+    fn get_assets(env: Env) -> Vec<Address>;
+    fn get_vault(env: Env, asset: Address) -> Option<Address>;
+}
+
+#[contractclient(name = "VaultClient")]
+pub trait VaultInterface {
+    fn get_total_assets(env: Env) -> i128;
+}
+
+/// Emission state returned by the farming contract's `get_pool_rewards`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolRewards {
+    pub reward_rate: i128,
+    pub reward_per_share: i128,
+    pub last_update: u64,
+    pub total_staked: i128,
+}
+
+#[contractclient(name = "FarmingClient")]
+pub trait FarmingInterface {
+    fn get_pool_rewards(env: Env, pool_id: Symbol) -> PoolRewards;
+}
+
+#[contractclient(name = "TokenClient")]
+pub trait TokenInterface {
+    fn total_supply(env: Env) -> i128;
+    fn total_burned(env: Env) -> i128;
+}
+
+// ============ Stats Aggregator Contract ============
+/// Read-only rollup of protocol TVL and token stats for frontends.
+///
+/// Contract addresses come from the registry on every call: the vault under
+/// `vault` plus every vault the factory under `factory` has deployed, the
+/// farming contract under `farming`, and the TUX token under `token`.
+#[contract]
+pub struct StatsAggregator;
+
+#[contractimpl]
+impl StatsAggregator {
+    /// Initialize the aggregator with the registry it resolves contracts from
+    pub fn initialize(env: Env, admin: Address, registry: Address) -> Result<(), StatsError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(StatsError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&REGISTRY, &registry);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("stats"), symbol_short!("init")),
+            (admin, registry),
+        );
+
+        Ok(())
+    }
+
+    /// Set the farming pools whose stakes are reported (admin only)
+    pub fn set_pools(env: Env, pools: Vec<Symbol>) -> Result<(), StatsError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&POOLS, &pools);
+
+        // Emit pools update event
+        env.events()
+            .publish((symbol_short!("stats"), symbol_short!("pools")), pools);
+
+        Ok(())
+    }
+
+    /// Read every configured contract and return the protocol totals
+    pub fn get_protocol_stats(env: Env) -> ProtocolStats {
+        let mut ok = 0;
+
+        let mut vaults_ok = true;
+        let mut vaults = Vec::new(&env);
+        let mut total_value_locked = 0;
+        for vault in Self::vault_addresses(&env, &mut vaults_ok).iter() {
+            let read = VaultClient::new(&env, &vault).try_get_total_assets();
+            let (total_assets, read_ok) = Self::unwrap_or_zero(read);
+            vaults_ok &= read_ok;
+            total_value_locked += total_assets;
+            vaults.push_back(VaultTvl {
+                vault,
+                total_assets,
+                ok: read_ok,
+            });
+        }
+        if vaults_ok {
+            ok |= OK_VAULTS;
+        }
+
+        let mut pools_ok = true;
+        let mut pools = Vec::new(&env);
+        let farming = Self::resolve(&env, FARMING_KEY);
+        for pool_id in Self::get_pools(env.clone()).iter() {
+            let stake = farming.as_ref().and_then(|farming| {
+                match FarmingClient::new(&env, farming).try_get_pool_rewards(&pool_id) {
+                    Ok(Ok(rewards)) => Some(rewards.total_staked),
+                    _ => None,
+                }
+            });
+            pools_ok &= stake.is_some();
+            pools.push_back(PoolStake {
+                pool_id,
+                total_staked: stake.unwrap_or(0),
+                ok: stake.is_some(),
+            });
+        }
+        if pools_ok {
+            ok |= OK_POOLS;
+        }
+
+        let (mut tux_total_supply, mut tux_burned) = (0, 0);
+        if let Some(token) = Self::resolve(&env, TOKEN_KEY) {
+            let client = TokenClient::new(&env, &token);
+            let (supply, supply_ok) = Self::unwrap_or_zero(client.try_total_supply());
+            let (burned, burned_ok) = Self::unwrap_or_zero(client.try_total_burned());
+            (tux_total_supply, tux_burned) = (supply, burned);
+            if supply_ok {
+                ok |= OK_TUX_SUPPLY;
+            }
+            if burned_ok {
+                ok |= OK_TUX_BURNED;
+            }
+        }
+
+        ProtocolStats {
+            vaults,
+            total_value_locked,
+            pools,
+            tux_total_supply,
+            tux_burned,
+            ok,
+        }
+    }
+
+    /// Get the farming pools whose stakes are reported
+    pub fn get_pools(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&POOLS)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the registry contracts are resolved from
+    pub fn get_registry(env: Env) -> Address {
+        env.storage().instance().get(&REGISTRY).unwrap()
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn require_admin(env: &Env) -> Result<(), StatsError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(StatsError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    /// Look up `key` in the registry, treating an unreadable registry as empty
+    fn resolve(env: &Env, key: Symbol) -> Option<Address> {
+        let registry: Address = env.storage().instance().get(&REGISTRY)?;
+        match RegistryClient::new(env, &registry).try_get(&key) {
+            Ok(Ok(addr)) => addr,
+            _ => None,
+        }
+    }
+
+    /// The registered vault plus the factory's vaults, without duplicates.
+    /// Clears `ok` if the factory's vault list can't be read.
+    fn vault_addresses(env: &Env, ok: &mut bool) -> Vec<Address> {
+        let mut vaults = Vec::new(env);
+        if let Some(vault) = Self::resolve(env, VAULT_KEY) {
+            vaults.push_back(vault);
+        }
+
+        let factory = match Self::resolve(env, FACTORY_KEY) {
+            Some(factory) => FactoryClient::new(env, &factory),
+            None => return vaults,
+        };
+        let assets = match factory.try_get_assets() {
+            Ok(Ok(assets)) => assets,
+            _ => {
+                *ok = false;
+                return vaults;
+            }
+        };
+        for asset in assets.iter() {
+            match factory.try_get_vault(&asset) {
+                Ok(Ok(Some(vault))) if !vaults.contains(&vault) => vaults.push_back(vault),
+                Ok(Ok(_)) => {}
+                _ => *ok = false,
+            }
+        }
+        vaults
+    }
+
+    fn unwrap_or_zero<E, F>(read: Result<Result<i128, E>, F>) -> (i128, bool) {
+        match read {
+            Ok(Ok(value)) => (value, true),
+            _ => (0, false),
+        }
+    }
+}
+
+mod test;
@@ -0,0 +1,69 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env};
+use tuxedo_registry::{TuxedoRegistry, TuxedoRegistryClient};
+
+struct StatsTest<'a> {
+    env: Env,
+    stats: StatsAggregatorClient<'a>,
+    registry: TuxedoRegistryClient<'a>,
+}
+
+impl<'a> StatsTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let registry = TuxedoRegistryClient::new(&env, &env.register(TuxedoRegistry, ()));
+        registry.initialize(&admin);
+
+        let stats = StatsAggregatorClient::new(&env, &env.register(StatsAggregator, ()));
+        stats.initialize(&admin, &registry.address);
+
+        StatsTest {
+            env,
+            stats,
+            registry,
+        }
+    }
+}
+
+#[test]
+fn test_unreachable_contracts_read_as_zero() {
+    let t = StatsTest::setup();
+    let vault = Address::generate(&t.env);
+    t.registry.set(&VAULT_KEY, &vault);
+    t.registry.set(&TOKEN_KEY, &Address::generate(&t.env));
+    t.stats.set_pools(&vec![&t.env, symbol_short!("TUX0")]);
+
+    let stats = t.stats.get_protocol_stats();
+    assert_eq!(
+        stats.vaults,
+        vec![
+            &t.env,
+            VaultTvl {
+                vault,
+                total_assets: 0,
+                ok: false,
+            }
+        ]
+    );
+    assert_eq!(stats.total_value_locked, 0);
+    assert!(!stats.pools.get(0).unwrap().ok);
+    assert_eq!(stats.ok, 0);
+}
+
+#[test]
+fn test_empty_registry_reports_nothing() {
+    let t = StatsTest::setup();
+
+    let stats = t.stats.get_protocol_stats();
+    assert_eq!(stats.vaults.len(), 0);
+    assert_eq!(stats.pools.len(), 0);
+    assert_eq!(stats.tux_total_supply, 0);
+
+    // Nothing configured means nothing failed, except the missing token
+    assert_eq!(stats.ok, OK_VAULTS | OK_POOLS);
+}
@@ -0,0 +1,323 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+};
+use tuxedo_common::tiers;
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const TUX_TOKEN: Symbol = symbol_short!("TUX_TKN");
+const TREASURY: Symbol = symbol_short!("TREASURY");
+const GRACE_PERIOD: Symbol = symbol_short!("GRACE");
+const TIER_ORACLE: Symbol = symbol_short!("TIER_ORCL");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SubscriptionError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidPlan = 3,
+    PlanNotFound = 4,
+    PlanInactive = 5,
+    TierTooLow = 6,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Plan {
+    pub price: i128,   // TUX per term
+    pub duration: u64, // seconds per term
+    /// Burn payments instead of sending them to the treasury
+    pub burn: bool,
+    /// Inactive plans can't be bought or renewed; existing terms run out normally
+    pub active: bool,
+    /// Lowest tier allowed to buy or renew the plan
+    pub min_tier: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub expires_at: u64,
+    /// Price of the most recent term, locked in when it was bought
+    pub price_paid: i128,
+    pub terms: u32,
+}
+
+// ============ Subscriptions Contract ============
+#[contract]
+pub struct TuxSubscriptions;
+
+#[contractimpl]
+impl TuxSubscriptions {
+    /// Initialize the subscriptions contract.
+    ///
+    /// Arguments:
+    /// - admin: Address allowed to manage plans
+    /// - tux_token: Token subscriptions are paid in
+    /// - treasury: Receives payments for plans that don't burn
+    /// - grace_period: Seconds after expiry a subscription still counts as active
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        tux_token: Address,
+        treasury: Address,
+        grace_period: u64,
+    ) -> Result<(), SubscriptionError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(SubscriptionError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&TUX_TOKEN, &tux_token);
+        env.storage().instance().set(&TREASURY, &treasury);
+        env.storage().instance().set(&GRACE_PERIOD, &grace_period);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("sub"), symbol_short!("init")),
+            (tux_token, treasury, grace_period),
+        );
+
+        Ok(())
+    }
+
+    /// Create or update a plan (admin only). Changes apply to terms bought afterwards;
+    /// running subscriptions keep their expiry. An existing plan keeps its minimum tier.
+    pub fn set_plan(
+        env: Env,
+        plan_id: Symbol,
+        price: i128,
+        duration: u64,
+        burn: bool,
+    ) -> Result<(), SubscriptionError> {
+        Self::require_admin(&env)?;
+
+        if price <= 0 || duration == 0 {
+            return Err(SubscriptionError::InvalidPlan);
+        }
+
+        let min_tier = match Self::get_plan(env.clone(), plan_id.clone()) {
+            Ok(plan) => plan.min_tier,
+            Err(_) => 0,
+        };
+        let plan = Plan {
+            price,
+            duration,
+            burn,
+            active: true,
+            min_tier,
+        };
+        env.storage()
+            .persistent()
+            .set(&Self::plan_key(&plan_id), &plan);
+
+        // Emit plan update event
+        env.events().publish(
+            (symbol_short!("sub"), symbol_short!("plan")),
+            (plan_id, price, duration, burn),
+        );
+
+        Ok(())
+    }
+
+    /// Stop or resume sales of a plan (admin only)
+    pub fn set_plan_active(
+        env: Env,
+        plan_id: Symbol,
+        active: bool,
+    ) -> Result<(), SubscriptionError> {
+        Self::require_admin(&env)?;
+
+        let mut plan = Self::get_plan(env.clone(), plan_id.clone())?;
+        plan.active = active;
+        env.storage()
+            .persistent()
+            .set(&Self::plan_key(&plan_id), &plan);
+
+        // Emit plan status event
+        env.events().publish(
+            (symbol_short!("sub"), symbol_short!("active")),
+            (plan_id, active),
+        );
+
+        Ok(())
+    }
+
+    /// Restrict a plan to users at `min_tier` or above (admin only)
+    pub fn set_plan_min_tier(
+        env: Env,
+        plan_id: Symbol,
+        min_tier: u32,
+    ) -> Result<(), SubscriptionError> {
+        Self::require_admin(&env)?;
+
+        let mut plan = Self::get_plan(env.clone(), plan_id.clone())?;
+        plan.min_tier = min_tier;
+        env.storage()
+            .persistent()
+            .set(&Self::plan_key(&plan_id), &plan);
+
+        // Emit plan tier event
+        env.events().publish(
+            (symbol_short!("sub"), symbol_short!("min_tier")),
+            (plan_id, min_tier),
+        );
+
+        Ok(())
+    }
+
+    /// Set the tier oracle used for plan minimum tiers (admin only)
+    pub fn set_tier_oracle(env: Env, oracle: Address) -> Result<(), SubscriptionError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&TIER_ORACLE, &oracle);
+
+        // Emit tier oracle event
+        env.events()
+            .publish((symbol_short!("sub"), symbol_short!("tiers")), oracle);
+
+        Ok(())
+    }
+
+    /// Set the grace period in seconds (admin only)
+    pub fn set_grace_period(env: Env, grace_period: u64) -> Result<(), SubscriptionError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&GRACE_PERIOD, &grace_period);
+
+        // Emit grace period event
+        env.events()
+            .publish((symbol_short!("sub"), symbol_short!("grace")), grace_period);
+
+        Ok(())
+    }
+
+    /// Buy one term of a plan at its current price. A renewal before the grace
+    /// period ends extends from the current expiry, so no time is lost or gained;
+    /// a lapsed subscription restarts from now. Returns the new expiry.
+    pub fn subscribe(env: Env, user: Address, plan_id: Symbol) -> Result<u64, SubscriptionError> {
+        user.require_auth();
+
+        let plan = Self::get_plan(env.clone(), plan_id.clone())?;
+        if !plan.active {
+            return Err(SubscriptionError::PlanInactive);
+        }
+        let oracle = Self::get_tier_oracle(env.clone());
+        if !tiers::meets_tier(&env, oracle, &user, plan.min_tier) {
+            return Err(SubscriptionError::TierTooLow);
+        }
+
+        // Take payment
+        let tux_token: Address = env.storage().instance().get(&TUX_TOKEN).unwrap();
+        let token_client = token::TokenClient::new(&env, &tux_token);
+        if plan.burn {
+            token_client.burn(&user, &plan.price);
+        } else {
+            let treasury: Address = env.storage().instance().get(&TREASURY).unwrap();
+            token_client.transfer(&user, &treasury, &plan.price);
+        }
+
+        // Stack onto the current term while it is still active
+        let now = env.ledger().timestamp();
+        let key = Self::subscription_key(&user, &plan_id);
+        let current: Option<Subscription> = env.storage().persistent().get(&key);
+        let (start, terms) = match current {
+            Some(sub) if now <= sub.expires_at + Self::get_grace_period(env.clone()) => {
+                (sub.expires_at, sub.terms + 1)
+            }
+            _ => (now, 1),
+        };
+
+        let subscription = Subscription {
+            expires_at: start + plan.duration,
+            price_paid: plan.price,
+            terms,
+        };
+        env.storage().persistent().set(&key, &subscription);
+
+        // Emit subscribe event
+        env.events().publish(
+            (symbol_short!("sub"), symbol_short!("subscribe")),
+            (user, plan_id, plan.price, subscription.expires_at),
+        );
+
+        Ok(subscription.expires_at)
+    }
+
+    /// Check whether `user` holds the plan, counting the grace period after expiry
+    pub fn is_active(env: Env, user: Address, plan_id: Symbol) -> bool {
+        let subscription: Option<Subscription> = env
+            .storage()
+            .persistent()
+            .get(&Self::subscription_key(&user, &plan_id));
+        match subscription {
+            Some(sub) => {
+                env.ledger().timestamp() <= sub.expires_at + Self::get_grace_period(env.clone())
+            }
+            None => false,
+        }
+    }
+
+    /// Get a user's subscription to a plan, if they ever had one
+    pub fn get_subscription(env: Env, user: Address, plan_id: Symbol) -> Option<Subscription> {
+        env.storage()
+            .persistent()
+            .get(&Self::subscription_key(&user, &plan_id))
+    }
+
+    /// Get a plan
+    pub fn get_plan(env: Env, plan_id: Symbol) -> Result<Plan, SubscriptionError> {
+        env.storage()
+            .persistent()
+            .get(&Self::plan_key(&plan_id))
+            .ok_or(SubscriptionError::PlanNotFound)
+    }
+
+    /// Get the grace period in seconds
+    pub fn get_grace_period(env: Env) -> u64 {
+        env.storage().instance().get(&GRACE_PERIOD).unwrap_or(0)
+    }
+
+    /// Get the tier oracle, if configured
+    pub fn get_tier_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&TIER_ORACLE)
+    }
+
+    /// Get the treasury receiving non-burned payments
+    pub fn get_treasury(env: Env) -> Address {
+        env.storage().instance().get(&TREASURY).unwrap()
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn require_admin(env: &Env) -> Result<(), SubscriptionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(SubscriptionError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    fn plan_key(plan_id: &Symbol) -> (Symbol, Symbol) {
+        (symbol_short!("plan"), plan_id.clone())
+    }
+
+    fn subscription_key(user: &Address, plan_id: &Symbol) -> (Symbol, Address, Symbol) {
+        (symbol_short!("sub"), user.clone(), plan_id.clone())
+    }
+}
+
+mod test;
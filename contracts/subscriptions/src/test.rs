@@ -0,0 +1,162 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+
+const UNIT: i128 = 10_000_000;
+const MONTH: u64 = 30 * 86_400;
+const GRACE: u64 = 3 * 86_400;
+const START: u64 = 1_000;
+
+struct SubscriptionTest<'a> {
+    env: Env,
+    subs: TuxSubscriptionsClient<'a>,
+    tux: TuxTokenClient<'a>,
+    treasury: Address,
+    user: Address,
+    pro: Symbol,
+}
+
+impl<'a> SubscriptionTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(START);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &(1_000_000 * UNIT));
+        tux.transfer(&admin, &user, &(1_000 * UNIT));
+
+        let subs = TuxSubscriptionsClient::new(&env, &env.register(TuxSubscriptions, ()));
+        subs.initialize(&admin, &tux.address, &treasury, &GRACE);
+
+        let pro = symbol_short!("pro");
+        subs.set_plan(&pro, &(100 * UNIT), &MONTH, &false);
+
+        SubscriptionTest {
+            env,
+            subs,
+            tux,
+            treasury,
+            user,
+            pro,
+        }
+    }
+
+    fn jump_to(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+    }
+}
+
+#[test]
+fn test_subscribe_pays_treasury_or_burns() {
+    let t = SubscriptionTest::setup();
+    assert!(!t.subs.is_active(&t.user, &t.pro));
+
+    assert_eq!(t.subs.subscribe(&t.user, &t.pro), START + MONTH);
+    assert!(t.subs.is_active(&t.user, &t.pro));
+    assert_eq!(t.tux.balance(&t.treasury), 100 * UNIT);
+
+    // A burning plan reduces supply instead
+    let max = symbol_short!("max");
+    t.subs.set_plan(&max, &(300 * UNIT), &MONTH, &true);
+    let supply = t.tux.total_supply();
+    t.subs.subscribe(&t.user, &max);
+    assert_eq!(t.tux.total_supply(), supply - 300 * UNIT);
+    assert_eq!(t.tux.balance(&t.treasury), 100 * UNIT);
+    assert_eq!(t.tux.balance(&t.user), 600 * UNIT);
+}
+
+#[test]
+fn test_renewal_stacks_on_current_expiry() {
+    let t = SubscriptionTest::setup();
+    t.subs.subscribe(&t.user, &t.pro);
+
+    // Renewing early keeps the remaining time
+    t.jump_to(START + 10 * 86_400);
+    assert_eq!(t.subs.subscribe(&t.user, &t.pro), START + 2 * MONTH);
+
+    // Renewing inside the grace period picks up from the old expiry
+    t.jump_to(START + 2 * MONTH + GRACE);
+    assert!(t.subs.is_active(&t.user, &t.pro));
+    assert_eq!(t.subs.subscribe(&t.user, &t.pro), START + 3 * MONTH);
+    assert_eq!(t.subs.get_subscription(&t.user, &t.pro).unwrap().terms, 3);
+}
+
+#[test]
+fn test_expiry_after_grace_period() {
+    let t = SubscriptionTest::setup();
+    t.subs.subscribe(&t.user, &t.pro);
+
+    t.jump_to(START + MONTH + GRACE);
+    assert!(t.subs.is_active(&t.user, &t.pro));
+    t.jump_to(START + MONTH + GRACE + 1);
+    assert!(!t.subs.is_active(&t.user, &t.pro));
+
+    // A lapsed subscription restarts from now
+    let now = START + MONTH + GRACE + 1;
+    assert_eq!(t.subs.subscribe(&t.user, &t.pro), now + MONTH);
+    assert_eq!(t.subs.get_subscription(&t.user, &t.pro).unwrap().terms, 1);
+}
+
+#[test]
+fn test_repricing_keeps_existing_terms() {
+    let t = SubscriptionTest::setup();
+    t.subs.subscribe(&t.user, &t.pro);
+
+    t.subs.set_plan(&t.pro, &(150 * UNIT), &(MONTH / 2), &false);
+    let sub = t.subs.get_subscription(&t.user, &t.pro).unwrap();
+    assert_eq!(sub.expires_at, START + MONTH);
+    assert_eq!(sub.price_paid, 100 * UNIT);
+
+    // The next term is bought at the new price and length
+    assert_eq!(t.subs.subscribe(&t.user, &t.pro), START + MONTH + MONTH / 2);
+    assert_eq!(t.tux.balance(&t.treasury), 250 * UNIT);
+
+    // Retired plans can't be renewed but running terms still count
+    t.subs.set_plan_active(&t.pro, &false);
+    assert_eq!(
+        t.subs.try_subscribe(&t.user, &t.pro),
+        Err(Ok(SubscriptionError::PlanInactive))
+    );
+    assert!(t.subs.is_active(&t.user, &t.pro));
+    assert_eq!(
+        t.subs.try_subscribe(&t.user, &symbol_short!("gold")),
+        Err(Ok(SubscriptionError::PlanNotFound))
+    );
+}
+
+#[test]
+fn test_plan_gated_by_tier() {
+    let t = SubscriptionTest::setup();
+    t.subs.set_plan_min_tier(&t.pro, &1);
+
+    // Without an oracle nobody reaches tier 1
+    assert_eq!(
+        t.subs.try_subscribe(&t.user, &t.pro),
+        Err(Ok(SubscriptionError::TierTooLow))
+    );
+
+    let admin = t.subs.get_admin();
+    t.subs.set_tier_oracle(&t.tux.address);
+    t.tux.set_tier_thresholds(&admin, &vec![&t.env, 500 * UNIT]);
+    assert_eq!(t.subs.subscribe(&t.user, &t.pro), START + MONTH);
+
+    // Repricing keeps the gate; 900 TUX left is below the raised threshold
+    t.subs.set_plan(&t.pro, &(50 * UNIT), &MONTH, &false);
+    assert_eq!(t.subs.get_plan(&t.pro).min_tier, 1);
+    t.tux
+        .set_tier_thresholds(&admin, &vec![&t.env, 1_000 * UNIT]);
+    assert_eq!(
+        t.subs.try_subscribe(&t.user, &t.pro),
+        Err(Ok(SubscriptionError::TierTooLow))
+    );
+}
@@ -0,0 +1,199 @@
+//! Mock Blend pool implementing the adapter's `Pool` interface, with test
+//! controls to accrue interest on positions, write them down, pay emissions,
+//! and to make calls fail.
+
+use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env, Map, Vec};
+use tuxedo_blend_adapter::{Pool, Positions, Request, RequestType};
+
+#[contracttype]
+enum DataKey {
+    Reserves,
+    Accounts,
+    FailNext,
+    Emissions,
+    Positions(Address),
+}
+
+#[contract]
+pub struct MockBlendPool;
+
+#[contractimpl]
+impl MockBlendPool {
+    /// Grow every supply and collateral position by `rate_bps`. The pool pays
+    /// withdrawals out of its own balance, so tests must fund the interest.
+    pub fn accrue(env: Env, rate_bps: u32) {
+        rescale_positions(&env, 10_000 + rate_bps as i128);
+    }
+
+    /// Shrink every supply and collateral position by `loss_bps`, as bad debt
+    /// would. The written-off tokens stay in the pool.
+    pub fn write_down(env: Env, loss_bps: u32) {
+        rescale_positions(&env, 10_000 - loss_bps.min(10_000) as i128);
+    }
+
+    /// Pay `amount` of `token` to the next `claim`. The pool pays emissions out
+    /// of its own balance, so tests must fund them.
+    pub fn set_emissions(env: Env, token: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Emissions, &(token, amount));
+    }
+
+    /// While set, `submit` and `claim` panic. A failed call is rolled back with
+    /// its caller, so the switch stays on until it is cleared.
+    pub fn set_fail_next(env: Env, fail: bool) {
+        env.storage().instance().set(&DataKey::FailNext, &fail);
+    }
+}
+
+#[contractimpl]
+impl Pool for MockBlendPool {
+    fn submit(
+        env: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+    ) -> Positions {
+        fail_if_set(&env);
+        from.require_auth();
+        if spender != from {
+            spender.require_auth();
+        }
+
+        let mut positions = read_positions(&env, &from);
+        for request in requests.iter() {
+            let index = reserve_index(&env, &request.address);
+            let token_client = token::TokenClient::new(&env, &request.address);
+
+            match request.request_type {
+                t if t == RequestType::Supply as u32 => {
+                    token_client.transfer(&spender, &env.current_contract_address(), &request.amount);
+                    add(&mut positions.supply, index, request.amount);
+                }
+                t if t == RequestType::SupplyCollateral as u32 => {
+                    token_client.transfer(&spender, &env.current_contract_address(), &request.amount);
+                    add(&mut positions.collateral, index, request.amount);
+                }
+                t if t == RequestType::Withdraw as u32 => {
+                    let amount = request.amount.min(positions.supply.get(index).unwrap_or(0));
+                    add(&mut positions.supply, index, -amount);
+                    token_client.transfer(&env.current_contract_address(), &to, &amount);
+                }
+                t if t == RequestType::WithdrawCollateral as u32 => {
+                    let amount = request.amount.min(positions.collateral.get(index).unwrap_or(0));
+                    add(&mut positions.collateral, index, -amount);
+                    token_client.transfer(&env.current_contract_address(), &to, &amount);
+                }
+                _ => panic!("unsupported request type"),
+            }
+        }
+
+        track_account(&env, &from);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Positions(from), &positions);
+        positions
+    }
+
+    fn claim(env: Env, from: Address, _reserve_token_ids: Vec<u32>, to: Address) -> i128 {
+        fail_if_set(&env);
+        from.require_auth();
+
+        let emissions: Option<(Address, i128)> =
+            env.storage().instance().get(&DataKey::Emissions);
+        let Some((emissions_token, amount)) = emissions else {
+            return 0;
+        };
+        env.storage().instance().remove(&DataKey::Emissions);
+        token::TokenClient::new(&env, &emissions_token).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+        amount
+    }
+
+    fn get_positions(env: Env, address: Address) -> Positions {
+        read_positions(&env, &address)
+    }
+}
+
+fn read_positions(env: &Env, address: &Address) -> Positions {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Positions(address.clone()))
+        .unwrap_or(Positions {
+            liabilities: Map::new(env),
+            collateral: Map::new(env),
+            supply: Map::new(env),
+        })
+}
+
+fn fail_if_set(env: &Env) {
+    if env.storage().instance().get(&DataKey::FailNext).unwrap_or(false) {
+        panic!("mock pool failure");
+    }
+}
+
+/// Remember `account` so `accrue` reaches its positions
+fn track_account(env: &Env, account: &Address) {
+    let mut accounts: Vec<Address> = env
+        .storage()
+        .instance()
+        .get(&DataKey::Accounts)
+        .unwrap_or(Vec::new(env));
+    if !accounts.contains(account) {
+        accounts.push_back(account.clone());
+        env.storage().instance().set(&DataKey::Accounts, &accounts);
+    }
+}
+
+/// Multiply every tracked account's supply and collateral by `factor_bps`
+fn rescale_positions(env: &Env, factor_bps: i128) {
+    let accounts: Vec<Address> = env
+        .storage()
+        .instance()
+        .get(&DataKey::Accounts)
+        .unwrap_or(Vec::new(env));
+    for account in accounts.iter() {
+        let mut positions = read_positions(env, &account);
+        positions.supply = rescaled(&positions.supply, factor_bps);
+        positions.collateral = rescaled(&positions.collateral, factor_bps);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Positions(account), &positions);
+    }
+}
+
+fn rescaled(balances: &Map<u32, i128>, factor_bps: i128) -> Map<u32, i128> {
+    let mut scaled = Map::new(balances.env());
+    for (index, balance) in balances.iter() {
+        scaled.set(index, balance * factor_bps / 10_000);
+    }
+    scaled
+}
+
+/// Index of `asset` in the reserve list, registering it on first use
+fn reserve_index(env: &Env, asset: &Address) -> u32 {
+    let mut reserves: Vec<Address> = env
+        .storage()
+        .instance()
+        .get(&DataKey::Reserves)
+        .unwrap_or(Vec::new(env));
+    if let Some(index) = reserves.first_index_of(asset) {
+        return index;
+    }
+    reserves.push_back(asset.clone());
+    env.storage().instance().set(&DataKey::Reserves, &reserves);
+    reserves.len() - 1
+}
+
+fn add(balances: &mut Map<u32, i128>, index: u32, amount: i128) {
+    let balance = balances.get(index).unwrap_or(0) + amount;
+    if balance == 0 {
+        balances.remove(index);
+    } else {
+        balances.set(index, balance);
+    }
+}
@@ -0,0 +1,36 @@
+//! Mock bridge delivering USDC to a target with the approve-and-call pattern.
+
+use soroban_sdk::{contract, contractclient, contractimpl, token, Address, Env};
+
+/// Entry point a bridge invokes on its target with `(recipient, amount)`
+#[contractclient(name = "BridgeReceiverClient")]
+pub trait BridgeReceiver {
+    fn receive_bridged_deposit(env: Env, bridge: Address, recipient: Address, amount: i128)
+        -> i128;
+}
+
+#[contract]
+pub struct MockBridge;
+
+#[contractimpl]
+impl MockBridge {
+    /// Approve `target` for `amount` of the bridge's `token` and hand the
+    /// funds to `recipient` through it. Tests must fund the bridge.
+    pub fn deliver(
+        env: Env,
+        token: Address,
+        target: Address,
+        recipient: Address,
+        amount: i128,
+    ) -> i128 {
+        let bridge = env.current_contract_address();
+        token::TokenClient::new(&env, &token).approve(
+            &bridge,
+            &target,
+            &amount,
+            &env.ledger().sequence(),
+        );
+        BridgeReceiverClient::new(&env, &target)
+            .receive_bridged_deposit(&bridge, &recipient, &amount)
+    }
+}
@@ -0,0 +1,60 @@
+//! Mock AMM router implementing the adapter's `Router` interface at a fixed rate.
+
+use soroban_sdk::{contract, contractimpl, symbol_short, token, vec, Address, Env, Symbol, Vec};
+use tuxedo_dex_adapter::Router;
+
+const RATE: Symbol = symbol_short!("RATE");
+
+/// Fixed-point scale of the configured rate: 10^7 = 1 output per input
+pub const RATE_SCALE: i128 = 10_000_000;
+
+#[contract]
+pub struct MockRouter;
+
+#[contractimpl]
+impl MockRouter {
+    /// Set the output paid per unit of input, scaled by `RATE_SCALE`.
+    /// The router pays out of its own balance, so tests must fund it.
+    pub fn set_rate(env: Env, rate: i128) {
+        env.storage().instance().set(&RATE, &rate);
+    }
+}
+
+#[contractimpl]
+impl Router for MockRouter {
+    fn swap_exact_tokens_for_tokens(
+        env: Env,
+        amount_in: i128,
+        amount_out_min: i128,
+        path: Vec<Address>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<i128> {
+        to.require_auth();
+        if env.ledger().timestamp() > deadline {
+            panic!("deadline passed");
+        }
+
+        let amounts = Self::router_get_amounts_out(env.clone(), amount_in, path.clone());
+        let amount_out = amounts.last().unwrap();
+        if amount_out < amount_out_min {
+            panic!("insufficient output amount");
+        }
+
+        let router = env.current_contract_address();
+        let token_in = path.first().unwrap();
+        let token_out = path.last().unwrap();
+        token::TokenClient::new(&env, &token_in).transfer_from(&router, &to, &router, &amount_in);
+        token::TokenClient::new(&env, &token_out).transfer(&router, &to, &amount_out);
+
+        amounts
+    }
+
+    fn router_get_amounts_out(env: Env, amount_in: i128, path: Vec<Address>) -> Vec<i128> {
+        if path.len() != 2 {
+            panic!("mock router only supports single-hop paths");
+        }
+        let rate: i128 = env.storage().instance().get(&RATE).unwrap_or(RATE_SCALE);
+        vec![&env, amount_in, amount_in * rate / RATE_SCALE]
+    }
+}
@@ -0,0 +1,10 @@
+#![no_std]
+
+//! Mock contracts shared by the Tuxedo contract test suites.
+
+pub mod blend;
+pub mod bridge;
+pub mod dex;
+pub mod oracle;
+pub mod tiers;
+pub mod token;
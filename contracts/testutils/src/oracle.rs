@@ -0,0 +1,35 @@
+//! Mock Reflector feed implementing the adapter's `Reflector` interface with settable prices.
+
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
+use tuxedo_oracle_adapter::{Asset, PriceData, Reflector};
+
+const DECIMALS: Symbol = symbol_short!("DECIMALS");
+
+#[contract]
+pub struct MockReflector;
+
+#[contractimpl]
+impl MockReflector {
+    /// Set the price of `asset` as last reported at `timestamp`
+    pub fn set_price(env: Env, asset: Address, price: i128, timestamp: u64) {
+        env.storage()
+            .persistent()
+            .set(&Asset::Stellar(asset), &PriceData { price, timestamp });
+    }
+
+    /// Set the feed's price decimals (defaults to 14, like Reflector)
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage().instance().set(&DECIMALS, &decimals);
+    }
+}
+
+#[contractimpl]
+impl Reflector for MockReflector {
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        env.storage().persistent().get(&asset)
+    }
+
+    fn decimals(env: Env) -> u32 {
+        env.storage().instance().get(&DECIMALS).unwrap_or(14)
+    }
+}
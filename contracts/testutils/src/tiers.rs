@@ -0,0 +1,26 @@
+//! Mock TUX tier oracle implementing the common `TierOracle` interface with settable tiers.
+
+use soroban_sdk::{contract, contractimpl, Address, Env};
+use tuxedo_common::tiers::TierOracle;
+
+#[contract]
+pub struct MockTierOracle;
+
+#[contractimpl]
+impl MockTierOracle {
+    /// Rank `user` at `tier` (everyone starts at tier 0)
+    pub fn set_tier(env: Env, user: Address, tier: u32) {
+        env.storage().persistent().set(&user, &tier);
+    }
+}
+
+#[contractimpl]
+impl TierOracle for MockTierOracle {
+    fn tier_of(env: Env, user: Address) -> u32 {
+        env.storage().persistent().get(&user).unwrap_or(0)
+    }
+
+    fn meets_tier(env: Env, user: Address, level: u32) -> bool {
+        Self::tier_of(env, user) >= level
+    }
+}
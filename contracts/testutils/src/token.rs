@@ -0,0 +1,138 @@
+//! Mock token with non-standard transfers: a fee taken out of every transfer,
+//! or transfers silently capped below the requested amount. Its decimals can
+//! be changed too.
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, token::TokenInterface, Address, Env, String,
+};
+
+#[contracttype]
+enum DataKey {
+    Decimals,
+    FeeBps,
+    Cap,
+    Balance(Address),
+    Allowance(Address, Address),
+}
+
+#[contract]
+pub struct MockFeeToken;
+
+#[contractimpl]
+impl MockFeeToken {
+    /// Credit `amount` to `to` out of thin air
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        write_balance(&env, &to, read_balance(&env, &to) + amount);
+    }
+
+    /// Burn `fee_bps` of every transfer: the sender pays the full amount and
+    /// the recipient receives the rest
+    pub fn set_fee_bps(env: Env, fee_bps: u32) {
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+    }
+
+    /// Move at most `cap` per transfer, whatever amount was asked for
+    pub fn set_cap(env: Env, cap: i128) {
+        env.storage().instance().set(&DataKey::Cap, &cap);
+    }
+
+    /// Report `decimals` instead of the default 7
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage().instance().set(&DataKey::Decimals, &decimals);
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for MockFeeToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allowance(from, spender))
+            .unwrap_or(0)
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, _live_until_ledger: u32) {
+        from.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Allowance(from, spender), &amount);
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        move_balance(&env, &from, &to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            panic!("insufficient allowance");
+        }
+        env.storage().persistent().set(
+            &DataKey::Allowance(from.clone(), spender),
+            &(allowance - amount),
+        );
+        move_balance(&env, &from, &to, amount);
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        write_balance(&env, &from, read_balance(&env, &from) - amount);
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        write_balance(&env, &from, read_balance(&env, &from) - amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or(7)
+    }
+
+    fn name(env: Env) -> String {
+        String::from_str(&env, "Mock Fee Token")
+    }
+
+    fn symbol(env: Env) -> String {
+        String::from_str(&env, "FEE")
+    }
+}
+
+fn read_balance(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(id.clone()))
+        .unwrap_or(0)
+}
+
+fn write_balance(env: &Env, id: &Address, amount: i128) {
+    if amount < 0 {
+        panic!("insufficient balance");
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(id.clone()), &amount);
+}
+
+/// Apply the configured cap and fee to a transfer of `amount`
+fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+    let cap: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::Cap)
+        .unwrap_or(i128::MAX);
+    let fee_bps: u32 = env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0);
+    let sent = amount.min(cap);
+    let received = sent - sent * fee_bps as i128 / 10_000;
+
+    write_balance(env, from, read_balance(env, from) - sent);
+    write_balance(env, to, read_balance(env, to) + received);
+}
@@ -0,0 +1,237 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, Env, Symbol,
+    Val, Vec,
+};
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const GUARDIAN: Symbol = symbol_short!("GUARDIAN");
+const MIN_DELAY: Symbol = symbol_short!("MIN_DELAY");
+const GRACE_PERIOD: Symbol = symbol_short!("GRACE");
+const OPERATION_COUNT: Symbol = symbol_short!("OP_CNT");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TimelockError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidEta = 3,
+    EmptyBatch = 4,
+    OperationNotFound = 5,
+    NotQueued = 6,
+    NotReady = 7,
+    Expired = 8,
+}
+
+// ============ Data Structures ============
+/// A single contract call made when an operation executes
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Call {
+    pub target: Address,
+    pub fn_name: Symbol,
+    pub args: Vec<Val>,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OperationStatus {
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Operation {
+    pub calls: Vec<Call>,
+    pub eta: u64,
+    pub status: OperationStatus,
+}
+
+// ============ Timelock Contract ============
+#[contract]
+pub struct Timelock;
+
+#[contractimpl]
+impl Timelock {
+    /// Initialize the timelock.
+    ///
+    /// Arguments:
+    /// - admin: Address allowed to queue operations (e.g. governance)
+    /// - guardian: Address allowed to cancel queued operations
+    /// - min_delay: Minimum seconds between queueing and `eta`
+    /// - grace_period: Seconds after `eta` during which an operation can still execute
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        guardian: Address,
+        min_delay: u64,
+        grace_period: u64,
+    ) -> Result<(), TimelockError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(TimelockError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&GUARDIAN, &guardian);
+        env.storage().instance().set(&MIN_DELAY, &min_delay);
+        env.storage().instance().set(&GRACE_PERIOD, &grace_period);
+        env.storage().instance().set(&OPERATION_COUNT, &0u32);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("tlock"), symbol_short!("init")),
+            (admin, guardian, min_delay, grace_period),
+        );
+
+        Ok(())
+    }
+
+    /// Queue a single call to run at or after `eta` (admin only). Returns the operation id.
+    pub fn queue(
+        env: Env,
+        target: Address,
+        fn_name: Symbol,
+        args: Vec<Val>,
+        eta: u64,
+    ) -> Result<u32, TimelockError> {
+        let calls = vec![&env, Call { target, fn_name, args }];
+        Self::queue_batch(env, calls, eta)
+    }
+
+    /// Queue several calls that execute together at or after `eta` (admin only)
+    pub fn queue_batch(env: Env, calls: Vec<Call>, eta: u64) -> Result<u32, TimelockError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(TimelockError::NotInitialized)?;
+        admin.require_auth();
+
+        if calls.is_empty() {
+            return Err(TimelockError::EmptyBatch);
+        }
+
+        // The delay is public: eta must leave at least the minimum delay
+        let min_delay: u64 = env.storage().instance().get(&MIN_DELAY).unwrap_or(0);
+        if eta < env.ledger().timestamp() + min_delay {
+            return Err(TimelockError::InvalidEta);
+        }
+
+        let operation_id: u32 = env.storage().instance().get(&OPERATION_COUNT).unwrap_or(0) + 1;
+        let operation = Operation {
+            calls,
+            eta,
+            status: OperationStatus::Queued,
+        };
+
+        env.storage().persistent().set(&(symbol_short!("op"), operation_id), &operation);
+        env.storage().instance().set(&OPERATION_COUNT, &operation_id);
+
+        // Emit queue event with the full call list so the delay is observable
+        env.events().publish(
+            (symbol_short!("tlock"), symbol_short!("queue")),
+            (operation_id, operation.calls, eta),
+        );
+
+        Ok(operation_id)
+    }
+
+    /// Execute a queued operation once its eta has passed and before it expires.
+    /// Anyone may call this.
+    pub fn execute(env: Env, operation_id: u32) -> Result<(), TimelockError> {
+        let mut operation = Self::load_operation(&env, operation_id)?;
+        if operation.status != OperationStatus::Queued {
+            return Err(TimelockError::NotQueued);
+        }
+
+        let now = env.ledger().timestamp();
+        if now < operation.eta {
+            return Err(TimelockError::NotReady);
+        }
+
+        let grace_period: u64 = env.storage().instance().get(&GRACE_PERIOD).unwrap_or(0);
+        if now > operation.eta + grace_period {
+            return Err(TimelockError::Expired);
+        }
+
+        // Mark executed before the external calls
+        operation.status = OperationStatus::Executed;
+        env.storage().persistent().set(&(symbol_short!("op"), operation_id), &operation);
+
+        for call in operation.calls.iter() {
+            env.invoke_contract::<Val>(&call.target, &call.fn_name, call.args);
+        }
+
+        // Emit execution event
+        env.events().publish(
+            (symbol_short!("tlock"), symbol_short!("execute")),
+            operation_id,
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a queued operation (guardian only)
+    pub fn cancel(env: Env, operation_id: u32) -> Result<(), TimelockError> {
+        let guardian: Address = env
+            .storage()
+            .instance()
+            .get(&GUARDIAN)
+            .ok_or(TimelockError::NotInitialized)?;
+        guardian.require_auth();
+
+        let mut operation = Self::load_operation(&env, operation_id)?;
+        if operation.status != OperationStatus::Queued {
+            return Err(TimelockError::NotQueued);
+        }
+
+        operation.status = OperationStatus::Cancelled;
+        env.storage().persistent().set(&(symbol_short!("op"), operation_id), &operation);
+
+        // Emit cancel event
+        env.events().publish(
+            (symbol_short!("tlock"), symbol_short!("cancel")),
+            operation_id,
+        );
+
+        Ok(())
+    }
+
+    /// Get a queued, executed, or cancelled operation
+    pub fn get_operation(env: Env, operation_id: u32) -> Result<Operation, TimelockError> {
+        Self::load_operation(&env, operation_id)
+    }
+
+    /// Get the minimum delay in seconds
+    pub fn get_min_delay(env: Env) -> u64 {
+        env.storage().instance().get(&MIN_DELAY).unwrap_or(0)
+    }
+
+    /// Get the admin allowed to queue operations
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    /// Get the guardian allowed to cancel operations
+    pub fn get_guardian(env: Env) -> Address {
+        env.storage().instance().get(&GUARDIAN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn load_operation(env: &Env, operation_id: u32) -> Result<Operation, TimelockError> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("op"), operation_id))
+            .ok_or(TimelockError::OperationNotFound)
+    }
+}
+
+mod test;
@@ -0,0 +1,117 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env, IntoVal,
+};
+use tuxedo_vault::{TuxedoVault, TuxedoVaultClient};
+
+const DELAY: u64 = 2 * 86_400;
+const GRACE: u64 = 7 * 86_400;
+
+struct TimelockTest<'a> {
+    env: Env,
+    timelock: TimelockClient<'a>,
+    vault: TuxedoVaultClient<'a>,
+}
+
+impl<'a> TimelockTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+
+        let timelock = TimelockClient::new(&env, &env.register(Timelock, ()));
+        timelock.initialize(&admin, &guardian, &DELAY, &GRACE);
+
+        // The vault's admin role points at the timelock
+        let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let platform = Address::generate(&env);
+        let vault = TuxedoVaultClient::new(
+            &env,
+            &env.register(TuxedoVault, (&timelock.address, &admin, &platform, &usdc, None::<u32>)),
+        );
+
+        TimelockTest { env, timelock, vault }
+    }
+
+    fn fee_call(&self, fee_bps: u32) -> Call {
+        Call {
+            target: self.vault.address.clone(),
+            fn_name: Symbol::new(&self.env, "set_platform_fee_bps"),
+            args: vec![&self.env, fee_bps.into_val(&self.env)],
+        }
+    }
+
+    fn now(&self) -> u64 {
+        self.env.ledger().timestamp()
+    }
+}
+
+#[test]
+fn test_queue_and_execute_after_delay() {
+    let t = TimelockTest::setup();
+    let eta = t.now() + DELAY;
+    let call = t.fee_call(500);
+
+    let id = t.timelock.queue(&call.target, &call.fn_name, &call.args, &eta);
+
+    // Too early
+    t.env.ledger().set_timestamp(eta - 1);
+    assert_eq!(t.timelock.try_execute(&id), Err(Ok(TimelockError::NotReady)));
+    assert_eq!(t.vault.get_platform_fee_bps(), 200);
+
+    t.env.ledger().set_timestamp(eta);
+    t.timelock.execute(&id);
+    assert_eq!(t.vault.get_platform_fee_bps(), 500);
+    assert_eq!(t.timelock.get_operation(&id).status, OperationStatus::Executed);
+    assert_eq!(t.timelock.try_execute(&id), Err(Ok(TimelockError::NotQueued)));
+}
+
+#[test]
+fn test_queue_rejects_short_eta() {
+    let t = TimelockTest::setup();
+    let call = t.fee_call(500);
+
+    assert_eq!(
+        t.timelock.try_queue(&call.target, &call.fn_name, &call.args, &(t.now() + DELAY - 1)),
+        Err(Ok(TimelockError::InvalidEta))
+    );
+    assert_eq!(
+        t.timelock.try_queue_batch(&Vec::new(&t.env), &(t.now() + DELAY)),
+        Err(Ok(TimelockError::EmptyBatch))
+    );
+}
+
+#[test]
+fn test_batch_runs_calls_in_order() {
+    let t = TimelockTest::setup();
+    let eta = t.now() + DELAY;
+
+    let id = t.timelock.queue_batch(&vec![&t.env, t.fee_call(300), t.fee_call(800)], &eta);
+
+    t.env.ledger().set_timestamp(eta + 1);
+    t.timelock.execute(&id);
+    assert_eq!(t.vault.get_platform_fee_bps(), 800);
+}
+
+#[test]
+fn test_cancel_and_expiry() {
+    let t = TimelockTest::setup();
+    let eta = t.now() + DELAY;
+    let call = t.fee_call(500);
+
+    let cancelled = t.timelock.queue(&call.target, &call.fn_name, &call.args, &eta);
+    t.timelock.cancel(&cancelled);
+    t.env.ledger().set_timestamp(eta);
+    assert_eq!(t.timelock.try_execute(&cancelled), Err(Ok(TimelockError::NotQueued)));
+
+    let stale = t.timelock.queue(&call.target, &call.fn_name, &call.args, &(eta + DELAY));
+    t.env.ledger().set_timestamp(eta + DELAY + GRACE + 1);
+    assert_eq!(t.timelock.try_execute(&stale), Err(Ok(TimelockError::Expired)));
+    assert_eq!(t.vault.get_platform_fee_bps(), 200);
+}
@@ -1,13 +1,17 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, Address, Env, String, Symbol,
-    token::TokenInterface, symbol_short,
+    contract, contracterror, contractimpl, contracttype, Address, Env, String, Symbol,
+    token::TokenInterface, symbol_short, IntoVal, Val, Vec,
 };
 use stellar_tokens::fungible::Base;
+use tuxedo_common::{events, tiers::TierOracle};
 
 // ============ Constants ============
 const OWNER: Symbol = symbol_short!("OWNER");
+const SUPPLY_CHECKPOINTS: Symbol = symbol_short!("SUPPLY_CK");
+const TIER_THRESHOLDS: Symbol = symbol_short!("TIERS");
+const BURNED: Symbol = symbol_short!("BURNED");
 
 // ============ Errors ============
 #[contracterror]
@@ -18,6 +22,17 @@ pub enum TokenError {
     Unauthorized = 2,
     InsufficientBalance = 3,
     InvalidAmount = 4,
+    FutureLookup = 5,
+    InvalidTiers = 6,
+}
+
+// ============ Data Structures ============
+/// Balance recorded at the end of a ledger, used for voting snapshots
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub amount: i128,
 }
 
 // ============ TUX Token Contract ============
@@ -52,13 +67,16 @@ impl TuxToken {
 
         // Mint initial supply to admin
         Base::mint(&env, &admin, initial_supply);
+        Self::checkpoint_balances(&env, None, Some(&admin));
 
         // Set owner
         env.storage().instance().set(&OWNER, &admin);
 
         // Emit initialization event
-        env.events().publish(
-            (symbol_short!("tkn"), symbol_short!("init")),
+        events::publish(
+            &env,
+            symbol_short!("tkn"),
+            symbol_short!("init"),
             (admin, initial_supply),
         );
 
@@ -82,21 +100,167 @@ impl TuxToken {
 
         // Mint tokens
         Base::mint(&env, &to, amount);
+        Self::checkpoint_balances(&env, None, Some(&to));
 
         // Emit mint event
-        env.events().publish(
-            (symbol_short!("tkn"), symbol_short!("mint")),
+        events::publish(
+            &env,
+            symbol_short!("tkn"),
+            symbol_short!("mint"),
             (admin, to, amount),
         );
 
         Ok(())
     }
 
-    
+    /// Get total token supply
+    pub fn total_supply(env: Env) -> i128 {
+        Base::total_supply(&env)
+    }
+
+    /// Get the total amount of TUX ever burned
+    pub fn total_burned(env: Env) -> i128 {
+        env.storage().instance().get(&BURNED).unwrap_or(0)
+    }
+
+    /// Get an account's balance at the end of a past ledger
+    pub fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError> {
+        if ledger >= env.ledger().sequence() {
+            return Err(TokenError::FutureLookup);
+        }
+
+        let key = (symbol_short!("ckpt"), account);
+        Ok(Self::lookup_checkpoint(&env, &key, ledger))
+    }
+
+    /// Get the total supply at the end of a past ledger
+    pub fn get_past_total_supply(env: Env, ledger: u32) -> Result<i128, TokenError> {
+        if ledger >= env.ledger().sequence() {
+            return Err(TokenError::FutureLookup);
+        }
+
+        Ok(Self::lookup_checkpoint(&env, &SUPPLY_CHECKPOINTS, ledger))
+    }
+
+    /// Set the minimum balances for tiers 1, 2, ... (admin only).
+    /// Thresholds must be positive and strictly increasing; an empty list puts
+    /// everyone at tier 0.
+    pub fn set_tier_thresholds(
+        env: Env,
+        admin: Address,
+        thresholds: Vec<i128>,
+    ) -> Result<(), TokenError> {
+        // Verify admin authorization
+        let owner: Address = env.storage().instance().get(&OWNER).unwrap();
+        if admin != owner {
+            return Err(TokenError::Unauthorized);
+        }
+
+        admin.require_auth();
+
+        let mut previous = 0;
+        for threshold in thresholds.iter() {
+            if threshold <= previous {
+                return Err(TokenError::InvalidTiers);
+            }
+            previous = threshold;
+        }
+
+        env.storage().instance().set(&TIER_THRESHOLDS, &thresholds);
+
+        // Emit tier update event
+        events::publish(
+            &env,
+            symbol_short!("tkn"),
+            symbol_short!("tiers"),
+            thresholds,
+        );
+
+        Ok(())
+    }
+
+    /// Get the minimum balances for tiers 1, 2, ...
+    pub fn get_tier_thresholds(env: Env) -> Vec<i128> {
+        env.storage()
+            .instance()
+            .get(&TIER_THRESHOLDS)
+            .unwrap_or(Vec::new(&env))
+    }
+
     /// Get contract admin
     pub fn get_admin(env: Env) -> Address {
         env.storage().instance().get(&OWNER).unwrap()
     }
+
+    // ============ Internal Helper Functions ============
+
+    /// Record post-update balances for the touched accounts and, on mint/burn, the supply
+    fn checkpoint_balances(env: &Env, from: Option<&Address>, to: Option<&Address>) {
+        for account in [from, to].into_iter().flatten() {
+            let key = (symbol_short!("ckpt"), account.clone());
+            Self::write_checkpoint(env, &key, Base::balance(env, account));
+        }
+
+        if from.is_none() || to.is_none() {
+            Self::write_checkpoint(env, &SUPPLY_CHECKPOINTS, Base::total_supply(env));
+        }
+    }
+
+    /// Add a burn to the running total
+    fn record_burn(env: &Env, amount: i128) {
+        let burned: i128 = env.storage().instance().get(&BURNED).unwrap_or(0);
+        env.storage().instance().set(&BURNED, &(burned + amount));
+    }
+
+    /// Append a checkpoint, overwriting the last one if it is for the current ledger
+    fn write_checkpoint<K>(env: &Env, key: &K, amount: i128)
+    where
+        K: IntoVal<Env, Val>,
+    {
+        let ledger = env.ledger().sequence();
+        let mut checkpoints: Vec<Checkpoint> = env
+            .storage()
+            .persistent()
+            .get(key)
+            .unwrap_or(Vec::new(env));
+
+        match checkpoints.last() {
+            Some(last) if last.ledger == ledger => {
+                checkpoints.set(checkpoints.len() - 1, Checkpoint { ledger, amount });
+            }
+            _ => checkpoints.push_back(Checkpoint { ledger, amount }),
+        }
+
+        env.storage().persistent().set(key, &checkpoints);
+    }
+
+    /// Binary search for the last checkpoint at or before `ledger`
+    fn lookup_checkpoint<K>(env: &Env, key: &K, ledger: u32) -> i128
+    where
+        K: IntoVal<Env, Val>,
+    {
+        let checkpoints: Vec<Checkpoint> = env
+            .storage()
+            .persistent()
+            .get(key)
+            .unwrap_or(Vec::new(env));
+
+        let (mut low, mut high) = (0u32, checkpoints.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if checkpoints.get_unchecked(mid).ledger <= ledger {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        if low == 0 {
+            0
+        } else {
+            checkpoints.get_unchecked(low - 1).amount
+        }
+    }
 }
 
 // ============ TokenInterface Implementation ============
@@ -116,18 +280,24 @@ impl TokenInterface for TuxToken {
 
     fn transfer(env: Env, from: Address, to: Address, amount: i128) {
         Base::transfer(&env, &from, &to, amount);
+        Self::checkpoint_balances(&env, Some(&from), Some(&to));
     }
 
     fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
         Base::transfer_from(&env, &spender, &from, &to, amount);
+        Self::checkpoint_balances(&env, Some(&from), Some(&to));
     }
 
     fn burn(env: Env, from: Address, amount: i128) {
         Base::burn(&env, &from, amount);
+        Self::checkpoint_balances(&env, Some(&from), None);
+        Self::record_burn(&env, amount);
     }
 
     fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
         Base::burn_from(&env, &spender, &from, amount);
+        Self::checkpoint_balances(&env, Some(&from), None);
+        Self::record_burn(&env, amount);
     }
 
     fn decimals(env: Env) -> u32 {
@@ -143,76 +313,23 @@ impl TokenInterface for TuxToken {
     }
 }
 
-// ============ Test Suite ============
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger as _};
-
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let admin = Address::generate(&env);
-
-        let initial_supply = 100_000_000i128 * 10_000_000i128; // 100M TUX with 7 decimals
-
-        TuxToken::initialize(env.clone(), admin.clone(), initial_supply).unwrap();
-
-        assert_eq!(TuxToken::name(env.clone()), "Tuxedo Token");
-        assert_eq!(TuxToken::symbol(env.clone()), "TUX");
-        assert_eq!(TuxToken::decimals(env.clone()), 7);
-        assert_eq!(TuxToken::balance(env.clone(), admin.clone()), initial_supply);
-        assert_eq!(TuxToken::get_admin(env.clone()), admin);
-    }
-
-    #[test]
-    fn test_transfer() {
-        let env = Env::default();
-        let admin = Address::generate(&env);
-        let user = Address::generate(&env);
-
-        let initial_supply = 100_000_000i128 * 10_000_000i128;
-        let transfer_amount = 1_000i128 * 10_000_000i128; // 1,000 TUX
-
-        TuxToken::initialize(env.clone(), admin.clone(), initial_supply).unwrap();
-
-        // Transfer from admin to user
-        TuxToken::transfer(env.clone(), admin.clone(), user.clone(), transfer_amount);
-
-        assert_eq!(TuxToken::balance(env.clone(), admin.clone()), initial_supply - transfer_amount);
-        assert_eq!(TuxToken::balance(env.clone(), user.clone()), transfer_amount);
-    }
-
-    #[test]
-    fn test_mint() {
-        let env = Env::default();
-        let admin = Address::generate(&env);
-        let user = Address::generate(&env);
-
-        let initial_supply = 50_000_000i128 * 10_000_000i128;
-        let mint_amount = 10_000i128 * 10_000_000i128; // 10,000 TUX
-
-        TuxToken::initialize(env.clone(), admin.clone(), initial_supply).unwrap();
-
-        // Mint additional tokens
-        TuxToken::mint(env.clone(), admin.clone(), user.clone(), mint_amount).unwrap();
-
-        assert_eq!(TuxToken::balance(env.clone(), user.clone()), mint_amount);
+// ============ TierOracle Implementation ============
+#[contractimpl]
+impl TierOracle for TuxToken {
+    /// Number of tier thresholds the user's balance reaches
+    fn tier_of(env: Env, user: Address) -> u32 {
+        let balance = Base::balance(&env, &user);
+        let mut tier = 0;
+        for threshold in Self::get_tier_thresholds(env).iter() {
+            if balance < threshold {
+                break;
+            }
+            tier += 1;
+        }
+        tier
     }
 
-    #[test]
-    fn test_burn() {
-        let env = Env::default();
-        let admin = Address::generate(&env);
-
-        let initial_supply = 100_000_000i128 * 10_000_000i128;
-        let burn_amount = 10_000i128 * 10_000_000i128; // 10,000 TUX
-
-        TuxToken::initialize(env.clone(), admin.clone(), initial_supply).unwrap();
-
-        // Burn tokens
-        TuxToken::burn(env.clone(), admin.clone(), burn_amount).unwrap();
-
-        assert_eq!(TuxToken::balance(env.clone(), admin.clone()), initial_supply - burn_amount);
+    fn meets_tier(env: Env, user: Address, level: u32) -> bool {
+        Self::tier_of(env, user) >= level
     }
-}
\ No newline at end of file
+}
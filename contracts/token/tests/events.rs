@@ -0,0 +1,43 @@
+//! Event schema of the TUX token's own events, decoded field by field. The
+//! SEP-41 transfer, mint and burn events come from the token library and are
+//! not covered here.
+
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, Env, Vec};
+use tux_token::{TuxToken, TuxTokenClient};
+use tuxedo_fixtures::{events::EventLog, TuxedoFixture, TUX_SUPPLY, UNIT};
+
+#[test]
+fn test_initialize_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+    tux.initialize(&admin, &TUX_SUPPLY);
+
+    let mut log = EventLog::new(&env, &tux.address, symbol_short!("tkn"));
+    assert_eq!(
+        log.next::<(Address, i128)>(symbol_short!("init")),
+        (admin, TUX_SUPPLY)
+    );
+    log.finish();
+}
+
+#[test]
+fn test_admin_events() {
+    let f = TuxedoFixture::new(&Env::default());
+    let user = Address::generate(&f.env);
+
+    f.tux.mint(&f.admin, &user, &(10 * UNIT));
+    let mut log = EventLog::new(&f.env, &f.tux.address, symbol_short!("tkn"));
+    assert_eq!(
+        log.next::<(Address, Address, i128)>(symbol_short!("mint")),
+        (f.admin.clone(), user, 10 * UNIT)
+    );
+    log.finish();
+
+    let thresholds = vec![&f.env, 100 * UNIT, 1_000 * UNIT];
+    f.tux.set_tier_thresholds(&f.admin, &thresholds);
+    let mut log = EventLog::new(&f.env, &f.tux.address, symbol_short!("tkn"));
+    assert_eq!(log.next::<Vec<i128>>(symbol_short!("tiers")), thresholds);
+    log.finish();
+}
@@ -0,0 +1,108 @@
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env, String,
+};
+use tux_token::TokenError;
+use tuxedo_fixtures::{TuxedoFixture, TUX_SUPPLY, UNIT};
+
+#[test]
+fn test_initialize() {
+    let f = TuxedoFixture::new(&Env::default());
+
+    assert_eq!(f.tux.name(), String::from_str(&f.env, "Tuxedo Token"));
+    assert_eq!(f.tux.symbol(), String::from_str(&f.env, "TUX"));
+    assert_eq!(f.tux.decimals(), 7);
+    assert_eq!(f.tux.balance(&f.admin), TUX_SUPPLY);
+    assert_eq!(f.tux.total_supply(), TUX_SUPPLY);
+    assert_eq!(f.tux.get_admin(), f.admin);
+}
+
+#[test]
+fn test_transfer() {
+    let f = TuxedoFixture::new(&Env::default());
+    let user = Address::generate(&f.env);
+    let transfer_amount = 1_000 * UNIT; // 1,000 TUX
+
+    // Transfer from admin to user
+    f.tux.transfer(&f.admin, &user, &transfer_amount);
+
+    assert_eq!(f.tux.balance(&f.admin), TUX_SUPPLY - transfer_amount);
+    assert_eq!(f.tux.balance(&user), transfer_amount);
+}
+
+#[test]
+fn test_mint() {
+    let f = TuxedoFixture::new(&Env::default());
+    let user = Address::generate(&f.env);
+    let mint_amount = 10_000 * UNIT; // 10,000 TUX
+
+    // Mint additional tokens
+    f.tux.mint(&f.admin, &user, &mint_amount);
+
+    assert_eq!(f.tux.balance(&user), mint_amount);
+    assert_eq!(
+        f.tux.try_mint(&user, &user, &mint_amount),
+        Err(Ok(TokenError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_burn() {
+    let f = TuxedoFixture::new(&Env::default());
+    let burn_amount = 10_000 * UNIT; // 10,000 TUX
+
+    // Burn tokens
+    f.tux.burn(&f.admin, &burn_amount);
+
+    assert_eq!(f.tux.balance(&f.admin), TUX_SUPPLY - burn_amount);
+    assert_eq!(f.tux.total_burned(), burn_amount);
+}
+
+#[test]
+fn test_past_votes_follow_checkpoints() {
+    let env = Env::default();
+    env.ledger().set_sequence_number(10);
+    let f = TuxedoFixture::new(&env);
+    let user = Address::generate(&env);
+
+    env.ledger().set_sequence_number(20);
+    f.tux.transfer(&f.admin, &user, &300);
+    f.tux.burn(&user, &100);
+
+    env.ledger().set_sequence_number(30);
+    assert_eq!(f.tux.get_past_votes(&user, &19), 0);
+    assert_eq!(f.tux.get_past_votes(&user, &20), 200);
+    assert_eq!(f.tux.get_past_votes(&f.admin, &15), TUX_SUPPLY);
+    assert_eq!(f.tux.get_past_votes(&f.admin, &29), TUX_SUPPLY - 300);
+    assert_eq!(f.tux.get_past_total_supply(&10), TUX_SUPPLY);
+    assert_eq!(f.tux.get_past_total_supply(&25), TUX_SUPPLY - 100);
+    assert_eq!(
+        f.tux.try_get_past_votes(&user, &30),
+        Err(Ok(TokenError::FutureLookup))
+    );
+}
+
+#[test]
+fn test_tiers_follow_balance_thresholds() {
+    let env = Env::default();
+    let f = TuxedoFixture::new(&env);
+    let user = Address::generate(&env);
+    assert_eq!(f.tux.tier_of(&f.admin), 0);
+
+    f.tux
+        .set_tier_thresholds(&f.admin, &vec![&env, 100 * UNIT, 1_000 * UNIT]);
+    f.tux.transfer(&f.admin, &user, &(999 * UNIT));
+    assert_eq!(f.tux.tier_of(&user), 1);
+    assert!(f.tux.meets_tier(&user, &1));
+    assert!(!f.tux.meets_tier(&user, &2));
+
+    f.tux.transfer(&f.admin, &user, &UNIT);
+    assert_eq!(f.tux.tier_of(&user), 2);
+    assert_eq!(f.tux.tier_of(&f.admin), 2);
+
+    assert_eq!(
+        f.tux
+            .try_set_tier_thresholds(&f.admin, &vec![&env, 1_000 * UNIT, 100 * UNIT]),
+        Err(Ok(TokenError::InvalidTiers))
+    );
+}
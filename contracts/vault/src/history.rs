@@ -0,0 +1,109 @@
+//! Per-user deposit and withdrawal history and share value snapshots for the
+//! APY estimate, compiled in with the `history` feature.
+
+use super::*;
+
+// Share value snapshots for the APY estimate, as (ledger timestamp, share
+// value): at most one every 6 hours, 29 of them spanning the last 7 days
+const SV_HISTORY: Symbol = symbol_short!("SV_HIST");
+const SNAPSHOT_INTERVAL: u64 = 6 * 60 * 60;
+const SNAPSHOT_COUNT: u32 = 29;
+
+/// Deposits and withdrawals kept per user for `get_user_history`, oldest
+/// dropped first. At about 160 bytes a record, a full history is one 3 KB
+/// persistent entry, rewritten and paid for in rent by every operation.
+pub const HISTORY_LEN: u32 = 20;
+
+/// One deposit or withdrawal in a user's history, with withdrawals recorded
+/// as negative `amount` and `shares`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositRecord {
+    pub amount: i128,
+    pub shares: i128,
+    /// Share value the shares were minted or redeemed at
+    pub share_value: i128,
+    pub timestamp: u64,
+}
+
+#[contractimpl]
+impl TuxedoVault {
+    /// Get the share value snapshots taken by deposits, withdrawals and yield
+    /// distributions, at most one every 6 hours over about the last 7 days,
+    /// oldest first, as (ledger timestamp, share value)
+    pub fn get_share_value_history(env: Env) -> Vec<(u64, i128)> {
+        env.storage().instance().get(&SV_HISTORY).unwrap_or(vec![&env])
+    }
+
+    /// Get the yearly return in basis points implied by the oldest and newest
+    /// share value snapshots, without compounding. 0 until there are two.
+    pub fn get_estimated_apy(env: Env) -> i128 {
+        let history = Self::get_share_value_history(env);
+        let (first, last) = match (history.first(), history.last()) {
+            (Some(first), Some(last)) if last.0 > first.0 => (first, last),
+            _ => return 0,
+        };
+
+        // (last / first - 1) * year / elapsed, in basis points
+        let per_year = BPS_DENOMINATOR as i128 * YEAR_IN_SECONDS as i128;
+        let elapsed = (last.0 - first.0) as i128;
+        first
+            .1
+            .checked_mul(elapsed)
+            .and_then(|denominator| math::muldiv(last.1 - first.1, per_year, denominator))
+            .unwrap_or(0)
+    }
+
+    /// Get `user`'s last `HISTORY_LEN` deposits and withdrawals, oldest first
+    pub fn get_user_history(env: Env, user: Address) -> Vec<DepositRecord> {
+        env.storage()
+            .persistent()
+            .get(&Self::history_key(&user))
+            .unwrap_or(vec![&env])
+    }
+}
+
+impl TuxedoVault {
+    /// Snapshot the share value for the APY estimate unless the last snapshot
+    /// is under `SNAPSHOT_INTERVAL` old, dropping the oldest beyond
+    /// `SNAPSHOT_COUNT`
+    pub(crate) fn record_share_value(env: &Env, state: &VaultState) -> Result<(), VaultError> {
+        let now = env.ledger().timestamp();
+        let mut history = Self::get_share_value_history(env.clone());
+        if let Some((last, _)) = history.last() {
+            if now < last + SNAPSHOT_INTERVAL {
+                return Ok(());
+            }
+        }
+
+        if history.len() >= SNAPSHOT_COUNT {
+            history.pop_front();
+        }
+        history.push_back((now, Self::calculate_share_value(env, state)?));
+        env.storage().instance().set(&SV_HISTORY, &history);
+        Ok(())
+    }
+
+    /// Append to `user`'s history, dropping the oldest record once it holds
+    /// `HISTORY_LEN`
+    pub(crate) fn record_history(env: &Env, user: &Address, record: DepositRecord) {
+        let key = Self::history_key(user);
+        let mut history: Vec<DepositRecord> =
+            env.storage().persistent().get(&key).unwrap_or(vec![env]);
+        if history.len() >= HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(record);
+
+        env.storage().persistent().set(&key, &history);
+        env.storage().persistent().extend_ttl(
+            &key,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+    }
+
+    pub(crate) fn history_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("history"), user.clone())
+    }
+}
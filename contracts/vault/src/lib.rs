@@ -1,24 +1,209 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, symbol_short,
-    token,
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error,
+    xdr::ToXdr, Address, BytesN, Env, IntoVal, String, Symbol, symbol_short, token, vec, Vec,
 };
+use tuxedo_blend_adapter::{self as blend, PoolClient, Positions, Request, RequestType};
+use tuxedo_common::{
+    constants::{BPS_DENOMINATOR, SCALAR_7},
+    events, math, tiers,
+};
+use tuxedo_dex_adapter as dex;
+use tuxedo_oracle_adapter::{self as oracle, FixedPrice, OracleError};
+
+// Optional modules, each behind the Cargo feature of the same purpose
+#[cfg(feature = "history")]
+mod history;
+#[cfg(feature = "withdrawal-queue")]
+mod queue;
+#[cfg(feature = "referrals")]
+mod referrals;
+#[cfg(feature = "strategy-timelock")]
+mod timelock;
+
+#[cfg(feature = "history")]
+pub use history::{DepositRecord, HISTORY_LEN};
+#[cfg(feature = "withdrawal-queue")]
+pub use queue::WithdrawRequest;
+#[cfg(feature = "referrals")]
+pub use referrals::{Referral, ReferralStats};
+#[cfg(feature = "strategy-timelock")]
+pub use timelock::PendingChange;
 
 // ============ Constants ============
 const ADMIN: Symbol = symbol_short!("ADMIN");
+// Agents allowed to run strategies, the first one primary. Vaults initialized
+// before there could be several keep their only agent under AGENT instead.
 const AGENT: Symbol = symbol_short!("AGENT");
+const AGENTS: Symbol = symbol_short!("AGENTS");
 const PLATFORM: Symbol = symbol_short!("PLATFORM");
 const TOTAL_SHARES: Symbol = symbol_short!("T_SHARES");
 const INITIAL_DEPOSITS: Symbol = symbol_short!("INIT_DEP");
 const SHARE_TOKEN: Symbol = symbol_short!("SHR_TKN");
+const SWAP_ROUTER: Symbol = symbol_short!("SWAP_RTR");
+// Emissions token the vault claims from pools, and where claimed tokens go
+const EMISSIONS_TOKEN: Symbol = symbol_short!("EMIT_TKN");
+const EMISSIONS_ROUTE: Symbol = symbol_short!("EMIT_RTE");
+// Vault holders may move their position into, such as the next version
+const MIGRATION_TARGET: Symbol = symbol_short!("MIG_TGT");
+// Version of the code an upgrade replaced, until the new code finishes it
+const UPGRADE_FROM: Symbol = symbol_short!("UPG_FROM");
+const FEE_BPS: Symbol = symbol_short!("FEE_BPS");
+const KEEPER_BPS: Symbol = symbol_short!("KPR_BPS");
+const DIST_INTERVAL: Symbol = symbol_short!("DIST_INT");
+const LAST_DIST: Symbol = symbol_short!("DIST_LAST");
+// Yearly benchmark return on deposits exempt from the platform fee, and when it
+// was last set; it accrues from then or the last distribution, whichever is later
+const HURDLE_BPS: Symbol = symbol_short!("HURDLE");
+const HURDLE_SINCE: Symbol = symbol_short!("HRD_SINCE");
+// USDC held by the vault but set aside by `sync` as a donation, outside the assets
+const EXCESS: Symbol = symbol_short!("EXCESS");
+const MGMT_FEE_BPS: Symbol = symbol_short!("MGMT_BPS");
+const LAST_ACCRUAL: Symbol = symbol_short!("ACCRUAL");
+const DEPOSIT_FEE_BPS: Symbol = symbol_short!("DEP_FEE");
+const TUX_TOKEN: Symbol = symbol_short!("TUX_TKN");
+// Other assets accepted for deposits, each priced in USDC by its own oracle
+const DEPOSIT_ASSETS: Symbol = symbol_short!("DEP_ASSTS");
+const LOCKUP: Symbol = symbol_short!("LOCKUP");
+const HOLDERS: Symbol = symbol_short!("HOLDERS");
+const WHITELIST_MODE: Symbol = symbol_short!("WL_MODE");
+const ORACLE: Symbol = symbol_short!("ORACLE");
+const MAX_PRICE_AGE: Symbol = symbol_short!("PX_AGE");
+const TUX0_TOKEN: Symbol = symbol_short!("TUX0_TKN");
+const POLICY_KEY: Symbol = symbol_short!("POLICY");
+const POLICY_NONCE: Symbol = symbol_short!("POL_NONCE");
+const KEEPER: Symbol = symbol_short!("KEEPER");
+const STOP_LOSS: Symbol = symbol_short!("STOP_LOSS");
+const HIGH_WATER: Symbol = symbol_short!("HWM");
+const FEE_HWM: Symbol = symbol_short!("FEE_HWM");
+const HWM_FEES: Symbol = symbol_short!("HWM_FEES");
+const STOPPED: Symbol = symbol_short!("STOPPED");
+// Circuit breaker: the largest drop in nominal share value between two
+// state-changing calls, the value at the last one, and whether it has tripped
+const BREAKER_BPS: Symbol = symbol_short!("BRK_BPS");
+const BREAKER_VALUE: Symbol = symbol_short!("BRK_VAL");
+const BREAKER_TRIPPED: Symbol = symbol_short!("BRK_TRIP");
+const PAUSE_STATE: Symbol = symbol_short!("PAUSED");
+// Cap on total assets deposits may reach; absent means no cap
+const DEPOSIT_CAP: Symbol = symbol_short!("DEP_CAP");
+// Cap on each wallet's net deposits; absent means no limit
+const USER_LIMIT: Symbol = symbol_short!("USER_LIM");
+const MIN_DEPOSIT: Symbol = symbol_short!("MIN_DEP");
+// Share balances a withdrawal may leave behind; smaller remainders are swept
+// out with it. Absent means none are swept.
+const DUST_THRESHOLD: Symbol = symbol_short!("DUST");
+// Agent strategy rate limits: seconds between strategies and amount per day,
+// plus the last strategy time and the current window's (start, volume)
+const STRATEGY_COOLDOWN: Symbol = symbol_short!("STR_CD");
+const STRATEGY_MAX_DAILY: Symbol = symbol_short!("STR_MAX");
+const STRATEGY_LAST: Symbol = symbol_short!("STR_LAST");
+const STRATEGY_VOLUME: Symbol = symbol_short!("STR_VOL");
+// Strategies executed so far; the last ones are kept in persistent slots
+const STRATEGY_COUNT: Symbol = symbol_short!("STR_CNT");
+// Share of assets the agent must leave idle, and the vault's positions across
+// pools as last reported by them
+const RESERVE_BPS: Symbol = symbol_short!("RSV_BPS");
+const DEPLOYED: Symbol = symbol_short!("DEPLOYED");
+
+// Keeper escrow task paying callers of `keeper_distribute_yield`
+const DISTRIBUTE_TASK: Symbol = symbol_short!("dist_yld");
+
+// Oracle prices older than this are rejected unless configured otherwise
+const DEFAULT_MAX_PRICE_AGE: u64 = 900; // 15 minutes
 
 // Initial share value: 1 USDC = 1 TUX0 (with 7 decimals)
-const INITIAL_SHARE_VALUE: i128 = 10_000_000; // 1.0000000
+const INITIAL_SHARE_VALUE: i128 = SCALAR_7; // 1.0000000
+
+// Decimals the vault asset must have: amounts, shares and the share value all
+// share its SCALAR_7 fixed point
+const ASSET_DECIMALS: u32 = 7;
+
+// Smallest deposit accepted unless the admin sets another: 1 USDC
+const DEFAULT_MIN_DEPOSIT: i128 = SCALAR_7;
+
+// Floor on the first deposit into an empty vault, so donating USDC to skew the
+// share value of a near-empty vault costs an attacker more than it can steal
+const MIN_FIRST_DEPOSIT: i128 = 10 * SCALAR_7; // 10 USDC
+
+// Agents at once; each strategy call looks itself up in the list
+const MAX_AGENTS: u32 = 10;
+
+// Other deposit assets at once; each held one is priced whenever the vault's
+// total assets are read
+const MAX_DEPOSIT_ASSETS: u32 = 5;
+
+// Hard cap on the dust threshold, whoever sets it: about one USDC of shares
+const MAX_DUST_THRESHOLD: i128 = SCALAR_7;
+
+// Shares are fixed-point with the same 7 decimals as the share value
+const SHARE_DECIMALS: u32 = 7;
+
+/// Version of this vault code, raised with every release so off-chain tooling
+/// can tell which code a deployment runs
+pub const VERSION: u32 = 1;
+
+/// Shares of the first deposit locked in the vault for good, so the supply can
+/// never be redeemed down to where a donation inflates the share value
+pub const DEAD_SHARES: i128 = 1_000;
+
+/// Executed strategies kept for `get_strategy_history`. Each has its own
+/// persistent slot, reused once the count wraps around, so recording one
+/// rewrites a single entry rather than the whole history.
+pub const STRATEGY_HISTORY_LEN: u32 = 50;
+
+// Storage TTLs in ledgers, at roughly five seconds per ledger
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+// Instance storage is extended back to 30 days once it has under 29 left
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+// A share balance lives 120 days past the last time it is touched
+const SHARES_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+const SHARES_LIFETIME_THRESHOLD: u32 = SHARES_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+// Idle USDC kept back from strategies unless the admin sets another ratio
+const DEFAULT_RESERVE_BPS: u32 = 2_000; // 20%
+
+// Agent swap volume limits reset every day
+const DAY_IN_SECONDS: u64 = 86_400;
+
+// Fee structure: 2% to platform by default, 98% stays with users
+const PLATFORM_FEE_BPS: u32 = 200; // 2% in basis points
+
+// Hard cap on the platform fee, whoever sets it
+const MAX_PLATFORM_FEE_BPS: u32 = 1_000; // 10%
+
+// Reward to the caller of `distribute_yield`, out of the yield distributed
+const KEEPER_REWARD_BPS: u32 = 10; // 0.1%
+
+// Hard cap on the caller reward, whoever sets it
+const MAX_KEEPER_REWARD_BPS: u32 = 100; // 1%
+
+// Hard cap on the yearly management fee, whoever sets it
+const MAX_MANAGEMENT_FEE_BPS: u32 = 200; // 2% a year
+
+// The management fee rate is yearly
+const YEAR_IN_SECONDS: u64 = 365 * DAY_IN_SECONDS;
+
+// Hard cap on the deposit fee, whoever sets it
+const MAX_DEPOSIT_FEE_BPS: u32 = 100; // 1%
+
+// Deposit fee discount by TUX tier: none, Bronze, Silver, Gold and above
+const TIER_DISCOUNT_BPS: [u32; 4] = [0, 1_000, 2_500, 5_000];
+
+// Hard cap on the early-withdrawal penalty, whoever sets it
+const MAX_EARLY_PENALTY_BPS: u32 = 500; // 5%
+
+// Whitelist batches write one entry per address, kept well inside the 50
+// entries a transaction may write
+const MAX_WHITELIST_BATCH: u32 = 40;
+
+// Share value drop that trips the circuit breaker unless the admin sets another
+const DEFAULT_BREAKER_BPS: u32 = 500; // 5%
 
-// Fee structure: 2% to platform, 98% stays with users
-const PLATFORM_FEE_BPS: i128 = 200; // 2% in basis points
-const BPS_DENOMINATOR: i128 = 10_000; // 100% = 10,000 basis points
 
 // ============ Errors ============
 #[contracterror]
@@ -34,16 +219,71 @@ pub enum VaultError {
     InvalidAsset = 7,
     TransferFailed = 8,
     DivisionByZero = 9,
+    RouterNotSet = 10,
+    DeadlinePassed = 11,
+    SlippageExceeded = 12,
+    StalePrice = 13,
+    PriceUnavailable = 14,
+    SharesOutstanding = 15,
+    MathOverflow = 16,
+    PolicyKeyNotSet = 17,
+    SignatureExpired = 18,
+    InvalidNonce = 19,
+    StopLossTriggered = 20,
+    SwapLimitExceeded = 21,
+    UnknownBridge = 22,
+    TransferShortfall = 23,
+    Paused = 24,
+    InvalidAddress = 25,
+    DepositCapExceeded = 26,
+    UserLimitExceeded = 27,
+    InvalidRequestType = 28,
+    PoolNotApproved = 29,
+    RateLimited = 30,
+    ReserveBreached = 31,
+    InsufficientAllowance = 32,
+    TooSoon = 33,
+    Blacklisted = 34,
+    NotWhitelisted = 35,
+    WithdrawPending = 36,
+    NoWithdrawRequest = 37,
+    NotClaimable = 38,
+    AlreadyFulfilled = 39,
+    QueueFull = 40,
+    BreakerTripped = 41,
+    MigrationNotApproved = 42,
+    NotInitialized = 43,
+    TooManyReferrers = 44,
+    TooManyAgents = 45,
+    ChangeNotQueued = 46,
+    TooManyAssets = 47,
+    NoUpgradePending = 48,
+    AllowanceOnShareToken = 49,
 }
 
 // ============ Data Structures ============
 #[contracttype]
 #[derive(Clone)]
 pub struct VaultStats {
+    /// Idle USDC held by the vault, other accepted assets at their USDC value
+    /// and pool positions: the assets shares are priced on
     pub total_assets: i128,
     pub total_shares: i128,
     pub share_value: i128,
     pub initial_deposits: i128,
+    pub platform_fee_bps: u32,
+    /// USDC deposits can still add under the cap, i128::MAX when uncapped
+    pub remaining_capacity: i128,
+    /// Smallest deposit accepted right now
+    pub min_deposit: i128,
+    /// Positions in pools as of the last strategy, part of `total_assets`
+    pub deployed_assets: i128,
+    /// Earliest ledger timestamp the next yield distribution can run at
+    pub next_distribution_at: u64,
+    /// Idle USDC alone, without the other accepted assets in `total_assets`
+    pub idle_assets: i128,
+    /// Addresses holding shares on the internal ledger, 0 with a TUX0 token
+    pub holder_count: u32,
 }
 
 #[contracttype]
@@ -53,6 +293,259 @@ pub struct Strategy {
     pub pool: Address,
     pub asset: Address,
     pub amount: i128,
+    pub request_type: u32, // Blend request type matching the action, e.g. 2 = supply collateral
+}
+
+/// One strategy the agent executed, with the amount the pool actually moved
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyRecord {
+    pub action: Symbol,
+    pub pool: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+    pub ledger: u32,
+}
+
+/// What `simulate_strategy` expects a strategy to do if executed right now
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyPreview {
+    /// Whether the strategy passes every check `agent_execute` makes
+    pub allowed: bool,
+    /// `VaultError` code of the first failed check, None if allowed
+    pub error: Option<u32>,
+    /// USDC the pool would move, a withdrawal capped at the vault's recorded
+    /// position there; 0 if not allowed
+    pub amount: i128,
+    /// Idle USDC afterwards, as `get_total_assets` counts it
+    pub idle_after: i128,
+    /// Positions in pools afterwards, as `get_deployed_assets` counts them
+    pub deployed_after: i128,
+    /// `get_share_value` afterwards
+    pub share_value_after: i128,
+}
+
+/// Which vault operations the admin has halted
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PauseState {
+    Active,
+    /// Deposits halted; withdrawals and strategies continue
+    DepositsPaused,
+    /// Deposits, withdrawals and strategies halted
+    FullyPaused,
+}
+
+/// What happens to emissions the vault claims from pools
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EmissionsRoute {
+    /// Keep them in the vault for the agent to swap with `agent_swap_rewards`
+    Hold,
+    /// Send them to the platform, which converts them off-chain and pays the
+    /// USDC back with `donate_yield`
+    Platform,
+}
+
+/// Agent swap between vault assets through the AMM router
+#[contracttype]
+#[derive(Clone)]
+pub struct Swap {
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: i128,
+    pub min_out: i128,
+    pub deadline: u64,
+}
+
+/// Payload of the `deposit` event, topics `("vault", "deposit", seq)`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositEvent {
+    pub user: Address,
+    pub amount: i128,
+    pub shares: i128,
+    /// `get_share_value` once the shares are minted
+    pub share_value_after: i128,
+    /// `get_total_assets` once the deposit is in
+    pub total_assets_after: i128,
+    /// `get_total_shares` once the shares are minted
+    pub total_shares_after: i128,
+}
+
+/// Another asset `deposit_asset` accepts, valued in USDC at its oracle's price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositAsset {
+    /// Reflector-compatible feed pricing the asset in USDC
+    pub oracle: Address,
+    /// Seconds after which the feed's price is rejected as stale
+    pub max_price_age: u64,
+    /// Amount of the asset the vault holds for depositors
+    pub balance: i128,
+}
+
+/// A sensitive configuration change. Once a change delay is set, these only
+/// take effect through `queue_change` and `execute_change`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigChange {
+    /// New platform fee, as `set_platform_fee_bps`
+    PlatformFeeBps(u32),
+    /// New primary agent, as `set_agent`
+    Agent(Address),
+    /// New platform address, as `set_platform`
+    Platform(Address),
+    /// New reserve ratio, as `set_reserve_bps`
+    ReserveRatio(u32),
+    /// Lower change delay, as `set_change_delay`
+    ChangeDelay(u64),
+}
+
+/// Payload of the `withdraw` event, topics `("vault", "withdraw", seq)`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawEvent {
+    pub user: Address,
+    pub shares: i128,
+    pub assets: i128,
+    /// Early-withdrawal penalty left in the vault, on top of `assets`
+    pub penalty: i128,
+    /// How far `assets` and `penalty` fall short of the shares' slice of the
+    /// deposit baseline, as after a pool loss is realized; 0 when paid in full
+    pub shortfall: i128,
+    /// `get_share_value` once the shares are burned and paid out
+    pub share_value_after: i128,
+    /// `get_total_assets` once `assets` has left
+    pub total_assets_after: i128,
+    /// `get_total_shares` once the shares are burned
+    pub total_shares_after: i128,
+}
+
+/// Payload of the `strategy` event, topics `("vault", "strategy", seq)`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyEvent {
+    pub agent: Address,
+    pub action: Symbol,
+    pub pool: Address,
+    /// USDC the pool actually moved
+    pub amount: i128,
+    /// `get_strategy_count` including this strategy, so indexers can spot gaps
+    pub count: u64,
+    /// `get_share_value` once the funds have moved
+    pub share_value_after: i128,
+    /// `get_total_assets`, which excludes pool positions, once the funds have
+    /// moved
+    pub total_assets_after: i128,
+    /// `get_total_shares`, which a strategy leaves unchanged
+    pub total_shares_after: i128,
+}
+
+/// Payload of the `yield` event, topics `("vault", "yield", seq)`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YieldEvent {
+    /// Yield counted since the last distribution, before the reward and fee
+    pub yield_earned: i128,
+    /// Fee taken for the platform, referral payouts included
+    pub platform_fee: i128,
+    /// Who ran the distribution and was paid `keeper_reward`
+    pub caller: Address,
+    pub keeper_reward: i128,
+    /// `get_share_value` once the reward and fee are paid
+    pub share_value_after: i128,
+    /// `get_total_assets` once the reward and fee are paid
+    pub total_assets_after: i128,
+    /// `get_total_shares`, which a distribution leaves unchanged
+    pub total_shares_after: i128,
+}
+
+/// Instance entries the share accounting reads, loaded once per invocation.
+/// Entrypoints mutate it through the setters and call `save`, which writes
+/// back only the fields that changed.
+struct VaultState {
+    usdc: Address,
+    share_token: Option<Address>,
+    total_shares: i128,
+    initial_deposits: i128,
+    shares_dirty: bool,
+    deposits_dirty: bool,
+}
+
+impl VaultState {
+    fn load(env: &Env) -> Result<Self, VaultError> {
+        let storage = env.storage().instance();
+        Ok(VaultState {
+            usdc: storage.get(&SHARE_TOKEN).ok_or(VaultError::NotInitialized)?,
+            share_token: storage.get(&TUX0_TOKEN),
+            total_shares: storage.get(&TOTAL_SHARES).unwrap_or(0),
+            initial_deposits: storage.get(&INITIAL_DEPOSITS).unwrap_or(0),
+            shares_dirty: false,
+            deposits_dirty: false,
+        })
+    }
+
+    /// `load` for views returning plain values, failing the call with the
+    /// typed error instead
+    fn view(env: &Env) -> Self {
+        Self::load(env).unwrap_or_else(|err| panic_with_error!(env, err))
+    }
+
+    fn set_total_shares(&mut self, total_shares: i128) {
+        self.total_shares = total_shares;
+        self.shares_dirty = true;
+    }
+
+    fn set_initial_deposits(&mut self, initial_deposits: i128) {
+        self.initial_deposits = initial_deposits;
+        self.deposits_dirty = true;
+    }
+
+    fn save(&self, env: &Env) {
+        let storage = env.storage().instance();
+        if self.shares_dirty {
+            storage.set(&TOTAL_SHARES, &self.total_shares);
+        }
+        if self.deposits_dirty {
+            storage.set(&INITIAL_DEPOSITS, &self.initial_deposits);
+        }
+    }
+}
+
+// ============ External Contract Interfaces ============
+/// Mint entrypoint of the TUX0 share token; balances, transfers and burns use `token::TokenClient`
+#[contractclient(name = "ShareTokenClient")]
+pub trait ShareToken {
+    fn mint(env: Env, to: Address, amount: i128);
+    fn get_vault(env: Env) -> Address;
+}
+
+/// Keeper escrow paying bounties for maintenance calls
+#[contractclient(name = "KeeperClient")]
+pub trait KeeperEscrow {
+    fn register_task(
+        env: Env,
+        contract: Address,
+        task_id: Symbol,
+        reward_per_call: i128,
+        min_interval: u64,
+    );
+    fn record_execution(env: Env, contract: Address, task_id: Symbol, caller: Address) -> i128;
+}
+
+/// Vault a holder's position can migrate into, such as the next vault version
+#[contractclient(name = "MigrationTargetClient")]
+pub trait MigrationTarget {
+    fn deposit_for(
+        env: Env,
+        payer: Address,
+        recipient: Address,
+        asset: Address,
+        amount: i128,
+    ) -> i128;
 }
 
 // ============ TuxedoVault Smart Contract ============
@@ -61,392 +554,4513 @@ pub struct TuxedoVault;
 
 #[contractimpl]
 impl TuxedoVault {
-    /// Initialize the vault with admin, agent, and platform addresses
-    pub fn initialize(
+    /// Initialize the vault at deployment with admin, agent, and platform
+    /// addresses, and the platform fee in basis points (2% if `None`).
+    /// Deploying and initializing in one transaction leaves no window for
+    /// someone else to claim the roles first.
+    pub fn __constructor(
         env: Env,
         admin: Address,
         agent: Address,
         platform: Address,
         usdc_asset: Address,
+        platform_fee_bps: Option<u32>,
     ) -> Result<(), VaultError> {
-        // Check if already initialized
-        if env.storage().instance().has(&ADMIN) {
-            return Err(VaultError::AlreadyInitialized);
-        }
-
-        // Set initial state
-        env.storage().instance().set(&ADMIN, &admin);
-        env.storage().instance().set(&AGENT, &agent);
-        env.storage().instance().set(&PLATFORM, &platform);
-        env.storage().instance().set(&SHARE_TOKEN, &usdc_asset);
-        env.storage().instance().set(&TOTAL_SHARES, &0i128);
-        env.storage().instance().set(&INITIAL_DEPOSITS, &0i128);
-
-        // Emit initialization event
-        env.events().publish(
-            (symbol_short!("vault"), symbol_short!("init")),
-            (admin, agent, platform),
-        );
+        Self::init_internal(&env, admin, agent, platform, usdc_asset, platform_fee_bps)
+    }
 
-        Ok(())
+    /// Initialize a vault deployed without constructor arguments (admin only).
+    /// Same checks as the constructor.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        agent: Address,
+        platform: Address,
+        usdc_asset: Address,
+        platform_fee_bps: Option<u32>,
+    ) -> Result<(), VaultError> {
+        admin.require_auth();
+        Self::init_internal(&env, admin, agent, platform, usdc_asset, platform_fee_bps)
     }
 
-    /// User deposits USDC and receives vault shares (TUX0)
+    /// User deposits `amount` of `asset` and receives vault shares (TUX0).
+    /// USDC is deposited 1:1; another accepted asset, such as XLM, is valued
+    /// in USDC at its oracle price and fails with `StalePrice` if the price
+    /// is older than the asset allows. Fails with `SlippageExceeded` if fewer
+    /// than `min_shares_out` would be minted; pass 0 to accept any share value.
     pub fn deposit(
         env: Env,
         user: Address,
+        asset: Address,
         amount: i128,
+        min_shares_out: i128,
     ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
         user.require_auth();
 
-        // Validate amount
-        if amount <= 0 {
-            return Err(VaultError::InvalidAmount);
-        }
-
-        // Get USDC asset
-        let usdc_asset: Address = env.storage().instance().get(&SHARE_TOKEN).unwrap();
+        let asset = Self::other_asset(&env, &asset)?;
+        let (shares, _) =
+            Self::deposit_internal(&env, &user, &user, asset, amount, min_shares_out, false)?;
+        Ok(shares)
+    }
 
-        // Calculate current share value
-        let share_value = Self::calculate_share_value(&env);
+    /// Payer deposits `amount` of `asset`, as in `deposit`, and the vault
+    /// shares (TUX0) are credited to recipient
+    pub fn deposit_for(
+        env: Env,
+        payer: Address,
+        recipient: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        payer.require_auth();
 
-        // Calculate shares to mint
-        let shares_to_mint = if share_value == 0 {
-            // First deposit: 1:1 ratio
-            amount
-        } else {
-            // shares = amount / share_value
-            // Using fixed-point arithmetic: amount * 10^7 / share_value
-            (amount * INITIAL_SHARE_VALUE) / share_value
-        };
+        let asset = Self::other_asset(&env, &asset)?;
+        let (shares, _) =
+            Self::deposit_internal(&env, &payer, &recipient, asset, amount, 0, false)?;
 
-        if shares_to_mint <= 0 {
-            return Err(VaultError::InvalidAmount);
-        }
+        // Emit sponsored deposit event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("dep_for"),
+            (payer, recipient, amount, shares),
+        );
 
-        // Transfer USDC from user to vault
-        let token_client = token::TokenClient::new(&env, &usdc_asset);
-        token_client.transfer(&user, &env.current_contract_address(), &amount);
+        Ok(shares)
+    }
 
-        // Update total shares
-        let total_shares: i128 = env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0);
-        env.storage().instance().set(&TOTAL_SHARES, &(total_shares + shares_to_mint));
+    /// Whitelisted bridge deposits USDC it has approved to the vault and the
+    /// vault shares (TUX0) are credited to recipient
+    pub fn receive_bridged_deposit(
+        env: Env,
+        bridge: Address,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        bridge.require_auth();
 
-        // Update initial deposits tracking
-        let initial_deposits: i128 = env.storage().instance().get(&INITIAL_DEPOSITS).unwrap_or(0);
-        env.storage().instance().set(&INITIAL_DEPOSITS, &(initial_deposits + amount));
+        if !Self::is_bridge(env.clone(), bridge.clone()) {
+            return Err(VaultError::UnknownBridge);
+        }
 
-        // Update user's share balance
-        let user_shares_key = (symbol_short!("shares"), user.clone());
-        let current_user_shares: i128 = env.storage().persistent().get(&user_shares_key).unwrap_or(0);
-        env.storage().persistent().set(&user_shares_key, &(current_user_shares + shares_to_mint));
+        let (shares, _) =
+            Self::deposit_internal(&env, &bridge, &recipient, None, amount, 0, true)?;
 
-        // Emit deposit event
-        env.events().publish(
-            (symbol_short!("vault"), symbol_short!("deposit")),
-            (user, amount, shares_to_mint),
+        // Emit bridged deposit event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("bridged"),
+            (bridge, recipient, amount, shares),
         );
 
-        Ok(shares_to_mint)
+        Ok(shares)
     }
 
-    /// User burns shares and receives proportional USDC
+    /// User burns shares and receives proportional USDC. Fails with
+    /// `SlippageExceeded` if that comes to less than `min_assets_out`; pass 0 to
+    /// accept any share value. A remainder under the dust threshold is burned
+    /// and paid out as well.
     pub fn withdraw(
         env: Env,
         user: Address,
         shares: i128,
+        min_assets_out: i128,
     ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
         user.require_auth();
 
-        // Validate shares
-        if shares <= 0 {
-            return Err(VaultError::InvalidAmount);
-        }
+        let (_, assets) = Self::redeem_shares(&env, user.clone(), &user, shares, min_assets_out)?;
+        Ok(assets)
+    }
 
-        // Check user has enough shares
-        let user_shares_key = (symbol_short!("shares"), user.clone());
-        let user_shares: i128 = env.storage().persistent().get(&user_shares_key).unwrap_or(0);
+    /// User burns shares and the proportional USDC is paid to `recipient`,
+    /// such as a cold wallet. Same slippage bound as `withdraw`.
+    pub fn withdraw_to(
+        env: Env,
+        user: Address,
+        recipient: Address,
+        shares: i128,
+        min_assets_out: i128,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        user.require_auth();
 
-        if user_shares < shares {
-            return Err(VaultError::InsufficientShares);
+        if recipient == env.current_contract_address() {
+            return Err(VaultError::InvalidAddress);
         }
+        let (shares, assets) =
+            Self::redeem_shares(&env, user.clone(), &recipient, shares, min_assets_out)?;
 
-        // Calculate current share value
-        let share_value = Self::calculate_share_value(&env);
-
-        // Calculate USDC to return
-        // assets = shares * share_value / 10^7
-        let assets_to_return = (shares * share_value) / INITIAL_SHARE_VALUE;
+        // Emit redirected withdrawal event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("wd_to"),
+            (user, recipient, shares, assets),
+        );
 
-        if assets_to_return <= 0 {
-            return Err(VaultError::InvalidAmount);
-        }
+        Ok(assets)
+    }
 
-        // Get total vault assets
-        let total_assets = Self::get_total_vault_assets(&env);
+    /// Operator `user` approved burns `shares` of theirs and the proportional
+    /// USDC is paid to `recipient`. Fails with `NotAuthorized` unless the
+    /// approval is live.
+    pub fn withdraw_from(
+        env: Env,
+        operator: Address,
+        user: Address,
+        shares: i128,
+        recipient: Address,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        operator.require_auth();
 
-        if total_assets < assets_to_return {
-            return Err(VaultError::InsufficientBalance);
+        if !Self::is_operator(env.clone(), user.clone(), operator.clone()) {
+            return Err(VaultError::NotAuthorized);
         }
-
-        // Update user's share balance
-        let new_user_shares = user_shares - shares;
-        if new_user_shares == 0 {
-            env.storage().persistent().remove(&user_shares_key);
-        } else {
-            env.storage().persistent().set(&user_shares_key, &new_user_shares);
+        if recipient == env.current_contract_address() {
+            return Err(VaultError::InvalidAddress);
         }
+        let (shares, assets) = Self::redeem_shares(&env, user.clone(), &recipient, shares, 0)?;
 
-        // Update total shares
-        let total_shares: i128 = env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0);
-        env.storage().instance().set(&TOTAL_SHARES, &(total_shares - shares));
-
-        // Update initial deposits proportionally
-        let initial_deposits: i128 = env.storage().instance().get(&INITIAL_DEPOSITS).unwrap_or(0);
-        let deposit_reduction = if total_shares > 0 {
-            (initial_deposits * shares) / total_shares
-        } else {
-            initial_deposits
-        };
-        env.storage().instance().set(&INITIAL_DEPOSITS, &(initial_deposits - deposit_reduction));
-
-        // Transfer USDC back to user
-        let usdc_asset: Address = env.storage().instance().get(&SHARE_TOKEN).unwrap();
-        let token_client = token::TokenClient::new(&env, &usdc_asset);
-        token_client.transfer(&env.current_contract_address(), &user, &assets_to_return);
-
-        // Emit withdraw event
-        env.events().publish(
-            (symbol_short!("vault"), symbol_short!("withdraw")),
-            (user, shares, assets_to_return),
+        // Emit operator withdrawal event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("wd_from"),
+            (operator, user, recipient, shares, assets),
         );
 
-        Ok(assets_to_return)
+        Ok(assets)
     }
 
-    /// Agent executes a yield strategy (Blend supply/withdraw)
-    /// Only the authorized agent can call this
-    pub fn agent_execute(
+    /// Let `operator` withdraw any of `user`'s shares through `withdraw_from`
+    /// until `expiration_ledger`. Approving again replaces the expiry.
+    pub fn approve_operator(
         env: Env,
-        strategy: Strategy,
+        user: Address,
+        operator: Address,
+        expiration_ledger: u32,
     ) -> Result<(), VaultError> {
-        // Verify agent authorization
-        let agent: Address = env.storage().instance().get(&AGENT).unwrap();
-        agent.require_auth();
+        Self::bump_instance(&env);
+        user.require_auth();
 
-        // Validate amount
-        if strategy.amount <= 0 {
+        let ledger = env.ledger().sequence();
+        if expiration_ledger < ledger {
             return Err(VaultError::InvalidAmount);
         }
 
-        // Clone action for later use in event
-        let action = strategy.action.clone();
-
-        // Execute strategy based on action
-        match strategy.action {
-            ref act if *act == symbol_short!("supply") => {
-                // Supply assets to Blend pool
-                let token_client = token::TokenClient::new(&env, &strategy.asset);
-                token_client.transfer(
-                    &env.current_contract_address(),
-                    &strategy.pool,
-                    &strategy.amount,
-                );
-            }
-            ref act if *act == symbol_short!("withdraw") => {
-                // Withdraw assets from Blend pool
-                // Note: This is simplified. Real implementation would call Blend contract
-                let token_client = token::TokenClient::new(&env, &strategy.asset);
-                token_client.transfer(
-                    &strategy.pool,
-                    &env.current_contract_address(),
-                    &strategy.amount,
-                );
-            }
-            _ => {
-                return Err(VaultError::NotAuthorized);
-            }
-        }
+        let key = Self::operator_key(&user, &operator);
+        env.storage().persistent().set(&key, &expiration_ledger);
+        let live_for = expiration_ledger - ledger;
+        env.storage().persistent().extend_ttl(&key, live_for, live_for);
 
-        // Emit strategy execution event
-        env.events().publish(
-            (symbol_short!("vault"), symbol_short!("strategy")),
-            (agent, action, strategy.amount),
+        // Emit operator approval event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("op_appr"),
+            (user, operator, expiration_ledger),
         );
 
         Ok(())
     }
 
-    /// Distribute yield: 98% stays in vault (for users), 2% to platform
-    /// Anyone can call this function
-    pub fn distribute_yield(env: Env) -> Result<(), VaultError> {
-        let total_assets = Self::get_total_vault_assets(&env);
-        let initial_deposits: i128 = env.storage().instance().get(&INITIAL_DEPOSITS).unwrap_or(0);
+    /// Withdraw the approval `user` gave `operator`
+    pub fn revoke_operator(env: Env, user: Address, operator: Address) {
+        Self::bump_instance(&env);
+        user.require_auth();
 
-        // Calculate yield earned
-        let yield_earned = total_assets - initial_deposits;
+        env.storage().persistent().remove(&Self::operator_key(&user, &operator));
 
-        if yield_earned <= 0 {
-            return Err(VaultError::NoYieldToDistribute);
-        }
+        // Emit operator revocation event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("op_rvk"),
+            (user, operator),
+        );
+    }
 
-        // Calculate platform fee: 2%
-        let platform_fee = (yield_earned * PLATFORM_FEE_BPS) / BPS_DENOMINATOR;
+    /// Whether `operator` may withdraw for `user`, false once expired
+    pub fn is_operator(env: Env, user: Address, operator: Address) -> bool {
+        let expiration: Option<u32> =
+            env.storage().persistent().get(&Self::operator_key(&user, &operator));
+        expiration.is_some_and(|ledger| ledger >= env.ledger().sequence())
+    }
 
-        if platform_fee <= 0 {
-            return Err(VaultError::NoYieldToDistribute);
+    /// User burns every share they hold, as read inside the transaction, and
+    /// receives proportional USDC
+    pub fn withdraw_all(env: Env, user: Address) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        user.require_auth();
+
+        let shares = Self::share_balance(&env, &VaultState::load(&env)?, &user);
+        if shares <= 0 {
+            return Err(VaultError::InsufficientShares);
         }
 
-        // Transfer fee to platform
-        let platform: Address = env.storage().instance().get(&PLATFORM).unwrap();
-        let usdc_asset: Address = env.storage().instance().get(&SHARE_TOKEN).unwrap();
-        let token_client = token::TokenClient::new(&env, &usdc_asset);
+        let (_, assets) = Self::redeem_shares(&env, user.clone(), &user, shares, 0)?;
+        Ok(assets)
+    }
 
-        token_client.transfer(&env.current_contract_address(), &platform, &platform_fee);
+    /// User receives exactly `assets` USDC and burns the shares they are worth,
+    /// rounded up so the vault never over-pays. Returns the shares burned.
+    pub fn withdraw_assets(
+        env: Env,
+        user: Address,
+        assets: i128,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        user.require_auth();
+        Self::require_not_fully_paused(&env)?;
 
-        // Update initial deposits to reflect the fee taken out
-        // This ensures share value reflects the fee distribution
-        let new_initial_deposits = initial_deposits + (yield_earned - platform_fee);
-        env.storage().instance().set(&INITIAL_DEPOSITS, &new_initial_deposits);
+        // Validate assets
+        if assets <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
 
-        // Emit yield distribution event
-        env.events().publish(
-            (symbol_short!("vault"), symbol_short!("yield")),
-            (yield_earned, platform_fee),
-        );
+        let mut state = VaultState::load(&env)?;
+        Self::accrue_management_fee(&env, &mut state)?;
 
-        Ok(())
-    }
+        // Value the shares on the nominal assets, pool positions included;
+        // with nothing left the shares can't cover any withdrawal
+        let total_assets = Self::nominal_assets(&env, &state);
+        if total_assets <= 0 {
+            return Err(VaultError::InsufficientBalance);
+        }
 
-    /// Get current share value in USDC (with 7 decimals)
-    pub fn get_share_value(env: Env) -> i128 {
-        Self::calculate_share_value(&env)
-    }
+        // A locked user's shares also cover the early-withdrawal penalty
+        let penalty_bps = Self::early_penalty_bps(&env, &user);
+        let gross_assets = math::muldiv_ceil(
+            assets,
+            BPS_DENOMINATOR as i128,
+            (BPS_DENOMINATOR - penalty_bps) as i128,
+        )
+        .ok_or(VaultError::MathOverflow)?;
 
-    /// Get total vault assets (USDC balance)
-    pub fn get_total_assets(env: Env) -> i128 {
-        Self::get_total_vault_assets(&env)
-    }
+        // Calculate shares to burn, rounding up
+        let shares_to_burn = math::muldiv_ceil(gross_assets, state.total_shares, total_assets)
+            .ok_or(VaultError::MathOverflow)?;
 
-    /// Get total shares issued
-    pub fn get_total_shares(env: Env) -> i128 {
-        env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0)
-    }
+        // Check user's shares are worth the requested assets
+        if Self::share_balance(&env, &state, &user) < shares_to_burn {
+            return Err(VaultError::InsufficientShares);
+        }
 
-    /// Get user's share balance
-    pub fn get_user_shares(env: Env, user: Address) -> i128 {
-        let user_shares_key = (symbol_short!("shares"), user);
-        env.storage().persistent().get(&user_shares_key).unwrap_or(0)
+        Self::withdraw_internal(
+            &env,
+            &mut state,
+            user.clone(),
+            &user,
+            shares_to_burn,
+            assets,
+            gross_assets - assets,
+            total_assets,
+        )?;
+
+        Ok(shares_to_burn)
     }
 
-    /// Get vault statistics
-    pub fn get_vault_stats(env: Env) -> VaultStats {
-        let total_assets = Self::get_total_vault_assets(&env);
-        let total_shares: i128 = env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0);
-        let share_value = Self::calculate_share_value(&env);
-        let initial_deposits: i128 = env.storage().instance().get(&INITIAL_DEPOSITS).unwrap_or(0);
+    /// User moves their whole position into the admin-approved `target_vault`
+    /// in one step: their shares are redeemed as in `withdraw`, early-withdrawal
+    /// penalty included, and the USDC is deposited into the target on their
+    /// behalf without passing through their wallet. Returns the target's shares.
+    pub fn migrate(env: Env, user: Address, target_vault: Address) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        user.require_auth();
 
-        VaultStats {
-            total_assets,
-            total_shares,
-            share_value,
-            initial_deposits,
+        if Self::get_migration_target(env.clone()) != Some(target_vault.clone()) {
+            return Err(VaultError::MigrationNotApproved);
         }
-    }
 
-    /// Get agent address
-    pub fn get_agent(env: Env) -> Address {
-        env.storage().instance().get(&AGENT).unwrap()
-    }
+        // The USDC stays in this vault until the target pulls it
+        let vault = env.current_contract_address();
+        let shares = Self::share_balance(&env, &VaultState::load(&env)?, &user);
+        let (_, assets) = Self::redeem_shares(&env, user.clone(), &vault, shares, 0)?;
 
-    /// Get platform address
-    pub fn get_platform(env: Env) -> Address {
-        env.storage().instance().get(&PLATFORM).unwrap()
-    }
+        // The target pulls the USDC from this vault inside its own invocation
+        let usdc_asset = Self::stored_address(&env, &SHARE_TOKEN)?;
+        env.authorize_as_current_contract(vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: usdc_asset.clone(),
+                    fn_name: symbol_short!("transfer"),
+                    args: (vault.clone(), target_vault.clone(), assets).into_val(&env),
+                },
+                sub_invocations: vec![&env],
+            }),
+        ]);
+        let target_shares = MigrationTargetClient::new(&env, &target_vault)
+            .deposit_for(&vault, &user, &usdc_asset, &assets);
 
-    /// Get admin address
+        // Emit migration event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("migrate"),
+            (user, target_vault, shares, assets, target_shares),
+        );
+
+        Ok(target_shares)
+    }
+
+    /// Transfer vault shares (TUX0) between addresses (SEP-41 compatible)
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        from.require_auth();
+
+        Self::transfer_internal(&env, from, to, amount)
+    }
+
+    /// Move `shares` of `from`'s position to `to`, such as between the user's
+    /// own wallets. Same as the SEP-41 `transfer`.
+    pub fn transfer_shares(
+        env: Env,
+        from: Address,
+        to: Address,
+        shares: i128,
+    ) -> Result<(), VaultError> {
+        Self::transfer(env, from, to, shares)
+    }
+
+    /// Transfer `from`'s shares using the allowance they gave `spender`
+    /// (SEP-41 compatible)
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        spender.require_auth();
+
+        Self::spend_allowance(&env, &from, &spender, amount)?;
+        Self::transfer_internal(&env, from, to, amount)
+    }
+
+    /// Let `spender` move up to `amount` of `from`'s shares until
+    /// `live_until_ledger` (SEP-41 compatible). Allowances only cover the
+    /// internal ledger; with a TUX0 token set they live on the token, and this
+    /// fails with `AllowanceOnShareToken`.
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        live_until_ledger: u32,
+    ) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        from.require_auth();
+
+        if VaultState::load(&env)?.share_token.is_some() {
+            return Err(VaultError::AllowanceOnShareToken);
+        }
+        let ledger = env.ledger().sequence();
+        if amount < 0 || (amount > 0 && live_until_ledger < ledger) {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let key = Self::allowance_key(&from, &spender);
+        if amount == 0 {
+            env.storage().temporary().remove(&key);
+        } else {
+            env.storage().temporary().set(&key, &(amount, live_until_ledger));
+            let live_for = live_until_ledger - ledger;
+            env.storage().temporary().extend_ttl(&key, live_for, live_for);
+        }
+
+        // Emit the SEP-41 approval event
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (amount, live_until_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Shares `spender` may still move for `from`, 0 once expired
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        let allowance: Option<(i128, u32)> =
+            env.storage().temporary().get(&Self::allowance_key(&from, &spender));
+        match allowance {
+            Some((amount, live_until)) if live_until >= env.ledger().sequence() => amount,
+            _ => 0,
+        }
+    }
+
+    /// Destroy shares without redeeming them; their USDC stays behind for the
+    /// remaining holders (SEP-41 compatible)
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        from.require_auth();
+
+        Self::burn_internal(&env, from, amount)
+    }
+
+    /// Burn `from`'s shares using the allowance they gave `spender`
+    /// (SEP-41 compatible)
+    pub fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128,
+    ) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        spender.require_auth();
+
+        Self::spend_allowance(&env, &from, &spender, amount)?;
+        Self::burn_internal(&env, from, amount)
+    }
+
+    /// Extend the TTL of `user`'s share balance, history and lifetime totals,
+    /// and of the vault itself, so a dormant account isn't archived. Anyone can
+    /// call this function.
+    pub fn bump_user_shares(env: Env, user: Address) {
+        Self::bump_instance(&env);
+        Self::bump_shares(&env, &user);
+
+        #[cfg(feature = "history")]
+        Self::bump_user_entry(&env, &Self::history_key(&user));
+        Self::bump_user_entry(&env, &Self::total_in_key(&user));
+        Self::bump_user_entry(&env, &Self::total_out_key(&user));
+    }
+
+    /// Share decimals (SEP-41 compatible)
+    pub fn decimals(_env: Env) -> u32 {
+        SHARE_DECIMALS
+    }
+
+    /// Share token name (SEP-41 compatible)
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, "Tuxedo Vault Share")
+    }
+
+    /// Share token symbol (SEP-41 compatible)
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, "TUX0")
+    }
+
+    /// Agent executes a yield strategy (Blend supply/withdraw)
+    /// Only one of the vault's agents can call this. Returns the amount the
+    /// pool actually moved.
+    pub fn agent_execute(
+        env: Env,
+        agent: Address,
+        strategy: Strategy,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        // Verify agent authorization
+        Self::require_listed_agent(&env, &agent)?;
+
+        Self::execute_strategy(&env, agent, strategy)
+    }
+
+    /// Preview `agent_execute` for `agent` without executing: whether it would
+    /// pass the pause, pool, rate limit, stop-loss and reserve checks, and the
+    /// idle/deployed split and share value it would leave. Positions are taken
+    /// as last recorded, so pool interest since then isn't projected. Performs
+    /// no transfers or writes.
+    pub fn simulate_strategy(env: Env, agent: Address, strategy: Strategy) -> StrategyPreview {
+        let state = VaultState::view(&env);
+        let idle = Self::idle_usdc(&env, &state);
+        let deployed = Self::get_deployed_assets(env.clone());
+
+        let checked = match Self::load_agents(&env) {
+            Ok(agents) if !agents.contains(&agent) => Err(VaultError::NotAuthorized),
+            Ok(_) => Self::check_strategy(&env, &agent, &strategy).and_then(|_| {
+                Self::check_supply(&env, &strategy, Self::stop_loss_tripped(&env), idle)
+            }),
+            Err(err) => Err(err),
+        };
+        // USDC leaving the vault for the pool; negative when it comes back
+        let moved = match checked {
+            Ok(_) if strategy.action == symbol_short!("supply") => strategy.amount,
+            Ok(_) => -strategy
+                .amount
+                .min(Self::get_pool_deployed(env.clone(), strategy.pool.clone())),
+            Err(_) => 0,
+        };
+        // Moving USDC to or from a pool leaves the nominal share value as is
+        let share_value_after = Self::calculate_share_value(&env, &state)
+            .unwrap_or_else(|err| panic_with_error!(&env, err));
+
+        StrategyPreview {
+            allowed: checked.is_ok(),
+            error: checked.err().map(|err| err as u32),
+            amount: moved.abs(),
+            idle_after: idle - moved,
+            deployed_after: deployed + moved,
+            share_value_after,
+        }
+    }
+
+    /// Agent executes several strategies atomically, in order, e.g. to move
+    /// funds from one pool to another. Each is checked as in `agent_execute`
+    /// and the batch counts once against the cooldown; if any step fails,
+    /// none take effect. Emits one summary event instead of one per strategy.
+    pub fn agent_execute_many(
+        env: Env,
+        agent: Address,
+        strategies: Vec<Strategy>,
+    ) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        // Verify agent authorization
+        Self::require_listed_agent(&env, &agent)?;
+
+        Self::require_not_fully_paused(&env)?;
+
+        if strategies.is_empty() {
+            return Err(VaultError::InvalidAmount);
+        }
+        let mut total: i128 = 0;
+        for strategy in strategies.iter() {
+            Self::validate_strategy(&env, &agent, &strategy)?;
+            total = total.checked_add(strategy.amount).ok_or(VaultError::MathOverflow)?;
+        }
+        let window = Self::check_strategy_allowance(&env, total)?;
+        Self::record_strategy_allowance(&env, window);
+
+        // Net USDC moved into pools; negative when more came back
+        let mut net: i128 = 0;
+        for strategy in strategies.iter() {
+            let amount = Self::run_strategy(&env, &strategy)?;
+            if strategy.action == symbol_short!("supply") {
+                net += amount;
+            } else {
+                net -= amount;
+            }
+        }
+
+        // Emit batch summary event, with the count after its last strategy
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("batch"),
+            (agent, strategies.len(), net, Self::get_strategy_count(env.clone())),
+        );
+
+        Ok(())
+    }
+
+    /// Agent executes a strategy approved off-chain by the risk policy key.
+    /// The signature covers `strategy_payload(strategy, nonce, expiry)`; nonces
+    /// must strictly increase so an approval can't be replayed.
+    pub fn agent_execute_signed(
+        env: Env,
+        agent: Address,
+        strategy: Strategy,
+        nonce: u64,
+        expiry: u64,
+        signature: BytesN<64>,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        // Verify agent authorization
+        Self::require_listed_agent(&env, &agent)?;
+
+        let policy_key: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&POLICY_KEY)
+            .ok_or(VaultError::PolicyKeyNotSet)?;
+
+        if env.ledger().timestamp() > expiry {
+            return Err(VaultError::SignatureExpired);
+        }
+
+        let last_nonce: Option<u64> = env.storage().instance().get(&POLICY_NONCE);
+        if last_nonce.is_some_and(|last| nonce <= last) {
+            return Err(VaultError::InvalidNonce);
+        }
+
+        // Panics if the policy key didn't sign this exact strategy
+        let payload = Self::strategy_payload(env.clone(), strategy.clone(), nonce, expiry);
+        env.crypto()
+            .ed25519_verify(&policy_key, &payload.into(), &signature);
+
+        env.storage().instance().set(&POLICY_NONCE, &nonce);
+
+        Self::execute_strategy(&env, agent, strategy)
+    }
+
+    /// Canonical hash the risk policy key signs for `agent_execute_signed`:
+    /// `sha256(xdr((vault, strategy, nonce, expiry)))`
+    pub fn strategy_payload(
+        env: Env,
+        strategy: Strategy,
+        nonce: u64,
+        expiry: u64,
+    ) -> BytesN<32> {
+        let data = (env.current_contract_address(), strategy, nonce, expiry).to_xdr(&env);
+        env.crypto().sha256(&data).into()
+    }
+
+    /// Agent claims the emissions `pool` pays on the vault's positions for
+    /// `token_ids`, then holds or forwards them along the configured route.
+    /// The pool must be approved and assigned to the agent. Returns the
+    /// amount claimed.
+    pub fn claim_emissions(
+        env: Env,
+        agent: Address,
+        pool: Address,
+        token_ids: Vec<u32>,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        // Verify agent authorization
+        Self::require_listed_agent(&env, &agent)?;
+
+        if !Self::is_pool_approved(env.clone(), pool.clone())
+            || !Self::load_agent_pools(&env, &agent).contains(&pool)
+        {
+            return Err(VaultError::PoolNotApproved);
+        }
+        if token_ids.is_empty() {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let vault = env.current_contract_address();
+        let amount = PoolClient::new(&env, &pool).claim(&vault, &token_ids, &vault);
+
+        let route = Self::get_emissions_route(env.clone());
+        if route == EmissionsRoute::Platform && amount > 0 {
+            let emissions: Address = env
+                .storage()
+                .instance()
+                .get(&EMISSIONS_TOKEN)
+                .ok_or(VaultError::InvalidAsset)?;
+            let platform = Self::stored_address(&env, &PLATFORM)?;
+            token::TokenClient::new(&env, &emissions).transfer(&vault, &platform, &amount);
+        }
+
+        // Emit emissions claim event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("emissions"),
+            (pool, amount, route),
+        );
+
+        Ok(amount)
+    }
+
+    /// Agent swaps claimed reward tokens (e.g. BLND) into USDC via the AMM router.
+    /// The USDC received stays in the vault and is recognized as yield by the
+    /// next `distribute_yield`, since it is not counted in initial deposits.
+    pub fn agent_swap_rewards(
+        env: Env,
+        agent: Address,
+        token_in: Address,
+        amount_in: i128,
+        min_out: i128,
+        deadline: u64,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        // Verify agent authorization
+        Self::require_listed_agent(&env, &agent)?;
+
+        if amount_in <= 0 || min_out < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        // Never swap away depositor funds or vault shares
+        let usdc_asset = Self::stored_address(&env, &SHARE_TOKEN)?;
+        if token_in == usdc_asset || Self::is_share_token(&env, &token_in) {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        Self::adjust_held_asset(&env, &token_in, -amount_in)?;
+        let amount_out =
+            Self::swap_exact_in(&env, &token_in, &usdc_asset, amount_in, min_out, deadline)?;
+
+        // Emit reward swap event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("swap"),
+            (token_in, amount_in, amount_out),
+        );
+
+        Ok(amount_out)
+    }
+
+    /// Agent swaps between vault assets via the AMM router, e.g. rotating EURC
+    /// exposure into USDC. `token_out` must be a supported asset, the input
+    /// token needs a daily swap limit and USDC swapped out must leave the
+    /// reserve idle. Returns the amount received.
+    pub fn agent_execute_swap(env: Env, agent: Address, swap: Swap) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        // Verify agent authorization
+        Self::require_listed_agent(&env, &agent)?;
+        Self::require_not_fully_paused(&env)?;
+
+        if swap.amount_in <= 0 || swap.min_out < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        // Only rotate into assets the vault values, never out of vault shares
+        if swap.token_in == swap.token_out
+            || Self::is_share_token(&env, &swap.token_in)
+            || !Self::is_supported_asset(env.clone(), swap.token_out.clone())
+        {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        // Enforce the input token's daily volume limit; tokens the admin set
+        // no limit for can't be swapped at all
+        let day = env.ledger().timestamp() / DAY_IN_SECONDS;
+        let volume = Self::get_swap_volume(env.clone(), swap.token_in.clone()) + swap.amount_in;
+        match Self::get_swap_limit(env.clone(), swap.token_in.clone()) {
+            Some(limit) if volume <= limit => {}
+            _ => return Err(VaultError::SwapLimitExceeded),
+        }
+
+        // USDC swapped into other assets leaves the vault's idle balance
+        let state = VaultState::load(&env)?;
+        if swap.token_in == state.usdc {
+            Self::check_reserve(&env, Self::idle_usdc(&env, &state), swap.amount_in)?;
+        }
+        env.storage()
+            .persistent()
+            .set(&Self::swap_volume_key(&swap.token_in), &(day, volume));
+
+        // Accepted deposit assets swapped in or out keep their holdings tracked
+        Self::adjust_held_asset(&env, &swap.token_in, -swap.amount_in)?;
+        let amount_out = Self::swap_exact_in(
+            &env,
+            &swap.token_in,
+            &swap.token_out,
+            swap.amount_in,
+            swap.min_out,
+            swap.deadline,
+        )?;
+        Self::adjust_held_asset(&env, &swap.token_out, amount_out)?;
+
+        // Emit asset swap event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("rotate"),
+            (swap.token_in, swap.token_out, swap.amount_in, amount_out),
+        );
+
+        Ok(amount_out)
+    }
+
+    /// Distribute yield: `caller` is paid the keeper reward out of it, the
+    /// platform takes its fee from the rest and the remainder stays with users.
+    /// Anyone can call this function.
+    pub fn distribute_yield(env: Env, caller: Address) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        caller.require_auth();
+
+        Self::distribute_internal(&env, &caller)
+    }
+
+    /// Distribute yield on behalf of a keeper, crediting them the escrow bounty.
+    /// The distribution goes ahead even if no bounty is due. Returns the bounty.
+    pub fn keeper_distribute_yield(env: Env, keeper: Address) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        keeper.require_auth();
+
+        Self::distribute_internal(&env, &keeper)?;
+
+        let escrow: Address = match env.storage().instance().get(&KEEPER) {
+            Some(escrow) => escrow,
+            None => return Ok(0),
+        };
+        let bounty = KeeperClient::new(&env, &escrow).try_record_execution(
+            &env.current_contract_address(),
+            &DISTRIBUTE_TASK,
+            &keeper,
+        );
+        Ok(match bounty {
+            Ok(Ok(bounty)) => bounty,
+            _ => 0,
+        })
+    }
+
+    /// Pay `amount` USDC into the vault without minting shares, e.g. insurance
+    /// covering a strategy loss. Share value rises for every holder.
+    pub fn recover_assets(env: Env, from: Address, amount: i128) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let usdc_asset = Self::stored_address(&env, &SHARE_TOKEN)?;
+        token::TokenClient::new(&env, &usdc_asset).transfer(
+            &from,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        // Emit recovery event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("recover"),
+            (from, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Platform pays `amount` USDC into the vault as earned yield, e.g. the
+    /// proceeds of emissions it converted off-chain. No shares are minted, so
+    /// share value rises and the next `distribute_yield` charges fees on it.
+    pub fn donate_yield(env: Env, amount: i128) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        let platform = Self::stored_address(&env, &PLATFORM)?;
+        platform.require_auth();
+
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let usdc_asset = Self::stored_address(&env, &SHARE_TOKEN)?;
+        token::TokenClient::new(&env, &usdc_asset).transfer(
+            &platform,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        // Emit yield donation event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("donate"),
+            (platform, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Mint the management fee accrued so far to the platform as new shares,
+    /// diluting holders instead of moving USDC. Anyone can call this function.
+    /// Returns the shares minted.
+    pub fn accrue_fees(env: Env) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        let mut state = VaultState::load(&env)?;
+        let shares = Self::accrue_management_fee(&env, &mut state)?;
+        state.save(&env);
+        Ok(shares)
+    }
+
+    /// Hand the admin role to a new address (admin only)
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), VaultError> {
+        let admin = Self::require_admin(&env)?;
+
+        env.storage().instance().set(&ADMIN, &new_admin);
+
+        // Emit admin rotation event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("admin"),
+            (admin, new_admin),
+        );
+
+        Ok(())
+    }
+
+    /// Replace the vault's code with the uploaded wasm `new_wasm_hash`, keeping
+    /// its storage (admin only). The new code only runs from the next call, so
+    /// this records the version replaced and `finish_upgrade` reports it.
+    /// Upgrading again before that keeps the version first replaced.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if !env.storage().instance().has(&UPGRADE_FROM) {
+            env.storage().instance().set(&UPGRADE_FROM, &VERSION);
+        }
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Finish an upgrade from the new code (admin only), emitting the version
+    /// `upgrade` replaced and this code's `VERSION`
+    pub fn finish_upgrade(env: Env) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let old_version: u32 = env
+            .storage()
+            .instance()
+            .get(&UPGRADE_FROM)
+            .ok_or(VaultError::NoUpgradePending)?;
+        env.storage().instance().remove(&UPGRADE_FROM);
+
+        // Emit upgrade event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("upgrade"),
+            (old_version, VERSION),
+        );
+
+        Ok(())
+    }
+
+    /// Replace the primary agent (admin only), leaving any others in place.
+    /// The new agent takes over the replaced one's pools. An agent can't also
+    /// be the admin or the platform, so one key never holds two roles. Goes
+    /// through `queue_change` once a change delay is set.
+    pub fn set_agent(env: Env, new_agent: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+        #[cfg(feature = "strategy-timelock")]
+        Self::require_no_change_delay(&env)?;
+        Self::apply_change(&env, &ConfigChange::Agent(new_agent))
+    }
+
+    /// Add an agent allowed to run strategies alongside the others (admin
+    /// only), with the same role separation as `set_agent`
+    pub fn add_agent(env: Env, agent: Address) -> Result<(), VaultError> {
+        let admin = Self::require_admin(&env)?;
+
+        let platform = Self::stored_address(&env, &PLATFORM)?;
+        if agent == admin || agent == platform {
+            return Err(VaultError::InvalidAddress);
+        }
+
+        let mut agents = Self::load_agents(&env)?;
+        if agents.contains(&agent) {
+            return Err(VaultError::InvalidAddress);
+        }
+        if agents.len() >= MAX_AGENTS {
+            return Err(VaultError::TooManyAgents);
+        }
+        agents.push_back(agent.clone());
+        Self::save_agents(&env, &agents);
+
+        // Emit agent added event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("agent_add"),
+            agent,
+        );
+
+        Ok(())
+    }
+
+    /// Remove an agent, rejecting its strategy calls from then on (admin
+    /// only). The last agent can only be replaced, with `set_agent`.
+    pub fn remove_agent(env: Env, agent: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let mut agents = Self::load_agents(&env)?;
+        let index = agents.first_index_of(&agent).ok_or(VaultError::InvalidAddress)?;
+        if agents.len() == 1 {
+            return Err(VaultError::InvalidAddress);
+        }
+        agents.remove(index);
+        Self::save_agents(&env, &agents);
+        Self::save_agent_pools(&env, &agent, &Vec::new(&env));
+
+        // Emit agent removed event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("agent_rm"),
+            agent,
+        );
+
+        Ok(())
+    }
+
+    /// Replace the address receiving platform fees (admin only). Goes through
+    /// `queue_change` once a change delay is set.
+    pub fn set_platform(env: Env, new_platform: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+        #[cfg(feature = "strategy-timelock")]
+        Self::require_no_change_delay(&env)?;
+        Self::apply_change(&env, &ConfigChange::Platform(new_platform))
+    }
+
+    /// Set the maximum drawdown of nominal share value, pool positions
+    /// included, from its high-water mark, in bps, before supply strategies are
+    /// frozen (admin only). Restarts the high-water mark from the current
+    /// nominal share value.
+    pub fn set_stop_loss(env: Env, max_drawdown_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if max_drawdown_bps > BPS_DENOMINATOR {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&STOP_LOSS, &max_drawdown_bps);
+        env.storage()
+            .instance()
+            .set(&HIGH_WATER, &Self::calculate_share_value(&env, &VaultState::load(&env)?)?);
+
+        // Emit stop-loss update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("stop_loss"),
+            max_drawdown_bps,
+        );
+
+        Ok(())
+    }
+
+    /// Unfreeze supply strategies after a stop-loss and restart the high-water
+    /// mark from the current nominal share value (admin only)
+    pub fn clear_stop_loss(env: Env) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().remove(&STOPPED);
+        let share_value = Self::calculate_share_value(&env, &VaultState::load(&env)?)?;
+        env.storage().instance().set(&HIGH_WATER, &share_value);
+
+        // Emit stop-loss clear event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("sl_clear"),
+            share_value,
+        );
+
+        Ok(())
+    }
+
+    /// Set the drop in nominal share value between two state-changing calls,
+    /// in bps, that trips the circuit breaker and pauses the vault (admin
+    /// only). 0 turns the breaker off.
+    pub fn set_breaker_bps(env: Env, max_drop_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if max_drop_bps > BPS_DENOMINATOR {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&BREAKER_BPS, &max_drop_bps);
+
+        // Emit breaker update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("brk_bps"),
+            max_drop_bps,
+        );
+
+        Ok(())
+    }
+
+    /// Reset a tripped circuit breaker and resume every operation, taking the
+    /// current share value as the new reference (admin only)
+    pub fn clear_breaker(env: Env) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().remove(&BREAKER_TRIPPED);
+        let state = VaultState::load(&env)?;
+        let total_assets = Self::get_total_vault_assets(&env, &state);
+        let share_value = Self::nominal_share_value(&env, total_assets, state.total_shares)?;
+        env.storage().instance().set(&BREAKER_VALUE, &share_value);
+        Self::set_pause_state(&env, PauseState::Active);
+
+        // Emit breaker clear event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("brk_clr"),
+            share_value,
+        );
+
+        Ok(())
+    }
+
+    /// Halt deposits, and unless `deposits_only` also withdrawals and agent
+    /// strategies (admin only)
+    pub fn pause(env: Env, deposits_only: bool) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let state = if deposits_only {
+            PauseState::DepositsPaused
+        } else {
+            PauseState::FullyPaused
+        };
+        Self::set_pause_state(&env, state);
+
+        Ok(())
+    }
+
+    /// Resume every operation halted by `pause` (admin only). A pause from the
+    /// circuit breaker is lifted with `clear_breaker` instead.
+    pub fn unpause(env: Env) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+        if Self::is_breaker_tripped(env.clone()) {
+            return Err(VaultError::BreakerTripped);
+        }
+
+        Self::set_pause_state(&env, PauseState::Active);
+
+        Ok(())
+    }
+
+    /// Pull the vault's whole position out of `pool` without the agent and
+    /// pause deposits (admin only). Works while fully paused, and leaves
+    /// withdrawals open so users can exit. Returns the amount recovered.
+    pub fn emergency_recall(env: Env, pool: Address) -> Result<i128, VaultError> {
+        Self::require_admin(&env)?;
+
+        let usdc = VaultState::load(&env)?.usdc;
+        let pool_client = PoolClient::new(&env, &pool);
+        let vault = env.current_contract_address();
+        let positions = pool_client.get_positions(&vault);
+
+        // Withdraw everything the pool reports, which includes interest
+        // accrued since the tracked amount was last updated
+        let supply: i128 = positions.supply.values().iter().sum();
+        let collateral: i128 = positions.collateral.values().iter().sum();
+        let mut requests = vec![&env];
+        if supply > 0 {
+            requests.push_back(Request {
+                request_type: RequestType::Withdraw as u32,
+                address: usdc.clone(),
+                amount: supply,
+            });
+        }
+        if collateral > 0 {
+            requests.push_back(Request {
+                request_type: RequestType::WithdrawCollateral as u32,
+                address: usdc,
+                amount: collateral,
+            });
+        }
+        if requests.is_empty() {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let remaining = pool_client.submit(&vault, &vault, &vault, &requests);
+        let amount = blend::lent_total(&positions) - blend::lent_total(&remaining);
+        Self::record_pool_position(&env, &pool, &remaining);
+
+        Self::set_pause_state(&env, PauseState::DepositsPaused);
+
+        // Emit emergency recall event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("emrg"),
+            (pool, amount),
+        );
+
+        Ok(amount)
+    }
+
+    /// Classify USDC the vault holds above its tracked deposits (admin only).
+    /// As yield it stays for `distribute_yield`; otherwise it is a donation,
+    /// such as a transfer sent to the vault by mistake, and is set aside for
+    /// `rescue_excess` without counting towards share value or yield.
+    /// Returns the surplus classified.
+    pub fn sync(env: Env, as_yield: bool) -> Result<i128, VaultError> {
+        Self::require_admin(&env)?;
+
+        // Deposits deployed to pools still count against the surplus, but
+        // only USDC on hand can be set aside
+        let state = VaultState::load(&env)?;
+        let surplus = Self::get_total_vault_assets(&env, &state)
+            .checked_add(Self::get_deployed_assets(env.clone()))
+            .and_then(|assets| assets.checked_sub(state.initial_deposits))
+            .ok_or(VaultError::MathOverflow)?
+            .min(Self::idle_usdc(&env, &state));
+        if surplus <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        if !as_yield {
+            let excess = Self::get_excess(env.clone())
+                .checked_add(surplus)
+                .ok_or(VaultError::MathOverflow)?;
+            env.storage().instance().set(&EXCESS, &excess);
+        }
+
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("sync"),
+            (surplus, as_yield),
+        );
+
+        Ok(surplus)
+    }
+
+    /// Send the USDC `sync` set aside as donations to `to` (admin only).
+    /// Returns the amount sent.
+    pub fn rescue_excess(env: Env, to: Address) -> Result<i128, VaultError> {
+        Self::require_admin(&env)?;
+
+        let excess = Self::get_excess(env.clone());
+        if excess <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        env.storage().instance().remove(&EXCESS);
+        let usdc = VaultState::load(&env)?.usdc;
+        token::TokenClient::new(&env, &usdc).transfer(
+            &env.current_contract_address(),
+            &to,
+            &excess,
+        );
+
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("rescue"),
+            (to, excess),
+        );
+
+        Ok(excess)
+    }
+
+    /// Send `amount` of a token sent to the vault by mistake, such as TUX, to
+    /// `to` (admin only). Depositor funds are refused with `InvalidAsset`: the
+    /// vault's USDC, its shares and emissions held for depositors.
+    pub fn rescue_tokens(
+        env: Env,
+        token: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let usdc_asset = Self::stored_address(&env, &SHARE_TOKEN)?;
+        if token == usdc_asset
+            || Self::is_share_token(&env, &token)
+            || Self::get_emissions_token(env.clone()).as_ref() == Some(&token)
+            || Self::get_deposit_asset(env.clone(), token.clone()).is_some()
+        {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        token::TokenClient::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+
+        // Emit token rescue event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("rescue_tk"),
+            (token, to, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Check the drawdown against the stop-loss, tripping it on a breach.
+    /// Anyone can call this function. Returns whether the stop-loss is tripped.
+    pub fn check_stop_loss(env: Env) -> bool {
+        Self::bump_instance(&env);
+        Self::update_stop_loss(&env)
+    }
+
+    /// Set the keeper escrow and register the yield distribution bounty with it (admin only)
+    pub fn set_keeper(
+        env: Env,
+        escrow: Address,
+        reward_per_call: i128,
+        min_interval: u64,
+    ) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        KeeperClient::new(&env, &escrow).register_task(
+            &env.current_contract_address(),
+            &DISTRIBUTE_TASK,
+            &reward_per_call,
+            &min_interval,
+        );
+        env.storage().instance().set(&KEEPER, &escrow);
+
+        // Emit keeper update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("keeper"),
+            (escrow, reward_per_call, min_interval),
+        );
+
+        Ok(())
+    }
+
+    /// Set the ed25519 public key of the off-chain risk policy (admin only)
+    pub fn set_policy_key(env: Env, public_key: BytesN<32>) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&POLICY_KEY, &public_key);
+
+        // Emit policy key update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("policy"),
+            public_key,
+        );
+
+        Ok(())
+    }
+
+    /// Set the AMM router used to swap reward tokens (admin only)
+    pub fn set_swap_router(env: Env, router: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&SWAP_ROUTER, &router);
+
+        // Emit router update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("router"),
+            router,
+        );
+
+        Ok(())
+    }
+
+    /// Approve the vault holders may `migrate` into (admin only)
+    pub fn set_migration_target(env: Env, target: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if target == env.current_contract_address() {
+            return Err(VaultError::InvalidAddress);
+        }
+
+        env.storage().instance().set(&MIGRATION_TARGET, &target);
+
+        // Emit migration target event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("mig_tgt"),
+            target,
+        );
+
+        Ok(())
+    }
+
+    /// Set the emissions token pools pay out and where claimed emissions go
+    /// (admin only)
+    pub fn set_emissions_route(
+        env: Env,
+        token: Address,
+        route: EmissionsRoute,
+    ) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        // Emissions are never the vault's own USDC or shares
+        let usdc_asset = Self::stored_address(&env, &SHARE_TOKEN)?;
+        if token == usdc_asset || Self::is_share_token(&env, &token) {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        env.storage().instance().set(&EMISSIONS_TOKEN, &token);
+        env.storage().instance().set(&EMISSIONS_ROUTE, &route);
+
+        // Emit emissions route event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("emit_rte"),
+            (token, route),
+        );
+
+        Ok(())
+    }
+
+    /// Allow or disallow an asset as the output of agent swaps (admin only).
+    /// The vault's USDC is always supported; other assets must also be
+    /// accepted deposit assets, so the vault values what it swaps into.
+    pub fn set_supported_asset(env: Env, asset: Address, supported: bool) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let key = Self::supported_asset_key(&asset);
+        if supported {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        // Emit supported asset update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("asset"),
+            (asset, supported),
+        );
+
+        Ok(())
+    }
+
+    /// Accept deposits of `asset` alongside USDC, valued by the
+    /// Reflector-compatible `oracle` and refused once its price is older than
+    /// `max_price_age` seconds (admin only). Re-adding an accepted asset
+    /// replaces its oracle. The asset must use the vault asset's decimals.
+    pub fn add_deposit_asset(
+        env: Env,
+        asset: Address,
+        oracle: Address,
+        max_price_age: u64,
+    ) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let usdc_asset = Self::stored_address(&env, &SHARE_TOKEN)?;
+        if asset == usdc_asset || Self::is_share_token(&env, &asset) {
+            return Err(VaultError::InvalidAsset);
+        }
+        let decimals = token::TokenClient::new(&env, &asset).try_decimals();
+        if decimals != Ok(Ok(ASSET_DECIMALS)) {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        let balance = match Self::get_deposit_asset(env.clone(), asset.clone()) {
+            Some(accepted) => accepted.balance,
+            None => {
+                let mut assets = Self::get_deposit_assets(env.clone());
+                if assets.len() >= MAX_DEPOSIT_ASSETS {
+                    return Err(VaultError::TooManyAssets);
+                }
+                assets.push_back(asset.clone());
+                env.storage().instance().set(&DEPOSIT_ASSETS, &assets);
+                0
+            }
+        };
+        Self::save_deposit_asset(
+            &env,
+            &asset,
+            &DepositAsset {
+                oracle: oracle.clone(),
+                max_price_age,
+                balance,
+            },
+        );
+
+        // Emit deposit asset event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("asset_add"),
+            (asset, oracle, max_price_age),
+        );
+
+        Ok(())
+    }
+
+    /// Stop accepting deposits of `asset` (admin only). Fails with
+    /// `InvalidAsset` while the vault still holds some of it.
+    pub fn remove_deposit_asset(env: Env, asset: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let accepted =
+            Self::get_deposit_asset(env.clone(), asset.clone()).ok_or(VaultError::InvalidAsset)?;
+        if accepted.balance > 0 {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        env.storage().persistent().remove(&Self::deposit_asset_key(&asset));
+        let mut assets = Self::get_deposit_assets(env.clone());
+        if let Some(index) = assets.first_index_of(&asset) {
+            assets.remove(index);
+        }
+        env.storage().instance().set(&DEPOSIT_ASSETS, &assets);
+
+        // Emit deposit asset removal event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("asset_rm"),
+            asset,
+        );
+
+        Ok(())
+    }
+
+    /// Approve a Blend pool for agent strategies (admin only)
+    pub fn add_approved_pool(env: Env, pool: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&Self::approved_pool_key(&pool), &true);
+
+        // Emit pool approval event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("pool_add"),
+            pool,
+        );
+
+        Ok(())
+    }
+
+    /// Revoke a pool's approval (admin only). The agent can't withdraw from
+    /// it afterwards either, so bring the vault's position home first.
+    pub fn remove_approved_pool(env: Env, pool: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .remove(&Self::approved_pool_key(&pool));
+
+        // Emit pool removal event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("pool_rm"),
+            pool,
+        );
+
+        Ok(())
+    }
+
+    /// Let `agent` run strategies on the approved `pool` (admin only)
+    pub fn assign_pool(env: Env, agent: Address, pool: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if !Self::load_agents(&env)?.contains(&agent) {
+            return Err(VaultError::InvalidAddress);
+        }
+        if !Self::is_pool_approved(env.clone(), pool.clone()) {
+            return Err(VaultError::PoolNotApproved);
+        }
+
+        let mut pools = Self::load_agent_pools(&env, &agent);
+        if !pools.contains(&pool) {
+            pools.push_back(pool.clone());
+            Self::save_agent_pools(&env, &agent, &pools);
+        }
+
+        // Emit pool assignment event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("pool_asgn"),
+            (agent, pool),
+        );
+
+        Ok(())
+    }
+
+    /// Take `pool` off `agent`'s assignments (admin only). Like revoking its
+    /// approval, this also stops the agent withdrawing from it.
+    pub fn unassign_pool(env: Env, agent: Address, pool: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let mut pools = Self::load_agent_pools(&env, &agent);
+        let index = pools.first_index_of(&pool).ok_or(VaultError::PoolNotApproved)?;
+        pools.remove(index);
+        Self::save_agent_pools(&env, &agent, &pools);
+
+        // Emit pool unassignment event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("pool_unas"),
+            (agent, pool),
+        );
+
+        Ok(())
+    }
+
+    /// Block `addr` from depositing or receiving deposits (admin only). Its
+    /// shares can still be withdrawn, so no funds are ever trapped.
+    pub fn add_to_blacklist(env: Env, addr: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .set(&Self::blacklist_key(&addr), &true);
+
+        // Emit blacklist event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("bl_add"),
+            addr,
+        );
+
+        Ok(())
+    }
+
+    /// Let a blacklisted address deposit again (admin only)
+    pub fn remove_from_blacklist(env: Env, addr: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage()
+            .persistent()
+            .remove(&Self::blacklist_key(&addr));
+
+        // Emit blacklist removal event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("bl_rm"),
+            addr,
+        );
+
+        Ok(())
+    }
+
+    /// Restrict deposits to whitelisted recipients, as for a beta, or lift the
+    /// restriction (admin only). The whitelist is ignored while this is off.
+    pub fn set_whitelist_mode(env: Env, enabled: bool) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if enabled {
+            env.storage().instance().set(&WHITELIST_MODE, &true);
+        } else {
+            env.storage().instance().remove(&WHITELIST_MODE);
+        }
+
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("wl_mode"),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Whitelist a batch of up to 40 addresses for deposits in whitelist mode
+    /// (admin only)
+    pub fn add_to_whitelist(env: Env, addrs: Vec<Address>) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if addrs.len() > MAX_WHITELIST_BATCH {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        for addr in addrs.iter() {
+            env.storage()
+                .persistent()
+                .set(&Self::whitelist_key(&addr), &true);
+        }
+
+        // Emit whitelist event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("wl_add"),
+            addrs,
+        );
+
+        Ok(())
+    }
+
+    /// Take a batch of up to 40 addresses off the whitelist (admin only)
+    pub fn remove_from_whitelist(env: Env, addrs: Vec<Address>) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if addrs.len() > MAX_WHITELIST_BATCH {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        for addr in addrs.iter() {
+            env.storage()
+                .persistent()
+                .remove(&Self::whitelist_key(&addr));
+        }
+
+        // Emit whitelist removal event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("wl_rm"),
+            addrs,
+        );
+
+        Ok(())
+    }
+
+    /// Allow or disallow a bridge to deliver deposits (admin only)
+    pub fn set_bridge(env: Env, bridge: Address, allowed: bool) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let key = Self::bridge_key(&bridge);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        // Emit bridge update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("bridge"),
+            (bridge, allowed),
+        );
+
+        Ok(())
+    }
+
+    /// Cap the amount of `token` the agent may swap per day (admin only). The
+    /// agent can't swap tokens without a limit.
+    pub fn set_swap_limit(env: Env, token: Address, max_daily: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if max_daily < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&Self::swap_limit_key(&token), &max_daily);
+
+        // Emit swap limit update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("swap_lim"),
+            (token, max_daily),
+        );
+
+        Ok(())
+    }
+
+    /// Rate-limit agent strategies: at least `cooldown` seconds between them
+    /// and at most `max_daily` USDC moved per 24 hour window, 0 for no cap
+    /// (admin only)
+    pub fn set_strategy_limits(env: Env, cooldown: u64, max_daily: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if max_daily < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&STRATEGY_COOLDOWN, &cooldown);
+        env.storage().instance().set(&STRATEGY_MAX_DAILY, &max_daily);
+
+        // Emit strategy limits event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("strat_lim"),
+            (cooldown, max_daily),
+        );
+
+        Ok(())
+    }
+
+    /// Set the share of idle plus deployed USDC the agent must leave idle for
+    /// withdrawals, in basis points (admin only). Goes through `queue_change`
+    /// once a change delay is set.
+    pub fn set_reserve_bps(env: Env, reserve_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+        #[cfg(feature = "strategy-timelock")]
+        Self::require_no_change_delay(&env)?;
+        Self::apply_change(&env, &ConfigChange::ReserveRatio(reserve_bps))
+    }
+
+    /// Set the platform's cut of distributed yield in basis points, at most
+    /// 10% (admin only). Applies from the next distribution. Goes through
+    /// `queue_change` once a change delay is set.
+    pub fn set_platform_fee_bps(env: Env, fee_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+        #[cfg(feature = "strategy-timelock")]
+        Self::require_no_change_delay(&env)?;
+        Self::apply_change(&env, &ConfigChange::PlatformFeeBps(fee_bps))
+    }
+
+    /// Set the yearly return on deposits, in basis points, the vault must earn
+    /// before the platform fee applies (admin only). It accrues from now or
+    /// the last distribution, whichever is later.
+    pub fn set_hurdle_bps(env: Env, hurdle_bps_per_year: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if hurdle_bps_per_year > BPS_DENOMINATOR {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let old_hurdle_bps = Self::get_hurdle_bps(env.clone());
+        if hurdle_bps_per_year == 0 {
+            env.storage().instance().remove(&HURDLE_BPS);
+        } else {
+            env.storage().instance().set(&HURDLE_BPS, &hurdle_bps_per_year);
+        }
+        env.storage().instance().set(&HURDLE_SINCE, &env.ledger().timestamp());
+
+        // Emit hurdle rate event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("hurdle"),
+            (old_hurdle_bps, hurdle_bps_per_year),
+        );
+
+        Ok(())
+    }
+
+    /// Set the yearly management fee, in basis points of the vault, taken by
+    /// minting shares to the platform (admin only, capped at 2%). The fee due
+    /// at the old rate is accrued first.
+    pub fn set_management_fee_bps(env: Env, fee_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if fee_bps > MAX_MANAGEMENT_FEE_BPS {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let mut state = VaultState::load(&env)?;
+        Self::accrue_management_fee(&env, &mut state)?;
+        state.save(&env);
+
+        let old_fee_bps = Self::get_management_fee_bps(env.clone());
+        env.storage().instance().set(&MGMT_FEE_BPS, &fee_bps);
+        env.storage().instance().set(&LAST_ACCRUAL, &env.ledger().timestamp());
+
+        // Emit management fee update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("mgmt_bps"),
+            (old_fee_bps, fee_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Set the fee taken from each deposit for the platform, in basis points
+    /// before any TUX tier discount (admin only, capped at 1%)
+    pub fn set_deposit_fee_bps(env: Env, fee_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if fee_bps > MAX_DEPOSIT_FEE_BPS {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let old_fee_bps = Self::get_deposit_fee_bps(env.clone());
+        if fee_bps == 0 {
+            env.storage().instance().remove(&DEPOSIT_FEE_BPS);
+        } else {
+            env.storage().instance().set(&DEPOSIT_FEE_BPS, &fee_bps);
+        }
+
+        // Emit deposit fee update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("dep_fee"),
+            (old_fee_bps, fee_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Lock deposited shares for `duration` seconds, during which withdrawing
+    /// them leaves `penalty_bps` of their value to the remaining holders
+    /// (admin only, penalty capped at 5%). A zero duration lifts the lock-up.
+    pub fn set_lockup(env: Env, duration: u64, penalty_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if penalty_bps > MAX_EARLY_PENALTY_BPS {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        if duration == 0 {
+            env.storage().instance().remove(&LOCKUP);
+        } else {
+            env.storage().instance().set(&LOCKUP, &(duration, penalty_bps));
+        }
+
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("lockup"),
+            (duration, penalty_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Set the TUX token whose holder tiers discount the deposit fee (admin only)
+    pub fn set_tux_token(env: Env, tux_token: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&TUX_TOKEN, &tux_token);
+
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("tux_token"),
+            tux_token,
+        );
+
+        Ok(())
+    }
+
+    /// Set the share of distributed yield paid to the caller of
+    /// `distribute_yield`, in basis points (admin only, capped at 1%)
+    pub fn set_keeper_reward_bps(env: Env, reward_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if reward_bps > MAX_KEEPER_REWARD_BPS {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let old_reward_bps = Self::get_keeper_reward_bps(env.clone());
+        env.storage().instance().set(&KEEPER_BPS, &reward_bps);
+
+        // Emit keeper reward update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("kpr_bps"),
+            (old_reward_bps, reward_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Set the minimum seconds between yield distributions, or allow them at
+    /// any time with 0 (admin only)
+    pub fn set_min_distribution_interval(env: Env, interval: u64) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if interval == 0 {
+            env.storage().instance().remove(&DIST_INTERVAL);
+        } else {
+            env.storage().instance().set(&DIST_INTERVAL, &interval);
+        }
+
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("dist_int"),
+            interval,
+        );
+
+        Ok(())
+    }
+
+    /// Charge fees only on share value gained above the high-water mark of past
+    /// distributions, instead of on all assets above the tracked deposits
+    /// (admin only)
+    pub fn set_hwm_fees(env: Env, enabled: bool) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if enabled {
+            env.storage().instance().set(&HWM_FEES, &true);
+        } else {
+            env.storage().instance().remove(&HWM_FEES);
+        }
+
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("hwm_fees"),
+            enabled,
+        );
+
+        Ok(())
+    }
+
+    /// Cap the vault's total assets at `cap` USDC, or remove the cap with 0
+    /// (admin only). Deposits that would exceed it are rejected.
+    pub fn set_deposit_cap(env: Env, cap: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if cap < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        if cap == 0 {
+            env.storage().instance().remove(&DEPOSIT_CAP);
+        } else {
+            env.storage().instance().set(&DEPOSIT_CAP, &cap);
+        }
+
+        // Emit deposit cap event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("dep_cap"),
+            cap,
+        );
+
+        Ok(())
+    }
+
+    /// Set the smallest deposit accepted, in USDC (admin only). The first
+    /// deposit into an empty vault must also meet a fixed 10 USDC floor.
+    pub fn set_min_deposit(env: Env, min_deposit: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if min_deposit < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        env.storage().instance().set(&MIN_DEPOSIT, &min_deposit);
+
+        // Emit minimum deposit event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("min_dep"),
+            min_deposit,
+        );
+
+        Ok(())
+    }
+
+    /// Sweep share balances a withdrawal would leave under `threshold` shares
+    /// out with it, or stop sweeping with 0 (admin only, capped at one share)
+    pub fn set_dust_threshold(env: Env, threshold: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if !(0..=MAX_DUST_THRESHOLD).contains(&threshold) {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let old_threshold = Self::get_dust_threshold(env.clone());
+        if threshold == 0 {
+            env.storage().instance().remove(&DUST_THRESHOLD);
+        } else {
+            env.storage().instance().set(&DUST_THRESHOLD, &threshold);
+        }
+
+        // Emit dust threshold event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("dust"),
+            (old_threshold, threshold),
+        );
+
+        Ok(())
+    }
+
+    /// Limit each wallet's net deposits to `limit` USDC, or remove the limit
+    /// with 0 (admin only). Withdrawals free up a wallet's capacity again.
+    pub fn set_user_deposit_limit(env: Env, limit: i128) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if limit < 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        if limit == 0 {
+            env.storage().instance().remove(&USER_LIMIT);
+        } else {
+            env.storage().instance().set(&USER_LIMIT, &limit);
+        }
+
+        // Emit user limit event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("user_lim"),
+            limit,
+        );
+
+        Ok(())
+    }
+
+    /// Set the Reflector-compatible price feed and its staleness threshold (admin only)
+    pub fn set_oracle(env: Env, feed: Address, max_age: u64) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        env.storage().instance().set(&ORACLE, &feed);
+        env.storage().instance().set(&MAX_PRICE_AGE, &max_age);
+
+        // Emit oracle update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("oracle"),
+            (feed, max_age),
+        );
+
+        Ok(())
+    }
+
+    /// Set a fixed fallback price for an asset the feed can't price, e.g. on testnet (admin only)
+    pub fn set_fallback_price(
+        env: Env,
+        asset: Address,
+        price: i128,
+        decimals: u32,
+    ) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if price <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        oracle::set_fallback_price(&env, &asset, FixedPrice { price, decimals });
+
+        // Emit fallback price event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("fb_price"),
+            (asset, price, decimals),
+        );
+
+        Ok(())
+    }
+
+    /// Get the price of an asset as (price, timestamp, decimals)
+    pub fn get_asset_price(env: Env, asset: Address) -> Result<(i128, u64, u32), VaultError> {
+        let feed: Option<Address> = env.storage().instance().get(&ORACLE);
+        let max_age: u64 = env
+            .storage()
+            .instance()
+            .get(&MAX_PRICE_AGE)
+            .unwrap_or(DEFAULT_MAX_PRICE_AGE);
+
+        oracle::get_price(&env, feed.as_ref(), &asset, max_age).map_err(Self::price_error)
+    }
+
+    /// Issue shares as an external TUX0 token instead of the internal ledger (admin only).
+    /// Only allowed before any shares exist; the token must be minted by this vault
+    /// and use the vault asset's decimals.
+    pub fn set_share_token(env: Env, share_token: Address) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let state = VaultState::load(&env)?;
+        if state.total_shares != 0 {
+            return Err(VaultError::SharesOutstanding);
+        }
+
+        let asset_decimals = token::TokenClient::new(&env, &state.usdc).decimals();
+        if token::TokenClient::new(&env, &share_token).decimals() != asset_decimals
+            || ShareTokenClient::new(&env, &share_token).get_vault() != env.current_contract_address()
+        {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        env.storage().instance().set(&TUX0_TOKEN, &share_token);
+
+        // Emit share token event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("tux0"),
+            share_token,
+        );
+
+        Ok(())
+    }
+
+    /// Shares `deposit` would mint for `amount` USDC right now, or 0 if the
+    /// deposit would be rejected. Charges the undiscounted deposit fee, so
+    /// TUX holders may receive more. Performs no transfers or writes.
+    pub fn preview_deposit(env: Env, amount: i128) -> i128 {
+        let state = Self::load_accrued(&env);
+        if amount <= 0 || amount < Self::min_deposit_for(&env, &state) {
+            return 0;
+        }
+        let fee = math::bps_of(amount, Self::get_deposit_fee_bps(env.clone())).unwrap_or(amount);
+        let total_assets = Self::nominal_assets(&env, &state);
+        let shares = Self::shares_for_assets(amount - fee, total_assets, state.total_shares)
+            // The deposit is rejected if it leaves a share value too large to store
+            .and_then(|shares| {
+                let assets = total_assets.checked_add(amount - fee);
+                let assets = assets.ok_or(VaultError::MathOverflow)?;
+                Self::share_value_of(assets, state.total_shares + shares).map(|_| shares)
+            });
+        match shares {
+            Ok(shares) => (shares - Self::dead_shares_for(&state)).max(0),
+            Err(_) => 0,
+        }
+    }
+
+    /// USDC `withdraw` would pay for redeeming `shares` right now, or 0 if the
+    /// withdrawal would be rejected. Locked shares pay the early-withdrawal
+    /// penalty on top. Performs no transfers or writes.
+    pub fn preview_withdraw(env: Env, shares: i128) -> i128 {
+        if shares <= 0 {
+            return 0;
+        }
+        let state = Self::load_accrued(&env);
+        let total_assets = Self::nominal_assets(&env, &state);
+        let assets = Self::assets_for_shares(shares, total_assets, state.total_shares);
+        match assets {
+            Ok(assets) if assets > 0 && assets <= Self::idle_usdc(&env, &state) => assets,
+            _ => 0,
+        }
+    }
+
+    /// Most USDC `deposit` accepts from `user` right now: unlimited unless a
+    /// deposit cap or per-user limit is stored, and nothing while deposits
+    /// are paused
+    pub fn max_deposit(env: Env, user: Address) -> i128 {
+        if Self::get_pause_state(env.clone()) != PauseState::Active {
+            return 0;
+        }
+        let total_assets = Self::nominal_assets(&env, &VaultState::view(&env));
+        Self::deposit_headroom(&env, total_assets).min(Self::user_headroom(&env, &user))
+    }
+
+    /// Most USDC `user` can withdraw right now: the value of their shares
+    /// less any early-withdrawal penalty, limited to the idle balance since
+    /// funds deployed to Blend can't be paid out, and nothing while
+    /// withdrawals are paused
+    pub fn max_withdraw(env: Env, user: Address) -> i128 {
+        if Self::require_not_fully_paused(&env).is_err() {
+            return 0;
+        }
+        let state = Self::load_accrued(&env);
+        let total_assets = Self::nominal_assets(&env, &state);
+        let shares = Self::share_balance(&env, &state, &user);
+        let penalty_bps = Self::early_penalty_bps(&env, &user);
+        Self::assets_for_shares(shares, total_assets, state.total_shares)
+            .map(|assets| assets - math::bps_of(assets, penalty_bps).unwrap_or(assets))
+            .unwrap_or(0)
+            .min(Self::idle_usdc(&env, &state))
+    }
+
+    /// Get current share value in USDC (with 7 decimals)
+    pub fn get_share_value(env: Env) -> i128 {
+        Self::calculate_share_value(&env, &VaultState::view(&env))
+            .unwrap_or_else(|err| panic_with_error!(&env, err))
+    }
+
+    /// Get total vault assets: idle USDC plus other accepted assets at their
+    /// USDC value
+    pub fn get_total_assets(env: Env) -> i128 {
+        Self::get_total_vault_assets(&env, &VaultState::view(&env))
+    }
+
+    /// Get total shares issued
+    pub fn get_total_shares(env: Env) -> i128 {
+        env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0)
+    }
+
+    /// Get user's share balance
+    pub fn get_user_shares(env: Env, user: Address) -> i128 {
+        Self::bump_shares(&env, &user);
+        Self::share_balance(&env, &VaultState::view(&env), &user)
+    }
+
+    /// Get share balance of an address (SEP-41 compatible alias of get_user_shares)
+    pub fn balance(env: Env, id: Address) -> i128 {
+        Self::get_user_shares(env, id)
+    }
+
+    /// Get vault statistics
+    pub fn get_vault_stats(env: Env) -> VaultStats {
+        let state = VaultState::view(&env);
+        let total_assets = Self::nominal_assets(&env, &state);
+
+        VaultStats {
+            total_assets,
+            total_shares: state.total_shares,
+            share_value: Self::calculate_share_value(&env, &state)
+                .unwrap_or_else(|err| panic_with_error!(&env, err)),
+            initial_deposits: state.initial_deposits,
+            platform_fee_bps: Self::get_platform_fee_bps(env.clone()),
+            remaining_capacity: Self::deposit_headroom(&env, total_assets),
+            min_deposit: Self::min_deposit_for(&env, &state),
+            deployed_assets: Self::get_deployed_assets(env.clone()),
+            next_distribution_at: Self::next_distribution_at(&env),
+            idle_assets: Self::idle_usdc(&env, &state),
+            holder_count: Self::get_holder_count(env.clone()),
+        }
+    }
+
+    /// Get the USDC value of `user`'s shares, rounded down like a withdrawal
+    pub fn get_user_assets(env: Env, user: Address) -> i128 {
+        let state = VaultState::view(&env);
+        let shares = Self::share_balance(&env, &state, &user);
+        let total_assets = Self::nominal_assets(&env, &state);
+        Self::assets_for_shares(shares, total_assets, state.total_shares)
+            .unwrap_or_else(|err| panic_with_error!(&env, err))
+    }
+
+    /// Get the number of addresses holding shares on the internal ledger,
+    /// not counting the vault's dead shares
+    pub fn get_holder_count(env: Env) -> u32 {
+        env.storage().instance().get(&HOLDERS).unwrap_or(0)
+    }
+
+    /// Get the primary agent's address
+    pub fn get_agent(env: Env) -> Address {
+        Self::load_agents(&env)
+            .map(|agents| agents.get_unchecked(0))
+            .unwrap_or_else(|err| panic_with_error!(&env, err))
+    }
+
+    /// Get every agent's address, the primary agent first
+    pub fn get_agents(env: Env) -> Vec<Address> {
+        Self::load_agents(&env).unwrap_or_else(|err| panic_with_error!(&env, err))
+    }
+
+    /// Get the pools assigned to `agent`, in the order they were assigned
+    pub fn get_agent_pools(env: Env, agent: Address) -> Vec<Address> {
+        Self::load_agent_pools(&env, &agent)
+    }
+
+    /// Get platform address
+    pub fn get_platform(env: Env) -> Address {
+        Self::stored_address(&env, &PLATFORM).unwrap_or_else(|err| panic_with_error!(&env, err))
+    }
+
+    /// Get admin address
     pub fn get_admin(env: Env) -> Address {
-        env.storage().instance().get(&ADMIN).unwrap()
+        Self::stored_address(&env, &ADMIN).unwrap_or_else(|err| panic_with_error!(&env, err))
+    }
+
+    /// Get the version of the code the vault runs
+    pub fn get_version(_env: Env) -> u32 {
+        VERSION
+    }
+
+    /// Get the external TUX0 share token, if shares are tokenized
+    pub fn get_share_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&TUX0_TOKEN)
+    }
+
+    /// Get the price feed, if configured
+    pub fn get_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ORACLE)
+    }
+
+    /// Get the AMM router used for reward swaps, if configured
+    pub fn get_swap_router(env: Env) -> Option<Address> {
+        env.storage().instance().get(&SWAP_ROUTER)
+    }
+
+    /// Get the vault holders may migrate into, if approved
+    pub fn get_migration_target(env: Env) -> Option<Address> {
+        env.storage().instance().get(&MIGRATION_TARGET)
+    }
+
+    /// Get the emissions token pools pay out, if configured
+    pub fn get_emissions_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&EMISSIONS_TOKEN)
+    }
+
+    /// Get where claimed emissions go; they are held in the vault by default
+    pub fn get_emissions_route(env: Env) -> EmissionsRoute {
+        env.storage()
+            .instance()
+            .get(&EMISSIONS_ROUTE)
+            .unwrap_or(EmissionsRoute::Hold)
+    }
+
+    /// Check whether agent swaps may output `asset`: USDC, or an accepted
+    /// deposit asset the admin marked supported
+    pub fn is_supported_asset(env: Env, asset: Address) -> bool {
+        if asset == VaultState::view(&env).usdc {
+            return true;
+        }
+        let supported = env
+            .storage()
+            .persistent()
+            .get(&Self::supported_asset_key(&asset))
+            .unwrap_or(false);
+        supported && Self::get_deposit_asset(env, asset).is_some()
+    }
+
+    /// Get the other assets accepted for deposits
+    pub fn get_deposit_assets(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DEPOSIT_ASSETS)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get how an accepted deposit asset is priced and how much of it the
+    /// vault holds
+    pub fn get_deposit_asset(env: Env, asset: Address) -> Option<DepositAsset> {
+        env.storage().persistent().get(&Self::deposit_asset_key(&asset))
+    }
+
+    /// Check whether `bridge` may deliver deposits
+    pub fn is_bridge(env: Env, bridge: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Self::bridge_key(&bridge))
+            .unwrap_or(false)
+    }
+
+    /// Check whether agent strategies may target `pool`
+    pub fn is_pool_approved(env: Env, pool: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Self::approved_pool_key(&pool))
+            .unwrap_or(false)
+    }
+
+    /// Check whether `addr` is blocked from depositing
+    pub fn is_blacklisted(env: Env, addr: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Self::blacklist_key(&addr))
+            .unwrap_or(false)
+    }
+
+    /// Check whether deposits are restricted to the whitelist
+    pub fn is_whitelist_mode(env: Env) -> bool {
+        env.storage().instance().get(&WHITELIST_MODE).unwrap_or(false)
+    }
+
+    /// Check whether `addr` is on the deposit whitelist
+    pub fn is_whitelisted(env: Env, addr: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&Self::whitelist_key(&addr))
+            .unwrap_or(false)
+    }
+
+    /// Get the daily swap limit for `token`, if one is set
+    pub fn get_swap_limit(env: Env, token: Address) -> Option<i128> {
+        env.storage().persistent().get(&Self::swap_limit_key(&token))
+    }
+
+    /// Get the amount of `token` the agent has swapped today
+    pub fn get_swap_volume(env: Env, token: Address) -> i128 {
+        let today = env.ledger().timestamp() / DAY_IN_SECONDS;
+        match env
+            .storage()
+            .persistent()
+            .get::<_, (u64, i128)>(&Self::swap_volume_key(&token))
+        {
+            Some((day, volume)) if day == today => volume,
+            _ => 0,
+        }
+    }
+
+    /// Get the share of assets the agent must leave idle, in basis points
+    pub fn get_reserve_ratio(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&RESERVE_BPS)
+            .unwrap_or(DEFAULT_RESERVE_BPS)
+    }
+
+    /// Get the vault's positions across pools, as each pool last reported
+    /// them to a strategy
+    pub fn get_deployed_assets(env: Env) -> i128 {
+        env.storage().instance().get(&DEPLOYED).unwrap_or(0)
+    }
+
+    /// Get the vault's position in `pool` as of the last strategy there
+    pub fn get_pool_deployed(env: Env, pool: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Self::deployed_key(&pool))
+            .unwrap_or(0)
+    }
+
+    /// Get the agent strategy limits as (cooldown seconds, max USDC per day)
+    pub fn get_strategy_limits(env: Env) -> (u64, i128) {
+        let storage = env.storage().instance();
+        (
+            storage.get(&STRATEGY_COOLDOWN).unwrap_or(0),
+            storage.get(&STRATEGY_MAX_DAILY).unwrap_or(0),
+        )
+    }
+
+    /// Get the USDC agent strategies have moved in the current 24 hour window
+    pub fn get_strategy_volume(env: Env) -> i128 {
+        Self::strategy_window(&env).1
+    }
+
+    /// Get the last `STRATEGY_HISTORY_LEN` executed strategies, oldest first
+    pub fn get_strategy_history(env: Env) -> Vec<StrategyRecord> {
+        let count = Self::get_strategy_count(env.clone());
+        let first = count.saturating_sub(STRATEGY_HISTORY_LEN as u64);
+        let mut history = vec![&env];
+        for index in first..count {
+            if let Some(record) = env.storage().persistent().get(&Self::strategy_key(index)) {
+                history.push_back(record);
+            }
+        }
+        history
+    }
+
+    /// Get the number of strategies executed since deployment
+    pub fn get_strategy_count(env: Env) -> u64 {
+        env.storage().instance().get(&STRATEGY_COUNT).unwrap_or(0)
+    }
+
+    /// Get the risk policy public key, if configured
+    pub fn get_policy_key(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&POLICY_KEY)
+    }
+
+    /// Get the maximum tolerated drawdown in bps, if a stop-loss is configured
+    pub fn get_stop_loss(env: Env) -> Option<u32> {
+        env.storage().instance().get(&STOP_LOSS)
+    }
+
+    /// Get the highest nominal share value seen since the stop-loss was set or
+    /// cleared
+    pub fn get_high_water_mark(env: Env) -> Option<i128> {
+        env.storage().instance().get(&HIGH_WATER)
+    }
+
+    /// Check whether the stop-loss has tripped and supply strategies are frozen
+    pub fn is_stopped(env: Env) -> bool {
+        env.storage().instance().get(&STOPPED).unwrap_or(false)
+    }
+
+    /// Get the share value drop in bps that trips the circuit breaker, 0 if off
+    pub fn get_breaker_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&BREAKER_BPS)
+            .unwrap_or(DEFAULT_BREAKER_BPS)
+    }
+
+    /// Check whether the circuit breaker has tripped and paused the vault
+    pub fn is_breaker_tripped(env: Env) -> bool {
+        env.storage().instance().get(&BREAKER_TRIPPED).unwrap_or(false)
+    }
+
+    /// Get the keeper escrow, if configured
+    pub fn get_keeper(env: Env) -> Option<Address> {
+        env.storage().instance().get(&KEEPER)
+    }
+
+    /// Get the last nonce accepted by `agent_execute_signed`, if any
+    pub fn get_policy_nonce(env: Env) -> Option<u64> {
+        env.storage().instance().get(&POLICY_NONCE)
+    }
+
+    /// Get the platform fee in basis points
+    pub fn get_platform_fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&FEE_BPS).unwrap_or(PLATFORM_FEE_BPS)
+    }
+
+    /// Get the deposit fee in basis points before any tier discount
+    pub fn get_deposit_fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DEPOSIT_FEE_BPS).unwrap_or(0)
+    }
+
+    /// Get the deposit fee `user` pays in basis points, after the discount for
+    /// their TUX tier. Without a TUX token, or if it can't be read, there is
+    /// no discount.
+    pub fn get_user_deposit_fee_bps(env: Env, user: Address) -> u32 {
+        let fee_bps = Self::get_deposit_fee_bps(env.clone());
+        if fee_bps == 0 {
+            return 0;
+        }
+        let tier = tiers::tier_of(&env, Self::get_tux_token(env.clone()), &user) as usize;
+        let discount_bps = TIER_DISCOUNT_BPS[tier.min(TIER_DISCOUNT_BPS.len() - 1)];
+        fee_bps - fee_bps * discount_bps / BPS_DENOMINATOR
+    }
+
+    /// Get the lock-up duration in seconds and early-withdrawal penalty in
+    /// basis points, (0, 0) without a lock-up
+    pub fn get_lockup(env: Env) -> (u64, u32) {
+        env.storage().instance().get(&LOCKUP).unwrap_or((0, 0))
+    }
+
+    /// Get when `user`'s shares unlock, 0 if they were never locked. Before
+    /// then withdrawals pay the early-withdrawal penalty.
+    pub fn get_unlock_time(env: Env, user: Address) -> u64 {
+        let (duration, _) = Self::get_lockup(env.clone());
+        match Self::get_deposit_time(&env, &user) {
+            Some(since) if duration > 0 => since.saturating_add(duration),
+            _ => 0,
+        }
+    }
+
+    /// Get the TUX token used for deposit fee discounts, if configured
+    pub fn get_tux_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&TUX_TOKEN)
+    }
+
+    /// Get the yearly management fee in basis points, 0 if none is charged
+    pub fn get_management_fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&MGMT_FEE_BPS).unwrap_or(0)
+    }
+
+    /// Get the ledger timestamp the management fee was last accrued at
+    pub fn get_last_accrual(env: Env) -> Option<u64> {
+        env.storage().instance().get(&LAST_ACCRUAL)
+    }
+
+    /// Get the share of distributed yield paid to its caller, in basis points
+    pub fn get_keeper_reward_bps(env: Env) -> u32 {
+        env.storage().instance().get(&KEEPER_BPS).unwrap_or(KEEPER_REWARD_BPS)
+    }
+
+    /// Get the minimum seconds between yield distributions, 0 if unlimited
+    pub fn get_min_distribution_interval(env: Env) -> u64 {
+        env.storage().instance().get(&DIST_INTERVAL).unwrap_or(0)
+    }
+
+    /// Get the yearly return on deposits exempt from the platform fee, in
+    /// basis points, 0 if the fee applies to all yield
+    pub fn get_hurdle_bps(env: Env) -> u32 {
+        env.storage().instance().get(&HURDLE_BPS).unwrap_or(0)
+    }
+
+    /// Check whether fees are charged only above the fee high-water mark
+    pub fn is_hwm_fees(env: Env) -> bool {
+        env.storage().instance().get(&HWM_FEES).unwrap_or(false)
+    }
+
+    /// Get the highest share value left after a yield distribution, the
+    /// initial share value before the first one
+    pub fn get_fee_high_water_mark(env: Env) -> i128 {
+        env.storage().instance().get(&FEE_HWM).unwrap_or(INITIAL_SHARE_VALUE)
+    }
+
+    /// Get the USDC set aside as donations, held outside the vault's assets
+    pub fn get_excess(env: Env) -> i128 {
+        env.storage().instance().get(&EXCESS).unwrap_or(0)
+    }
+
+    /// Get the cap on total assets, 0 if deposits are uncapped
+    pub fn get_deposit_cap(env: Env) -> i128 {
+        env.storage().instance().get(&DEPOSIT_CAP).unwrap_or(0)
+    }
+
+    /// Get the configured minimum deposit; the first deposit into an empty
+    /// vault has a higher floor, see `get_vault_stats`
+    pub fn get_min_deposit(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&MIN_DEPOSIT)
+            .unwrap_or(DEFAULT_MIN_DEPOSIT)
+    }
+
+    /// Get the share balance below which withdrawals sweep the remainder, 0
+    /// when they never do
+    pub fn get_dust_threshold(env: Env) -> i128 {
+        env.storage().instance().get(&DUST_THRESHOLD).unwrap_or(0)
+    }
+
+    /// Get the per-user deposit limit, 0 if unlimited
+    pub fn get_user_deposit_limit(env: Env) -> i128 {
+        env.storage().instance().get(&USER_LIMIT).unwrap_or(0)
+    }
+
+    /// Get the USDC `user` has deposited, net of what they have withdrawn
+    pub fn get_user_deposited(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&Self::deposited_key(&user))
+            .unwrap_or(0)
+    }
+
+    /// Get `user`'s cost basis and P&L as (USDC deposited, USDC withdrawn,
+    /// current value of their shares), the first two over the account's whole
+    /// life, full withdrawals included. Realized P&L is withdrawn minus
+    /// deposited once the shares are gone.
+    pub fn get_user_pnl(env: Env, user: Address) -> (i128, i128, i128) {
+        let total_in = env
+            .storage()
+            .persistent()
+            .get(&Self::total_in_key(&user))
+            .unwrap_or(0);
+        let total_out = env
+            .storage()
+            .persistent()
+            .get(&Self::total_out_key(&user))
+            .unwrap_or(0);
+        (total_in, total_out, Self::get_user_assets(env, user))
+    }
+
+    /// Get which operations are paused
+    pub fn get_pause_state(env: Env) -> PauseState {
+        env.storage()
+            .instance()
+            .get(&PAUSE_STATE)
+            .unwrap_or(PauseState::Active)
+    }
+
+    // ============ Internal Helper Functions ============
+
+    /// Validate and store the vault's roles and asset. The three roles must be
+    /// distinct and none of them the vault itself, and the asset must be a
+    /// token with `ASSET_DECIMALS` decimals.
+    fn init_internal(
+        env: &Env,
+        admin: Address,
+        agent: Address,
+        platform: Address,
+        usdc_asset: Address,
+        platform_fee_bps: Option<u32>,
+    ) -> Result<(), VaultError> {
+        Self::bump_instance(env);
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(VaultError::AlreadyInitialized);
+        }
+
+        let fee_bps = platform_fee_bps.unwrap_or(PLATFORM_FEE_BPS);
+        if fee_bps > MAX_PLATFORM_FEE_BPS {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let vault = env.current_contract_address();
+        if admin == agent
+            || admin == platform
+            || agent == platform
+            || [&admin, &agent, &platform, &usdc_asset].contains(&&vault)
+        {
+            return Err(VaultError::InvalidAddress);
+        }
+        let decimals = token::TokenClient::new(env, &usdc_asset).try_decimals();
+        if decimals != Ok(Ok(ASSET_DECIMALS)) {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        // Set initial state
+        env.storage().instance().set(&ADMIN, &admin);
+        Self::save_agents(env, &vec![env, agent.clone()]);
+        env.storage().instance().set(&PLATFORM, &platform);
+        env.storage().instance().set(&SHARE_TOKEN, &usdc_asset);
+        env.storage().instance().set(&TOTAL_SHARES, &0i128);
+        env.storage().instance().set(&INITIAL_DEPOSITS, &0i128);
+        env.storage().instance().set(&FEE_BPS, &fee_bps);
+
+        // Emit initialization event
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("init"),
+            (admin, agent, platform),
+        );
+
+        Ok(())
+    }
+
+    /// Require authorization from the stored admin
+    fn require_admin(env: &Env) -> Result<Address, VaultError> {
+        Self::bump_instance(env);
+        let admin = Self::stored_address(env, &ADMIN)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Require `agent` to be one of the vault's agents and to have authorized
+    /// the call
+    fn require_listed_agent(env: &Env, agent: &Address) -> Result<(), VaultError> {
+        if !Self::load_agents(env)?.contains(agent) {
+            return Err(VaultError::NotAuthorized);
+        }
+        agent.require_auth();
+        Ok(())
+    }
+
+    /// The vault's agents, primary first, kept from being archived while in use
+    fn load_agents(env: &Env) -> Result<Vec<Address>, VaultError> {
+        match env.storage().persistent().get::<_, Vec<Address>>(&AGENTS) {
+            Some(agents) => {
+                env.storage().persistent().extend_ttl(
+                    &AGENTS,
+                    SHARES_LIFETIME_THRESHOLD,
+                    SHARES_BUMP_AMOUNT,
+                );
+                Ok(agents)
+            }
+            None => Ok(vec![env, Self::stored_address(env, &AGENT)?]),
+        }
+    }
+
+    fn save_agents(env: &Env, agents: &Vec<Address>) {
+        env.storage().persistent().set(&AGENTS, agents);
+        env.storage().persistent().extend_ttl(
+            &AGENTS,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+        env.storage().instance().remove(&AGENT);
+    }
+
+    /// Pools assigned to `agent`, kept from being archived while in use
+    fn load_agent_pools(env: &Env, agent: &Address) -> Vec<Address> {
+        let key = Self::agent_pools_key(agent);
+        match env.storage().persistent().get::<_, Vec<Address>>(&key) {
+            Some(pools) => {
+                env.storage().persistent().extend_ttl(
+                    &key,
+                    SHARES_LIFETIME_THRESHOLD,
+                    SHARES_BUMP_AMOUNT,
+                );
+                pools
+            }
+            None => Vec::new(env),
+        }
+    }
+
+    fn save_agent_pools(env: &Env, agent: &Address, pools: &Vec<Address>) {
+        let key = Self::agent_pools_key(agent);
+        if pools.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, pools);
+            env.storage().persistent().extend_ttl(
+                &key,
+                SHARES_LIFETIME_THRESHOLD,
+                SHARES_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    /// Read an address set at initialization: a role or the vault's USDC
+    fn stored_address(env: &Env, key: &Symbol) -> Result<Address, VaultError> {
+        env.storage().instance().get(key).ok_or(VaultError::NotInitialized)
+    }
+
+    /// Validate a configuration change against the current configuration
+    fn check_change(env: &Env, change: &ConfigChange) -> Result<(), VaultError> {
+        match change {
+            ConfigChange::PlatformFeeBps(fee_bps) if *fee_bps > MAX_PLATFORM_FEE_BPS => {
+                Err(VaultError::InvalidAmount)
+            }
+            ConfigChange::ReserveRatio(reserve_bps) if *reserve_bps > BPS_DENOMINATOR => {
+                Err(VaultError::InvalidAmount)
+            }
+            #[cfg(feature = "strategy-timelock")]
+            ConfigChange::ChangeDelay(delay) if *delay > timelock::MAX_CHANGE_DELAY => {
+                Err(VaultError::InvalidAmount)
+            }
+            ConfigChange::Agent(new_agent) => {
+                let admin = Self::stored_address(env, &ADMIN)?;
+                let platform = Self::stored_address(env, &PLATFORM)?;
+                if *new_agent == admin || *new_agent == platform {
+                    return Err(VaultError::InvalidAddress);
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate and store a configuration change, emitting the same event as
+    /// its setter
+    fn apply_change(env: &Env, change: &ConfigChange) -> Result<(), VaultError> {
+        Self::check_change(env, change)?;
+
+        match change.clone() {
+            ConfigChange::PlatformFeeBps(fee_bps) => {
+                let old_fee_bps = Self::get_platform_fee_bps(env.clone());
+                env.storage().instance().set(&FEE_BPS, &fee_bps);
+
+                // Emit fee update event
+                events::publish(
+                    env,
+                    symbol_short!("vault"),
+                    symbol_short!("fee"),
+                    (old_fee_bps, fee_bps),
+                );
+            }
+            ConfigChange::Agent(new_agent) => {
+                let mut agents = Self::load_agents(env)?;
+                let agent = agents.get_unchecked(0);
+                // An agent already listed moves up to primary
+                if let Some(index) = agents.first_index_of(&new_agent).filter(|&index| index > 0)
+                {
+                    agents.remove(index);
+                }
+                agents.set(0, new_agent.clone());
+                Self::save_agents(env, &agents);
+
+                if agent != new_agent {
+                    let mut pools = Self::load_agent_pools(env, &new_agent);
+                    for pool in Self::load_agent_pools(env, &agent).iter() {
+                        if !pools.contains(&pool) {
+                            pools.push_back(pool);
+                        }
+                    }
+                    Self::save_agent_pools(env, &new_agent, &pools);
+                    Self::save_agent_pools(env, &agent, &Vec::new(env));
+                }
+
+                // Emit agent rotation event
+                events::publish(
+                    env,
+                    symbol_short!("vault"),
+                    symbol_short!("agent"),
+                    (agent, new_agent),
+                );
+            }
+            ConfigChange::Platform(new_platform) => {
+                let platform = Self::stored_address(env, &PLATFORM)?;
+                env.storage().instance().set(&PLATFORM, &new_platform);
+
+                // Emit platform update event
+                events::publish(
+                    env,
+                    symbol_short!("vault"),
+                    symbol_short!("platform"),
+                    (platform, new_platform),
+                );
+            }
+            ConfigChange::ReserveRatio(reserve_bps) => {
+                env.storage().instance().set(&RESERVE_BPS, &reserve_bps);
+
+                // Emit reserve ratio event
+                events::publish(
+                    env,
+                    symbol_short!("vault"),
+                    symbol_short!("reserve"),
+                    reserve_bps,
+                );
+            }
+            #[cfg(feature = "strategy-timelock")]
+            ConfigChange::ChangeDelay(delay) => Self::store_change_delay(env, delay),
+            // Only `queue_change` and `set_change_delay` make one
+            #[cfg(not(feature = "strategy-timelock"))]
+            ConfigChange::ChangeDelay(_) => return Err(VaultError::InvalidAmount),
+        }
+
+        Ok(())
+    }
+
+    /// Store the pause state and emit the transition from the previous one
+    fn set_pause_state(env: &Env, state: PauseState) {
+        let previous = Self::get_pause_state(env.clone());
+        if state == PauseState::Active {
+            env.storage().instance().remove(&PAUSE_STATE);
+        } else {
+            env.storage().instance().set(&PAUSE_STATE, &state);
+        }
+
+        // Emit pause transition event
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("pause"),
+            (previous, state),
+        );
+    }
+
+    /// Fail while withdrawals and strategies are paused
+    fn require_not_fully_paused(env: &Env) -> Result<(), VaultError> {
+        if Self::get_pause_state(env.clone()) == PauseState::FullyPaused {
+            return Err(VaultError::Paused);
+        }
+        Ok(())
+    }
+
+    /// Run a Blend supply/withdraw strategy for an authorized agent
+    fn execute_strategy(env: &Env, agent: Address, strategy: Strategy) -> Result<i128, VaultError> {
+        let window = Self::check_strategy(env, &agent, &strategy)?;
+        Self::record_strategy_allowance(env, window);
+
+        let amount = Self::run_strategy(env, &strategy)?;
+        let (share_value_after, total_assets_after, total_shares_after) =
+            Self::totals_after(env, &VaultState::load(env)?, 0)?;
+
+        // Emit strategy execution event, numbered so indexers can spot gaps
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("strategy"),
+            StrategyEvent {
+                agent,
+                action: strategy.action,
+                pool: strategy.pool,
+                amount,
+                count: Self::get_strategy_count(env.clone()),
+                share_value_after,
+                total_assets_after,
+                total_shares_after,
+            },
+        );
+
+        Ok(amount)
+    }
+
+    /// Checks `agent_execute` makes before running a strategy, shared with
+    /// `simulate_strategy`. Returns the strategy volume window to record.
+    fn check_strategy(
+        env: &Env,
+        agent: &Address,
+        strategy: &Strategy,
+    ) -> Result<(u64, i128), VaultError> {
+        Self::require_not_fully_paused(env)?;
+        Self::validate_strategy(env, agent, strategy)?;
+        Self::check_strategy_allowance(env, strategy.amount)
+    }
+
+    /// Checks on a strategy that don't depend on the strategies before it
+    fn validate_strategy(
+        env: &Env,
+        agent: &Address,
+        strategy: &Strategy,
+    ) -> Result<(), VaultError> {
+        // Validate amount
+        if strategy.amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        // Funds only move between the vault's USDC and pools the admin
+        // approved and assigned to this agent
+        if !Self::is_pool_approved(env.clone(), strategy.pool.clone())
+            || !Self::load_agent_pools(env, agent).contains(&strategy.pool)
+        {
+            return Err(VaultError::PoolNotApproved);
+        }
+        if strategy.asset != VaultState::load(env)?.usdc {
+            return Err(VaultError::InvalidAsset);
+        }
+
+        // The Blend request must move funds the way the action says
+        let request_types = match strategy.action {
+            ref act if *act == symbol_short!("supply") => {
+                [RequestType::Supply, RequestType::SupplyCollateral]
+            }
+            ref act if *act == symbol_short!("withdraw") => {
+                [RequestType::Withdraw, RequestType::WithdrawCollateral]
+            }
+            _ => {
+                return Err(VaultError::NotAuthorized);
+            }
+        };
+        if !request_types.iter().any(|t| *t as u32 == strategy.request_type) {
+            return Err(VaultError::InvalidRequestType);
+        }
+
+        Ok(())
+    }
+
+    /// Submit a validated strategy to its pool, returning the amount moved
+    fn run_strategy(env: &Env, strategy: &Strategy) -> Result<i128, VaultError> {
+        let stopped = Self::update_stop_loss(env);
+        if strategy.action == symbol_short!("supply") {
+            let idle = Self::idle_usdc(env, &VaultState::load(env)?);
+            Self::check_supply(env, strategy, stopped, idle)?;
+        }
+
+        // Execute strategy against the Blend pool
+        let pool_client = PoolClient::new(env, &strategy.pool);
+        let vault = env.current_contract_address();
+        let lent_before = blend::lent_total(&pool_client.get_positions(&vault));
+        // Authorizations only cover the next call, so this has to come last
+        if strategy.action == symbol_short!("supply") {
+            blend::authorize_supply(env, &strategy.pool, &strategy.asset, strategy.amount);
+        }
+        let positions = pool_client.submit(
+            &vault,
+            &vault,
+            &vault,
+            &vec![
+                env,
+                Request {
+                    request_type: strategy.request_type,
+                    address: strategy.asset.clone(),
+                    amount: strategy.amount,
+                },
+            ],
+        );
+
+        // The pool can move less than requested, e.g. a withdrawal capped at
+        // the position, so report the change it shows
+        let amount = (blend::lent_total(&positions) - lent_before).abs();
+
+        Self::record_pool_position(env, &strategy.pool, &positions);
+        Self::record_strategy(env, strategy, amount);
+        Self::check_breaker(env, &VaultState::load(env)?)?;
+
+        Ok(amount)
+    }
+
+    /// Checks on a supply given the stop-loss and the vault's idle USDC: a
+    /// tripped stop-loss only lets funds come back to the vault, and supplying
+    /// must leave the reserve idle. Withdrawals always pass.
+    fn check_supply(
+        env: &Env,
+        strategy: &Strategy,
+        stopped: bool,
+        idle: i128,
+    ) -> Result<(), VaultError> {
+        if strategy.action != symbol_short!("supply") {
+            return Ok(());
+        }
+        if stopped {
+            return Err(VaultError::StopLossTriggered);
+        }
+        Self::check_reserve(env, idle, strategy.amount)
+    }
+
+    /// Check `outflow` USDC can leave the vault's `idle` USDC and still keep
+    /// the reserve ratio of idle and deployed assets idle
+    fn check_reserve(env: &Env, idle: i128, outflow: i128) -> Result<(), VaultError> {
+        let reserve = math::muldiv_ceil(
+            idle + Self::get_deployed_assets(env.clone()),
+            Self::get_reserve_ratio(env.clone()) as i128,
+            BPS_DENOMINATOR as i128,
+        )
+        .ok_or(VaultError::MathOverflow)?;
+        if idle - outflow < reserve {
+            return Err(VaultError::ReserveBreached);
+        }
+        Ok(())
+    }
+
+    /// Store an executed strategy in the next history slot, overwriting the
+    /// one `STRATEGY_HISTORY_LEN` strategies older
+    fn record_strategy(env: &Env, strategy: &Strategy, amount: i128) {
+        let count = Self::get_strategy_count(env.clone());
+        let key = Self::strategy_key(count);
+        let record = StrategyRecord {
+            action: strategy.action.clone(),
+            pool: strategy.pool.clone(),
+            asset: strategy.asset.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+            ledger: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&key, &record);
+        env.storage().persistent().extend_ttl(
+            &key,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+        env.storage().instance().set(&STRATEGY_COUNT, &(count + 1));
+    }
+
+    /// Track the position `pool` now reports, interest and losses included,
+    /// in the per-pool and total deployed amounts
+    fn record_pool_position(env: &Env, pool: &Address, positions: &Positions) {
+        let pool_deployed = blend::lent_total(positions);
+        let previous = Self::get_pool_deployed(env.clone(), pool.clone());
+        let deployed = Self::get_deployed_assets(env.clone()) - previous + pool_deployed;
+        env.storage().instance().set(&DEPLOYED, &deployed);
+
+        let key = Self::deployed_key(pool);
+        if pool_deployed == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &pool_deployed);
+        }
+    }
+
+    /// Check a strategy moving `amount` against the cooldown and daily cap,
+    /// returning the volume window it would leave
+    fn check_strategy_allowance(env: &Env, amount: i128) -> Result<(u64, i128), VaultError> {
+        let (cooldown, max_daily) = Self::get_strategy_limits(env.clone());
+        let now = env.ledger().timestamp();
+
+        let last: Option<u64> = env.storage().instance().get(&STRATEGY_LAST);
+        if last.is_some_and(|last| now < last.saturating_add(cooldown)) {
+            return Err(VaultError::RateLimited);
+        }
+
+        let (window_start, volume) = Self::strategy_window(env);
+        let volume = volume.checked_add(amount).ok_or(VaultError::MathOverflow)?;
+        if max_daily > 0 && volume > max_daily {
+            return Err(VaultError::RateLimited);
+        }
+        Ok((window_start, volume))
+    }
+
+    /// Record a checked strategy against the cooldown and daily cap
+    fn record_strategy_allowance(env: &Env, window: (u64, i128)) {
+        env.storage()
+            .instance()
+            .set(&STRATEGY_LAST, &env.ledger().timestamp());
+        env.storage().instance().set(&STRATEGY_VOLUME, &window);
+    }
+
+    /// The current strategy volume window as (start, volume); a new window
+    /// opens 24 hours after the previous one started
+    fn strategy_window(env: &Env) -> (u64, i128) {
+        let now = env.ledger().timestamp();
+        match env.storage().instance().get::<_, (u64, i128)>(&STRATEGY_VOLUME) {
+            Some((start, volume)) if now < start + DAY_IN_SECONDS => (start, volume),
+            _ => (now, 0),
+        }
+    }
+
+    /// Swap `amount_in` of `token_in` held by the vault into `token_out` through
+    /// the configured router, returning the amount received
+    fn swap_exact_in(
+        env: &Env,
+        token_in: &Address,
+        token_out: &Address,
+        amount_in: i128,
+        min_out: i128,
+        deadline: u64,
+    ) -> Result<i128, VaultError> {
+        if env.ledger().timestamp() > deadline {
+            return Err(VaultError::DeadlinePassed);
+        }
+
+        let router: Address = env
+            .storage()
+            .instance()
+            .get(&SWAP_ROUTER)
+            .ok_or(VaultError::RouterNotSet)?;
+
+        // Check the quote first so a bad rate fails with a typed error
+        let quoted = dex::quote_exact_in(env, &router, token_in, token_out, amount_in);
+        if quoted < min_out {
+            return Err(VaultError::SlippageExceeded);
+        }
+
+        // Measure what actually arrived rather than trusting the router
+        let token_client = token::TokenClient::new(env, token_out);
+        let balance_before = token_client.balance(&env.current_contract_address());
+        dex::swap_exact_in(env, &router, token_in, token_out, amount_in, min_out, deadline);
+        let amount_out = token_client.balance(&env.current_contract_address()) - balance_before;
+
+        if amount_out < min_out {
+            return Err(VaultError::SlippageExceeded);
+        }
+
+        Ok(amount_out)
+    }
+
+    /// Track the nominal share value after a state-changing call and trip the
+    /// circuit breaker if it fell further below the value after the previous
+    /// one than allowed, pausing the vault. The call that observed the drop
+    /// still completes, so the pause sticks.
+    fn check_breaker(env: &Env, state: &VaultState) -> Result<(), VaultError> {
+        // An emptied vault starts over at the initial share value
+        if state.total_shares == 0 {
+            env.storage().instance().remove(&BREAKER_VALUE);
+            return Ok(());
+        }
+
+        let total_assets = Self::get_total_vault_assets(env, state);
+        let share_value = Self::nominal_share_value(env, total_assets, state.total_shares)?;
+        let last: Option<i128> = env.storage().instance().get(&BREAKER_VALUE);
+        env.storage().instance().set(&BREAKER_VALUE, &share_value);
+
+        let max_drop_bps = Self::get_breaker_bps(env.clone());
+        let last = match last {
+            Some(last) if max_drop_bps > 0 && share_value < last => last,
+            _ => return Ok(()),
+        };
+        if Self::is_breaker_tripped(env.clone()) {
+            return Ok(());
+        }
+
+        let drop_bps = math::muldiv(last - share_value, BPS_DENOMINATOR as i128, last)
+            .unwrap_or(BPS_DENOMINATOR as i128);
+        if drop_bps <= max_drop_bps as i128 {
+            return Ok(());
+        }
+
+        env.storage().instance().set(&BREAKER_TRIPPED, &true);
+        Self::set_pause_state(env, PauseState::FullyPaused);
+
+        // Emit circuit breaker event
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("brk"),
+            (last, share_value, drop_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Raise the high-water mark, or trip the stop-loss if share value has fallen
+    /// further below it than allowed. Returns whether the stop-loss is tripped.
+    fn update_stop_loss(env: &Env) -> bool {
+        if Self::is_stopped(env.clone()) {
+            return true;
+        }
+        let (max_drawdown_bps, high_water, share_value) = match Self::stop_loss_levels(env) {
+            Some(levels) => levels,
+            None => return false,
+        };
+        if share_value >= high_water {
+            env.storage().instance().set(&HIGH_WATER, &share_value);
+            return false;
+        }
+
+        let drawdown_bps = Self::drawdown_bps(high_water, share_value);
+        if drawdown_bps <= max_drawdown_bps as i128 {
+            return false;
+        }
+
+        env.storage().instance().set(&STOPPED, &true);
+
+        // Emit stop-loss event
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("stopped"),
+            (high_water, share_value, drawdown_bps),
+        );
+
+        true
+    }
+
+    /// Whether `update_stop_loss` would report the stop-loss tripped, without
+    /// recording anything
+    fn stop_loss_tripped(env: &Env) -> bool {
+        if Self::is_stopped(env.clone()) {
+            return true;
+        }
+        match Self::stop_loss_levels(env) {
+            Some((max_drawdown_bps, high_water, share_value)) => {
+                share_value < high_water
+                    && Self::drawdown_bps(high_water, share_value) > max_drawdown_bps as i128
+            }
+            None => false,
+        }
+    }
+
+    /// The stop-loss's allowed drawdown with the high-water mark and current
+    /// nominal share value, or None without a stop-loss. A share value too large to
+    /// represent is no drawdown, so also None.
+    fn stop_loss_levels(env: &Env) -> Option<(u32, i128, i128)> {
+        let max_drawdown_bps: u32 = env.storage().instance().get(&STOP_LOSS)?;
+        let share_value = Self::calculate_share_value(env, &VaultState::view(env)).ok()?;
+        let high_water: i128 = env
+            .storage()
+            .instance()
+            .get(&HIGH_WATER)
+            .unwrap_or(share_value);
+        Some((max_drawdown_bps, high_water, share_value))
+    }
+
+    /// How far `share_value` is below `high_water`, in basis points
+    fn drawdown_bps(high_water: i128, share_value: i128) -> i128 {
+        math::muldiv(high_water - share_value, BPS_DENOMINATOR as i128, high_water)
+            .unwrap_or(BPS_DENOMINATOR as i128)
+    }
+
+    /// `asset` as `deposit_internal` takes it: None for USDC, which keeps its
+    /// 1:1 path
+    fn other_asset<'a>(env: &Env, asset: &'a Address) -> Result<Option<&'a Address>, VaultError> {
+        let usdc_asset = Self::stored_address(env, &SHARE_TOKEN)?;
+        Ok(Some(asset).filter(|&asset| *asset != usdc_asset))
+    }
+
+    /// Shared deposit path: pull USDC from payer and mint shares to recipient.
+    /// With `from_allowance` the USDC is pulled against payer's approval to the
+    /// vault instead of a transfer authorized by payer. With `asset`, another
+    /// accepted asset is pulled instead and valued in USDC. Returns the shares
+    /// minted and the USDC value deposited.
+    fn deposit_internal(
+        env: &Env,
+        payer: &Address,
+        recipient: &Address,
+        asset: Option<&Address>,
+        amount: i128,
+        min_shares_out: i128,
+        from_allowance: bool,
+    ) -> Result<(i128, i128), VaultError> {
+        // Any pause halts deposits
+        if Self::get_pause_state(env.clone()) != PauseState::Active {
+            return Err(VaultError::Paused);
+        }
+
+        // Validate amount
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        // Shares credited to the vault itself would be stranded
+        if *recipient == env.current_contract_address() {
+            return Err(VaultError::InvalidAddress);
+        }
+
+        // Blacklisted addresses can neither fund deposits nor receive them
+        if Self::is_blacklisted(env.clone(), payer.clone())
+            || (recipient != payer && Self::is_blacklisted(env.clone(), recipient.clone()))
+        {
+            return Err(VaultError::Blacklisted);
+        }
+
+        // In whitelist mode only whitelisted addresses receive shares
+        if Self::is_whitelist_mode(env.clone())
+            && !Self::is_whitelisted(env.clone(), recipient.clone())
+        {
+            return Err(VaultError::NotWhitelisted);
+        }
+
+        let mut state = VaultState::load(env)?;
+        Self::accrue_management_fee(env, &mut state)?;
+
+        // Another accepted asset counts for its USDC value at the oracle price
+        let (token, value) = match asset {
+            Some(asset) => {
+                let accepted = Self::get_deposit_asset(env.clone(), asset.clone())
+                    .ok_or(VaultError::InvalidAsset)?;
+                (asset.clone(), Self::value_in_usdc(env, asset, &accepted, amount)?)
+            }
+            None => (state.usdc.clone(), amount),
+        };
+
+        // Dust deposits cost more in storage rent than they are worth
+        if value < Self::min_deposit_for(env, &state) {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        // The balance doubles as the pre-transfer reading for the shortfall
+        // check below. The new shares are priced on the nominal assets, so
+        // USDC deployed to pools doesn't cheapen them; the share value is
+        // recorded in the user's history, and a vault too large to price
+        // takes no deposits
+        let balance_before = Self::get_total_vault_assets(env, &state);
+        let nominal_before = Self::nominal_assets(env, &state);
+        #[cfg_attr(not(feature = "history"), allow(unused_variables))]
+        let share_value = Self::share_value_of(nominal_before, state.total_shares)?;
+
+        // The platform's deposit fee, discounted by the recipient's TUX tier,
+        // is paid in the deposited token and buys no shares
+        let fee_bps = Self::get_user_deposit_fee_bps(env.clone(), recipient.clone());
+        let fee = math::bps_of(amount, fee_bps).ok_or(VaultError::MathOverflow)?;
+        let net_value = value - math::bps_of(value, fee_bps).ok_or(VaultError::MathOverflow)?;
+
+        // Calculate shares to mint; the first deposit pays for the dead shares
+        let dead_shares = Self::dead_shares_for(&state);
+        let shares_to_mint =
+            Self::shares_for_assets(net_value, nominal_before, state.total_shares)? - dead_shares;
+
+        if shares_to_mint <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        if shares_to_mint < min_shares_out {
+            return Err(VaultError::SlippageExceeded);
+        }
+
+        // Enforce the TVL cap, counting USDC deployed to pools
+        if value > Self::deposit_headroom(env, nominal_before) {
+            return Err(VaultError::DepositCapExceeded);
+        }
+        if value > Self::user_headroom(env, recipient) {
+            return Err(VaultError::UserLimitExceeded);
+        }
+
+        // Transfer the deposit from payer to vault, reading another asset's
+        // balance around it since the total assets only count its tracked
+        // holding
+        let token_client = token::TokenClient::new(env, &token);
+        let vault = env.current_contract_address();
+        let held_before = match asset {
+            Some(_) => token_client.balance(&vault),
+            None => balance_before,
+        };
+        if from_allowance {
+            token_client.transfer_from(&vault, payer, &vault, &amount);
+        } else {
+            token_client.transfer(payer, &vault, &amount);
+        }
+        let held_after = match asset {
+            Some(_) => token_client.balance(&vault),
+            None => Self::get_total_vault_assets(env, &state),
+        };
+
+        // Fee-on-transfer or short-paying tokens would break share accounting
+        if held_after - held_before != amount {
+            return Err(VaultError::TransferShortfall);
+        }
+
+        if fee > 0 {
+            let platform = Self::stored_address(env, &PLATFORM)?;
+            token_client.transfer(&vault, &platform, &fee);
+
+            events::publish(
+                env,
+                symbol_short!("vault"),
+                symbol_short!("dep_fee"),
+                (recipient.clone(), fee, fee_bps),
+            );
+        }
+        if let Some(asset) = asset {
+            Self::adjust_held_asset(env, asset, amount - fee)?;
+        }
+
+        // Update total shares and initial deposits tracking
+        let total_shares = state
+            .total_shares
+            .checked_add(shares_to_mint + dead_shares)
+            .ok_or(VaultError::MathOverflow)?;
+        let initial_deposits =
+            state.initial_deposits.checked_add(net_value).ok_or(VaultError::MathOverflow)?;
+        state.set_total_shares(total_shares);
+        state.set_initial_deposits(initial_deposits);
+        state.save(env);
+
+        // Credit the recipient's shares and count the deposit against their limit
+        Self::lock_shares(env, &state, recipient, shares_to_mint, env.ledger().timestamp());
+        Self::mint_shares(env, &state, recipient, shares_to_mint);
+        if dead_shares > 0 {
+            Self::mint_shares(env, &state, &vault, dead_shares);
+        }
+        let deposited = Self::get_user_deposited(env.clone(), recipient.clone());
+        Self::set_user_deposited(env, recipient, deposited.saturating_add(value));
+        Self::add_user_total(env, &Self::total_in_key(recipient), value)?;
+        #[cfg(feature = "history")]
+        Self::record_share_value(env, &state)?;
+        Self::check_breaker(env, &state)?;
+        #[cfg(feature = "history")]
+        Self::record_history(
+            env,
+            recipient,
+            DepositRecord {
+                amount: value,
+                shares: shares_to_mint,
+                share_value,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        // Emit deposit event
+        let (share_value_after, total_assets_after, total_shares_after) =
+            Self::totals_after(env, &state, 0)?;
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("deposit"),
+            DepositEvent {
+                user: recipient.clone(),
+                amount: value,
+                shares: shares_to_mint,
+                share_value_after,
+                total_assets_after,
+                total_shares_after,
+            },
+        );
+        if let Some(asset) = asset {
+            // Emit asset deposit event
+            events::publish(
+                env,
+                symbol_short!("vault"),
+                symbol_short!("dep_asset"),
+                (recipient.clone(), asset.clone(), amount, shares_to_mint),
+            );
+        }
+
+        Ok((shares_to_mint, value))
+    }
+
+    /// Move shares between holders after the caller has authorized it
+    fn transfer_internal(
+        env: &Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), VaultError> {
+        // Validate amount
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let state = VaultState::load(env)?;
+
+        // Check sender has enough shares
+        if Self::share_balance(env, &state, &from) < amount {
+            return Err(VaultError::InsufficientShares);
+        }
+
+        // Move shares, still locked as long as they were; totals are unchanged
+        if let Some(since) = Self::get_deposit_time(env, &from) {
+            Self::lock_shares(env, &state, &to, amount, since);
+        }
+        Self::move_shares(env, &state, &from, &to, amount);
+
+        // Emit the SEP-41 transfer event, so wallets track shares like any
+        // other token
+        env.events().publish((symbol_short!("transfer"), from, to), amount);
+
+        Ok(())
+    }
+
+    /// Burn shares after the caller has authorized it, leaving the deposit
+    /// baseline alone so the USDC left behind isn't charged as yield
+    fn burn_internal(env: &Env, from: Address, amount: i128) -> Result<(), VaultError> {
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let mut state = VaultState::load(env)?;
+        if Self::share_balance(env, &state, &from) < amount {
+            return Err(VaultError::InsufficientShares);
+        }
+
+        Self::burn_shares(env, &state, &from, amount);
+        state.set_total_shares(state.total_shares - amount);
+        state.save(env);
+
+        // Emit the SEP-41 burn event
+        env.events().publish((symbol_short!("burn"), from), amount);
+
+        Ok(())
+    }
+
+    /// Use up `amount` of the allowance `from` gave `spender`
+    fn spend_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+    ) -> Result<(), VaultError> {
+        if amount <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        if VaultState::load(env)?.share_token.is_some() {
+            return Err(VaultError::AllowanceOnShareToken);
+        }
+
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            return Err(VaultError::InsufficientAllowance);
+        }
+
+        // Keep the expiry; TTL was set on approval
+        let key = Self::allowance_key(from, spender);
+        let (_, live_until): (i128, u32) = env.storage().temporary().get(&key).unwrap();
+        env.storage().temporary().set(&key, &(allowance - amount, live_until));
+
+        Ok(())
     }
 
-    // ============ Internal Helper Functions ============
+    /// Pay `caller` the keeper reward and the platform its fee out of the yield
+    /// earned since the last distribution, leaving the rest with users
+    fn distribute_internal(env: &Env, caller: &Address) -> Result<(), VaultError> {
+        let now = env.ledger().timestamp();
+        if now < Self::next_distribution_at(env) {
+            return Err(VaultError::TooSoon);
+        }
+
+        // Yield is measured on the nominal assets, so funds deployed to pools
+        // don't hide it
+        let mut state = VaultState::load(env)?;
+        let total_assets = Self::nominal_assets(env, &state);
+        let initial_deposits = state.initial_deposits;
+
+        // Calculate yield earned: with high-water mark fees, only the share
+        // value gained above the peak of past distributions counts
+        let yield_earned = if Self::is_hwm_fees(env.clone()) {
+            let share_value = Self::share_value_of(total_assets, state.total_shares)?;
+            let high_water = Self::get_fee_high_water_mark(env.clone());
+            if share_value <= high_water {
+                return Err(VaultError::NoYieldToDistribute);
+            }
+            math::muldiv(share_value - high_water, state.total_shares, SCALAR_7)
+                .ok_or(VaultError::MathOverflow)?
+        } else {
+            total_assets.checked_sub(initial_deposits).ok_or(VaultError::MathOverflow)?
+        };
+
+        if yield_earned <= 0 {
+            return Err(VaultError::NoYieldToDistribute);
+        }
+
+        // The caller is paid first, then the platform fee is taken from what
+        // the rest earned above the hurdle
+        let keeper_bps = Self::get_keeper_reward_bps(env.clone());
+        let keeper_reward =
+            math::bps_of(yield_earned, keeper_bps).ok_or(VaultError::MathOverflow)?;
+        let hurdle = Self::hurdle_amount(env, initial_deposits)?;
+        let fee_bps = Self::get_platform_fee_bps(env.clone());
+        let platform_fee = math::bps_of((yield_earned - keeper_reward - hurdle).max(0), fee_bps)
+            .ok_or(VaultError::MathOverflow)?;
+
+        // Yield under the hurdle, or with no platform fee, still pays the
+        // keeper and moves the distribution forward
+        if keeper_reward + platform_fee <= 0 {
+            return Err(VaultError::NoYieldToDistribute);
+        }
+        // The reward and fee are paid in USDC on hand
+        if keeper_reward + platform_fee > Self::idle_usdc(env, &state) {
+            return Err(VaultError::InsufficientBalance);
+        }
+
+        let platform = Self::stored_address(env, &PLATFORM)?;
+        let token_client = token::TokenClient::new(env, &state.usdc);
+
+        if keeper_reward > 0 {
+            token_client.transfer(&env.current_contract_address(), caller, &keeper_reward);
+        }
+        #[cfg(feature = "referrals")]
+        let referral_paid = Self::accrue_referrals(env, platform_fee, state.total_shares)?;
+        #[cfg(not(feature = "referrals"))]
+        let referral_paid = 0;
+        if platform_fee > referral_paid {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &platform,
+                &(platform_fee - referral_paid),
+            );
+        }
+
+        // Update initial deposits to reflect the reward and fee taken out
+        // This ensures share value reflects the fee distribution
+        let retained = yield_earned - keeper_reward - platform_fee;
+        state.set_initial_deposits(initial_deposits + retained);
+        state.save(env);
+        env.storage().instance().set(&LAST_DIST, &now);
+
+        // The share value left after fees is the peak later fees are charged above
+        let share_value = Self::share_value_of(
+            total_assets - keeper_reward - platform_fee,
+            state.total_shares,
+        )?;
+        if share_value > Self::get_fee_high_water_mark(env.clone()) {
+            env.storage().instance().set(&FEE_HWM, &share_value);
+        }
+        #[cfg(feature = "history")]
+        Self::record_share_value(env, &state)?;
+        Self::check_breaker(env, &state)?;
+
+        // Emit yield distribution event
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("yield"),
+            YieldEvent {
+                yield_earned,
+                platform_fee,
+                caller: caller.clone(),
+                keeper_reward,
+                share_value_after: share_value,
+                total_assets_after: total_assets - keeper_reward - platform_fee,
+                total_shares_after: state.total_shares,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Yield exempt from the platform fee: the hurdle's yearly rate on
+    /// `initial_deposits` over the time since the last distribution, or since
+    /// the hurdle was set if that is later
+    fn hurdle_amount(env: &Env, initial_deposits: i128) -> Result<i128, VaultError> {
+        let hurdle_bps = Self::get_hurdle_bps(env.clone());
+        if hurdle_bps == 0 {
+            return Ok(0);
+        }
+        let last_dist: u64 = env.storage().instance().get(&LAST_DIST).unwrap_or(0);
+        let since: u64 = env.storage().instance().get(&HURDLE_SINCE).unwrap_or(0);
+        let elapsed = env.ledger().timestamp().saturating_sub(last_dist.max(since));
+
+        math::muldiv(
+            initial_deposits,
+            hurdle_bps as i128 * elapsed as i128,
+            BPS_DENOMINATOR as i128 * YEAR_IN_SECONDS as i128,
+        )
+        .ok_or(VaultError::MathOverflow)
+    }
+
+    /// Ledger timestamp the minimum interval since the last yield distribution
+    /// ends at, 0 before the first one
+    fn next_distribution_at(env: &Env) -> u64 {
+        match env.storage().instance().get::<_, u64>(&LAST_DIST) {
+            Some(last) => last.saturating_add(Self::get_min_distribution_interval(env.clone())),
+            None => 0,
+        }
+    }
+
+    /// Shares the management fee has earned the platform since the last
+    /// accrual: enough for it to hold `fee_bps * elapsed / year` of the vault
+    fn pending_fee_shares(env: &Env, state: &VaultState) -> Result<i128, VaultError> {
+        let fee_bps = Self::get_management_fee_bps(env.clone());
+        let elapsed = match Self::get_last_accrual(env.clone()) {
+            Some(last) => env.ledger().timestamp().saturating_sub(last),
+            None => 0,
+        };
+        if fee_bps == 0 || elapsed == 0 || state.total_shares == 0 {
+            return Ok(0);
+        }
+
+        // Minting total * fee / (1 - fee) leaves the platform exactly `fee` of
+        // the supply; the fee is held under half so decades unaccrued can't
+        // zero the divisor
+        let year = BPS_DENOMINATOR as i128 * YEAR_IN_SECONDS as i128;
+        let fee = (fee_bps as i128 * elapsed as i128).min(year / 2);
+        math::muldiv(state.total_shares, fee, year - fee).ok_or(VaultError::MathOverflow)
+    }
+
+    /// Vault state with the pending management fee counted in the share
+    /// supply, as the next deposit or withdrawal will see it. Never saved.
+    fn load_accrued(env: &Env) -> VaultState {
+        let mut state = VaultState::view(env);
+        if let Ok(shares) = Self::pending_fee_shares(env, &state) {
+            state.total_shares += shares;
+        }
+        state
+    }
+
+    /// Mint the pending management fee to the platform and restart the clock.
+    /// Callers save `state`. Returns the shares minted.
+    fn accrue_management_fee(env: &Env, state: &mut VaultState) -> Result<i128, VaultError> {
+        if Self::get_management_fee_bps(env.clone()) == 0 {
+            return Ok(0);
+        }
+
+        let shares = Self::pending_fee_shares(env, state)?;
+        env.storage().instance().set(&LAST_ACCRUAL, &env.ledger().timestamp());
+        if shares == 0 {
+            return Ok(0);
+        }
+
+        let total_shares = state.total_shares.checked_add(shares).ok_or(VaultError::MathOverflow)?;
+        state.set_total_shares(total_shares);
+        let platform = Self::stored_address(env, &PLATFORM)?;
+        Self::mint_shares(env, state, &platform, shares);
+
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("mgmt_fee"),
+            (platform, shares),
+        );
+
+        Ok(shares)
+    }
+
+    /// Redeem `shares` from `user` at the current share value, paying
+    /// `recipient`. A remainder under the dust threshold is redeemed with them.
+    /// Returns the shares burned and the USDC paid.
+    fn redeem_shares(
+        env: &Env,
+        user: Address,
+        recipient: &Address,
+        shares: i128,
+        min_assets_out: i128,
+    ) -> Result<(i128, i128), VaultError> {
+        Self::require_not_fully_paused(env)?;
+
+        // Validate shares
+        if shares <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let mut state = VaultState::load(env)?;
+        Self::accrue_management_fee(env, &mut state)?;
+
+        // Check user has enough shares
+        let balance = Self::share_balance(env, &state, &user);
+        if balance < shares {
+            return Err(VaultError::InsufficientShares);
+        }
+
+        // Sweep a remainder too small to be worth its rent or another withdrawal
+        let remainder = balance - shares;
+        let shares = if remainder > 0 && remainder < Self::get_dust_threshold(env.clone()) {
+            balance
+        } else {
+            shares
+        };
+
+        // Value the shares on the nominal assets, pool positions included, so
+        // only a loss the pools report lowers the payout
+        let total_assets = Self::nominal_assets(env, &state);
+        if total_assets <= 0 {
+            return Err(VaultError::InsufficientBalance);
+        }
+
+        // Calculate USDC to return, less any early-withdrawal penalty
+        let assets = Self::assets_for_shares(shares, total_assets, state.total_shares)?;
+        let penalty = math::bps_of(assets, Self::early_penalty_bps(env, &user))
+            .ok_or(VaultError::MathOverflow)?;
+        let assets_to_return = assets - penalty;
+
+        if assets_to_return <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        if assets_to_return < min_assets_out {
+            return Err(VaultError::SlippageExceeded);
+        }
+
+        Self::withdraw_internal(
+            env,
+            &mut state,
+            user,
+            recipient,
+            shares,
+            assets_to_return,
+            penalty,
+            total_assets,
+        )?;
+
+        Ok((shares, assets_to_return))
+    }
+
+    /// Burn `shares` from `user` and pay `recipient` `assets` USDC, reducing
+    /// the deposit baseline in proportion. The `penalty` the shares were also
+    /// worth stays behind for the remaining holders. Callers size `assets` as
+    /// the shares' slice of the nominal `total_assets`, so a realized loss
+    /// lowers every payout alike instead of trapping the last holders.
+    #[allow(clippy::too_many_arguments)]
+    fn withdraw_internal(
+        env: &Env,
+        state: &mut VaultState,
+        user: Address,
+        recipient: &Address,
+        shares: i128,
+        assets: i128,
+        penalty: i128,
+        total_assets: i128,
+    ) -> Result<(), VaultError> {
+        // Only idle USDC is paid out, so a payout can exceed it until the
+        // agent brings funds back from pools or swaps other accepted assets;
+        // `request_withdraw` queues it instead
+        if assets > Self::idle_usdc(env, state) {
+            return Err(VaultError::InsufficientBalance);
+        }
+
+        // Compare with the shares' slice of the deposit baseline, which only a
+        // realized loss leaves unbacked
+        let share_value = Self::share_value_of(total_assets, state.total_shares)?;
+        let principal =
+            Self::assets_for_shares(shares, state.initial_deposits, state.total_shares)?;
+        let shortfall = (principal - assets - penalty).max(0);
+
+        // Burn the user's shares
+        Self::burn_shares(env, state, &user, shares);
+        Self::retire_shares(env, state, &user, shares, assets, penalty, share_value)?;
+        state.save(env);
+
+        // Transfer USDC back to the recipient, unless the vault keeps it to
+        // pay it on, as a migration does
+        if *recipient != env.current_contract_address() {
+            let token_client = token::TokenClient::new(env, &state.usdc);
+            let balance_before = token_client.balance(recipient);
+            token_client.transfer(&env.current_contract_address(), recipient, &assets);
+
+            // Fee-on-transfer or short-paying tokens would break share accounting
+            if token_client.balance(recipient) - balance_before != assets {
+                return Err(VaultError::TransferShortfall);
+            }
+        }
+        #[cfg(feature = "history")]
+        Self::record_share_value(env, state)?;
+        Self::check_breaker(env, state)?;
+
+        // Emit withdraw event, a migration's USDC already counted as gone
+        let in_transit = if *recipient == env.current_contract_address() {
+            assets
+        } else {
+            0
+        };
+        let (share_value_after, total_assets_after, total_shares_after) =
+            Self::totals_after(env, state, in_transit)?;
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("withdraw"),
+            WithdrawEvent {
+                user,
+                shares,
+                assets,
+                penalty,
+                shortfall,
+                share_value_after,
+                total_assets_after,
+                total_shares_after,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Account for `user`'s burned `shares` paying out `assets`: reduce the
+    /// deposit baseline in proportion and the user's net deposits, and
+    /// record the withdrawal in their history. The caller saves `state`.
+    #[cfg_attr(not(feature = "history"), allow(unused_variables))]
+    fn retire_shares(
+        env: &Env,
+        state: &mut VaultState,
+        user: &Address,
+        shares: i128,
+        assets: i128,
+        penalty: i128,
+        share_value: i128,
+    ) -> Result<(), VaultError> {
+        // Update initial deposits proportionally
+        let total_shares = state.total_shares;
+        let deposit_reduction = if total_shares > 0 {
+            math::muldiv(state.initial_deposits, shares, total_shares).ok_or(VaultError::MathOverflow)?
+        } else {
+            state.initial_deposits
+        };
+        // The penalty stays in the baseline so it isn't charged as yield
+        state.set_initial_deposits(state.initial_deposits - deposit_reduction + penalty);
+
+        // Update total shares
+        state.set_total_shares(total_shares - shares);
+
+        // Free the user's deposit capacity; shares received by transfer or
+        // yield can pay out more than was deposited
+        let deposited = Self::get_user_deposited(env.clone(), user.clone());
+        Self::set_user_deposited(env, user, (deposited - assets - penalty).max(0));
+        Self::add_user_total(env, &Self::total_out_key(user), assets)?;
+        #[cfg(feature = "history")]
+        Self::record_history(
+            env,
+            user,
+            DepositRecord {
+                amount: -assets,
+                shares: -shares,
+                share_value,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// USDC deposits can still add before total assets reach the deposit cap,
+    /// or i128::MAX when there is no cap
+    fn deposit_headroom(env: &Env, total_assets: i128) -> i128 {
+        match env.storage().instance().get::<_, i128>(&DEPOSIT_CAP) {
+            Some(cap) => (cap - total_assets).max(0),
+            None => i128::MAX,
+        }
+    }
+
+    /// Smallest deposit accepted: the configured minimum, raised to the
+    /// first-deposit floor while the vault has no shares
+    fn min_deposit_for(env: &Env, state: &VaultState) -> i128 {
+        let min_deposit = Self::get_min_deposit(env.clone());
+        if state.total_shares == 0 {
+            min_deposit.max(MIN_FIRST_DEPOSIT)
+        } else {
+            min_deposit
+        }
+    }
+
+    /// USDC `user` can still deposit under the per-user limit, or i128::MAX
+    /// when there is no limit
+    fn user_headroom(env: &Env, user: &Address) -> i128 {
+        match env.storage().instance().get::<_, i128>(&USER_LIMIT) {
+            Some(limit) => (limit - Self::get_user_deposited(env.clone(), user.clone())).max(0),
+            None => i128::MAX,
+        }
+    }
+
+    /// Write a user's net deposits, dropping empty entries
+    fn set_user_deposited(env: &Env, user: &Address, deposited: i128) {
+        let key = Self::deposited_key(user);
+        if deposited == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &deposited);
+        }
+    }
+
+    /// Add `amount` to one of a user's lifetime USDC totals. Unlike net
+    /// deposits these are kept when the user's shares are gone, so the cost
+    /// basis outlives a full withdrawal.
+    fn add_user_total(
+        env: &Env,
+        key: &(Symbol, Address),
+        amount: i128,
+    ) -> Result<(), VaultError> {
+        let total: i128 = env.storage().persistent().get(key).unwrap_or(0);
+        let total = total.checked_add(amount).ok_or(VaultError::MathOverflow)?;
+        env.storage().persistent().set(key, &total);
+        env.storage().persistent().extend_ttl(
+            key,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+        Ok(())
+    }
+
+    /// Share balance from the TUX0 token if configured, otherwise the internal ledger
+    fn share_balance(env: &Env, state: &VaultState, user: &Address) -> i128 {
+        match &state.share_token {
+            Some(share_token) => token::TokenClient::new(env, share_token).balance(user),
+            None => env
+                .storage()
+                .persistent()
+                .get(&(symbol_short!("shares"), user.clone()))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Whether `token` is the vault's share token (TUX0, or the vault itself
+    /// when shares are kept in the internal ledger)
+    fn is_share_token(env: &Env, token: &Address) -> bool {
+        *token == env.current_contract_address()
+            || env.storage().instance().get::<_, Address>(&TUX0_TOKEN).as_ref() == Some(token)
+    }
+
+    fn allowance_key(from: &Address, spender: &Address) -> (Symbol, Address, Address) {
+        (symbol_short!("allow"), from.clone(), spender.clone())
+    }
+
+    fn operator_key(user: &Address, operator: &Address) -> (Symbol, Address, Address) {
+        (symbol_short!("operator"), user.clone(), operator.clone())
+    }
+
+    /// Early-withdrawal penalty `user` pays right now, in basis points
+    fn early_penalty_bps(env: &Env, user: &Address) -> u32 {
+        let (_, penalty_bps) = Self::get_lockup(env.clone());
+        if env.ledger().timestamp() < Self::get_unlock_time(env.clone(), user.clone()) {
+            penalty_bps
+        } else {
+            0
+        }
+    }
+
+    /// Average deposit time of `user`'s locked shares, if they have any
+    fn get_deposit_time(env: &Env, user: &Address) -> Option<u64> {
+        if Self::get_lockup(env.clone()).0 == 0 {
+            return None;
+        }
+        env.storage().persistent().get(&Self::deposit_time_key(user))
+    }
+
+    /// Lock `shares` arriving at `user`, deposited at `since`, by averaging
+    /// their deposit time with that of the shares `user` already holds,
+    /// weighted by shares. Shares past their unlock count as unlocking now,
+    /// so topping up restarts the lock only in proportion to the new shares.
+    fn lock_shares(env: &Env, state: &VaultState, user: &Address, shares: i128, since: u64) {
+        let (duration, _) = Self::get_lockup(env.clone());
+        if duration == 0 || shares <= 0 {
+            return;
+        }
+
+        // Deposit times as seconds past the earliest still-locked time
+        let unlocked = env.ledger().timestamp().saturating_sub(duration);
+        let key = Self::deposit_time_key(user);
+        let held_since: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+        let held = Self::share_balance(env, state, user).max(0);
+        let held_age = held_since.saturating_sub(unlocked) as i128;
+        let added_age = since.saturating_sub(unlocked) as i128;
+        let age = (held_age * held + added_age * shares) / (held + shares);
+
+        env.storage().persistent().set(&key, &(unlocked + age as u64));
+        env.storage().persistent().extend_ttl(
+            &key,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+    }
+
+    fn deposit_time_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("dep_time"), user.clone())
+    }
+
+    fn deposited_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("deposited"), user.clone())
+    }
+
+    fn total_in_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("total_in"), user.clone())
+    }
+
+    fn total_out_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("total_out"), user.clone())
+    }
+
+    fn supported_asset_key(asset: &Address) -> (Symbol, Address) {
+        (symbol_short!("supported"), asset.clone())
+    }
+
+    /// Slot of the `index`th strategy in the history ring
+    fn strategy_key(index: u64) -> (Symbol, u32) {
+        (
+            symbol_short!("strategy"),
+            (index % STRATEGY_HISTORY_LEN as u64) as u32,
+        )
+    }
+
+    fn deployed_key(pool: &Address) -> (Symbol, Address) {
+        (symbol_short!("deployed"), pool.clone())
+    }
+
+    fn whitelist_key(addr: &Address) -> (Symbol, Address) {
+        (symbol_short!("whitelist"), addr.clone())
+    }
+
+    fn blacklist_key(addr: &Address) -> (Symbol, Address) {
+        (symbol_short!("blacklist"), addr.clone())
+    }
+
+    fn approved_pool_key(pool: &Address) -> (Symbol, Address) {
+        (symbol_short!("pool"), pool.clone())
+    }
+
+    fn deposit_asset_key(asset: &Address) -> (Symbol, Address) {
+        (symbol_short!("dep_asset"), asset.clone())
+    }
+
+    fn agent_pools_key(agent: &Address) -> (Symbol, Address) {
+        (symbol_short!("agt_pools"), agent.clone())
+    }
+
+    fn bridge_key(bridge: &Address) -> (Symbol, Address) {
+        (symbol_short!("bridge"), bridge.clone())
+    }
+
+    fn swap_limit_key(token: &Address) -> (Symbol, Address) {
+        (symbol_short!("swap_lim"), token.clone())
+    }
+
+    fn swap_volume_key(token: &Address) -> (Symbol, Address) {
+        (symbol_short!("swap_vol"), token.clone())
+    }
+
+    /// Credit newly issued shares to `to`
+    fn mint_shares(env: &Env, state: &VaultState, to: &Address, amount: i128) {
+        match &state.share_token {
+            Some(share_token) => ShareTokenClient::new(env, share_token).mint(to, &amount),
+            None => Self::adjust_ledger_shares(env, state, to, amount),
+        }
+    }
+
+    /// Remove redeemed shares from `from`; callers check the balance first
+    fn burn_shares(env: &Env, state: &VaultState, from: &Address, amount: i128) {
+        match &state.share_token {
+            Some(share_token) => token::TokenClient::new(env, share_token).burn(from, &amount),
+            None => Self::adjust_ledger_shares(env, state, from, -amount),
+        }
+        #[cfg(feature = "referrals")]
+        Self::release_referred(env, state, from);
+    }
+
+    /// Move shares between holders; callers check the balance first
+    fn move_shares(env: &Env, state: &VaultState, from: &Address, to: &Address, amount: i128) {
+        match &state.share_token {
+            Some(share_token) => {
+                token::TokenClient::new(env, share_token).transfer(from, to, &amount)
+            }
+            None => {
+                Self::adjust_ledger_shares(env, state, from, -amount);
+                Self::adjust_ledger_shares(env, state, to, amount);
+            }
+        }
+        #[cfg(feature = "referrals")]
+        Self::release_referred(env, state, from);
+    }
+
+    /// Add `delta` to an internal ledger balance, counting a holder in when
+    /// their balance appears and out when it empties
+    fn adjust_ledger_shares(env: &Env, state: &VaultState, user: &Address, delta: i128) {
+        let before = Self::share_balance(env, state, user);
+        let after = before + delta;
+        Self::set_ledger_shares(env, user, after);
+
+        if (before == 0) != (after == 0) && *user != env.current_contract_address() {
+            let holders = Self::get_holder_count(env.clone());
+            let holders = if after == 0 {
+                holders.saturating_sub(1)
+            } else {
+                holders + 1
+            };
+            env.storage().instance().set(&HOLDERS, &holders);
+        }
+    }
+
+    /// Write an internal ledger balance, dropping empty entries
+    fn set_ledger_shares(env: &Env, user: &Address, shares: i128) {
+        let key = (symbol_short!("shares"), user.clone());
+        if shares == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &shares);
+            env.storage().persistent().extend_ttl(
+                &key,
+                SHARES_LIFETIME_THRESHOLD,
+                SHARES_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    /// Keep an internal ledger share balance from being archived; no-op when
+    /// the user holds none
+    fn bump_shares(env: &Env, user: &Address) {
+        Self::bump_user_entry(env, &(symbol_short!("shares"), user.clone()));
+    }
+
+    /// Keep one of a user's persistent entries from being archived; no-op
+    /// when it doesn't exist
+    fn bump_user_entry(env: &Env, key: &(Symbol, Address)) {
+        if env.storage().persistent().has(key) {
+            env.storage().persistent().extend_ttl(
+                key,
+                SHARES_LIFETIME_THRESHOLD,
+                SHARES_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    /// Keep the vault's instance storage, and with it the contract, live
+    fn bump_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Shares the next deposit locks in the vault: `DEAD_SHARES` for the first
+    /// deposit, none afterwards
+    fn dead_shares_for(state: &VaultState) -> i128 {
+        if state.total_shares == 0 {
+            DEAD_SHARES
+        } else {
+            0
+        }
+    }
+
+    /// Shares minted for depositing `amount` into `total_assets` backing
+    /// `total_shares`, rounded down. Priced from the totals directly rather
+    /// than through the 7-decimal share value, so no precision is lost.
+    fn shares_for_assets(
+        amount: i128,
+        total_assets: i128,
+        total_shares: i128,
+    ) -> Result<i128, VaultError> {
+        if total_shares == 0 || total_assets <= 0 {
+            // First deposit, or no assets left to price against: 1:1 ratio
+            return Ok(amount);
+        }
 
-    /// Calculate current share value: total_assets / total_shares
-    fn calculate_share_value(env: &Env) -> i128 {
-        let total_assets = Self::get_total_vault_assets(env);
-        let total_shares: i128 = env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0);
+        // shares = amount * total_shares / total_assets
+        math::muldiv(amount, total_shares, total_assets).ok_or(VaultError::MathOverflow)
+    }
 
+    /// USDC paid for redeeming `shares` out of `total_assets` backing
+    /// `total_shares`, rounded down
+    fn assets_for_shares(
+        shares: i128,
+        total_assets: i128,
+        total_shares: i128,
+    ) -> Result<i128, VaultError> {
         if total_shares == 0 {
-            return INITIAL_SHARE_VALUE; // 1.0 USDC per share
+            return Ok(shares);
         }
 
-        // share_value = (total_assets * 10^7) / total_shares
-        (total_assets * INITIAL_SHARE_VALUE) / total_shares
+        // assets = shares * total_assets / total_shares
+        math::muldiv(shares, total_assets, total_shares).ok_or(VaultError::MathOverflow)
     }
 
-    /// Get total USDC balance held by the vault
-    fn get_total_vault_assets(env: &Env) -> i128 {
-        let usdc_asset: Address = env.storage().instance().get(&SHARE_TOKEN).unwrap();
-        let token_client = token::TokenClient::new(env, &usdc_asset);
-        token_client.balance(&env.current_contract_address())
+    /// Calculate current share value for display and history: nominal assets
+    /// / total_shares at 7 decimals, so supplying USDC to a pool leaves it
+    /// unchanged and only a loss the pool reports lowers it. Deposits and
+    /// withdrawals are priced from the totals instead.
+    fn calculate_share_value(env: &Env, state: &VaultState) -> Result<i128, VaultError> {
+        Self::share_value_of(Self::nominal_assets(env, state), state.total_shares)
     }
-}
 
-// ============ Tests ============
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Address, Env};
+    /// Share value counting the USDC deployed to pools as well as `total_assets`
+    fn nominal_share_value(
+        env: &Env,
+        total_assets: i128,
+        total_shares: i128,
+    ) -> Result<i128, VaultError> {
+        Self::share_value_of(total_assets + Self::get_deployed_assets(env.clone()), total_shares)
+    }
 
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, TuxedoVault);
-        let client = TuxedoVaultClient::new(&env, &contract_id);
+    /// Share value, total assets and total shares as an operation leaves
+    /// them, for its event, not counting `in_transit` USDC the vault still
+    /// holds but has already paid out
+    fn totals_after(
+        env: &Env,
+        state: &VaultState,
+        in_transit: i128,
+    ) -> Result<(i128, i128, i128), VaultError> {
+        let total_assets = Self::get_total_vault_assets(env, state) - in_transit;
+        let share_value = Self::nominal_share_value(env, total_assets, state.total_shares)?;
+        Ok((share_value, total_assets, state.total_shares))
+    }
 
-        let admin = Address::generate(&env);
-        let agent = Address::generate(&env);
-        let platform = Address::generate(&env);
-        let usdc = Address::generate(&env);
+    /// Share value for already-fetched totals, to avoid a second balance call
+    fn share_value_of(total_assets: i128, total_shares: i128) -> Result<i128, VaultError> {
+        if total_shares == 0 {
+            return Ok(INITIAL_SHARE_VALUE); // 1.0 USDC per share
+        }
 
-        client.initialize(&admin, &agent, &platform, &usdc);
+        // share_value = (total_assets * 10^7) / total_shares
+        math::muldiv(total_assets, SCALAR_7, total_shares).ok_or(VaultError::MathOverflow)
+    }
 
-        assert_eq!(client.get_admin(), admin);
-        assert_eq!(client.get_agent(), agent);
-        assert_eq!(client.get_platform(), platform);
+    /// `get_total_vault_assets` plus the USDC deployed to pools as last
+    /// recorded: what the shares are nominally worth
+    fn nominal_assets(env: &Env, state: &VaultState) -> i128 {
+        Self::get_total_vault_assets(env, state) + Self::get_deployed_assets(env.clone())
     }
 
-    #[test]
-    #[should_panic(expected = "AlreadyInitialized")]
-    fn test_double_initialize() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, TuxedoVault);
-        let client = TuxedoVaultClient::new(&env, &contract_id);
+    /// Get the vault's idle USDC plus the other accepted assets it holds at
+    /// their oracle prices. Panics with the pricing error if a held asset
+    /// can't be priced, rather than value shares without it.
+    fn get_total_vault_assets(env: &Env, state: &VaultState) -> i128 {
+        Self::idle_usdc(env, state)
+            + Self::held_asset_value(env).unwrap_or_else(|err| panic_with_error!(env, err))
+    }
 
-        let admin = Address::generate(&env);
-        let agent = Address::generate(&env);
-        let platform = Address::generate(&env);
-        let usdc = Address::generate(&env);
+    /// USDC value of the other accepted assets the vault holds
+    fn held_asset_value(env: &Env) -> Result<i128, VaultError> {
+        let mut value: i128 = 0;
+        for asset in Self::get_deposit_assets(env.clone()).iter() {
+            let Some(accepted) = Self::get_deposit_asset(env.clone(), asset.clone()) else {
+                continue;
+            };
+            if accepted.balance > 0 {
+                let held = Self::value_in_usdc(env, &asset, &accepted, accepted.balance)?;
+                value = value.checked_add(held).ok_or(VaultError::MathOverflow)?;
+            }
+        }
+        Ok(value)
+    }
 
-        client.initialize(&admin, &agent, &platform, &usdc);
-        client.initialize(&admin, &agent, &platform, &usdc); // Should panic
+    /// USDC value of `amount` of an accepted deposit asset at its oracle price
+    fn value_in_usdc(
+        env: &Env,
+        asset: &Address,
+        accepted: &DepositAsset,
+        amount: i128,
+    ) -> Result<i128, VaultError> {
+        let (price, _, decimals) =
+            oracle::get_price(env, Some(&accepted.oracle), asset, accepted.max_price_age)
+                .map_err(Self::price_error)?;
+        let scale = 10i128.checked_pow(decimals).ok_or(VaultError::MathOverflow)?;
+        math::muldiv(amount, price, scale).ok_or(VaultError::MathOverflow)
     }
 
-    #[test]
-    fn test_share_value_calculation() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, TuxedoVault);
-        let client = TuxedoVaultClient::new(&env, &contract_id);
+    fn price_error(err: OracleError) -> VaultError {
+        match err {
+            OracleError::StalePrice => VaultError::StalePrice,
+            OracleError::PriceUnavailable => VaultError::PriceUnavailable,
+        }
+    }
 
-        let admin = Address::generate(&env);
-        let agent = Address::generate(&env);
-        let platform = Address::generate(&env);
-        let usdc = Address::generate(&env);
+    /// Change the vault's tracked holding of `asset` by `delta` if it is an
+    /// accepted deposit asset, failing if that would take it below zero
+    fn adjust_held_asset(env: &Env, asset: &Address, delta: i128) -> Result<(), VaultError> {
+        let Some(mut accepted) = Self::get_deposit_asset(env.clone(), asset.clone()) else {
+            return Ok(());
+        };
+        accepted.balance = accepted.balance.checked_add(delta).ok_or(VaultError::MathOverflow)?;
+        if accepted.balance < 0 {
+            return Err(VaultError::InsufficientBalance);
+        }
+        Self::save_deposit_asset(env, asset, &accepted);
+        Ok(())
+    }
 
-        client.initialize(&admin, &agent, &platform, &usdc);
+    fn save_deposit_asset(env: &Env, asset: &Address, accepted: &DepositAsset) {
+        let key = Self::deposit_asset_key(asset);
+        env.storage().persistent().set(&key, accepted);
+        env.storage().persistent().extend_ttl(
+            &key,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+    }
 
-        // Initial share value should be 1.0 (10^7)
-        let share_value = client.get_share_value();
-        assert_eq!(share_value, INITIAL_SHARE_VALUE);
+    /// Get total USDC balance held by the vault, less donations, claimable
+    /// withdrawals and referral payouts set aside
+    fn idle_usdc(env: &Env, state: &VaultState) -> i128 {
+        let set_aside = Self::get_excess(env.clone());
+        #[cfg(feature = "withdrawal-queue")]
+        let set_aside = set_aside + Self::get_claimable_withdrawals(env.clone());
+        #[cfg(feature = "referrals")]
+        let set_aside = set_aside + Self::get_referral_owed(env.clone());
+        let token_client = token::TokenClient::new(env, &state.usdc);
+        token_client.balance(&env.current_contract_address()) - set_aside
     }
 }
@@ -0,0 +1,297 @@
+//! Withdrawal queue, compiled in with the `withdrawal-queue` feature. Users
+//! request a withdrawal while the vault's USDC is deployed, the agent fulfills
+//! requests once it has pulled enough back, and users claim what was set aside.
+
+use super::*;
+
+// USDC set aside for fulfilled withdrawal requests until they are claimed, and
+// the users whose requests still wait for fulfillment, oldest first
+const CLAIMABLE: Symbol = symbol_short!("CLAIMABLE");
+const WITHDRAW_QUEUE: Symbol = symbol_short!("WD_QUEUE");
+
+// Withdrawal requests waiting at once; the queue is a single ledger entry
+const MAX_WITHDRAW_QUEUE: u32 = 100;
+
+// Smallest request, so filling the queue ties up real funds rather than a
+// hundred dust positions
+const MIN_WITHDRAW_REQUEST: i128 = 10 * SCALAR_7; // 10 USDC
+
+/// A queued withdrawal: `shares` are held by the vault until fulfillment, which
+/// sets `assets` aside for the user to claim
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawRequest {
+    pub shares: i128,
+    /// USDC owed, at the share value when requested and net of `penalty`
+    pub assets: i128,
+    /// Early-withdrawal penalty left behind for the remaining holders
+    pub penalty: i128,
+    pub share_value: i128,
+    pub requested_at: u64,
+    pub fulfilled: bool,
+}
+
+#[contractimpl]
+impl TuxedoVault {
+    /// Queue a withdrawal for when the vault's USDC is deployed. The shares
+    /// move to the vault and the USDC they are worth now, pool positions
+    /// included and less any early-withdrawal penalty, is owed to the user
+    /// once `fulfill_withdrawals` reaches the request. One request per user
+    /// at a time, owed at least 10 USDC. Returns the USDC owed.
+    pub fn request_withdraw(env: Env, user: Address, shares: i128) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        user.require_auth();
+        Self::require_not_fully_paused(&env)?;
+
+        if shares <= 0 {
+            return Err(VaultError::InvalidAmount);
+        }
+        let key = Self::withdraw_request_key(&user);
+        if env.storage().persistent().has(&key) {
+            return Err(VaultError::WithdrawPending);
+        }
+        let mut queue = Self::get_withdraw_queue(env.clone());
+        if queue.len() >= MAX_WITHDRAW_QUEUE {
+            return Err(VaultError::QueueFull);
+        }
+
+        let mut state = VaultState::load(&env)?;
+        Self::accrue_management_fee(&env, &mut state)?;
+        state.save(&env);
+        if Self::share_balance(&env, &state, &user) < shares {
+            return Err(VaultError::InsufficientShares);
+        }
+
+        // Capture what the shares are worth now, pool positions included, as
+        // a withdrawal values them
+        let nominal_assets = Self::nominal_assets(&env, &state);
+        let share_value = Self::share_value_of(nominal_assets, state.total_shares)?;
+        let gross_assets = Self::assets_for_shares(shares, nominal_assets, state.total_shares)?;
+        let penalty = math::bps_of(gross_assets, Self::early_penalty_bps(&env, &user))
+            .ok_or(VaultError::MathOverflow)?;
+        let assets = gross_assets - penalty;
+        if assets < MIN_WITHDRAW_REQUEST {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        Self::move_shares(&env, &state, &user, &env.current_contract_address(), shares);
+        let request = WithdrawRequest {
+            shares,
+            assets,
+            penalty,
+            share_value,
+            requested_at: env.ledger().timestamp(),
+            fulfilled: false,
+        };
+        Self::set_withdraw_request(&env, &user, &request);
+        queue.push_back(user.clone());
+        Self::save_withdraw_queue(&env, &queue);
+
+        // Emit withdrawal request event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("wd_req"),
+            (user, shares, assets),
+        );
+
+        Ok(assets)
+    }
+
+    /// `caller`, the admin or one of the vault's agents, fulfills up to
+    /// `max_count` queued withdrawals, oldest first. Each burns the request's
+    /// shares and sets its USDC aside to be claimed; the first request the
+    /// vault's idle USDC can't cover stops the run, so withdraw from pools
+    /// with `agent_execute` or `emergency_recall` first. Returns the number
+    /// fulfilled.
+    pub fn fulfill_withdrawals(
+        env: Env,
+        caller: Address,
+        max_count: u32,
+    ) -> Result<u32, VaultError> {
+        Self::bump_instance(&env);
+        // The admin can fulfill requests too, e.g. after an emergency recall
+        if caller == Self::stored_address(&env, &ADMIN)? {
+            caller.require_auth();
+        } else {
+            Self::require_listed_agent(&env, &caller)?;
+        }
+        Self::require_not_fully_paused(&env)?;
+
+        let mut state = VaultState::load(&env)?;
+        Self::accrue_management_fee(&env, &mut state)?;
+
+        let vault = env.current_contract_address();
+        let mut queue = Self::get_withdraw_queue(env.clone());
+        let mut idle = Self::idle_usdc(&env, &state);
+        let mut claimable = Self::get_claimable_withdrawals(env.clone());
+        let mut fulfilled: u32 = 0;
+        while fulfilled < max_count {
+            let Some(user) = queue.first() else {
+                break;
+            };
+            let mut request = Self::get_withdraw_request(env.clone(), user.clone())
+                .ok_or(VaultError::NoWithdrawRequest)?;
+            if request.assets > idle {
+                break;
+            }
+
+            Self::burn_shares(&env, &state, &vault, request.shares);
+            Self::retire_shares(
+                &env,
+                &mut state,
+                &user,
+                request.shares,
+                request.assets,
+                request.penalty,
+                request.share_value,
+            )?;
+            idle -= request.assets;
+            claimable += request.assets;
+            request.fulfilled = true;
+            Self::set_withdraw_request(&env, &user, &request);
+            queue.pop_front();
+            fulfilled += 1;
+
+            // Emit fulfillment event
+            events::publish(
+                &env,
+                symbol_short!("vault"),
+                symbol_short!("wd_fill"),
+                (user, request.shares, request.assets),
+            );
+        }
+
+        state.save(&env);
+        env.storage().instance().set(&CLAIMABLE, &claimable);
+        Self::save_withdraw_queue(&env, &queue);
+        #[cfg(feature = "history")]
+        Self::record_share_value(&env, &state)?;
+        Self::check_breaker(&env, &state)?;
+
+        Ok(fulfilled)
+    }
+
+    /// Pay out a fulfilled withdrawal request. Returns the USDC paid.
+    pub fn claim_withdraw(env: Env, user: Address) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        user.require_auth();
+        Self::require_not_fully_paused(&env)?;
+
+        let request = Self::get_withdraw_request(env.clone(), user.clone())
+            .ok_or(VaultError::NoWithdrawRequest)?;
+        if !request.fulfilled {
+            return Err(VaultError::NotClaimable);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&Self::withdraw_request_key(&user));
+        let claimable = Self::get_claimable_withdrawals(env.clone()) - request.assets;
+        if claimable == 0 {
+            env.storage().instance().remove(&CLAIMABLE);
+        } else {
+            env.storage().instance().set(&CLAIMABLE, &claimable);
+        }
+
+        let token_client = token::TokenClient::new(&env, &VaultState::load(&env)?.usdc);
+        let balance_before = token_client.balance(&user);
+        token_client.transfer(&env.current_contract_address(), &user, &request.assets);
+        if token_client.balance(&user) - balance_before != request.assets {
+            return Err(VaultError::TransferShortfall);
+        }
+
+        // Emit withdrawal claim event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("wd_claim"),
+            (user, request.assets),
+        );
+
+        Ok(request.assets)
+    }
+
+    /// Cancel a withdrawal request that hasn't been fulfilled, returning its
+    /// shares to the user
+    pub fn cancel_withdraw(env: Env, user: Address) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        user.require_auth();
+
+        let request = Self::get_withdraw_request(env.clone(), user.clone())
+            .ok_or(VaultError::NoWithdrawRequest)?;
+        if request.fulfilled {
+            return Err(VaultError::AlreadyFulfilled);
+        }
+
+        let mut queue = Self::get_withdraw_queue(env.clone());
+        if let Some(index) = queue.first_index_of(&user) {
+            queue.remove(index);
+        }
+        Self::save_withdraw_queue(&env, &queue);
+        env.storage()
+            .persistent()
+            .remove(&Self::withdraw_request_key(&user));
+
+        let state = VaultState::load(&env)?;
+        Self::move_shares(&env, &state, &env.current_contract_address(), &user, request.shares);
+
+        // Emit withdrawal cancellation event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("wd_cancel"),
+            (user, request.shares),
+        );
+
+        Ok(())
+    }
+
+    /// Get `user`'s withdrawal request, if one is queued or waiting to be claimed
+    pub fn get_withdraw_request(env: Env, user: Address) -> Option<WithdrawRequest> {
+        env.storage()
+            .persistent()
+            .get(&Self::withdraw_request_key(&user))
+    }
+
+    /// Get the users whose withdrawal requests await fulfillment, oldest first
+    pub fn get_withdraw_queue(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&WITHDRAW_QUEUE)
+            .unwrap_or(vec![&env])
+    }
+
+    /// Get the USDC set aside for fulfilled withdrawals not yet claimed
+    pub fn get_claimable_withdrawals(env: Env) -> i128 {
+        env.storage().instance().get(&CLAIMABLE).unwrap_or(0)
+    }
+}
+
+impl TuxedoVault {
+    fn withdraw_request_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("wd_req"), user.clone())
+    }
+
+    /// Store the withdrawal queue, keeping it alive as long as the requests in
+    /// it
+    fn save_withdraw_queue(env: &Env, queue: &Vec<Address>) {
+        env.storage().persistent().set(&WITHDRAW_QUEUE, queue);
+        env.storage().persistent().extend_ttl(
+            &WITHDRAW_QUEUE,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+    }
+
+    /// Store a withdrawal request, keeping it alive as long as a share balance
+    fn set_withdraw_request(env: &Env, user: &Address, request: &WithdrawRequest) {
+        let key = Self::withdraw_request_key(user);
+        env.storage().persistent().set(&key, request);
+        env.storage().persistent().extend_ttl(
+            &key,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! Referrals, compiled in with the `referrals` feature. Deposits can credit a
+//! referrer with the shares they mint, each distribution sets a share of the
+//! platform fee aside for referrers by the referred shares still held, and
+//! referrers claim what they accrued.
+
+use super::*;
+
+// Share of the platform fee set aside for referrers, the referred shares still
+// held, the fee set aside per referred share so far, and the USDC set aside
+// that referrers haven't claimed
+const REFERRAL_BPS: Symbol = symbol_short!("REF_BPS");
+const REFERRED: Symbol = symbol_short!("REFERRED");
+const REFERRAL_INDEX: Symbol = symbol_short!("REF_IDX");
+const REFERRAL_OWED: Symbol = symbol_short!("REF_OWED");
+
+// Hard cap on the referrers' share of the platform fee, whoever sets it
+const MAX_REFERRAL_BPS: u32 = 5_000; // 50%
+
+// Fixed point of the referral index, fine enough that a single stroop of fee
+// spread over a large vault's shares still counts
+const INDEX_SCALE: i128 = SCALAR_7 * SCALAR_7;
+
+/// What a referrer brought into the vault and has been paid for it
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReferralStats {
+    /// USDC deposited through their referrals, never reduced by withdrawals
+    pub volume: i128,
+    /// Shares their referrals minted that the users still hold; payouts
+    /// follow these
+    pub shares: i128,
+    /// USDC accrued to them and not yet claimed
+    pub accrued: i128,
+    /// USDC paid to them out of the platform fee
+    pub paid: i128,
+    /// Referral index `accrued` was brought up to
+    pub index: i128,
+}
+
+/// The referrer a user's deposits credited, and how many of the user's shares
+/// still count for them
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Referral {
+    pub referrer: Address,
+    pub shares: i128,
+}
+
+#[contractimpl]
+impl TuxedoVault {
+    /// `deposit`, crediting `referrer` with its USDC value as referred
+    /// volume and with the shares minted. A user has one referrer at a time:
+    /// referring them again moves their referred shares to the new referrer.
+    /// Users can't refer themselves.
+    pub fn deposit_with_referral(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+        min_shares_out: i128,
+        referrer: Address,
+    ) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        user.require_auth();
+
+        if referrer == user || referrer == env.current_contract_address() {
+            return Err(VaultError::InvalidAddress);
+        }
+        let asset = Self::other_asset(&env, &asset)?;
+        let (shares, value) =
+            Self::deposit_internal(&env, &user, &user, asset, amount, min_shares_out, false)?;
+        Self::record_referral(&env, &user, &referrer, shares, value)?;
+
+        // Emit referred deposit event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("referred"),
+            (user, referrer, amount, shares),
+        );
+
+        Ok(shares)
+    }
+
+    /// Pay `referrer` the USDC accrued to them. Returns the USDC paid.
+    pub fn claim_referral(env: Env, referrer: Address) -> Result<i128, VaultError> {
+        Self::bump_instance(&env);
+        referrer.require_auth();
+        Self::require_not_fully_paused(&env)?;
+
+        let mut stats = Self::get_referral_stats(env.clone(), referrer.clone());
+        let amount = stats.accrued;
+        if amount <= 0 {
+            return Err(VaultError::NotClaimable);
+        }
+        stats.accrued = 0;
+        stats.paid += amount;
+        Self::save_referral(&env, &referrer, &stats);
+        let owed = Self::get_referral_owed(env.clone()) - amount;
+        env.storage().instance().set(&REFERRAL_OWED, &owed);
+
+        let usdc = VaultState::load(&env)?.usdc;
+        token::TokenClient::new(&env, &usdc).transfer(
+            &env.current_contract_address(),
+            &referrer,
+            &amount,
+        );
+
+        // Emit referral payout event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("ref_paid"),
+            (referrer, amount),
+        );
+
+        Ok(amount)
+    }
+
+    /// Stop counting shares `user` no longer holds for their referrer, such as
+    /// TUX0 moved on the share token itself. Anyone can call this function.
+    pub fn sync_referral(env: Env, user: Address) -> Result<(), VaultError> {
+        Self::bump_instance(&env);
+        let state = VaultState::load(&env)?;
+        Self::release_referred(&env, &state, &user);
+        Ok(())
+    }
+
+    /// Set the share of the platform fee paid to referrers at each
+    /// distribution, in basis points (admin only, capped at 50%)
+    pub fn set_referral_bps(env: Env, referral_bps: u32) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if referral_bps > MAX_REFERRAL_BPS {
+            return Err(VaultError::InvalidAmount);
+        }
+
+        let old_referral_bps = Self::get_referral_bps(env.clone());
+        env.storage().instance().set(&REFERRAL_BPS, &referral_bps);
+
+        // Emit referral share update event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("ref_bps"),
+            (old_referral_bps, referral_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Get the share of the platform fee paid to referrers in basis points
+    pub fn get_referral_bps(env: Env) -> u32 {
+        env.storage().instance().get(&REFERRAL_BPS).unwrap_or(0)
+    }
+
+    /// Get what `referrer` has referred, accrued and been paid
+    pub fn get_referral_stats(env: Env, referrer: Address) -> ReferralStats {
+        let mut stats: ReferralStats = env
+            .storage()
+            .persistent()
+            .get(&Self::referral_key(&referrer))
+            .unwrap_or_default();
+        Self::settle_referrer(&env, &mut stats);
+        stats
+    }
+
+    /// Get `user`'s referrer and the shares that still count for them
+    pub fn get_referral(env: Env, user: Address) -> Option<Referral> {
+        env.storage().persistent().get(&Self::referred_by_key(&user))
+    }
+
+    /// Get the USDC set aside for referrers and not yet claimed
+    pub fn get_referral_owed(env: Env) -> i128 {
+        env.storage().instance().get(&REFERRAL_OWED).unwrap_or(0)
+    }
+}
+
+impl TuxedoVault {
+    /// Set the referral share of `platform_fee` aside for referrers: the part
+    /// the referred shares are of all `total_shares`, spread over the
+    /// referred shares for each referrer to claim. The work is the same
+    /// however many referrers there are. Returns the USDC set aside, which
+    /// the platform doesn't get.
+    pub(crate) fn accrue_referrals(
+        env: &Env,
+        platform_fee: i128,
+        total_shares: i128,
+    ) -> Result<i128, VaultError> {
+        let referral_bps = Self::get_referral_bps(env.clone());
+        let referred = Self::referred_shares(env).min(total_shares);
+        if referral_bps == 0 || referred <= 0 {
+            return Ok(0);
+        }
+        let pot = math::bps_of(platform_fee, referral_bps).ok_or(VaultError::MathOverflow)?;
+        let share = math::muldiv(pot, referred, total_shares).ok_or(VaultError::MathOverflow)?;
+
+        // Only what the index hands out is set aside; what rounds away goes
+        // to the platform
+        let step = math::muldiv(share, INDEX_SCALE, referred).ok_or(VaultError::MathOverflow)?;
+        let set_aside = math::muldiv(step, referred, INDEX_SCALE).ok_or(VaultError::MathOverflow)?;
+        if set_aside <= 0 {
+            return Ok(0);
+        }
+        let index = Self::referral_index(env)
+            .checked_add(step)
+            .ok_or(VaultError::MathOverflow)?;
+        env.storage().instance().set(&REFERRAL_INDEX, &index);
+        let owed = Self::get_referral_owed(env.clone()) + set_aside;
+        env.storage().instance().set(&REFERRAL_OWED, &owed);
+
+        // Emit referral accrual event
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("ref_accr"),
+            (set_aside, index),
+        );
+
+        Ok(set_aside)
+    }
+
+    /// Cap `user`'s referred shares at the shares they still hold, taking
+    /// the difference off their referrer once what it earned is accrued
+    pub(crate) fn release_referred(env: &Env, state: &VaultState, user: &Address) {
+        let Some(mut referral) = Self::get_referral(env.clone(), user.clone()) else {
+            return;
+        };
+        let held = Self::share_balance(env, state, user);
+        if referral.shares <= held {
+            return;
+        }
+
+        Self::adjust_referrer_shares(env, &referral.referrer, held - referral.shares);
+        referral.shares = held;
+        Self::save_referred_by(env, user, &referral);
+    }
+
+    /// Credit `referrer` with the `shares` and USDC `value` of a referred
+    /// deposit by `user`, moving the user's earlier referred shares over if
+    /// another referrer had them
+    fn record_referral(
+        env: &Env,
+        user: &Address,
+        referrer: &Address,
+        shares: i128,
+        value: i128,
+    ) -> Result<(), VaultError> {
+        let mut referral = Self::get_referral(env.clone(), user.clone()).unwrap_or(Referral {
+            referrer: referrer.clone(),
+            shares: 0,
+        });
+        if referral.referrer != *referrer {
+            Self::adjust_referrer_shares(env, &referral.referrer, -referral.shares);
+            Self::adjust_referrer_shares(env, referrer, referral.shares);
+            referral.referrer = referrer.clone();
+        }
+        Self::adjust_referrer_shares(env, referrer, shares);
+        referral.shares = referral.shares.checked_add(shares).ok_or(VaultError::MathOverflow)?;
+        Self::save_referred_by(env, user, &referral);
+
+        let mut stats = Self::get_referral_stats(env.clone(), referrer.clone());
+        stats.volume = stats.volume.checked_add(value).ok_or(VaultError::MathOverflow)?;
+        Self::save_referral(env, referrer, &stats);
+
+        Ok(())
+    }
+
+    /// Change `referrer`'s referred shares by `delta`, accruing what their
+    /// shares earned so far first
+    fn adjust_referrer_shares(env: &Env, referrer: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+        let mut stats = Self::get_referral_stats(env.clone(), referrer.clone());
+        stats.shares += delta;
+        Self::save_referral(env, referrer, &stats);
+        let referred = Self::referred_shares(env) + delta;
+        env.storage().instance().set(&REFERRED, &referred);
+    }
+
+    /// Bring `stats.accrued` up to the current referral index
+    fn settle_referrer(env: &Env, stats: &mut ReferralStats) {
+        let index = Self::referral_index(env);
+        if stats.shares > 0 {
+            let earned = math::muldiv(stats.shares, index - stats.index, INDEX_SCALE)
+                .unwrap_or_else(|| panic_with_error!(env, VaultError::MathOverflow));
+            stats.accrued += earned;
+        }
+        stats.index = index;
+    }
+
+    fn referred_shares(env: &Env) -> i128 {
+        env.storage().instance().get(&REFERRED).unwrap_or(0)
+    }
+
+    fn referral_index(env: &Env) -> i128 {
+        env.storage().instance().get(&REFERRAL_INDEX).unwrap_or(0)
+    }
+
+    fn save_referral(env: &Env, referrer: &Address, stats: &ReferralStats) {
+        let key = Self::referral_key(referrer);
+        env.storage().persistent().set(&key, stats);
+        env.storage().persistent().extend_ttl(
+            &key,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+    }
+
+    /// Store `user`'s referral, dropping it once no shares count
+    fn save_referred_by(env: &Env, user: &Address, referral: &Referral) {
+        let key = Self::referred_by_key(user);
+        if referral.shares == 0 {
+            env.storage().persistent().remove(&key);
+            return;
+        }
+        env.storage().persistent().set(&key, referral);
+        env.storage().persistent().extend_ttl(
+            &key,
+            SHARES_LIFETIME_THRESHOLD,
+            SHARES_BUMP_AMOUNT,
+        );
+    }
+
+    fn referral_key(referrer: &Address) -> (Symbol, Address) {
+        (symbol_short!("referral"), referrer.clone())
+    }
+
+    fn referred_by_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("ref_by"), user.clone())
+    }
+}
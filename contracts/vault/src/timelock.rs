@@ -0,0 +1,162 @@
+//! Timelock on sensitive configuration, compiled in with the
+//! `strategy-timelock` feature. Once a change delay is set, the setters it
+//! guards only take effect through `queue_change` and `execute_change`.
+
+use super::*;
+
+// Timelock on sensitive configuration: seconds a queued change waits before it
+// can be executed, and the changes queued so far. Each pending one is kept in a
+// persistent slot until executed or cancelled.
+const CHANGE_DELAY: Symbol = symbol_short!("CHG_DELAY");
+const CHANGE_COUNT: Symbol = symbol_short!("CHG_CNT");
+
+// Hard cap on the change delay, whoever sets it, so the timelock can't freeze
+// the configuration it guards
+pub(crate) const MAX_CHANGE_DELAY: u64 = 30 * DAY_IN_SECONDS;
+
+/// A configuration change waiting out the change delay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingChange {
+    pub change: ConfigChange,
+    /// Ledger timestamp from which it can be executed
+    pub eta: u64,
+}
+
+#[contractimpl]
+impl TuxedoVault {
+    /// Set how long, in seconds, `queue_change` holds sensitive configuration
+    /// changes before `execute_change` may apply them (admin only, capped at
+    /// 30 days). Raising it applies at once; lowering it has to be queued.
+    pub fn set_change_delay(env: Env, delay: u64) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        if delay < Self::get_change_delay(env.clone()) {
+            return Err(VaultError::TooSoon);
+        }
+
+        Self::apply_change(&env, &ConfigChange::ChangeDelay(delay))
+    }
+
+    /// Queue a configuration change to be executed once the change delay has
+    /// passed (admin only). Returns its id.
+    pub fn queue_change(env: Env, change: ConfigChange) -> Result<u64, VaultError> {
+        Self::require_admin(&env)?;
+        Self::check_change(&env, &change)?;
+
+        let id: u64 = env.storage().instance().get(&CHANGE_COUNT).unwrap_or(0);
+        env.storage().instance().set(&CHANGE_COUNT, &(id + 1));
+
+        let eta = env.ledger().timestamp() + Self::get_change_delay(env.clone());
+        let key = Self::change_key(id);
+        env.storage().persistent().set(
+            &key,
+            &PendingChange {
+                change: change.clone(),
+                eta,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, SHARES_LIFETIME_THRESHOLD, SHARES_BUMP_AMOUNT);
+
+        // Emit change queued event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("chg_queue"),
+            (id, change, eta),
+        );
+
+        Ok(id)
+    }
+
+    /// Apply a queued configuration change whose delay has passed (admin only)
+    pub fn execute_change(env: Env, id: u64) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let pending = Self::get_pending_change(env.clone(), id)
+            .ok_or(VaultError::ChangeNotQueued)?;
+        let now = env.ledger().timestamp();
+        if now < pending.eta {
+            return Err(VaultError::TooSoon);
+        }
+
+        env.storage().persistent().remove(&Self::change_key(id));
+        Self::apply_change(&env, &pending.change)?;
+
+        // Emit change executed event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("chg_exec"),
+            (id, pending.change, now),
+        );
+
+        Ok(())
+    }
+
+    /// Drop a queued configuration change before it is executed (admin only)
+    pub fn cancel_change(env: Env, id: u64) -> Result<(), VaultError> {
+        Self::require_admin(&env)?;
+
+        let pending = Self::get_pending_change(env.clone(), id)
+            .ok_or(VaultError::ChangeNotQueued)?;
+        env.storage().persistent().remove(&Self::change_key(id));
+
+        // Emit change cancelled event
+        events::publish(
+            &env,
+            symbol_short!("vault"),
+            symbol_short!("chg_cncl"),
+            (id, pending.change, pending.eta),
+        );
+
+        Ok(())
+    }
+
+    /// Get the delay queued configuration changes wait out, 0 if changes
+    /// apply directly
+    pub fn get_change_delay(env: Env) -> u64 {
+        env.storage().instance().get(&CHANGE_DELAY).unwrap_or(0)
+    }
+
+    /// Get a queued configuration change that hasn't been executed or
+    /// cancelled yet
+    pub fn get_pending_change(env: Env, id: u64) -> Option<PendingChange> {
+        env.storage().persistent().get(&Self::change_key(id))
+    }
+}
+
+impl TuxedoVault {
+    /// Reject direct changes to timelocked configuration while a change delay
+    /// is set
+    pub(crate) fn require_no_change_delay(env: &Env) -> Result<(), VaultError> {
+        if Self::get_change_delay(env.clone()) > 0 {
+            return Err(VaultError::TooSoon);
+        }
+        Ok(())
+    }
+
+    /// Store a new change delay, applied through `apply_change`
+    pub(crate) fn store_change_delay(env: &Env, delay: u64) {
+        let old_delay = Self::get_change_delay(env.clone());
+        if delay == 0 {
+            env.storage().instance().remove(&CHANGE_DELAY);
+        } else {
+            env.storage().instance().set(&CHANGE_DELAY, &delay);
+        }
+
+        // Emit change delay event
+        events::publish(
+            env,
+            symbol_short!("vault"),
+            symbol_short!("chg_delay"),
+            (old_delay, delay),
+        );
+    }
+
+    fn change_key(id: u64) -> (Symbol, u64) {
+        (symbol_short!("change"), id)
+    }
+}
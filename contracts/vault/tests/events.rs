@@ -0,0 +1,1076 @@
+//! Event schema of the vault: each operation's full event stream, decoded
+//! field by field. The indexer depends on these shapes, so changing one
+//! means updating the expectation here.
+
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Events as _},
+    token::StellarAssetClient,
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, TryFromVal, Val, Vec,
+};
+use tuxedo_blend_adapter::RequestType;
+use tuxedo_fixtures::{events::EventLog, TuxedoFixture, UNIT};
+use tuxedo_share_token::{ShareToken, ShareTokenClient as Tux0Client};
+use tuxedo_testutils::{
+    blend::MockBlendPoolClient,
+    bridge::{MockBridge, MockBridgeClient},
+    dex::{MockRouter, MockRouterClient, RATE_SCALE},
+    oracle::{MockReflector, MockReflectorClient},
+};
+use tuxedo_vault::{
+    DepositEvent, EmissionsRoute, PauseState, Strategy, StrategyEvent, Swap, TuxedoVault,
+    TuxedoVaultClient, WithdrawEvent, YieldEvent, DEAD_SHARES,
+};
+#[cfg(feature = "strategy-timelock")]
+use tuxedo_vault::ConfigChange;
+
+/// Keeper escrow stand-in that accepts any task registration
+#[contract]
+struct StubKeeper;
+
+#[contractimpl]
+impl StubKeeper {
+    pub fn register_task(
+        _env: Env,
+        _contract: Address,
+        _task_id: Symbol,
+        _reward_per_call: i128,
+        _min_interval: u64,
+    ) {
+    }
+
+    pub fn record_execution(
+        _env: Env,
+        _contract: Address,
+        _task_id: Symbol,
+        _caller: Address,
+    ) -> i128 {
+        0
+    }
+}
+
+/// Events the vault published in the last invocation
+fn vault_log(t: &TuxedoFixture) -> EventLog {
+    EventLog::new(&t.env, &t.vault.address, symbol_short!("vault"))
+}
+
+/// Decode the only event the last invocation published
+fn single<D: TryFromVal<Env, Val>>(t: &TuxedoFixture, action: Symbol) -> D {
+    let mut log = vault_log(t);
+    let data = log.next(action);
+    log.finish();
+    data
+}
+
+/// Assert the last invocation published just the SEP-41 token event
+/// `topics` carrying `data`
+#[track_caller]
+fn assert_token_event(
+    t: &TuxedoFixture,
+    topics: impl IntoVal<Env, Vec<Val>>,
+    data: impl IntoVal<Env, Val>,
+) {
+    assert_eq!(
+        t.env.events().all(),
+        vec![
+            &t.env,
+            (t.vault.address.clone(), topics.into_val(&t.env), data.into_val(&t.env)),
+        ]
+    );
+}
+
+#[test]
+fn test_initialize_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, agent, platform) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let vault = TuxedoVaultClient::new(
+        &env,
+        &env.register(TuxedoVault, (&admin, &agent, &platform, usdc, None::<u32>)),
+    );
+
+    let mut log = EventLog::new(&env, &vault.address, symbol_short!("vault"));
+    assert_eq!(
+        log.next::<(Address, Address, Address)>(symbol_short!("init")),
+        (admin, agent, platform)
+    );
+    log.finish();
+}
+
+#[test]
+fn test_user_flow_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    let bob = Address::generate(&t.env);
+
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+    assert_eq!(
+        single::<DepositEvent>(&t, symbol_short!("deposit")),
+        DepositEvent {
+            user: alice.clone(),
+            amount: 100 * UNIT,
+            shares: 100 * UNIT - DEAD_SHARES,
+            share_value_after: UNIT,
+            total_assets_after: 100 * UNIT,
+            total_shares_after: 100 * UNIT,
+        }
+    );
+
+    // deposit_for credits the recipient, then names the payer
+    let payer = t.user_with_usdc(10 * UNIT);
+    t.vault.deposit_for(&payer, &bob, &t.usdc.address, &(10 * UNIT));
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<DepositEvent>(symbol_short!("deposit")),
+        DepositEvent {
+            user: bob.clone(),
+            amount: 10 * UNIT,
+            shares: 10 * UNIT,
+            share_value_after: UNIT,
+            total_assets_after: 110 * UNIT,
+            total_shares_after: 110 * UNIT,
+        }
+    );
+    assert_eq!(
+        log.next::<(Address, Address, i128, i128)>(symbol_short!("dep_for")),
+        (payer, bob.clone(), 10 * UNIT, 10 * UNIT)
+    );
+    log.finish();
+
+    // Share transfers publish the standard SEP-41 event
+    t.vault.transfer(&alice, &bob, &(30 * UNIT));
+    assert_token_event(
+        &t,
+        (symbol_short!("transfer"), alice.clone(), bob.clone()),
+        30 * UNIT,
+    );
+
+    t.vault.withdraw(&bob, &(40 * UNIT), &0);
+    assert_eq!(
+        single::<WithdrawEvent>(&t, symbol_short!("withdraw")),
+        WithdrawEvent {
+            user: bob.clone(),
+            shares: 40 * UNIT,
+            assets: 40 * UNIT,
+            penalty: 0,
+            shortfall: 0,
+            share_value_after: UNIT,
+            total_assets_after: 70 * UNIT,
+            total_shares_after: 70 * UNIT,
+        }
+    );
+
+    // Withdrawals to another wallet add the recipient after the usual event
+    let cold = Address::generate(&t.env);
+    t.vault.withdraw_to(&alice, &cold, &(5 * UNIT), &0);
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<WithdrawEvent>(symbol_short!("withdraw")),
+        WithdrawEvent {
+            user: alice.clone(),
+            shares: 5 * UNIT,
+            assets: 5 * UNIT,
+            penalty: 0,
+            shortfall: 0,
+            share_value_after: UNIT,
+            total_assets_after: 65 * UNIT,
+            total_shares_after: 65 * UNIT,
+        }
+    );
+    assert_eq!(
+        log.next::<(Address, Address, i128, i128)>(symbol_short!("wd_to")),
+        (alice.clone(), cold, 5 * UNIT, 5 * UNIT)
+    );
+    log.finish();
+
+    // A swept remainder is reported in the shares burned
+    let carol = t.user_with_usdc(10 * UNIT);
+    t.vault.deposit(&carol, &t.usdc.address, &(10 * UNIT), &0);
+    t.vault.set_dust_threshold(&UNIT);
+    assert_eq!(single::<(i128, i128)>(&t, symbol_short!("dust")), (0, UNIT));
+    t.vault.withdraw(&carol, &(10 * UNIT - 1), &0);
+    assert_eq!(
+        single::<WithdrawEvent>(&t, symbol_short!("withdraw")),
+        WithdrawEvent {
+            user: carol,
+            shares: 10 * UNIT,
+            assets: 10 * UNIT,
+            penalty: 0,
+            shortfall: 0,
+            share_value_after: UNIT,
+            total_assets_after: 65 * UNIT,
+            total_shares_after: 65 * UNIT,
+        }
+    );
+    t.vault.set_dust_threshold(&0);
+
+    // Exact-asset withdrawals report the same shape
+    t.vault.withdraw_assets(&alice, &(10 * UNIT));
+    assert_eq!(
+        single::<WithdrawEvent>(&t, symbol_short!("withdraw")),
+        WithdrawEvent {
+            user: alice.clone(),
+            shares: 10 * UNIT,
+            assets: 10 * UNIT,
+            penalty: 0,
+            shortfall: 0,
+            share_value_after: UNIT,
+            total_assets_after: 55 * UNIT,
+            total_shares_after: 55 * UNIT,
+        }
+    );
+
+    // So does redeeming everything at once
+    let shares = t.vault.get_user_shares(&alice);
+    t.vault.withdraw_all(&alice);
+    assert_eq!(
+        single::<WithdrawEvent>(&t, symbol_short!("withdraw")),
+        WithdrawEvent {
+            user: alice.clone(),
+            shares,
+            assets: shares,
+            penalty: 0,
+            shortfall: 0,
+            share_value_after: UNIT,
+            total_assets_after: DEAD_SHARES,
+            total_shares_after: DEAD_SHARES,
+        }
+    );
+
+    // Locked shares report the early-withdrawal penalty they left behind,
+    // which lifts the share value of the rest
+    t.vault.set_lockup(&86_400, &100);
+    t.vault.deposit(&alice, &t.usdc.address, &(60 * UNIT), &0);
+    t.vault.withdraw(&alice, &(50 * UNIT), &0);
+    let (assets, shares) = (105 * UNIT / 10 + DEAD_SHARES, 10 * UNIT + DEAD_SHARES);
+    assert_eq!(
+        single::<WithdrawEvent>(&t, symbol_short!("withdraw")),
+        WithdrawEvent {
+            user: alice.clone(),
+            shares: 50 * UNIT,
+            assets: 495 * UNIT / 10,
+            penalty: UNIT / 2,
+            shortfall: 0,
+            share_value_after: assets * UNIT / shares,
+            total_assets_after: assets,
+            total_shares_after: shares,
+        }
+    );
+    t.vault.set_lockup(&0, &0);
+
+    let insurer = t.user_with_usdc(5 * UNIT);
+    t.vault.recover_assets(&insurer, &(5 * UNIT));
+    assert_eq!(
+        single::<(Address, i128)>(&t, symbol_short!("recover")),
+        (insurer, 5 * UNIT)
+    );
+
+    // Recovered assets count as yield: 5 USDC earned, 0.1% to the keeper and
+    // 2% of the rest to the platform
+    t.vault.distribute_yield(&t.keeper);
+    let assets = assets + 5 * UNIT - 999 * UNIT / 10_000 - 5 * UNIT / 1_000;
+    assert_eq!(
+        single::<YieldEvent>(&t, symbol_short!("yield")),
+        YieldEvent {
+            yield_earned: 5 * UNIT,
+            platform_fee: 999 * UNIT / 10_000,
+            caller: t.keeper.clone(),
+            keeper_reward: 5 * UNIT / 1_000,
+            share_value_after: assets * UNIT / shares,
+            total_assets_after: assets,
+            total_shares_after: shares,
+        }
+    );
+}
+
+#[test]
+fn test_withdraw_shortfall_event() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+
+    // The pool writes off a tenth of the 50 USDC supplied and the admin
+    // recalls the rest, so shares pay out 95% of what was deposited
+    t.vault.set_reserve_bps(&0);
+    t.vault.agent_execute(&t.agent, &Strategy {
+        action: symbol_short!("supply"),
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount: 50 * UNIT,
+        request_type: RequestType::SupplyCollateral as u32,
+    });
+    t.simulate_loss(1_000);
+    t.vault.emergency_recall(&t.pool);
+    t.vault.withdraw(&alice, &(10 * UNIT), &0);
+    assert_eq!(
+        single::<WithdrawEvent>(&t, symbol_short!("withdraw")),
+        WithdrawEvent {
+            user: alice,
+            shares: 10 * UNIT,
+            assets: 95 * UNIT / 10,
+            penalty: 0,
+            shortfall: UNIT / 2,
+            share_value_after: 95 * UNIT / 100,
+            total_assets_after: 855 * UNIT / 10,
+            total_shares_after: 90 * UNIT,
+        }
+    );
+}
+
+#[cfg(feature = "withdrawal-queue")]
+#[test]
+fn test_withdraw_queue_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    let bob = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+    t.vault.deposit(&bob, &t.usdc.address, &(100 * UNIT), &0);
+
+    t.vault.request_withdraw(&alice, &(40 * UNIT));
+    assert_eq!(
+        single::<(Address, i128, i128)>(&t, symbol_short!("wd_req")),
+        (alice.clone(), 40 * UNIT, 40 * UNIT)
+    );
+
+    t.vault.request_withdraw(&bob, &(20 * UNIT));
+    t.vault.cancel_withdraw(&bob);
+    assert_eq!(
+        single::<(Address, i128)>(&t, symbol_short!("wd_cancel")),
+        (bob.clone(), 20 * UNIT)
+    );
+
+    // One event per request fulfilled
+    t.vault.request_withdraw(&bob, &(10 * UNIT));
+    t.vault.fulfill_withdrawals(&t.agent, &10);
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<(Address, i128, i128)>(symbol_short!("wd_fill")),
+        (alice.clone(), 40 * UNIT, 40 * UNIT)
+    );
+    assert_eq!(
+        log.next::<(Address, i128, i128)>(symbol_short!("wd_fill")),
+        (bob, 10 * UNIT, 10 * UNIT)
+    );
+    log.finish();
+
+    t.vault.claim_withdraw(&alice);
+    assert_eq!(
+        single::<(Address, i128)>(&t, symbol_short!("wd_claim")),
+        (alice, 40 * UNIT)
+    );
+}
+
+#[test]
+fn test_migrate_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let target = TuxedoVaultClient::new(
+        &t.env,
+        &t.env.register(
+            TuxedoVault,
+            (&t.admin, &t.agent, &t.platform, &t.usdc.address, None::<u32>),
+        ),
+    );
+    let alice = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+
+    t.vault.set_migration_target(&target.address);
+    assert_eq!(single::<Address>(&t, symbol_short!("mig_tgt")), target.address);
+
+    let shares = t.vault.migrate(&alice, &target.address);
+    let mut log = vault_log(&t);
+    // The USDC on its way to the target no longer counts as the vault's
+    let withdrawal = log.next::<WithdrawEvent>(symbol_short!("withdraw"));
+    assert_eq!(
+        (withdrawal.total_assets_after, withdrawal.total_shares_after),
+        (DEAD_SHARES, DEAD_SHARES)
+    );
+    assert_eq!(
+        log.next::<(Address, Address, i128, i128, i128)>(symbol_short!("migrate")),
+        (
+            alice,
+            target.address.clone(),
+            100 * UNIT - DEAD_SHARES,
+            100 * UNIT - DEAD_SHARES,
+            shares
+        )
+    );
+    log.finish();
+}
+
+#[test]
+fn test_operator_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    let manager = Address::generate(&t.env);
+    let payout = Address::generate(&t.env);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+
+    t.vault.approve_operator(&alice, &manager, &1_000);
+    assert_eq!(
+        single::<(Address, Address, u32)>(&t, symbol_short!("op_appr")),
+        (alice.clone(), manager.clone(), 1_000)
+    );
+
+    // Operator withdrawals add who acted after the usual event
+    t.vault.withdraw_from(&manager, &alice, &(5 * UNIT), &payout);
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<WithdrawEvent>(symbol_short!("withdraw")),
+        WithdrawEvent {
+            user: alice.clone(),
+            shares: 5 * UNIT,
+            assets: 5 * UNIT,
+            penalty: 0,
+            shortfall: 0,
+            share_value_after: UNIT,
+            total_assets_after: 95 * UNIT,
+            total_shares_after: 95 * UNIT,
+        }
+    );
+    assert_eq!(
+        log.next::<(Address, Address, Address, i128, i128)>(symbol_short!("wd_from")),
+        (manager.clone(), alice.clone(), payout, 5 * UNIT, 5 * UNIT)
+    );
+    log.finish();
+
+    t.vault.revoke_operator(&alice, &manager);
+    assert_eq!(
+        single::<(Address, Address)>(&t, symbol_short!("op_rvk")),
+        (alice, manager)
+    );
+}
+
+#[cfg(feature = "referrals")]
+#[test]
+fn test_referral_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let seed = t.user_with_usdc(100 * UNIT);
+    let alice = t.user_with_usdc(100 * UNIT);
+    let referrer = Address::generate(&t.env);
+    t.vault.deposit(&seed, &t.usdc.address, &(100 * UNIT), &0);
+
+    t.vault.set_referral_bps(&5_000);
+    assert_eq!(
+        single::<(u32, u32)>(&t, symbol_short!("ref_bps")),
+        (0, 5_000)
+    );
+
+    // The regular deposit event comes first, then the referral attribution
+    t.vault.deposit_with_referral(&alice, &t.usdc.address, &(100 * UNIT), &0, &referrer);
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<DepositEvent>(symbol_short!("deposit")),
+        DepositEvent {
+            user: alice.clone(),
+            amount: 100 * UNIT,
+            shares: 100 * UNIT,
+            share_value_after: UNIT,
+            total_assets_after: 200 * UNIT,
+            total_shares_after: 200 * UNIT,
+        }
+    );
+    assert_eq!(
+        log.next::<(Address, Address, i128, i128)>(symbol_short!("referred")),
+        (alice, referrer.clone(), 100 * UNIT, 100 * UNIT)
+    );
+    log.finish();
+
+    // 10 USDC earned on 200 in the vault: half the referred shares' part of
+    // half the platform fee is set aside, reported before the distribution
+    // itself, with the referral index per 10^14
+    t.simulate_yield(10 * UNIT);
+    t.vault.distribute_yield(&t.keeper);
+    let platform_fee = 1_998 * UNIT / 10_000;
+    let set_aside = platform_fee / 2 / 2;
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<(i128, i128)>(symbol_short!("ref_accr")),
+        (set_aside, set_aside * 100_000)
+    );
+    let assets = 210 * UNIT - platform_fee - UNIT / 100;
+    assert_eq!(
+        log.next::<YieldEvent>(symbol_short!("yield")),
+        YieldEvent {
+            yield_earned: 10 * UNIT,
+            platform_fee,
+            caller: t.keeper.clone(),
+            keeper_reward: UNIT / 100,
+            share_value_after: assets * UNIT / (200 * UNIT),
+            total_assets_after: assets,
+            total_shares_after: 200 * UNIT,
+        }
+    );
+    log.finish();
+
+    t.vault.claim_referral(&referrer);
+    assert_eq!(
+        single::<(Address, i128)>(&t, symbol_short!("ref_paid")),
+        (referrer, set_aside)
+    );
+}
+
+#[test]
+fn test_deposit_asset_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let xlm = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    let feed = MockReflectorClient::new(&t.env, &t.env.register(MockReflector, ()));
+    feed.set_price(&xlm, &10_000_000_000_000, &t.env.ledger().timestamp());
+
+    t.vault.add_deposit_asset(&xlm, &feed.address, &600);
+    assert_eq!(
+        single::<(Address, Address, u64)>(&t, symbol_short!("asset_add")),
+        (xlm.clone(), feed.address.clone(), 600)
+    );
+
+    // The deposit event reports the USDC value, then the asset deposited
+    let alice = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &xlm).mint(&alice, &(1_000 * UNIT));
+    t.vault.deposit(&alice, &xlm, &(1_000 * UNIT), &0);
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<DepositEvent>(symbol_short!("deposit")),
+        DepositEvent {
+            user: alice.clone(),
+            amount: 100 * UNIT,
+            shares: 100 * UNIT - DEAD_SHARES,
+            share_value_after: UNIT,
+            total_assets_after: 100 * UNIT,
+            total_shares_after: 100 * UNIT,
+        }
+    );
+    assert_eq!(
+        log.next::<(Address, Address, i128, i128)>(symbol_short!("dep_asset")),
+        (alice, xlm.clone(), 1_000 * UNIT, 100 * UNIT - DEAD_SHARES)
+    );
+    log.finish();
+
+    let eurc = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    t.vault.add_deposit_asset(&eurc, &feed.address, &600);
+    t.vault.remove_deposit_asset(&eurc);
+    assert_eq!(single::<Address>(&t, symbol_short!("asset_rm")), eurc);
+}
+
+#[test]
+fn test_share_token_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    let spender = Address::generate(&t.env);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+
+    // Shares publish the standard SEP-41 events rather than vault events
+    t.vault.approve(&alice, &spender, &(5 * UNIT), &1_000);
+    assert_token_event(
+        &t,
+        (symbol_short!("approve"), alice.clone(), spender.clone()),
+        (5 * UNIT, 1_000u32),
+    );
+
+    // Burns through an allowance report the owner
+    t.vault.burn_from(&spender, &alice, &(5 * UNIT));
+    assert_token_event(&t, (symbol_short!("burn"), alice.clone()), 5 * UNIT);
+    t.vault.burn(&alice, &UNIT);
+    assert_token_event(&t, (symbol_short!("burn"), alice), UNIT);
+}
+
+#[test]
+fn test_bridged_deposit_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let bridge = MockBridgeClient::new(&t.env, &t.env.register(MockBridge, ()));
+    let recipient = Address::generate(&t.env);
+    t.usdc_admin.mint(&bridge.address, &(40 * UNIT));
+    t.vault.set_bridge(&bridge.address, &true);
+
+    bridge.deliver(&t.usdc.address, &t.vault.address, &recipient, &(40 * UNIT));
+
+    // The regular deposit event comes first, then the bridge attribution
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<DepositEvent>(symbol_short!("deposit")),
+        DepositEvent {
+            user: recipient.clone(),
+            amount: 40 * UNIT,
+            shares: 40 * UNIT - DEAD_SHARES,
+            share_value_after: UNIT,
+            total_assets_after: 40 * UNIT,
+            total_shares_after: 40 * UNIT,
+        }
+    );
+    assert_eq!(
+        log.next::<(Address, Address, i128, i128)>(symbol_short!("bridged")),
+        (
+            bridge.address.clone(),
+            recipient,
+            40 * UNIT,
+            40 * UNIT - DEAD_SHARES
+        )
+    );
+    log.finish();
+}
+
+#[test]
+fn test_agent_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    t.vault.agent_execute(&t.agent, &Strategy {
+        action: symbol_short!("supply"),
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount: 100 * UNIT,
+        request_type: RequestType::SupplyCollateral as u32,
+    });
+    assert_eq!(
+        single::<StrategyEvent>(&t, symbol_short!("strategy")),
+        StrategyEvent {
+            agent: t.agent.clone(),
+            action: symbol_short!("supply"),
+            pool: t.pool.clone(),
+            amount: 100 * UNIT,
+            count: 1,
+            share_value_after: UNIT,
+            total_assets_after: 900 * UNIT,
+            total_shares_after: 1_000 * UNIT,
+        }
+    );
+
+    // A batch publishes one summary with the net amount sent to pools
+    t.vault.agent_execute_many(&t.agent, &vec![
+        &t.env,
+        Strategy {
+            action: symbol_short!("withdraw"),
+            pool: t.pool.clone(),
+            asset: t.usdc.address.clone(),
+            amount: 40 * UNIT,
+            request_type: RequestType::WithdrawCollateral as u32,
+        },
+        Strategy {
+            action: symbol_short!("supply"),
+            pool: t.pool.clone(),
+            asset: t.usdc.address.clone(),
+            amount: 10 * UNIT,
+            request_type: RequestType::SupplyCollateral as u32,
+        },
+    ]);
+    assert_eq!(
+        single::<(Address, u32, i128, u64)>(&t, symbol_short!("batch")),
+        (t.agent.clone(), 2, -30 * UNIT, 3)
+    );
+
+    // Reward and rotation swaps through a router paying 1:1
+    let router = t.env.register(MockRouter, ());
+    MockRouterClient::new(&t.env, &router).set_rate(&RATE_SCALE);
+    t.usdc_admin.mint(&router, &(100 * UNIT));
+    t.vault.set_swap_router(&router);
+    let blnd = t
+        .env
+        .register_stellar_asset_contract_v2(t.admin.clone())
+        .address();
+    StellarAssetClient::new(&t.env, &blnd).mint(&t.vault.address, &(20 * UNIT));
+    t.vault.set_swap_limit(&blnd, &(10 * UNIT));
+    let deadline = t.env.ledger().timestamp();
+
+    t.vault
+        .agent_swap_rewards(&t.agent, &blnd, &(10 * UNIT), &(10 * UNIT), &deadline);
+    assert_eq!(
+        single::<(Address, i128, i128)>(&t, symbol_short!("swap")),
+        (blnd.clone(), 10 * UNIT, 10 * UNIT)
+    );
+
+    t.vault.agent_execute_swap(&t.agent, &Swap {
+        token_in: blnd.clone(),
+        token_out: t.usdc.address.clone(),
+        amount_in: 10 * UNIT,
+        min_out: 10 * UNIT,
+        deadline,
+    });
+    assert_eq!(
+        single::<(Address, Address, i128, i128)>(&t, symbol_short!("rotate")),
+        (blnd, t.usdc.address.clone(), 10 * UNIT, 10 * UNIT)
+    );
+
+    // An emergency recall pauses deposits, then reports what came back
+    t.vault.emergency_recall(&t.pool);
+    let mut log = EventLog::new(&t.env, &t.vault.address, symbol_short!("vault"));
+    assert_eq!(
+        log.next::<(PauseState, PauseState)>(symbol_short!("pause")),
+        (PauseState::Active, PauseState::DepositsPaused)
+    );
+    assert_eq!(
+        log.next::<(Address, i128)>(symbol_short!("emrg")),
+        (t.pool.clone(), 70 * UNIT)
+    );
+    log.finish();
+}
+
+#[test]
+fn test_emissions_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let blnd = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    StellarAssetClient::new(&t.env, &blnd).mint(&t.pool, &(40 * UNIT));
+
+    t.vault.set_emissions_route(&blnd, &EmissionsRoute::Platform);
+    assert_eq!(
+        single::<(Address, EmissionsRoute)>(&t, symbol_short!("emit_rte")),
+        (blnd.clone(), EmissionsRoute::Platform)
+    );
+
+    MockBlendPoolClient::new(&t.env, &t.pool).set_emissions(&blnd, &(40 * UNIT));
+    t.vault.claim_emissions(&t.agent, &t.pool, &vec![&t.env, 1u32]);
+    assert_eq!(
+        single::<(Address, i128, EmissionsRoute)>(&t, symbol_short!("emissions")),
+        (t.pool.clone(), 40 * UNIT, EmissionsRoute::Platform)
+    );
+
+    t.usdc_admin.mint(&t.platform, &(10 * UNIT));
+    t.vault.donate_yield(&(10 * UNIT));
+    assert_eq!(
+        single::<(Address, i128)>(&t, symbol_short!("donate")),
+        (t.platform.clone(), 10 * UNIT)
+    );
+}
+
+#[test]
+fn test_stop_loss_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    t.vault.set_stop_loss(&1_000);
+    assert_eq!(single::<u32>(&t, symbol_short!("stop_loss")), 1_000);
+
+    // The pool writes off 40% of a 500 USDC position, a fifth of the vault,
+    // which shows once a strategy records it
+    let strategy = |action, request_type: RequestType, amount| Strategy {
+        action,
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount,
+        request_type: request_type as u32,
+    };
+    t.vault.agent_execute(
+        &t.agent,
+        &strategy(symbol_short!("supply"), RequestType::SupplyCollateral, 500 * UNIT),
+    );
+    assert!(!t.vault.check_stop_loss());
+    MockBlendPoolClient::new(&t.env, &t.pool).write_down(&4_000);
+    t.vault.agent_execute(
+        &t.agent,
+        &strategy(symbol_short!("withdraw"), RequestType::WithdrawCollateral, 100 * UNIT),
+    );
+    assert!(t.vault.check_stop_loss());
+    assert_eq!(
+        single::<(i128, i128, i128)>(&t, symbol_short!("stopped")),
+        (UNIT, 8 * UNIT / 10, 2_000)
+    );
+
+    t.vault.clear_stop_loss();
+    assert_eq!(single::<i128>(&t, symbol_short!("sl_clear")), 8 * UNIT / 10);
+}
+
+#[test]
+fn test_breaker_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    t.vault.set_breaker_bps(&800);
+    assert_eq!(single::<u32>(&t, symbol_short!("brk_bps")), 800);
+
+    let supply = |amount: i128| Strategy {
+        action: symbol_short!("supply"),
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount,
+        request_type: RequestType::SupplyCollateral as u32,
+    };
+    t.vault.agent_execute(&t.agent, &supply(500 * UNIT));
+
+    // The pool writes off a fifth of the position, 10% of the vault
+    MockBlendPoolClient::new(&t.env, &t.pool).write_down(&2_000);
+    t.vault.agent_execute(&t.agent, &supply(10 * UNIT));
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<(PauseState, PauseState)>(symbol_short!("pause")),
+        (PauseState::Active, PauseState::FullyPaused)
+    );
+    assert_eq!(
+        log.next::<(i128, i128, i128)>(symbol_short!("brk")),
+        (UNIT, 9 * UNIT / 10, 1_000)
+    );
+    log.next::<StrategyEvent>(symbol_short!("strategy"));
+    log.finish();
+
+    t.vault.clear_breaker();
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<(PauseState, PauseState)>(symbol_short!("pause")),
+        (PauseState::FullyPaused, PauseState::Active)
+    );
+    assert_eq!(log.next::<i128>(symbol_short!("brk_clr")), 9 * UNIT / 10);
+    log.finish();
+}
+
+#[test]
+fn test_admin_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    let asset = Address::generate(&t.env);
+
+    t.vault.set_platform_fee_bps(&300);
+    assert_eq!(single::<(u32, u32)>(&t, symbol_short!("fee")), (200, 300));
+
+    t.vault.set_keeper_reward_bps(&50);
+    assert_eq!(single::<(u32, u32)>(&t, symbol_short!("kpr_bps")), (10, 50));
+
+    t.vault.set_min_distribution_interval(&3_600);
+    assert_eq!(single::<u64>(&t, symbol_short!("dist_int")), 3_600);
+
+    t.vault.set_hwm_fees(&true);
+    assert!(single::<bool>(&t, symbol_short!("hwm_fees")));
+
+    t.vault.set_management_fee_bps(&100);
+    assert_eq!(single::<(u32, u32)>(&t, symbol_short!("mgmt_bps")), (0, 100));
+
+    t.vault.set_hurdle_bps(&100);
+    assert_eq!(single::<(u32, u32)>(&t, symbol_short!("hurdle")), (0, 100));
+
+    t.vault.set_deposit_fee_bps(&50);
+    assert_eq!(single::<(u32, u32)>(&t, symbol_short!("dep_fee")), (0, 50));
+
+    let sanctioned = Address::generate(&t.env);
+    t.vault.add_to_blacklist(&sanctioned);
+    assert_eq!(single::<Address>(&t, symbol_short!("bl_add")), sanctioned);
+    t.vault.remove_from_blacklist(&sanctioned);
+    assert_eq!(single::<Address>(&t, symbol_short!("bl_rm")), sanctioned);
+
+    t.vault.set_whitelist_mode(&true);
+    assert!(single::<bool>(&t, symbol_short!("wl_mode")));
+    t.vault.set_whitelist_mode(&false);
+    let testers = vec![&t.env, sanctioned.clone()];
+    t.vault.add_to_whitelist(&testers);
+    assert_eq!(single::<Vec<Address>>(&t, symbol_short!("wl_add")), testers);
+    t.vault.remove_from_whitelist(&testers);
+    assert_eq!(single::<Vec<Address>>(&t, symbol_short!("wl_rm")), testers);
+
+    t.vault.set_lockup(&3_600, &200);
+    assert_eq!(single::<(u64, u32)>(&t, symbol_short!("lockup")), (3_600, 200));
+    t.vault.set_lockup(&0, &0);
+
+    t.vault.set_tux_token(&asset);
+    assert_eq!(single::<Address>(&t, symbol_short!("tux_token")), asset.clone());
+
+    // USDC sent straight to the vault, set aside as a donation and returned
+    let sender = t.user_with_usdc(5 * UNIT);
+    t.usdc.transfer(&sender, &t.vault.address, &(5 * UNIT));
+    t.vault.sync(&false);
+    assert_eq!(
+        single::<(i128, bool)>(&t, symbol_short!("sync")),
+        (5 * UNIT, false)
+    );
+    t.vault.rescue_excess(&sender);
+    assert_eq!(
+        single::<(Address, i128)>(&t, symbol_short!("rescue")),
+        (sender.clone(), 5 * UNIT)
+    );
+
+    // Other tokens sent by mistake name the token
+    t.tux.transfer(&t.admin, &t.vault.address, &UNIT);
+    t.vault.rescue_tokens(&t.tux.address, &sender, &UNIT);
+    assert_eq!(
+        single::<(Address, Address, i128)>(&t, symbol_short!("rescue_tk")),
+        (t.tux.address.clone(), sender, UNIT)
+    );
+    t.simulate_yield(UNIT);
+    t.vault.sync(&true);
+    assert_eq!(
+        single::<(i128, bool)>(&t, symbol_short!("sync")),
+        (UNIT, true)
+    );
+
+    let router = Address::generate(&t.env);
+    t.vault.set_swap_router(&router);
+    assert_eq!(single::<Address>(&t, symbol_short!("router")), router);
+
+    t.vault.set_supported_asset(&asset, &true);
+    assert_eq!(
+        single::<(Address, bool)>(&t, symbol_short!("asset")),
+        (asset.clone(), true)
+    );
+
+    let bridge = Address::generate(&t.env);
+    t.vault.set_bridge(&bridge, &true);
+    assert_eq!(
+        single::<(Address, bool)>(&t, symbol_short!("bridge")),
+        (bridge, true)
+    );
+
+    let pool = Address::generate(&t.env);
+    t.vault.add_approved_pool(&pool);
+    assert_eq!(single::<Address>(&t, symbol_short!("pool_add")), pool);
+    t.vault.assign_pool(&t.agent, &pool);
+    assert_eq!(
+        single::<(Address, Address)>(&t, symbol_short!("pool_asgn")),
+        (t.agent.clone(), pool.clone())
+    );
+    t.vault.unassign_pool(&t.agent, &pool);
+    assert_eq!(
+        single::<(Address, Address)>(&t, symbol_short!("pool_unas")),
+        (t.agent.clone(), pool.clone())
+    );
+    t.vault.remove_approved_pool(&pool);
+    assert_eq!(single::<Address>(&t, symbol_short!("pool_rm")), pool);
+
+    t.vault.set_swap_limit(&asset, &(50 * UNIT));
+    assert_eq!(
+        single::<(Address, i128)>(&t, symbol_short!("swap_lim")),
+        (asset.clone(), 50 * UNIT)
+    );
+
+    t.vault.set_deposit_cap(&(50_000 * UNIT));
+    assert_eq!(single::<i128>(&t, symbol_short!("dep_cap")), 50_000 * UNIT);
+
+    t.vault.set_user_deposit_limit(&(5_000 * UNIT));
+    assert_eq!(single::<i128>(&t, symbol_short!("user_lim")), 5_000 * UNIT);
+
+    t.vault.set_min_deposit(&(2 * UNIT));
+    assert_eq!(single::<i128>(&t, symbol_short!("min_dep")), 2 * UNIT);
+
+    t.vault.set_reserve_bps(&1_000);
+    assert_eq!(single::<u32>(&t, symbol_short!("reserve")), 1_000);
+
+    t.vault.set_strategy_limits(&3_600, &(500 * UNIT));
+    assert_eq!(
+        single::<(u64, i128)>(&t, symbol_short!("strat_lim")),
+        (3_600, 500 * UNIT)
+    );
+
+    let feed = Address::generate(&t.env);
+    t.vault.set_oracle(&feed, &600);
+    assert_eq!(
+        single::<(Address, u64)>(&t, symbol_short!("oracle")),
+        (feed, 600)
+    );
+
+    t.vault.set_fallback_price(&asset, &1_200_000, &7);
+    assert_eq!(
+        single::<(Address, i128, u32)>(&t, symbol_short!("fb_price")),
+        (asset, 1_200_000, 7)
+    );
+
+    let key = BytesN::from_array(&t.env, &[7u8; 32]);
+    t.vault.set_policy_key(&key);
+    assert_eq!(single::<BytesN<32>>(&t, symbol_short!("policy")), key);
+
+    let escrow = t.env.register(StubKeeper, ());
+    t.vault.set_keeper(&escrow, &UNIT, &3_600);
+    assert_eq!(
+        single::<(Address, i128, u64)>(&t, symbol_short!("keeper")),
+        (escrow, UNIT, 3_600)
+    );
+
+    let name = String::from_str(&t.env, "Tuxedo Vault Share");
+    let symbol = String::from_str(&t.env, "TUX0");
+    let tux0 = Tux0Client::new(
+        &t.env,
+        &t.env.register(ShareToken, (t.vault.address.clone(), t.usdc.decimals(), name, symbol)),
+    );
+    t.vault.set_share_token(&tux0.address);
+    assert_eq!(single::<Address>(&t, symbol_short!("tux0")), tux0.address);
+
+    // Pause transitions carry the old and new state
+    t.vault.pause(&true);
+    assert_eq!(
+        single::<(PauseState, PauseState)>(&t, symbol_short!("pause")),
+        (PauseState::Active, PauseState::DepositsPaused)
+    );
+    t.vault.pause(&false);
+    assert_eq!(
+        single::<(PauseState, PauseState)>(&t, symbol_short!("pause")),
+        (PauseState::DepositsPaused, PauseState::FullyPaused)
+    );
+    t.vault.unpause();
+    assert_eq!(
+        single::<(PauseState, PauseState)>(&t, symbol_short!("pause")),
+        (PauseState::FullyPaused, PauseState::Active)
+    );
+
+    // Management fee accrual carries the platform and the shares minted
+    let holder = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&holder, &t.usdc.address, &(100 * UNIT), &0);
+    t.advance_time(86_400);
+    let fee_shares = t.vault.accrue_fees();
+    assert_eq!(
+        single::<(Address, i128)>(&t, symbol_short!("mgmt_fee")),
+        (t.platform.clone(), fee_shares)
+    );
+
+    // Role rotations carry the old and new holder
+    let (admin, agent, platform) = (
+        Address::generate(&t.env),
+        Address::generate(&t.env),
+        Address::generate(&t.env),
+    );
+    t.vault.add_agent(&agent);
+    assert_eq!(single::<Address>(&t, symbol_short!("agent_add")), agent.clone());
+    t.vault.remove_agent(&agent);
+    assert_eq!(single::<Address>(&t, symbol_short!("agent_rm")), agent.clone());
+    t.vault.set_agent(&agent);
+    assert_eq!(
+        single::<(Address, Address)>(&t, symbol_short!("agent")),
+        (t.agent.clone(), agent)
+    );
+    t.vault.set_platform(&platform);
+    assert_eq!(
+        single::<(Address, Address)>(&t, symbol_short!("platform")),
+        (t.platform.clone(), platform)
+    );
+    t.vault.set_admin(&admin);
+    assert_eq!(
+        single::<(Address, Address)>(&t, symbol_short!("admin")),
+        (t.admin.clone(), admin)
+    );
+}
+
+#[cfg(feature = "strategy-timelock")]
+#[test]
+fn test_change_timelock_events() {
+    let t = TuxedoFixture::new(&Env::default());
+    t.vault.set_change_delay(&86_400);
+    assert_eq!(single::<(u64, u64)>(&t, symbol_short!("chg_delay")), (0, 86_400));
+
+    let now = t.env.ledger().timestamp();
+    let platform = Address::generate(&t.env);
+    let change = ConfigChange::Platform(platform.clone());
+    let id = t.vault.queue_change(&change);
+    assert_eq!(
+        single::<(u64, ConfigChange, u64)>(&t, symbol_short!("chg_queue")),
+        (id, change.clone(), now + 86_400)
+    );
+
+    // Executing emits the setter's own event, then the effective timestamp
+    t.advance_time(86_400);
+    t.vault.execute_change(&id);
+    let mut log = vault_log(&t);
+    assert_eq!(
+        log.next::<(Address, Address)>(symbol_short!("platform")),
+        (t.platform.clone(), platform)
+    );
+    assert_eq!(
+        log.next::<(u64, ConfigChange, u64)>(symbol_short!("chg_exec")),
+        (id, change, now + 86_400)
+    );
+    log.finish();
+
+    let change = ConfigChange::ReserveRatio(1_000);
+    let id = t.vault.queue_change(&change);
+    t.vault.cancel_change(&id);
+    assert_eq!(
+        single::<(u64, ConfigChange, u64)>(&t, symbol_short!("chg_cncl")),
+        (id, change, now + 2 * 86_400)
+    );
+}
@@ -0,0 +1,203 @@
+//! Property tests for the vault's fixed-point share accounting.
+//!
+//! Random sequences of deposits, withdrawals, yield injections and
+//! distributions run against a fresh vault, checking the share invariants
+//! after every step and that the previews match what each call returns, and
+//! that a deposit withdrawn straight away loses no more than rounding forces.
+//! Failing sequences are shrunk by proptest and persisted under
+//! `proptest-regressions/`; copy a minimal one into `vault.rs` as a regular
+//! regression test once it is understood.
+
+use proptest::prelude::*;
+use soroban_sdk::Env;
+use tuxedo_fixtures::{TuxedoFixture, UNIT};
+use tuxedo_vault::VaultError;
+
+const USERS: usize = 3;
+const SCALAR_7: i128 = 10_000_000;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Deposit {
+        user: usize,
+        amount: i128,
+    },
+    /// Withdraw `bps` of the user's shares
+    Withdraw {
+        user: usize,
+        bps: i128,
+    },
+    Yield {
+        amount: i128,
+    },
+    Distribute,
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (0..USERS, 1..=1_000_000 * UNIT).prop_map(|(user, amount)| Op::Deposit { user, amount }),
+        (0..USERS, 1..=10_000i128).prop_map(|(user, bps)| Op::Withdraw { user, bps }),
+        (1..=10_000 * UNIT).prop_map(|amount| Op::Yield { amount }),
+        Just(Op::Distribute),
+    ]
+}
+
+/// What each user is owed: deposits plus their pro-rata yield, net of fees and
+/// withdrawals. Yield rounds in the users' favour so the model never
+/// under-promises.
+struct Model {
+    entitled: [i128; USERS],
+    /// One stroop of rounding allowance per operation so far
+    tolerance: i128,
+}
+
+fn run(ops: Vec<Op>) -> Result<(), TestCaseError> {
+    let t = TuxedoFixture::new(&Env::default());
+    let mut model = Model {
+        entitled: [0; USERS],
+        tolerance: 0,
+    };
+
+    for op in ops {
+        model.tolerance += 1;
+        let shares: [i128; USERS] = core::array::from_fn(|i| t.vault.get_user_shares(&t.users[i]));
+        let total_shares = t.vault.get_total_shares();
+
+        match op {
+            Op::Deposit { user, amount } => {
+                t.usdc_admin.mint(&t.users[user], &amount);
+                let preview = t.vault.preview_deposit(&amount);
+                match t.vault.try_deposit(&t.users[user], &t.usdc.address, &amount, &0) {
+                    Ok(Ok(minted)) => {
+                        prop_assert_eq!(minted, preview);
+                        model.entitled[user] += amount;
+                    }
+                    // Below the minimum deposit, or too small to mint a single share
+                    Err(Ok(VaultError::InvalidAmount)) => prop_assert_eq!(preview, 0),
+                    other => prop_assert!(false, "deposit failed: {:?}", other),
+                }
+            }
+            Op::Withdraw { user, bps } => {
+                let burn = shares[user] * bps / 10_000;
+                if burn == 0 {
+                    continue;
+                }
+                let preview = t.vault.preview_withdraw(&burn);
+                match t.vault.try_withdraw(&t.users[user], &burn, &0) {
+                    Ok(Ok(received)) => {
+                        prop_assert_eq!(received, preview);
+                        prop_assert!(
+                            received <= model.entitled[user] + model.tolerance,
+                            "user {} received {} but is owed {}",
+                            user,
+                            received,
+                            model.entitled[user]
+                        );
+                        model.entitled[user] -= received;
+                    }
+                    // Dust worth less than a stroop
+                    Err(Ok(VaultError::InvalidAmount)) => prop_assert_eq!(preview, 0),
+                    other => prop_assert!(false, "withdraw failed: {:?}", other),
+                }
+            }
+            Op::Yield { amount } => {
+                // Yield with no shareholders would be captured by the next depositor
+                if total_shares == 0 {
+                    continue;
+                }
+                t.simulate_yield(amount);
+                for (entitled, user_shares) in model.entitled.iter_mut().zip(shares) {
+                    *entitled += (amount * user_shares + total_shares - 1) / total_shares;
+                }
+            }
+            Op::Distribute => {
+                let before = t.usdc.balance(&t.platform);
+                match t.vault.try_distribute_yield(&t.keeper) {
+                    Ok(_) => {
+                        let fee = t.usdc.balance(&t.platform) - before;
+                        for (entitled, user_shares) in model.entitled.iter_mut().zip(shares) {
+                            *entitled -= fee * user_shares / total_shares;
+                        }
+                    }
+                    Err(Ok(VaultError::NoYieldToDistribute)) => {}
+                    Err(e) => prop_assert!(false, "distribution failed: {:?}", e),
+                }
+            }
+        }
+
+        // Every share is owned by someone, the dead shares by the vault itself,
+        // and the token view agrees
+        let shares: [i128; USERS] = core::array::from_fn(|i| t.vault.get_user_shares(&t.users[i]));
+        for (user, user_shares) in t.users.iter().zip(shares) {
+            prop_assert_eq!(t.vault.balance(user), user_shares);
+        }
+        prop_assert_eq!(
+            shares.iter().sum::<i128>() + t.vault.get_user_shares(&t.vault.address),
+            t.vault.get_total_shares()
+        );
+
+        // The vault can always pay out every position in full
+        let balance = t.usdc.balance(&t.vault.address);
+        let share_value = t.vault.get_share_value();
+        let owed: i128 = shares.iter().map(|s| s * share_value / SCALAR_7).sum();
+        prop_assert!(balance >= 0);
+        prop_assert!(
+            owed <= balance,
+            "positions worth {} exceed balance {}",
+            owed,
+            balance
+        );
+    }
+
+    Ok(())
+}
+
+/// Deposit `amount` into a vault holding `deposits` plus `yield_amount`, then
+/// withdraw everything straight away
+fn round_trip(deposits: Vec<i128>, yield_amount: i128, amount: i128) -> Result<(), TestCaseError> {
+    let t = TuxedoFixture::new(&Env::default());
+    for deposit in deposits {
+        let holder = t.user_with_usdc(deposit);
+        t.vault.deposit(&holder, &t.usdc.address, &deposit, &0);
+    }
+    t.simulate_yield(yield_amount);
+    let total_assets = t.vault.get_total_assets();
+    let total_shares = t.vault.get_total_shares();
+
+    let user = t.user_with_usdc(amount);
+    let shares = t.vault.deposit(&user, &t.usdc.address, &amount, &0);
+    let received = t.vault.withdraw_all(&user);
+
+    // The shares pay out at least what they were worth when minted, so the
+    // only loss is the fraction of a share the deposit couldn't buy: at most
+    // a stroop while a share is worth no more than one
+    prop_assert!(received >= shares * total_assets / total_shares);
+    let one_share = (total_assets + total_shares - 1) / total_shares;
+    prop_assert!(
+        amount - received <= one_share,
+        "deposited {} but withdrew {} at {} assets for {} shares",
+        amount,
+        received,
+        total_assets,
+        total_shares
+    );
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn share_math_invariants_hold(ops in prop::collection::vec(op(), 1..24)) {
+        run(ops)?;
+    }
+
+    #[test]
+    fn deposit_then_withdraw_loses_at_most_a_share(
+        deposits in prop::collection::vec(10 * UNIT..=1_000_000 * UNIT, 1..4),
+        yield_amount in 1..=100_000 * UNIT,
+        amount in UNIT..=1_000_000 * UNIT,
+    ) {
+        round_trip(deposits, yield_amount, amount)?;
+    }
+}
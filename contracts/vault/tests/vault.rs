@@ -0,0 +1,3447 @@
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, Ledger as _, MockAuth, MockAuthInvoke,
+    },
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol,
+};
+use tuxedo_fixtures::{
+    scenario::{half, pct, Scenario},
+    TuxedoFixture, UNIT,
+};
+use tuxedo_share_token::{ShareToken, ShareTokenClient as Tux0Client};
+use tuxedo_blend_adapter::{PoolClient, RequestType};
+use tuxedo_testutils::{
+    blend::{MockBlendPool, MockBlendPoolClient},
+    bridge::{MockBridge, MockBridgeClient},
+    dex::{MockRouter, MockRouterClient, RATE_SCALE},
+    oracle::{MockReflector, MockReflectorClient},
+    tiers::{MockTierOracle, MockTierOracleClient},
+    token::{MockFeeToken, MockFeeTokenClient},
+};
+use tuxedo_vault::{
+    DepositAsset, EmissionsRoute, PauseState, Strategy, Swap, TuxedoVault, TuxedoVaultClient,
+    VaultError, DEAD_SHARES, STRATEGY_HISTORY_LEN,
+};
+#[cfg(feature = "history")]
+use {
+    soroban_sdk::xdr::ToXdr,
+    tuxedo_vault::{DepositRecord, HISTORY_LEN},
+};
+#[cfg(feature = "strategy-timelock")]
+use tuxedo_vault::{ConfigChange, PendingChange};
+#[cfg(feature = "referrals")]
+use tuxedo_vault::{Referral, ReferralStats};
+
+#[test]
+fn test_initialize() {
+    let t = TuxedoFixture::new(&Env::default());
+
+    assert_eq!(t.vault.get_admin(), t.admin);
+    assert_eq!(t.vault.get_agent(), t.agent);
+    assert_eq!(t.vault.get_platform(), t.platform);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")] // AlreadyInitialized
+fn test_double_initialize() {
+    let t = TuxedoFixture::new(&Env::default());
+
+    t.vault
+        .initialize(&t.admin, &t.agent, &t.platform, &t.usdc.address, &None); // Should panic
+}
+
+#[test]
+fn test_share_value_calculation() {
+    let t = TuxedoFixture::new(&Env::default());
+
+    // Initial share value should be 1.0 (10^7)
+    let share_value = t.vault.get_share_value();
+    assert_eq!(share_value, UNIT);
+}
+
+#[test]
+fn test_agent_supply_and_withdraw_through_pool() {
+    let t = TuxedoFixture::new(&Env::default());
+    let pool = t.pool.clone();
+    let pool_client = PoolClient::new(&t.env, &pool);
+
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    // Supply 600 USDC to the pool as collateral
+    t.vault.agent_execute(&t.agent, &Strategy {
+        action: symbol_short!("supply"),
+        pool: pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount: 600 * UNIT,
+        request_type: RequestType::SupplyCollateral as u32,
+    });
+
+    assert_eq!(t.usdc.balance(&pool), 600 * UNIT);
+    assert_eq!(t.usdc.balance(&t.vault.address), 400 * UNIT);
+    let positions = pool_client.get_positions(&t.vault.address);
+    assert_eq!(positions.collateral.get(0), Some(600 * UNIT));
+
+    // Withdraw 250 USDC back into the vault
+    t.vault.agent_execute(&t.agent, &Strategy {
+        action: symbol_short!("withdraw"),
+        pool: pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount: 250 * UNIT,
+        request_type: RequestType::WithdrawCollateral as u32,
+    });
+
+    assert_eq!(t.usdc.balance(&pool), 350 * UNIT);
+    assert_eq!(t.usdc.balance(&t.vault.address), 650 * UNIT);
+    let positions = pool_client.get_positions(&t.vault.address);
+    assert_eq!(positions.collateral.get(0), Some(350 * UNIT));
+}
+
+#[test]
+fn test_agent_withdraws_accrued_interest_as_yield() {
+    Scenario::new()
+        .deposit("user", 1_000 * UNIT)
+        .agent_supply(pct(60))
+        // 5% interest on the supplied collateral, funded into the pool
+        .accrue_yield(pct(5))
+        .expect_pool_position(630 * UNIT)
+        .agent_withdraw(pct(100))
+        .expect_total_assets(1_030 * UNIT)
+        // The interest is yield: the keeper takes 0.1% and the platform 2% of
+        // the rest
+        .distribute()
+        .expect_platform_fees(5_994 * UNIT / 10_000);
+}
+
+#[test]
+fn test_distribute_counts_deployed_assets() {
+    Scenario::new()
+        .deposit("user", 1_000 * UNIT)
+        .agent_supply(pct(60))
+        // Yield lands in the vault while most of the deposit is still lent out
+        .simulate_yield(30 * UNIT)
+        .expect_total_assets(430 * UNIT)
+        .distribute()
+        .expect_platform_fees(5_994 * UNIT / 10_000)
+        .expect_pool_position(600 * UNIT);
+}
+
+#[test]
+fn test_agent_execute_pool_failure_reverts() {
+    let t = TuxedoFixture::new(&Env::default());
+    let pool = MockBlendPoolClient::new(&t.env, &t.pool);
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    pool.set_fail_next(&true);
+    assert!(t
+        .vault
+        .try_agent_execute(&t.agent, &supply_strategy(&t, 600 * UNIT))
+        .is_err());
+    assert_eq!(t.usdc.balance(&t.vault.address), 1_000 * UNIT);
+    assert_eq!(t.usdc.balance(&t.pool), 0);
+
+    pool.set_fail_next(&false);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 600 * UNIT));
+    assert_eq!(t.usdc.balance(&t.pool), 600 * UNIT);
+}
+
+#[test]
+fn test_agent_execute_reports_pool_amount() {
+    let t = TuxedoFixture::new(&Env::default());
+    let pool_client = PoolClient::new(&t.env, &t.pool);
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    let strategy = |action, request_type: RequestType, amount| Strategy {
+        action,
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount,
+        request_type: request_type as u32,
+    };
+
+    // Plain supply, not collateral
+    let supplied = t.vault.agent_execute(
+        &t.agent,
+        &strategy(symbol_short!("supply"), RequestType::Supply, 600 * UNIT),
+    );
+    assert_eq!(supplied, 600 * UNIT);
+    let positions = pool_client.get_positions(&t.vault.address);
+    assert_eq!(positions.supply.get(0), Some(600 * UNIT));
+
+    // The pool caps a withdrawal at the position
+    let withdrawn = t.vault.agent_execute(&t.agent, &strategy(
+        symbol_short!("withdraw"),
+        RequestType::Withdraw,
+        1_000 * UNIT,
+    ));
+    assert_eq!(withdrawn, 600 * UNIT);
+    assert_eq!(t.usdc.balance(&t.vault.address), 1_000 * UNIT);
+
+    // The request type has to match the action
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &strategy(
+            symbol_short!("supply"),
+            RequestType::Withdraw,
+            UNIT
+        )),
+        Err(Ok(VaultError::InvalidRequestType))
+    );
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &strategy(
+            symbol_short!("withdraw"),
+            RequestType::Borrow,
+            UNIT
+        )),
+        Err(Ok(VaultError::InvalidRequestType))
+    );
+}
+
+#[test]
+fn test_agent_strategy_needs_approved_pool_and_usdc() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    assert!(t.vault.is_pool_approved(&t.pool));
+
+    // Any other address is refused, even a working Blend pool
+    let rogue_pool = t.env.register(MockBlendPool, ());
+    assert!(!t.vault.is_pool_approved(&rogue_pool));
+    let mut strategy = supply_strategy(&t, 600 * UNIT);
+    strategy.pool = rogue_pool.clone();
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &strategy),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+
+    // Only the vault's own USDC can be moved
+    let mut strategy = supply_strategy(&t, 600 * UNIT);
+    strategy.asset = t
+        .env
+        .register_stellar_asset_contract_v2(t.admin.clone())
+        .address();
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &strategy),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+    strategy.asset = Address::generate(&t.env);
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &strategy),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+    assert_eq!(t.usdc.balance(&t.vault.address), 1_000 * UNIT);
+
+    // Approval can be granted and revoked
+    t.vault.add_approved_pool(&rogue_pool);
+    t.vault.assign_pool(&t.agent, &rogue_pool);
+    let mut strategy = supply_strategy(&t, 600 * UNIT);
+    strategy.pool = rogue_pool.clone();
+    t.vault.agent_execute(&t.agent, &strategy);
+    assert_eq!(t.usdc.balance(&rogue_pool), 600 * UNIT);
+
+    t.vault.remove_approved_pool(&t.pool);
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &supply_strategy(&t, 100 * UNIT)),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+}
+
+#[test]
+fn test_agent_keeps_reserve_idle() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    assert_eq!(t.vault.get_reserve_ratio(), 2_000);
+
+    // 20% of 1,000 USDC stays idle: 800 can go out, not a stroop more
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &supply_strategy(&t, 800 * UNIT + 1)),
+        Err(Ok(VaultError::ReserveBreached))
+    );
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 800 * UNIT));
+    let stats = t.vault.get_vault_stats();
+    assert_eq!((stats.total_assets, stats.deployed_assets), (1_000 * UNIT, 800 * UNIT));
+
+    // Deployed funds still count towards the reserve base
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &supply_strategy(&t, 1)),
+        Err(Ok(VaultError::ReserveBreached))
+    );
+
+    // Withdrawals are never held back; the position tracks what the pool reports
+    t.vault.agent_execute(&t.agent, &Strategy {
+        action: symbol_short!("withdraw"),
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount: 300 * UNIT,
+        request_type: RequestType::WithdrawCollateral as u32,
+    });
+    assert_eq!(t.vault.get_deployed_assets(), 500 * UNIT);
+
+    assert_eq!(
+        t.vault.try_set_reserve_bps(&10_001),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.set_reserve_bps(&5_000);
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &supply_strategy(&t, 1)),
+        Err(Ok(VaultError::ReserveBreached))
+    );
+    t.vault.set_reserve_bps(&0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 500 * UNIT));
+    assert_eq!(t.usdc.balance(&t.vault.address), 0);
+}
+
+#[test]
+fn test_agent_strategy_rate_limits() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(2_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(2_000 * UNIT), &0);
+    assert_eq!(
+        t.vault.try_set_strategy_limits(&3_600, &-1),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.set_strategy_limits(&3_600, &(500 * UNIT));
+    assert_eq!(t.vault.get_strategy_limits(), (3_600, 500 * UNIT));
+
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 300 * UNIT));
+    assert_eq!(t.vault.get_strategy_volume(), 300 * UNIT);
+
+    // Too soon after the last strategy
+    t.advance_time(3_599);
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &supply_strategy(&t, 100 * UNIT)),
+        Err(Ok(VaultError::RateLimited))
+    );
+
+    // Withdrawals count towards the daily cap as well
+    t.advance_time(1);
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &Strategy {
+            action: symbol_short!("withdraw"),
+            pool: t.pool.clone(),
+            asset: t.usdc.address.clone(),
+            amount: 200 * UNIT + 1,
+            request_type: RequestType::WithdrawCollateral as u32,
+        }),
+        Err(Ok(VaultError::RateLimited))
+    );
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 200 * UNIT));
+    assert_eq!(t.vault.get_strategy_volume(), 500 * UNIT);
+
+    // The cap resets a day after the window opened
+    t.advance_time(24 * 60 * 60 - 3_600);
+    assert_eq!(t.vault.get_strategy_volume(), 0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 500 * UNIT));
+    assert_eq!(t.usdc.balance(&t.pool), 1_000 * UNIT);
+}
+
+#[test]
+fn test_simulate_strategy_matches_execution() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.set_strategy_limits(&3_600, &(1_000 * UNIT));
+
+    // An allowed supply: the preview is what executing it then reports
+    let supply = supply_strategy(&t, 600 * UNIT);
+    let preview = t.vault.simulate_strategy(&t.agent, &supply);
+    assert!(preview.allowed);
+    assert_eq!(preview.error, None);
+    assert_eq!(t.vault.get_strategy_volume(), 0);
+    assert_eq!(t.usdc.balance(&t.pool), 0);
+    assert_eq!(t.vault.agent_execute(&t.agent, &supply), preview.amount);
+    assert_eq!(preview.idle_after, t.vault.get_total_assets());
+    assert_eq!(preview.deployed_after, t.vault.get_deployed_assets());
+    assert_eq!(preview.share_value_after, t.vault.get_share_value());
+
+    // Failing checks report the error executing would return, and no change
+    let rejected = t.vault.simulate_strategy(&t.agent, &supply_strategy(&t, 100 * UNIT));
+    assert_eq!(rejected.error, Some(VaultError::RateLimited as u32));
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &supply_strategy(&t, 100 * UNIT)),
+        Err(Ok(VaultError::RateLimited))
+    );
+    assert!(!rejected.allowed);
+    assert_eq!(
+        (rejected.amount, rejected.idle_after, rejected.deployed_after),
+        (0, 400 * UNIT, 600 * UNIT)
+    );
+    assert_eq!(rejected.share_value_after, t.vault.get_share_value());
+
+    t.advance_time(3_600);
+    let breach = supply_strategy(&t, 200 * UNIT + 1);
+    assert_eq!(
+        t.vault.simulate_strategy(&t.agent, &breach).error,
+        Some(VaultError::ReserveBreached as u32)
+    );
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &breach),
+        Err(Ok(VaultError::ReserveBreached))
+    );
+    let stranger = Address::generate(&t.env);
+    assert_eq!(
+        t.vault.simulate_strategy(&stranger, &supply).error,
+        Some(VaultError::NotAuthorized as u32)
+    );
+
+    // A withdrawal past the position moves only what the pool holds
+    t.advance_time(24 * 60 * 60);
+    let withdraw = Strategy {
+        action: symbol_short!("withdraw"),
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount: 700 * UNIT,
+        request_type: RequestType::WithdrawCollateral as u32,
+    };
+    let preview = t.vault.simulate_strategy(&t.agent, &withdraw);
+    assert!(preview.allowed);
+    assert_eq!(t.vault.agent_execute(&t.agent, &withdraw), preview.amount);
+    assert_eq!(preview.amount, 600 * UNIT);
+    assert_eq!(preview.idle_after, t.vault.get_total_assets());
+    assert_eq!(preview.deployed_after, t.vault.get_deployed_assets());
+    assert_eq!(preview.share_value_after, t.vault.get_share_value());
+}
+
+#[test]
+fn test_agent_execute_many_moves_between_pools_atomically() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    let other_pool = t.env.register(MockBlendPool, ());
+    t.vault.add_approved_pool(&other_pool);
+    t.vault.assign_pool(&t.agent, &other_pool);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 500 * UNIT));
+
+    let withdraw = |pool: &Address, amount| Strategy {
+        action: symbol_short!("withdraw"),
+        pool: pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount,
+        request_type: RequestType::WithdrawCollateral as u32,
+    };
+    let supply = |pool: &Address, amount| Strategy {
+        pool: pool.clone(),
+        ..supply_strategy(&t, amount)
+    };
+
+    // One batch counts once against the cooldown
+    t.vault.set_strategy_limits(&3_600, &0);
+    t.advance_time(3_600);
+    t.vault.agent_execute_many(&t.agent, &vec![
+        &t.env,
+        withdraw(&t.pool, 300 * UNIT),
+        supply(&other_pool, 300 * UNIT),
+    ]);
+    assert_eq!(t.usdc.balance(&t.pool), 200 * UNIT);
+    assert_eq!(t.usdc.balance(&other_pool), 300 * UNIT);
+    assert_eq!(t.vault.get_deployed_assets(), 500 * UNIT);
+
+    // A failing step undoes the ones before it
+    t.advance_time(3_600);
+    MockBlendPoolClient::new(&t.env, &other_pool).set_fail_next(&true);
+    assert!(t
+        .vault
+        .try_agent_execute_many(&t.agent, &vec![
+            &t.env,
+            withdraw(&t.pool, 200 * UNIT),
+            supply(&other_pool, 200 * UNIT),
+        ])
+        .is_err());
+    assert_eq!(t.usdc.balance(&t.pool), 200 * UNIT);
+    assert_eq!(t.usdc.balance(&t.vault.address), 500 * UNIT);
+
+    // Every strategy is checked before any runs
+    let mut bad = supply(&other_pool, UNIT);
+    bad.request_type = RequestType::Withdraw as u32;
+    assert_eq!(
+        t.vault
+            .try_agent_execute_many(&t.agent, &vec![&t.env, withdraw(&t.pool, 200 * UNIT), bad]),
+        Err(Ok(VaultError::InvalidRequestType))
+    );
+    assert_eq!(
+        t.vault.try_agent_execute_many(&t.agent, &vec![&t.env]),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_agent_unknown_action_rejected() {
+    let t = TuxedoFixture::new(&Env::default());
+    let pool = t.pool.clone();
+
+    let result = t.vault.try_agent_execute(&t.agent, &Strategy {
+        action: symbol_short!("borrow"),
+        pool,
+        asset: t.usdc.address.clone(),
+        amount: UNIT,
+        request_type: RequestType::SupplyCollateral as u32,
+    });
+    assert_eq!(result, Err(Ok(VaultError::NotAuthorized)));
+}
+
+fn supply_strategy(t: &TuxedoFixture, amount: i128) -> Strategy {
+    Strategy {
+        action: symbol_short!("supply"),
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount,
+        request_type: RequestType::SupplyCollateral as u32,
+    }
+}
+
+/// Sign a strategy the way the off-chain risk engine does
+fn sign_strategy(
+    t: &TuxedoFixture,
+    key: &SigningKey,
+    strategy: &Strategy,
+    nonce: u64,
+    expiry: u64,
+) -> BytesN<64> {
+    let payload = t.vault.strategy_payload(strategy, &nonce, &expiry);
+    BytesN::from_array(&t.env, &key.sign(&payload.to_array()).to_bytes())
+}
+
+fn setup_policy(t: &TuxedoFixture) -> SigningKey {
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    t.vault
+        .set_policy_key(&BytesN::from_array(&t.env, &key.verifying_key().to_bytes()));
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    key
+}
+
+#[test]
+fn test_agent_execute_signed_strategy() {
+    let t = TuxedoFixture::new(&Env::default());
+    let key = setup_policy(&t);
+    let expiry = t.env.ledger().timestamp() + 60;
+
+    let strategy = supply_strategy(&t, 600 * UNIT);
+    let signature = sign_strategy(&t, &key, &strategy, 1, expiry);
+    t.vault.agent_execute_signed(&t.agent, &strategy, &1, &expiry, &signature);
+
+    assert_eq!(t.usdc.balance(&t.pool), 600 * UNIT);
+    assert_eq!(t.vault.get_policy_nonce(), Some(1));
+}
+
+#[test]
+fn test_agent_execute_signed_rejects_tampered_amount() {
+    let t = TuxedoFixture::new(&Env::default());
+    let key = setup_policy(&t);
+    let expiry = t.env.ledger().timestamp() + 60;
+
+    let signature = sign_strategy(&t, &key, &supply_strategy(&t, 100 * UNIT), 1, expiry);
+    let tampered = supply_strategy(&t, 900 * UNIT);
+
+    assert!(t
+        .vault
+        .try_agent_execute_signed(&t.agent, &tampered, &1, &expiry, &signature)
+        .is_err());
+    assert_eq!(t.usdc.balance(&t.pool), 0);
+}
+
+#[test]
+fn test_agent_execute_signed_rejects_replay_and_expiry() {
+    let t = TuxedoFixture::new(&Env::default());
+    let key = setup_policy(&t);
+    let expiry = t.env.ledger().timestamp() + 60;
+
+    let strategy = supply_strategy(&t, 100 * UNIT);
+    let signature = sign_strategy(&t, &key, &strategy, 5, expiry);
+    t.vault.agent_execute_signed(&t.agent, &strategy, &5, &expiry, &signature);
+
+    // Same approval again, and an older nonce, are both rejected
+    assert_eq!(
+        t.vault.try_agent_execute_signed(&t.agent, &strategy, &5, &expiry, &signature),
+        Err(Ok(VaultError::InvalidNonce))
+    );
+    let older = sign_strategy(&t, &key, &strategy, 4, expiry);
+    assert_eq!(
+        t.vault.try_agent_execute_signed(&t.agent, &strategy, &4, &expiry, &older),
+        Err(Ok(VaultError::InvalidNonce))
+    );
+
+    let late = sign_strategy(&t, &key, &strategy, 6, expiry);
+    t.env.ledger().set_timestamp(expiry + 1);
+    assert_eq!(
+        t.vault.try_agent_execute_signed(&t.agent, &strategy, &6, &expiry, &late),
+        Err(Ok(VaultError::SignatureExpired))
+    );
+    assert_eq!(t.usdc.balance(&t.pool), 100 * UNIT);
+}
+
+#[test]
+fn test_agent_execute_signed_requires_policy_key() {
+    let t = TuxedoFixture::new(&Env::default());
+    let strategy = supply_strategy(&t, 100 * UNIT);
+    let signature = BytesN::from_array(&t.env, &[0u8; 64]);
+
+    assert_eq!(
+        t.vault.try_agent_execute_signed(&t.agent, &strategy, &1, &u64::MAX, &signature),
+        Err(Ok(VaultError::PolicyKeyNotSet))
+    );
+}
+
+#[test]
+fn test_agent_swap_rewards_credits_yield() {
+    let t = TuxedoFixture::new(&Env::default());
+    let blnd = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    let router = t.env.register(MockRouter, ());
+    MockRouterClient::new(&t.env, &router).set_rate(&(RATE_SCALE / 4)); // 1 BLND = 0.25 USDC
+    t.usdc_admin.mint(&router, &(1_000 * UNIT));
+    t.vault.set_swap_router(&router);
+
+    let user = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(100 * UNIT), &0);
+    StellarAssetClient::new(&t.env, &blnd).mint(&t.vault.address, &(40 * UNIT));
+
+    let received = t.vault.agent_swap_rewards(
+        &t.agent,
+        &blnd,
+        &(40 * UNIT),
+        &(10 * UNIT),
+        &t.env.ledger().timestamp(),
+    );
+
+    assert_eq!(received, 10 * UNIT);
+    assert_eq!(TokenClient::new(&t.env, &blnd).balance(&t.vault.address), 0);
+    assert_eq!(t.vault.get_total_assets(), 110 * UNIT);
+
+    // Proceeds count as yield, so the platform takes its 2% cut of them after
+    // the keeper's 0.1%
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(t.usdc.balance(&t.platform), 1_998 * UNIT / 10_000);
+}
+
+#[test]
+fn test_agent_swap_rewards_guards() {
+    let t = TuxedoFixture::new(&Env::default());
+    let blnd = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    let router = t.env.register(MockRouter, ());
+    let deadline = t.env.ledger().timestamp();
+
+    // Router must be configured
+    assert_eq!(
+        t.vault.try_agent_swap_rewards(&t.agent, &blnd, &100, &0, &deadline),
+        Err(Ok(VaultError::RouterNotSet))
+    );
+    t.vault.set_swap_router(&router);
+
+    // Depositor USDC and vault shares can never be swapped
+    assert_eq!(
+        t.vault.try_agent_swap_rewards(&t.agent, &t.usdc.address, &100, &0, &deadline),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+    assert_eq!(
+        t.vault.try_agent_swap_rewards(&t.agent, &t.vault.address, &100, &0, &deadline),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+
+    // Quote below min_out is rejected before swapping
+    assert_eq!(
+        t.vault.try_agent_swap_rewards(&t.agent, &blnd, &100, &101, &deadline),
+        Err(Ok(VaultError::SlippageExceeded))
+    );
+
+    // Expired deadline is rejected
+    t.env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    assert_eq!(
+        t.vault.try_agent_swap_rewards(&t.agent, &blnd, &100, &0, &deadline),
+        Err(Ok(VaultError::DeadlinePassed))
+    );
+}
+
+#[test]
+fn test_bridged_deposit_credits_recipient() {
+    let t = TuxedoFixture::new(&Env::default());
+    let bridge = MockBridgeClient::new(&t.env, &t.env.register(MockBridge, ()));
+    let recipient = Address::generate(&t.env);
+    t.usdc_admin.mint(&bridge.address, &(100 * UNIT));
+
+    // Unknown bridges can't deliver deposits
+    assert!(bridge
+        .try_deliver(&t.usdc.address, &t.vault.address, &recipient, &(40 * UNIT))
+        .is_err());
+    assert_eq!(
+        t.vault
+            .try_receive_bridged_deposit(&bridge.address, &recipient, &(40 * UNIT)),
+        Err(Ok(VaultError::UnknownBridge))
+    );
+
+    t.vault.set_bridge(&bridge.address, &true);
+    let shares = bridge.deliver(&t.usdc.address, &t.vault.address, &recipient, &(40 * UNIT));
+    assert_eq!(shares, 40 * UNIT - DEAD_SHARES);
+    assert_eq!(t.vault.get_user_shares(&recipient), shares);
+    assert_eq!(t.vault.get_user_shares(&bridge.address), 0);
+    assert_eq!(t.usdc.balance(&bridge.address), 60 * UNIT);
+    assert_eq!(t.usdc.balance(&t.vault.address), 40 * UNIT);
+}
+
+#[test]
+fn test_agent_execute_swap_rotates_into_supported_asset() {
+    let t = TuxedoFixture::new(&Env::default());
+    let eurc = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    let router = t.env.register(MockRouter, ());
+    MockRouterClient::new(&t.env, &router).set_rate(&(RATE_SCALE * 11 / 10)); // 1 EURC = 1.1 USDC
+    t.usdc_admin.mint(&router, &(1_000 * UNIT));
+    t.vault.set_swap_router(&router);
+    StellarAssetClient::new(&t.env, &eurc).mint(&t.vault.address, &(300 * UNIT));
+    t.vault.set_swap_limit(&eurc, &(150 * UNIT));
+
+    let swap = |amount_in: i128, min_out: i128| Swap {
+        token_in: eurc.clone(),
+        token_out: t.usdc.address.clone(),
+        amount_in,
+        min_out,
+        deadline: t.env.ledger().timestamp(),
+    };
+
+    assert_eq!(t.vault.agent_execute_swap(&t.agent, &swap(100 * UNIT, 110 * UNIT)), 110 * UNIT);
+    assert_eq!(t.usdc.balance(&t.vault.address), 110 * UNIT);
+    assert_eq!(t.vault.get_swap_volume(&eurc), 100 * UNIT);
+
+    // Quote below min_out is rejected and doesn't use up the limit
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(10 * UNIT, 12 * UNIT)),
+        Err(Ok(VaultError::SlippageExceeded))
+    );
+    assert_eq!(t.vault.get_swap_volume(&eurc), 100 * UNIT);
+
+    // Daily volume is capped per input token and resets the next day
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(60 * UNIT, 0)),
+        Err(Ok(VaultError::SwapLimitExceeded))
+    );
+    t.env.ledger().with_mut(|li| li.timestamp += 86_400);
+    t.vault.agent_execute_swap(&t.agent, &swap(60 * UNIT, 0));
+    assert_eq!(t.vault.get_swap_volume(&eurc), 60 * UNIT);
+}
+
+#[test]
+fn test_agent_execute_swap_asset_guards() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    let eurc = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    let (xlm, _) = accept_xlm(&t);
+    let swap = |token_in: &Address, token_out: &Address| Swap {
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in: 100,
+        min_out: 0,
+        deadline: t.env.ledger().timestamp(),
+    };
+
+    // Output must be a supported asset the vault accepts, and so values
+    assert!(!t.vault.is_supported_asset(&eurc));
+    t.vault.set_supported_asset(&eurc, &true);
+    assert!(!t.vault.is_supported_asset(&eurc));
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(&t.usdc.address, &eurc)),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+
+    // Vault shares can never be swapped
+    t.vault.set_supported_asset(&xlm, &true);
+    assert!(t.vault.is_supported_asset(&xlm));
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(&t.vault.address, &xlm)),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+
+    // Nor can tokens without a daily limit
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(&t.usdc.address, &xlm)),
+        Err(Ok(VaultError::SwapLimitExceeded))
+    );
+
+    // Supported swaps still need a router
+    t.vault.set_swap_limit(&t.usdc.address, &(100 * UNIT));
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(&t.usdc.address, &xlm)),
+        Err(Ok(VaultError::RouterNotSet))
+    );
+}
+
+#[test]
+fn test_agent_execute_swap_keeps_reserve() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    let (xlm, _) = accept_xlm(&t);
+    t.vault.set_supported_asset(&xlm, &true);
+    t.vault.set_swap_limit(&t.usdc.address, &(1_000 * UNIT));
+    let router = t.env.register(MockRouter, ());
+    MockRouterClient::new(&t.env, &router).set_rate(&(RATE_SCALE * 10));
+    StellarAssetClient::new(&t.env, &xlm).mint(&router, &(10_000 * UNIT));
+    t.vault.set_swap_router(&router);
+    let swap = |amount_in: i128| Swap {
+        token_in: t.usdc.address.clone(),
+        token_out: xlm.clone(),
+        amount_in,
+        min_out: 0,
+        deadline: t.env.ledger().timestamp(),
+    };
+
+    // 20% of 1,000 USDC stays idle: 800 can be swapped out, not a stroop more
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(800 * UNIT + 1)),
+        Err(Ok(VaultError::ReserveBreached))
+    );
+    assert_eq!(t.vault.agent_execute_swap(&t.agent, &swap(800 * UNIT)), 8_000 * UNIT);
+    assert_eq!(t.vault.get_deposit_asset(&xlm).unwrap().balance, 8_000 * UNIT);
+    assert_eq!(t.vault.get_total_assets(), 1_000 * UNIT);
+
+    // A full pause halts swaps like strategies
+    t.vault.pause(&false);
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(UNIT)),
+        Err(Ok(VaultError::Paused))
+    );
+}
+
+#[test]
+fn test_deposit_for_credits_recipient() {
+    let t = TuxedoFixture::new(&Env::default());
+    let payer = t.user_with_usdc(50 * UNIT);
+    let recipient = Address::generate(&t.env);
+
+    let shares = t.vault.deposit_for(&payer, &recipient, &t.usdc.address, &(50 * UNIT));
+
+    assert_eq!(t.usdc.balance(&payer), 0);
+    assert_eq!(t.vault.get_user_shares(&payer), 0);
+    assert_eq!(t.vault.get_user_shares(&recipient), shares);
+
+    // Shares credited to the vault itself could never be redeemed
+    assert_eq!(
+        t.vault.try_deposit_for(&payer, &t.vault.address, &t.usdc.address, &(10 * UNIT)),
+        Err(Ok(VaultError::InvalidAddress))
+    );
+}
+
+#[test]
+fn test_withdraw_to_pays_recipient() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(100 * UNIT);
+    let cold = Address::generate(&t.env);
+    t.vault.deposit(&user, &t.usdc.address, &(100 * UNIT), &0);
+
+    assert_eq!(t.vault.withdraw_to(&user, &cold, &(40 * UNIT), &0), 40 * UNIT);
+    assert_eq!(t.env.auths()[0].0, user);
+    assert_eq!(t.usdc.balance(&cold), 40 * UNIT);
+    assert_eq!(t.usdc.balance(&user), 0);
+    assert_eq!(t.vault.get_user_shares(&user), 60 * UNIT - DEAD_SHARES);
+    assert_eq!(t.vault.get_user_shares(&cold), 0);
+
+    assert_eq!(
+        t.vault.try_withdraw_to(&user, &cold, &(10 * UNIT), &(10 * UNIT + 1)),
+        Err(Ok(VaultError::SlippageExceeded))
+    );
+    assert_eq!(
+        t.vault.try_withdraw_to(&user, &t.vault.address, &(10 * UNIT), &0),
+        Err(Ok(VaultError::InvalidAddress))
+    );
+}
+
+#[test]
+fn test_transfer_moves_shares_only() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(50 * UNIT);
+    let bob = Address::generate(&t.env);
+    t.vault.deposit(&alice, &t.usdc.address, &(50 * UNIT), &0);
+
+    t.vault.transfer(&alice, &bob, &(20 * UNIT));
+
+    assert_eq!(t.vault.balance(&alice), 30 * UNIT - DEAD_SHARES);
+    assert_eq!(t.vault.balance(&bob), 20 * UNIT);
+    assert_eq!(t.vault.get_total_shares(), 50 * UNIT);
+    assert_eq!(t.vault.get_vault_stats().initial_deposits, 50 * UNIT);
+    assert_eq!(
+        t.vault.try_transfer_shares(&bob, &alice, &(20 * UNIT + 1)),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+    assert_eq!(
+        t.vault.try_transfer_shares(&bob, &alice, &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // Sending to yourself changes nothing
+    t.vault.transfer_shares(&bob, &bob, &(20 * UNIT));
+    assert_eq!(t.vault.balance(&bob), 20 * UNIT);
+
+    // Emptying a balance drops its entry, as a full withdrawal does
+    t.vault.transfer_shares(&bob, &alice, &(20 * UNIT));
+    assert_eq!(t.vault.balance(&alice), 50 * UNIT - DEAD_SHARES);
+    let has_entry = t.env.as_contract(&t.vault.address, || {
+        t.env
+            .storage()
+            .persistent()
+            .has(&(symbol_short!("shares"), bob.clone()))
+    });
+    assert!(!has_entry);
+}
+
+#[test]
+fn test_previews_match_deposit_and_withdraw() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.users[0].clone();
+    assert_eq!(t.vault.preview_deposit(&0), 0);
+    assert_eq!(t.vault.preview_withdraw(&0), 0);
+
+    // Deposit and partly withdraw, each against the preview taken just before
+    let round_trip = |amount: i128| {
+        let preview = t.vault.preview_deposit(&amount);
+        let minted = t.vault.deposit(&user, &t.usdc.address, &amount, &0);
+        assert_eq!(minted, preview);
+
+        let shares = minted / 3;
+        let preview = t.vault.preview_withdraw(&shares);
+        assert_eq!(t.vault.withdraw(&user, &shares, &0), preview);
+    };
+
+    // First deposit mints 1:1, less the dead shares
+    round_trip(1_000 * UNIT);
+    assert_eq!(t.vault.get_share_value(), UNIT);
+
+    // Yield leaves a share value that doesn't divide evenly
+    t.simulate_yield(1_000 * UNIT / 3);
+    round_trip(777 * UNIT + 7);
+
+    // Funds in the pool still count towards the share value
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 400 * UNIT));
+    round_trip(1_234 * UNIT + 1);
+
+    // After the platform takes its fee
+    t.vault.agent_execute(&t.agent, &Strategy {
+        action: symbol_short!("withdraw"),
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount: 400 * UNIT,
+        request_type: RequestType::WithdrawCollateral as u32,
+    });
+    t.vault.distribute_yield(&t.keeper);
+    round_trip(UNIT + 30);
+}
+
+#[test]
+fn test_deposit_priced_with_deployed_assets() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let bob = t.user_with_usdc(500 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 600 * UNIT));
+    assert_eq!(t.vault.get_share_value(), UNIT);
+
+    // Bob buys at the nominal value rather than off the 400 USDC left idle,
+    // so alice's shares keep theirs
+    assert_eq!(t.vault.preview_deposit(&(500 * UNIT)), 500 * UNIT);
+    assert_eq!(t.vault.deposit(&bob, &t.usdc.address, &(500 * UNIT), &0), 500 * UNIT);
+    assert_eq!(t.vault.get_share_value(), UNIT);
+    assert_eq!(t.vault.get_total_assets(), 900 * UNIT);
+    assert!(!t.vault.is_breaker_tripped());
+}
+
+#[test]
+fn test_slippage_bounds_guard_deposit_and_withdraw() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let bob = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(500 * UNIT), &0);
+
+    // Yield lands between the preview and the deposit: fewer shares than quoted
+    let quote = t.vault.preview_deposit(&(500 * UNIT));
+    t.simulate_yield(100 * UNIT);
+    assert_eq!(
+        t.vault.try_deposit(&bob, &t.usdc.address, &(500 * UNIT), &quote),
+        Err(Ok(VaultError::SlippageExceeded))
+    );
+    assert_eq!(t.usdc.balance(&bob), 1_000 * UNIT);
+    let quote = t.vault.preview_deposit(&(500 * UNIT));
+    assert_eq!(t.vault.deposit(&bob, &t.usdc.address, &(500 * UNIT), &quote), quote);
+
+    // The platform fee comes out first: fewer assets than quoted
+    let shares = t.vault.get_user_shares(&alice);
+    let quote = t.vault.preview_withdraw(&shares);
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(
+        t.vault.try_withdraw(&alice, &shares, &quote),
+        Err(Ok(VaultError::SlippageExceeded))
+    );
+    assert_eq!(t.vault.get_user_shares(&alice), shares);
+    let quote = t.vault.preview_withdraw(&shares);
+    assert_eq!(t.vault.withdraw(&alice, &shares, &quote), quote);
+}
+
+#[test]
+fn test_withdraw_all_leaves_no_dust() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let bob = t.user_with_usdc(300 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.deposit(&bob, &t.usdc.address, &(300 * UNIT), &0);
+    t.simulate_yield(1_000 * UNIT / 3);
+
+    let shares = t.vault.get_user_shares(&bob);
+    let expected = t.vault.preview_withdraw(&shares);
+    assert_eq!(t.vault.withdraw_all(&bob), expected);
+    assert_eq!(t.usdc.balance(&bob), expected);
+    assert_eq!(t.vault.get_user_shares(&bob), 0);
+    let has_entry = t.env.as_contract(&t.vault.address, || {
+        t.env
+            .storage()
+            .persistent()
+            .has(&(symbol_short!("shares"), bob.clone()))
+    });
+    assert!(!has_entry);
+
+    assert_eq!(
+        t.vault.try_withdraw_all(&bob),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+}
+
+#[test]
+fn test_share_balances_outlive_their_original_ttl() {
+    const DAY_IN_LEDGERS: u32 = 17_280;
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let bob = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.deposit(&bob, &t.usdc.address, &(1_000 * UNIT), &0);
+    let shares = t.vault.get_user_shares(&bob);
+
+    // Ledgers left before bob's balance and the vault instance are archived
+    let ttls = || {
+        t.env.as_contract(&t.vault.address, || {
+            let storage = t.env.storage();
+            (
+                storage.persistent().get_ttl(&(symbol_short!("shares"), bob.clone())),
+                storage.instance().get_ttl(),
+            )
+        })
+    };
+    assert_eq!(ttls(), (120 * DAY_IN_LEDGERS, 30 * DAY_IN_LEDGERS));
+
+    // Alice keeps using the vault for 200 days while bob stays dormant, past
+    // the 120 days his deposit kept the balance live; he is bumped once
+    for round in 1..=10 {
+        t.env
+            .ledger()
+            .with_mut(|li| li.sequence_number += 20 * DAY_IN_LEDGERS);
+        t.vault.withdraw(&alice, &(10 * UNIT), &0);
+        if round == 5 {
+            t.vault.bump_user_shares(&bob);
+        }
+    }
+    assert_eq!(ttls(), (20 * DAY_IN_LEDGERS, 30 * DAY_IN_LEDGERS));
+
+    assert_eq!(t.vault.get_user_shares(&bob), shares);
+    let assets = t.vault.preview_withdraw(&shares);
+    let wallet = Address::generate(&t.env);
+    assert_eq!(t.vault.withdraw_to(&bob, &wallet, &shares, &0), assets);
+    assert_eq!(t.usdc.balance(&wallet), assets);
+}
+
+#[test]
+fn test_withdraw_assets_pays_exact_amount() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    // A third more assets than shares: the share value doesn't divide evenly
+    t.simulate_yield(1_000 * UNIT / 3);
+    let total_assets = t.vault.get_total_assets();
+    let total_shares = t.vault.get_total_shares();
+
+    let burned = t.vault.withdraw_assets(&user, &(100 * UNIT));
+    assert_eq!(t.usdc.balance(&user), 100 * UNIT);
+    assert_eq!(
+        t.vault.get_user_shares(&user),
+        1_000 * UNIT - DEAD_SHARES - burned
+    );
+    assert_eq!(t.vault.get_total_shares(), 1_000 * UNIT - burned);
+
+    // Shares round up: they cover the payout, and one fewer wouldn't
+    assert!(burned * total_assets / total_shares >= 100 * UNIT);
+    assert!((burned - 1) * total_assets / total_shares < 100 * UNIT);
+}
+
+#[test]
+fn test_withdraw_assets_guards() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    let bob = t.user_with_usdc(300 * UNIT);
+    t.vault.deposit(&bob, &t.usdc.address, &(300 * UNIT), &0);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+
+    assert_eq!(
+        t.vault.try_withdraw_assets(&alice, &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // Asking for more than the shares are worth fails rather than clamping,
+    // even though the vault holds enough USDC
+    assert_eq!(
+        t.vault.try_withdraw_assets(&alice, &(100 * UNIT + 1)),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+    assert_eq!(t.vault.get_user_shares(&alice), 100 * UNIT);
+
+    assert_eq!(t.vault.withdraw_assets(&alice, &(100 * UNIT)), 100 * UNIT);
+    assert_eq!(t.vault.get_user_shares(&alice), 0);
+}
+
+#[test]
+fn test_max_withdraw_counts_only_idle_assets() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    let bob = t.user_with_usdc(300 * UNIT);
+    assert_eq!(t.vault.max_deposit(&alice), i128::MAX);
+    assert_eq!(t.vault.max_withdraw(&alice), 0);
+
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+    t.vault.deposit(&bob, &t.usdc.address, &(300 * UNIT), &0);
+    assert_eq!(t.vault.max_withdraw(&alice), 100 * UNIT - DEAD_SHARES);
+
+    // Half the assets go to Blend: the shares keep their value, but only the
+    // idle half can be paid out
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 200 * UNIT));
+    assert_eq!(t.vault.get_user_assets(&bob), 300 * UNIT);
+    let max = t.vault.max_withdraw(&bob);
+    assert_eq!(max, 200 * UNIT);
+    assert_eq!(
+        t.vault.try_withdraw_assets(&bob, &(max + 1)),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    t.vault.withdraw_assets(&bob, &max);
+    assert_eq!(t.vault.get_user_assets(&bob), 100 * UNIT);
+    assert_eq!(t.vault.max_withdraw(&bob), 0);
+    assert_eq!(t.vault.max_withdraw(&alice), 0);
+}
+
+#[test]
+fn test_withdraw_pays_full_value_while_deployed() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let bob = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.deposit(&bob, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.set_reserve_bps(&0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 1_000 * UNIT));
+
+    // Without a loss, deploying half the vault costs alice nothing
+    assert_eq!(t.vault.withdraw_all(&alice), 1_000 * UNIT - DEAD_SHARES);
+    assert_eq!(t.usdc.balance(&alice), 1_000 * UNIT - DEAD_SHARES);
+    assert_eq!(t.vault.get_share_value(), UNIT);
+
+    // Bob's shares are worth more than is left idle, so he waits for the
+    // agent to bring the rest back rather than take less
+    let shares = t.vault.get_user_shares(&bob);
+    assert_eq!(t.vault.preview_withdraw(&shares), 0);
+    assert_eq!(
+        t.vault.try_withdraw(&bob, &shares, &0),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    t.vault.agent_execute(&t.agent, &Strategy {
+        action: symbol_short!("withdraw"),
+        pool: t.pool.clone(),
+        asset: t.usdc.address.clone(),
+        amount: 1_000 * UNIT,
+        request_type: RequestType::WithdrawCollateral as u32,
+    });
+    assert_eq!(t.vault.withdraw_all(&bob), 1_000 * UNIT);
+}
+
+#[test]
+fn test_emergency_recall_brings_funds_home() {
+    let t = TuxedoFixture::new(&Env::default());
+    let pool = MockBlendPoolClient::new(&t.env, &t.pool);
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 800 * UNIT));
+    assert_eq!(t.vault.get_pool_deployed(&t.pool), 800 * UNIT);
+
+    // 5% interest accrues after the agent last touched the pool
+    pool.accrue(&500);
+    t.usdc_admin.mint(&t.pool, &(40 * UNIT));
+
+    // The agent is gone and the admin has halted everything
+    t.vault.pause(&false);
+    assert_eq!(t.vault.emergency_recall(&t.pool), 840 * UNIT);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.usdc.balance(&t.vault.address), 1_040 * UNIT);
+    assert_eq!(t.vault.get_pool_deployed(&t.pool), 0);
+    assert_eq!(t.vault.get_deployed_assets(), 0);
+    assert_eq!(t.vault.get_pause_state(), PauseState::DepositsPaused);
+    assert_eq!(
+        t.vault.try_emergency_recall(&t.pool),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // Users can leave, but no new money comes in
+    assert_eq!(
+        t.vault.try_deposit(&user, &t.usdc.address, &(10 * UNIT), &0),
+        Err(Ok(VaultError::Paused))
+    );
+    t.vault.withdraw(&user, &t.vault.get_user_shares(&user), &0);
+    assert_eq!(
+        t.usdc.balance(&user),
+        1_040 * UNIT - DEAD_SHARES * 104 / 100
+    );
+}
+
+#[test]
+fn test_pause_modes() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(500 * UNIT), &0);
+    assert_eq!(t.vault.get_pause_state(), PauseState::Active);
+
+    // Deposits-only pause: users can still leave and the agent can unwind
+    t.vault.pause(&true);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_pause_state(), PauseState::DepositsPaused);
+    assert_eq!(
+        t.vault.try_deposit(&user, &t.usdc.address, &(100 * UNIT), &0),
+        Err(Ok(VaultError::Paused))
+    );
+    assert_eq!(t.vault.max_deposit(&user), 0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 20 * UNIT));
+    t.vault.withdraw(&user, &(100 * UNIT), &0);
+
+    // A full pause halts withdrawals and strategies too
+    t.vault.pause(&false);
+    assert_eq!(t.vault.get_pause_state(), PauseState::FullyPaused);
+    assert_eq!(
+        t.vault.try_withdraw(&user, &UNIT, &0),
+        Err(Ok(VaultError::Paused))
+    );
+    assert_eq!(
+        t.vault.try_withdraw_assets(&user, &UNIT),
+        Err(Ok(VaultError::Paused))
+    );
+    assert_eq!(
+        t.vault.try_agent_execute(&t.agent, &supply_strategy(&t, UNIT)),
+        Err(Ok(VaultError::Paused))
+    );
+    assert_eq!(t.vault.max_withdraw(&user), 0);
+
+    t.vault.unpause();
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_pause_state(), PauseState::Active);
+    t.vault.deposit(&user, &t.usdc.address, &(100 * UNIT), &0);
+    t.vault.withdraw(&user, &UNIT, &0);
+}
+
+#[test]
+fn test_set_agent_rotates_strategy_key() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    // One key can't hold two roles
+    assert_eq!(
+        t.vault.try_set_agent(&t.admin),
+        Err(Ok(VaultError::InvalidAddress))
+    );
+    assert_eq!(
+        t.vault.try_set_agent(&t.platform),
+        Err(Ok(VaultError::InvalidAddress))
+    );
+    assert_eq!(t.vault.get_agent(), t.agent);
+
+    let new_agent = Address::generate(&t.env);
+    t.vault.set_agent(&new_agent);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_agent(), new_agent);
+
+    // The old agent's signature no longer passes the strategy check
+    let strategy = supply_strategy(&t, 100 * UNIT);
+    let invoke = MockAuthInvoke {
+        contract: &t.vault.address,
+        fn_name: "agent_execute",
+        args: (t.agent.clone(), strategy.clone()).into_val(&t.env),
+        sub_invokes: &[],
+    };
+    assert_eq!(
+        t.vault
+            .mock_auths(&[MockAuth {
+                address: &t.agent,
+                invoke: &invoke,
+            }])
+            .try_agent_execute(&t.agent, &strategy),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+
+    t.env.mock_all_auths();
+    t.vault.agent_execute(&new_agent, &strategy);
+    assert_eq!(t.env.auths()[0].0, new_agent);
+}
+
+#[test]
+fn test_deposit_cap_limits_total_assets() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    assert_eq!(t.vault.get_deposit_cap(), 0);
+    assert_eq!(t.vault.get_vault_stats().remaining_capacity, i128::MAX);
+    assert_eq!(
+        t.vault.try_set_deposit_cap(&-1),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    t.vault.set_deposit_cap(&(500 * UNIT));
+    t.vault.deposit(&user, &t.usdc.address, &(200 * UNIT), &0);
+    assert_eq!(t.vault.get_vault_stats().remaining_capacity, 300 * UNIT);
+    assert_eq!(t.vault.max_deposit(&user), 300 * UNIT);
+
+    // One stroop over the cap fails; exactly reaching it succeeds
+    assert_eq!(
+        t.vault.try_deposit(&user, &t.usdc.address, &(300 * UNIT + 1), &0),
+        Err(Ok(VaultError::DepositCapExceeded))
+    );
+    t.vault.deposit(&user, &t.usdc.address, &(300 * UNIT), &0);
+    assert_eq!(t.vault.get_total_assets(), 500 * UNIT);
+    assert_eq!(t.vault.get_vault_stats().remaining_capacity, 0);
+
+    // A cap of 0 lifts it
+    t.vault.set_deposit_cap(&0);
+    assert_eq!(t.vault.get_deposit_cap(), 0);
+    t.vault.deposit(&user, &t.usdc.address, &(100 * UNIT), &0);
+}
+
+#[test]
+fn test_deposit_cap_counts_deployed_assets() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.set_deposit_cap(&(500 * UNIT));
+    t.vault.deposit(&user, &t.usdc.address, &(400 * UNIT), &0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 300 * UNIT));
+
+    // Lending USDC out frees no room under the cap
+    assert_eq!(t.vault.get_vault_stats().remaining_capacity, 100 * UNIT);
+    assert_eq!(t.vault.max_deposit(&user), 100 * UNIT);
+    assert_eq!(
+        t.vault.try_deposit(&user, &t.usdc.address, &(100 * UNIT + 1), &0),
+        Err(Ok(VaultError::DepositCapExceeded))
+    );
+    t.vault.deposit(&user, &t.usdc.address, &(100 * UNIT), &0);
+    assert_eq!(t.vault.get_vault_stats().total_assets, 500 * UNIT);
+}
+
+#[test]
+fn test_user_deposit_limit_tracks_net_deposits() {
+    let t = TuxedoFixture::new(&Env::default());
+    let whale = t.user_with_usdc(1_000 * UNIT);
+    let other = t.user_with_usdc(1_000 * UNIT);
+    assert_eq!(
+        t.vault.try_set_user_deposit_limit(&-1),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    t.vault.set_user_deposit_limit(&(100 * UNIT));
+    t.vault.deposit(&whale, &t.usdc.address, &(60 * UNIT), &0);
+    assert_eq!(t.vault.get_user_deposited(&whale), 60 * UNIT);
+    assert_eq!(t.vault.max_deposit(&whale), 40 * UNIT);
+    assert_eq!(
+        t.vault.try_deposit(&whale, &t.usdc.address, &(40 * UNIT + 1), &0),
+        Err(Ok(VaultError::UserLimitExceeded))
+    );
+    t.vault.deposit(&whale, &t.usdc.address, &(40 * UNIT), &0);
+
+    // The limit is per wallet
+    t.vault.deposit(&other, &t.usdc.address, &(100 * UNIT), &0);
+
+    // Withdrawing frees capacity to deposit again
+    t.vault.withdraw(&whale, &(30 * UNIT), &0);
+    assert_eq!(t.vault.get_user_deposited(&whale), 70 * UNIT);
+    t.vault.deposit(&whale, &t.usdc.address, &(30 * UNIT), &0);
+    assert_eq!(t.vault.get_user_deposited(&whale), 100 * UNIT);
+
+    // A limit of 0 lifts it
+    t.vault.set_user_deposit_limit(&0);
+    assert_eq!(t.vault.get_user_deposit_limit(), 0);
+    t.vault.deposit(&whale, &t.usdc.address, &(500 * UNIT), &0);
+    assert_eq!(t.vault.get_user_deposited(&whale), 600 * UNIT);
+}
+
+#[test]
+fn test_user_pnl_tracks_cost_basis_across_full_exit() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    assert_eq!(t.vault.get_user_pnl(&alice), (1_000 * UNIT, 0, 1_000 * UNIT - DEAD_SHARES));
+
+    // Yield shows up as unrealized
+    t.simulate_yield(100 * UNIT);
+    let value = t.vault.get_user_assets(&alice);
+    assert!(value > 1_099 * UNIT);
+    assert_eq!(t.vault.get_user_pnl(&alice), (1_000 * UNIT, 0, value));
+
+    // A partial withdrawal moves half of it into what was taken out
+    let half = t.vault.withdraw(&alice, &(t.vault.get_user_shares(&alice) / 2), &0);
+    assert_eq!(
+        t.vault.get_user_pnl(&alice),
+        (1_000 * UNIT, half, t.vault.get_user_assets(&alice))
+    );
+
+    // After a full exit the gain is realized and the totals stay
+    let rest = t.vault.withdraw_all(&alice);
+    assert_eq!(t.vault.get_user_pnl(&alice), (1_000 * UNIT, half + rest, 0));
+    assert_eq!(half + rest, t.usdc.balance(&alice));
+    assert_eq!(t.vault.get_user_deposited(&alice), 0);
+
+    // Depositing again adds to the same totals
+    t.vault.deposit(&alice, &t.usdc.address, &(500 * UNIT), &0);
+    let (total_in, total_out, unrealized) = t.vault.get_user_pnl(&alice);
+    assert_eq!((total_in, total_out), (1_500 * UNIT, half + rest));
+    assert_eq!(unrealized, t.vault.get_user_assets(&alice));
+}
+
+#[test]
+fn test_min_deposit() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.users[0].clone();
+    assert_eq!(t.vault.get_min_deposit(), UNIT);
+
+    // An empty vault needs a larger first deposit
+    assert_eq!(t.vault.get_vault_stats().min_deposit, 10 * UNIT);
+    assert_eq!(t.vault.preview_deposit(&(10 * UNIT - 1)), 0);
+    assert_eq!(
+        t.vault.try_deposit(&user, &t.usdc.address, &(10 * UNIT - 1), &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.deposit(&user, &t.usdc.address, &(10 * UNIT), &0);
+
+    // Later deposits only need the configured minimum
+    assert_eq!(t.vault.get_vault_stats().min_deposit, UNIT);
+    assert_eq!(
+        t.vault.try_deposit(&user, &t.usdc.address, &(UNIT - 1), &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.deposit(&user, &t.usdc.address, &UNIT, &0);
+
+    assert_eq!(
+        t.vault.try_set_min_deposit(&-1),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.set_min_deposit(&(5 * UNIT));
+    assert_eq!(t.vault.get_vault_stats().min_deposit, 5 * UNIT);
+    assert_eq!(
+        t.vault.try_deposit(&user, &t.usdc.address, &(5 * UNIT - 1), &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.deposit(&user, &t.usdc.address, &(5 * UNIT), &0);
+}
+
+#[test]
+fn test_first_deposit_locks_dead_shares() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.users[0].clone();
+
+    assert_eq!(t.vault.preview_deposit(&(10 * UNIT)), 10 * UNIT - DEAD_SHARES);
+    assert_eq!(t.vault.deposit(&user, &t.usdc.address, &(10 * UNIT), &0), 10 * UNIT - DEAD_SHARES);
+    assert_eq!(t.vault.get_user_shares(&t.vault.address), DEAD_SHARES);
+    assert_eq!(t.vault.get_total_shares(), 10 * UNIT);
+
+    // Only the first deposit pays for them
+    assert_eq!(t.vault.deposit(&user, &t.usdc.address, &(10 * UNIT), &0), 10 * UNIT);
+
+    // Redeeming everything leaves the dead shares and what they're worth
+    t.vault.withdraw(&user, &t.vault.get_user_shares(&user), &0);
+    assert_eq!(t.vault.get_total_shares(), DEAD_SHARES);
+    assert_eq!(t.usdc.balance(&t.vault.address), DEAD_SHARES);
+}
+
+#[test]
+fn test_donation_cannot_inflate_share_value() {
+    let t = TuxedoFixture::new(&Env::default());
+    let attacker = t.user_with_usdc(1_010 * UNIT);
+    let victim = t.user_with_usdc(2_000 * UNIT);
+
+    // The attacker gets in first, then donates as much as they deposited: the
+    // victim's deposit still rounds to within a stroop of its value
+    t.vault.deposit(&attacker, &t.usdc.address, &(10 * UNIT), &0);
+    t.usdc.transfer(&attacker, &t.vault.address, &(10 * UNIT));
+    let shares = t.vault.deposit(&victim, &t.usdc.address, &(15 * UNIT), &0);
+    assert!(t.vault.preview_withdraw(&shares) >= 15 * UNIT - 1);
+    t.vault.withdraw(&victim, &shares, &0);
+
+    // Redeeming down to the dead shares before donating only hands the
+    // donation to them: the victim still gets shares at a fair price
+    t.vault.withdraw(&attacker, &t.vault.get_user_shares(&attacker), &0);
+    t.usdc.transfer(&attacker, &t.vault.address, &(1_000 * UNIT));
+    let share_value = t.vault.get_share_value();
+    let shares = t.vault.deposit(&victim, &t.usdc.address, &(1_985 * UNIT), &0);
+    assert!(shares > 0);
+    assert!(t.vault.preview_withdraw(&shares) >= 1_985 * UNIT - share_value / UNIT);
+
+    // And the attacker has nothing left to redeem the donation with
+    assert_eq!(t.vault.get_user_shares(&attacker), 0);
+}
+
+#[test]
+fn test_share_math_overflow_is_an_error() {
+    let t = TuxedoFixture::new(&Env::default());
+    let whale = t.user_with_usdc(i128::MAX / 2);
+
+    // Too large to price in shares: rejected without moving funds
+    assert_eq!(t.vault.preview_deposit(&(i128::MAX / 2)), 0);
+    assert_eq!(
+        t.vault.try_deposit(&whale, &t.usdc.address, &(i128::MAX / 2), &0),
+        Err(Ok(VaultError::MathOverflow))
+    );
+    assert_eq!(t.usdc.balance(&whale), i128::MAX / 2);
+
+    // A balance that overflows the share value stops every priced call
+    let user = t.user_with_usdc(1_000 * UNIT);
+    let shares = t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.simulate_yield(i128::MAX / 2);
+    assert_eq!(
+        t.vault.try_get_share_value(),
+        Err(Ok(VaultError::MathOverflow.into()))
+    );
+    assert_eq!(t.vault.preview_withdraw(&shares), 0);
+    assert_eq!(t.vault.max_withdraw(&user), 0);
+    assert_eq!(
+        t.vault.try_withdraw(&user, &shares, &0),
+        Err(Ok(VaultError::MathOverflow))
+    );
+    assert_eq!(
+        t.vault.try_withdraw_assets(&user, &UNIT),
+        Err(Ok(VaultError::MathOverflow))
+    );
+    assert_eq!(
+        t.vault.try_deposit(&whale, &t.usdc.address, &(1_000 * UNIT), &0),
+        Err(Ok(VaultError::MathOverflow))
+    );
+    assert_eq!(
+        t.vault.try_distribute_yield(&t.keeper),
+        Err(Ok(VaultError::MathOverflow))
+    );
+}
+
+#[test]
+fn test_platform_fee_bps_applies_to_yield() {
+    let t = TuxedoFixture::new(&Env::default());
+    assert_eq!(t.vault.get_platform_fee_bps(), 200);
+    assert_eq!(t.vault.get_vault_stats().platform_fee_bps, 200);
+    assert_eq!(
+        t.vault.try_set_platform_fee_bps(&10_001),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    // Capped at 10% in code
+    assert_eq!(
+        t.vault.try_set_platform_fee_bps(&1_001),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    let user = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(100 * UNIT), &0);
+    t.usdc_admin.mint(&t.vault.address, &(10 * UNIT));
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(t.usdc.balance(&t.platform), 1_998 * UNIT / 10_000);
+
+    // The new fee applies from the next distribution
+    t.vault.set_platform_fee_bps(&500);
+    assert_eq!(t.vault.get_vault_stats().platform_fee_bps, 500);
+    t.usdc_admin.mint(&t.vault.address, &(10 * UNIT));
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(
+        t.usdc.balance(&t.platform),
+        1_998 * UNIT / 10_000 + 4_995 * UNIT / 10_000
+    );
+}
+
+#[test]
+fn test_platform_fee_only_above_hurdle() {
+    let t = TuxedoFixture::new(&Env::default());
+    assert_eq!(t.vault.try_set_hurdle_bps(&10_001), Err(Ok(VaultError::InvalidAmount)));
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    // 1% a year of 1,000 USDC deposits: 10 USDC of yield is fee-free
+    t.vault.set_hurdle_bps(&100);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_hurdle_bps(), 100);
+    t.advance_time(365 * 86_400);
+    assert_eq!(
+        t.vault.try_distribute_yield(&t.keeper),
+        Err(Ok(VaultError::NoYieldToDistribute))
+    );
+
+    // Above it, only the yield left after the keeper and the hurdle is charged
+    t.simulate_yield(20 * UNIT);
+    t.vault.distribute_yield(&t.keeper);
+    let keeper_reward = 20 * UNIT / 1_000;
+    assert_eq!(t.usdc.balance(&t.keeper), keeper_reward);
+    assert_eq!(
+        t.usdc.balance(&t.platform),
+        (20 * UNIT - keeper_reward - 10 * UNIT) * 200 / 10_000
+    );
+
+    // The hurdle restarts at each distribution
+    t.simulate_yield(10 * UNIT);
+    let platform_before = t.usdc.balance(&t.platform);
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(
+        t.usdc.balance(&t.platform) - platform_before,
+        (10 * UNIT - 10 * UNIT / 1_000) * 200 / 10_000
+    );
+
+    // Below the hurdle nothing is charged, but the keeper is still paid and
+    // the yield stays with users
+    t.advance_time(365 * 86_400);
+    t.simulate_yield(5 * UNIT);
+    let (keeper_before, platform_before) = (t.usdc.balance(&t.keeper), t.usdc.balance(&t.platform));
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(t.usdc.balance(&t.keeper) - keeper_before, 5 * UNIT / 1_000);
+    assert_eq!(t.usdc.balance(&t.platform), platform_before);
+    assert_eq!(
+        t.vault.try_distribute_yield(&t.keeper),
+        Err(Ok(VaultError::NoYieldToDistribute))
+    );
+}
+
+#[test]
+fn test_keeper_reward_on_distribute_yield() {
+    let t = TuxedoFixture::new(&Env::default());
+    assert_eq!(t.vault.get_keeper_reward_bps(), 10);
+    // Capped at 1% in code
+    assert_eq!(
+        t.vault.try_set_keeper_reward_bps(&101),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+    // Each distribution's yield splits exactly into the keeper reward, the
+    // platform fee and what stays with depositors
+    for reward_bps in [10, 100, 0] {
+        t.vault.set_keeper_reward_bps(&reward_bps);
+        assert_eq!(t.vault.get_keeper_reward_bps(), reward_bps);
+        t.usdc_admin.mint(&t.vault.address, &123_456_789);
+
+        let deposits_before = t.vault.get_vault_stats().initial_deposits;
+        let yield_earned = t.vault.get_total_assets() - deposits_before;
+        let keeper_before = t.usdc.balance(&t.keeper);
+        let platform_before = t.usdc.balance(&t.platform);
+
+        t.vault.distribute_yield(&t.keeper);
+        assert_eq!(t.env.auths()[0].0, t.keeper);
+
+        let keeper_reward = t.usdc.balance(&t.keeper) - keeper_before;
+        let platform_fee = t.usdc.balance(&t.platform) - platform_before;
+        let retained = t.vault.get_vault_stats().initial_deposits - deposits_before;
+        assert_eq!(keeper_reward, yield_earned * reward_bps as i128 / 10_000);
+        assert_eq!(platform_fee, (yield_earned - keeper_reward) * 200 / 10_000);
+        assert_eq!(keeper_reward + platform_fee + retained, yield_earned);
+    }
+}
+
+#[test]
+fn test_sync_sets_donations_aside() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    let share_value = t.vault.get_share_value();
+    assert_eq!(t.vault.try_sync(&false), Err(Ok(VaultError::InvalidAmount)));
+
+    // A third party sends USDC straight to the vault by mistake
+    let stranger = t.user_with_usdc(50 * UNIT);
+    t.usdc.transfer(&stranger, &t.vault.address, &(50 * UNIT));
+    assert!(t.vault.get_share_value() > share_value);
+
+    // Set aside as a donation, it neither lifts share value nor pays fees
+    assert_eq!(t.vault.sync(&false), 50 * UNIT);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_excess(), 50 * UNIT);
+    assert_eq!(t.vault.get_share_value(), share_value);
+    assert_eq!(t.vault.get_total_assets(), 1_000 * UNIT);
+    assert_eq!(
+        t.vault.try_distribute_yield(&t.keeper),
+        Err(Ok(VaultError::NoYieldToDistribute))
+    );
+
+    // Deposits price shares without the donation while it is set aside
+    let bob = t.user_with_usdc(100 * UNIT);
+    assert_eq!(t.vault.deposit(&bob, &t.usdc.address, &(100 * UNIT), &0), 100 * UNIT);
+    assert_eq!(t.vault.get_share_value(), share_value);
+
+    assert_eq!(t.vault.rescue_excess(&stranger), 50 * UNIT);
+    assert_eq!(t.usdc.balance(&stranger), 50 * UNIT);
+    assert_eq!(t.vault.get_excess(), 0);
+    assert_eq!(t.vault.get_share_value(), share_value);
+    assert_eq!(
+        t.vault.try_rescue_excess(&stranger),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // Classified as yield, the surplus stays for distribution
+    t.simulate_yield(10 * UNIT);
+    assert_eq!(t.vault.sync(&true), 10 * UNIT);
+    assert_eq!(t.vault.get_excess(), 0);
+    t.vault.distribute_yield(&t.keeper);
+    assert!(t.usdc.balance(&t.platform) > 0);
+
+    let shares = t.vault.get_user_shares(&alice);
+    let assets = t.vault.preview_withdraw(&shares);
+    assert_eq!(t.vault.withdraw(&alice, &shares, &0), assets);
+}
+
+#[test]
+fn test_sync_counts_deployed_deposits() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 600 * UNIT));
+    assert_eq!(t.vault.try_sync(&false), Err(Ok(VaultError::InvalidAmount)));
+
+    // Only the stray transfer is surplus, not the deposits left idle
+    let stranger = t.user_with_usdc(50 * UNIT);
+    t.usdc.transfer(&stranger, &t.vault.address, &(50 * UNIT));
+    assert_eq!(t.vault.sync(&false), 50 * UNIT);
+    assert_eq!(t.vault.get_excess(), 50 * UNIT);
+    assert_eq!(t.vault.get_total_assets(), 400 * UNIT);
+}
+
+#[test]
+fn test_min_distribution_interval() {
+    let t = TuxedoFixture::new(&Env::default());
+    assert_eq!(t.vault.get_min_distribution_interval(), 0);
+    assert_eq!(t.vault.get_vault_stats().next_distribution_at, 0);
+
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.set_min_distribution_interval(&3_600);
+    assert_eq!(t.vault.get_min_distribution_interval(), 3_600);
+
+    t.simulate_yield(10 * UNIT);
+    t.vault.distribute_yield(&t.keeper);
+    let next = t.env.ledger().timestamp() + 3_600;
+    assert_eq!(t.vault.get_vault_stats().next_distribution_at, next);
+
+    // One second short of the interval is too soon, exactly at it is fine
+    t.simulate_yield(10 * UNIT);
+    t.advance_time(3_599);
+    assert_eq!(
+        t.vault.try_distribute_yield(&t.keeper),
+        Err(Ok(VaultError::TooSoon))
+    );
+    assert_eq!(
+        t.vault.try_keeper_distribute_yield(&t.keeper),
+        Err(Ok(VaultError::TooSoon))
+    );
+    t.advance_time(1);
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(t.vault.get_vault_stats().next_distribution_at, next + 3_600);
+
+    // Without an interval, distributions can run back to back
+    t.vault.set_min_distribution_interval(&0);
+    t.simulate_yield(10 * UNIT);
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(
+        t.vault.get_vault_stats().next_distribution_at,
+        t.env.ledger().timestamp()
+    );
+}
+
+/// Register a vault whose constructor is given `roles` (admin, agent,
+/// platform) and `fee_bps`, over a fresh asset unless one is passed
+fn register_vault<'a>(
+    env: &Env,
+    roles: [&Address; 3],
+    asset: Option<&Address>,
+    fee_bps: Option<u32>,
+) -> TuxedoVaultClient<'a> {
+    let asset = match asset {
+        Some(asset) => asset.clone(),
+        None => env.register_stellar_asset_contract_v2(roles[0].clone()).address(),
+    };
+    let args = (roles[0], roles[1], roles[2], asset, fee_bps);
+    TuxedoVaultClient::new(env, &env.register(TuxedoVault, args))
+}
+
+#[test]
+fn test_initialize_with_platform_fee() {
+    let env = Env::default();
+    let (admin, agent, platform) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+
+    let vault = register_vault(&env, [&admin, &agent, &platform], None, Some(50));
+    assert_eq!(vault.get_platform_fee_bps(), 50);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")] // InvalidAmount
+fn test_constructor_rejects_platform_fee_over_cap() {
+    let env = Env::default();
+    let (admin, agent, platform) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+
+    register_vault(&env, [&admin, &agent, &platform], None, Some(1_001));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")] // InvalidAddress
+fn test_constructor_rejects_duplicate_roles() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    register_vault(&env, [&admin, &agent, &admin], None, None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")] // InvalidAsset
+fn test_constructor_rejects_asset_that_is_not_a_token() {
+    let env = Env::default();
+    let (admin, agent, platform) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+
+    register_vault(&env, [&admin, &agent, &platform], Some(&agent), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")] // InvalidAsset
+fn test_constructor_rejects_asset_without_7_decimals() {
+    let env = Env::default();
+    let (admin, agent, platform) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    let token = MockFeeTokenClient::new(&env, &env.register(MockFeeToken, ()));
+    token.set_decimals(&6);
+
+    register_vault(&env, [&admin, &agent, &platform], Some(&token.address), None);
+}
+
+#[test]
+fn test_initialize_requires_admin_auth() {
+    let t = TuxedoFixture::new(&Env::default());
+    let attacker = Address::generate(&t.env);
+    t.env.set_auths(&[]);
+
+    // Without the named admin's signature nobody gets as far as the state
+    assert!(t
+        .vault
+        .try_initialize(&t.admin, &attacker, &t.platform, &t.usdc.address, &None)
+        .is_err());
+    assert!(t.env.auths().is_empty());
+
+    // Naming themselves admin doesn't help either: the constructor already ran
+    t.env.mock_all_auths();
+    assert_eq!(
+        t.vault
+            .try_initialize(&attacker, &t.agent, &t.platform, &t.usdc.address, &None),
+        Err(Ok(VaultError::AlreadyInitialized))
+    );
+    assert_eq!(t.vault.get_admin(), t.admin);
+    assert_eq!(t.vault.get_agent(), t.agent);
+}
+
+#[test]
+fn test_asset_price_from_feed_and_fallback() {
+    let t = TuxedoFixture::new(&Env::default());
+    t.env.ledger().set_timestamp(10_000);
+    let blnd = Address::generate(&t.env);
+    let xlm = Address::generate(&t.env);
+    assert_eq!(
+        t.vault.try_get_asset_price(&blnd),
+        Err(Ok(VaultError::PriceUnavailable))
+    );
+
+    let feed = MockReflectorClient::new(&t.env, &t.env.register(MockReflector, ()));
+    feed.set_price(&blnd, &2_000_000, &9_500);
+    t.vault.set_oracle(&feed.address, &600);
+    assert_eq!(t.vault.get_asset_price(&blnd), (2_000_000, 9_500, 14));
+
+    // Older than the threshold
+    t.env.ledger().set_timestamp(10_101);
+    assert_eq!(
+        t.vault.try_get_asset_price(&blnd),
+        Err(Ok(VaultError::StalePrice))
+    );
+
+    // Assets missing from the feed use the fixed fallback
+    t.vault.set_fallback_price(&xlm, &1_200_000, &7);
+    assert_eq!(t.vault.get_asset_price(&xlm), (1_200_000, 10_101, 7));
+}
+
+/// XLM accepted for deposits at 0.1 USDC, priced by a feed with 14 decimals
+fn accept_xlm<'a>(t: &TuxedoFixture<'a>) -> (Address, MockReflectorClient<'a>) {
+    let xlm = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    let feed = MockReflectorClient::new(&t.env, &t.env.register(MockReflector, ()));
+    feed.set_price(&xlm, &10_000_000_000_000, &t.env.ledger().timestamp());
+    t.vault.add_deposit_asset(&xlm, &feed.address, &600);
+    (xlm, feed)
+}
+
+#[test]
+fn test_deposit_asset_valued_at_oracle_price() {
+    let t = TuxedoFixture::new(&Env::default());
+    t.env.ledger().set_timestamp(10_000);
+    let (xlm, feed) = accept_xlm(&t);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_deposit_assets(), vec![&t.env, xlm.clone()]);
+    for asset in [&t.usdc.address, &t.vault.address] {
+        assert_eq!(
+            t.vault.try_add_deposit_asset(asset, &feed.address, &600),
+            Err(Ok(VaultError::InvalidAsset))
+        );
+    }
+
+    let bob = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&bob, &t.usdc.address, &(1_000 * UNIT), &0);
+    let alice = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &xlm).mint(&alice, &(1_000 * UNIT));
+
+    // 1,000 XLM at 0.1 USDC buys 100 USDC of shares
+    assert_eq!(t.vault.deposit(&alice, &xlm, &(1_000 * UNIT), &0), 100 * UNIT);
+    assert_eq!(t.vault.get_user_shares(&alice), 100 * UNIT);
+    assert_eq!(t.vault.get_total_assets(), 1_100 * UNIT);
+    let stats = t.vault.get_vault_stats();
+    assert_eq!((stats.idle_assets, stats.total_assets), (1_000 * UNIT, 1_100 * UNIT));
+    assert_eq!(
+        t.vault.get_deposit_asset(&xlm),
+        Some(DepositAsset {
+            oracle: feed.address.clone(),
+            max_price_age: 600,
+            balance: 1_000 * UNIT,
+        })
+    );
+
+    // USDC keeps its 1:1 path, and other assets must be accepted first
+    let carol = t.user_with_usdc(10 * UNIT);
+    assert_eq!(t.vault.deposit(&carol, &t.usdc.address, &(10 * UNIT), &0), 10 * UNIT);
+    let eurc = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    assert_eq!(
+        t.vault.try_deposit(&alice, &eurc, &(10 * UNIT), &0),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+
+    // Holdings are valued at the current price, the gain shared by every
+    // holder, and withdrawals pay USDC
+    feed.set_price(&xlm, &20_000_000_000_000, &t.env.ledger().timestamp());
+    assert_eq!(t.vault.get_total_assets(), 1_210 * UNIT);
+    let shares = t.vault.get_user_shares(&alice);
+    let assets = t.vault.preview_withdraw(&shares);
+    assert_eq!(assets, 100 * UNIT * 1_210 / 1_110);
+    assert_eq!(t.vault.withdraw(&alice, &shares, &0), assets);
+    assert_eq!(t.usdc.balance(&alice), assets);
+    assert_eq!(t.vault.get_deposit_asset(&xlm).unwrap().balance, 1_000 * UNIT);
+    assert_eq!(
+        t.vault.try_remove_deposit_asset(&xlm),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+}
+
+#[cfg(feature = "referrals")]
+#[test]
+fn test_referral_counts_deposit_asset_value() {
+    let t = TuxedoFixture::new(&Env::default());
+    t.env.ledger().set_timestamp(10_000);
+    let (xlm, _) = accept_xlm(&t);
+    let alice = Address::generate(&t.env);
+    let referrer = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &xlm).mint(&alice, &(1_000 * UNIT));
+
+    // 1,000 XLM at 0.1 USDC is referred as 100 USDC
+    t.vault.deposit_with_referral(&alice, &xlm, &(1_000 * UNIT), &0, &referrer);
+    let stats = t.vault.get_referral_stats(&referrer);
+    assert_eq!((stats.volume, stats.shares), (100 * UNIT, 100 * UNIT - DEAD_SHARES));
+}
+
+#[test]
+fn test_deposit_asset_rejects_stale_price() {
+    let t = TuxedoFixture::new(&Env::default());
+    t.env.ledger().set_timestamp(10_000);
+    let (xlm, _) = accept_xlm(&t);
+    let alice = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &xlm).mint(&alice, &(2_000 * UNIT));
+    t.vault.deposit(&alice, &xlm, &(1_000 * UNIT), &0);
+
+    // Past the allowed age neither deposits nor the holdings can be priced
+    t.env.ledger().set_timestamp(10_601);
+    assert_eq!(
+        t.vault.try_deposit(&alice, &xlm, &(1_000 * UNIT), &0),
+        Err(Ok(VaultError::StalePrice))
+    );
+    assert_eq!(
+        t.vault.try_get_total_assets().map(|_| ()),
+        Err(Ok(VaultError::StalePrice.into()))
+    );
+    assert_eq!(t.vault.get_deposit_asset(&xlm).unwrap().balance, 1_000 * UNIT);
+}
+
+#[test]
+fn test_agent_swaps_deposited_asset_into_usdc() {
+    let t = TuxedoFixture::new(&Env::default());
+    let (xlm, _) = accept_xlm(&t);
+    let alice = Address::generate(&t.env);
+    StellarAssetClient::new(&t.env, &xlm).mint(&alice, &(1_000 * UNIT));
+    t.vault.deposit(&alice, &xlm, &(1_000 * UNIT), &0);
+
+    let router = t.env.register(MockRouter, ());
+    MockRouterClient::new(&t.env, &router).set_rate(&(RATE_SCALE / 10));
+    t.usdc_admin.mint(&router, &(100 * UNIT));
+    t.vault.set_swap_router(&router);
+    t.vault.set_swap_limit(&xlm, &(2_000 * UNIT));
+    let swap = |amount_in: i128| Swap {
+        token_in: xlm.clone(),
+        token_out: t.usdc.address.clone(),
+        amount_in,
+        min_out: 0,
+        deadline: t.env.ledger().timestamp(),
+    };
+
+    // The swapped XLM leaves the tracked holding, and no more than it can
+    assert_eq!(
+        t.vault.try_agent_execute_swap(&t.agent, &swap(1_001 * UNIT)),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    assert_eq!(t.vault.agent_execute_swap(&t.agent, &swap(1_000 * UNIT)), 100 * UNIT);
+    assert_eq!(t.vault.get_deposit_asset(&xlm).unwrap().balance, 0);
+    assert_eq!(t.vault.get_total_assets(), 100 * UNIT);
+
+    // Emptied, the asset can be dropped
+    t.vault.remove_deposit_asset(&xlm);
+    assert_eq!(t.vault.get_deposit_assets(), vec![&t.env]);
+    assert_eq!(t.vault.get_deposit_asset(&xlm), None);
+}
+
+#[test]
+fn test_vault_shares_are_a_sep41_token() {
+    let t = TuxedoFixture::new(&Env::default());
+    let shares = TokenClient::new(&t.env, &t.vault.address);
+    assert_eq!(shares.decimals(), 7);
+    assert_eq!(shares.name(), String::from_str(&t.env, "Tuxedo Vault Share"));
+    assert_eq!(shares.symbol(), String::from_str(&t.env, "TUX0"));
+
+    let alice = t.user_with_usdc(100 * UNIT);
+    let bob = Address::generate(&t.env);
+    let spender = Address::generate(&t.env);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+    assert_eq!(shares.balance(&alice), t.vault.get_user_shares(&alice));
+
+    // Allowances are spent by transfer_from and burn_from, and expire
+    let expiry = t.env.ledger().sequence() + 100;
+    shares.approve(&alice, &spender, &(30 * UNIT), &expiry);
+    assert_eq!(shares.allowance(&alice, &spender), 30 * UNIT);
+    shares.transfer_from(&spender, &alice, &bob, &(20 * UNIT));
+    assert_eq!(shares.allowance(&alice, &spender), 10 * UNIT);
+    assert_eq!(
+        t.vault.try_transfer_from(&spender, &alice, &bob, &(10 * UNIT + 1)),
+        Err(Ok(VaultError::InsufficientAllowance))
+    );
+    shares.burn_from(&spender, &alice, &(10 * UNIT));
+    assert_eq!(shares.allowance(&alice, &spender), 0);
+
+    shares.approve(&alice, &spender, &UNIT, &expiry);
+    t.env.ledger().with_mut(|li| li.sequence_number = expiry + 1);
+    assert_eq!(shares.allowance(&alice, &spender), 0);
+    assert_eq!(
+        t.vault.try_approve(&alice, &spender, &UNIT, &expiry),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // Burning leaves the USDC to the remaining holders, outside the fee baseline
+    shares.burn(&bob, &(10 * UNIT));
+    assert_eq!(shares.balance(&bob), 10 * UNIT);
+    assert_eq!(t.vault.get_user_shares(&bob), 10 * UNIT);
+    assert_eq!(t.vault.get_total_shares(), 80 * UNIT);
+    assert_eq!(t.vault.get_share_value(), 100 * UNIT / 80);
+    assert_eq!(
+        t.vault.try_distribute_yield(&t.keeper),
+        Err(Ok(VaultError::NoYieldToDistribute))
+    );
+    assert_eq!(
+        t.vault.try_burn(&bob, &(10 * UNIT + 1)),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+}
+
+#[test]
+fn test_tux0_share_token_round_trip() {
+    let t = TuxedoFixture::new(&Env::default());
+    let name = String::from_str(&t.env, "Tuxedo Vault Share");
+    let symbol = String::from_str(&t.env, "TUX0");
+    let tux0 = Tux0Client::new(
+        &t.env,
+        &t.env.register(ShareToken, (t.vault.address.clone(), t.usdc.decimals(), name, symbol)),
+    );
+    t.vault.set_share_token(&tux0.address);
+    assert_eq!(t.vault.get_share_token(), Some(tux0.address.clone()));
+
+    // Deposit mints TUX0
+    let alice = t.user_with_usdc(100 * UNIT);
+    let bob = Address::generate(&t.env);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+    assert_eq!(tux0.balance(&alice), 100 * UNIT - DEAD_SHARES);
+
+    // Shares move on the token itself
+    tux0.transfer(&alice, &bob, &(40 * UNIT));
+    assert_eq!(t.vault.get_user_shares(&bob), 40 * UNIT);
+
+    // So do allowances, which the vault's own ledger no longer keeps
+    let expiry = t.env.ledger().sequence() + 100;
+    assert_eq!(
+        t.vault.try_approve(&alice, &bob, &UNIT, &expiry),
+        Err(Ok(VaultError::AllowanceOnShareToken))
+    );
+    assert_eq!(
+        t.vault.try_transfer_from(&bob, &alice, &bob, &UNIT),
+        Err(Ok(VaultError::AllowanceOnShareToken))
+    );
+
+    // The recipient redeems through the vault
+    t.vault.withdraw(&bob, &(40 * UNIT), &0);
+    assert_eq!(t.usdc.balance(&bob), 40 * UNIT);
+    assert_eq!(tux0.balance(&bob), 0);
+    assert_eq!(tux0.total_supply(), 60 * UNIT);
+    assert_eq!(t.vault.get_total_shares(), 60 * UNIT);
+
+    // Share mode is fixed once shares exist
+    assert_eq!(
+        t.vault.try_set_share_token(&tux0.address),
+        Err(Ok(VaultError::SharesOutstanding))
+    );
+}
+
+#[test]
+fn test_stop_loss_freezes_supply_after_drawdown() {
+    Scenario::new()
+        .deposit("user", 1_000 * UNIT)
+        // Tolerate a 10% drawdown from the high-water mark
+        .then(|t| {
+            t.vault.set_stop_loss(&1_000);
+            assert_eq!(t.vault.get_high_water_mark(), Some(UNIT));
+        })
+        // Funds in the pool still count, so deploying most of the vault is no
+        // drawdown
+        .agent_supply(pct(80))
+        .then(|t| {
+            assert!(!t.vault.check_stop_loss());
+            assert_eq!(t.vault.get_high_water_mark(), Some(UNIT));
+        })
+        // The pool writes off a fifth of the 800 USDC position, 16% of the
+        // vault, and pulling half the rest back records the loss
+        .write_down(pct(20))
+        .agent_withdraw(half())
+        .clear_breaker()
+        .then(|t| {
+            assert!(!t.vault.is_stopped());
+            let preview = t.vault.simulate_strategy(&t.agent, &supply_strategy(t, 10 * UNIT));
+            assert_eq!(preview.error, Some(VaultError::StopLossTriggered as u32));
+            assert_eq!(
+                t.vault.try_agent_execute(&t.agent, &supply_strategy(t, 10 * UNIT)),
+                Err(Ok(VaultError::StopLossTriggered))
+            );
+            assert!(t.vault.check_stop_loss());
+        })
+        // Pulling funds back is still allowed, but supply stays frozen
+        .agent_withdraw(pct(100))
+        .then(|t| {
+            assert!(t.vault.is_stopped());
+            t.vault.clear_stop_loss();
+            assert!(!t.vault.is_stopped());
+        })
+        .agent_supply(pct(10));
+}
+
+#[test]
+fn test_non_standard_token_transfers_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let token = MockFeeTokenClient::new(&env, &env.register(MockFeeToken, ()));
+    let (agent, platform) = (Address::generate(&env), Address::generate(&env));
+    let vault = register_vault(&env, [&admin, &agent, &platform], Some(&token.address), None);
+    let user = Address::generate(&env);
+    token.mint(&user, &(1_000 * UNIT));
+
+    // A 1% transfer fee leaves the vault short on deposit
+    token.set_fee_bps(&100);
+    assert_eq!(
+        vault.try_deposit(&user, &token.address, &(100 * UNIT), &0),
+        Err(Ok(VaultError::TransferShortfall))
+    );
+
+    // So does a token that moves less than asked, on the way out too
+    token.set_fee_bps(&0);
+    let shares = vault.deposit(&user, &token.address, &(100 * UNIT), &0);
+    token.set_cap(&(50 * UNIT));
+    assert_eq!(
+        vault.try_deposit(&user, &token.address, &(100 * UNIT), &0),
+        Err(Ok(VaultError::TransferShortfall))
+    );
+    assert_eq!(
+        vault.try_withdraw(&user, &shares, &0),
+        Err(Ok(VaultError::TransferShortfall))
+    );
+    assert_eq!(vault.get_user_shares(&user), shares);
+}
+
+#[test]
+fn test_management_fee_dilutes_over_a_year() {
+    let t = TuxedoFixture::new(&Env::default());
+    assert_eq!(t.vault.get_management_fee_bps(), 0);
+    // Capped at 2% a year in code
+    assert_eq!(
+        t.vault.try_set_management_fee_bps(&201),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.set_management_fee_bps(&50);
+    assert_eq!(t.vault.get_last_accrual(), Some(t.env.ledger().timestamp()));
+
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    let total_shares = t.vault.get_total_shares();
+
+    // No time has passed, so nothing is owed
+    assert_eq!(t.vault.accrue_fees(), 0);
+    assert_eq!(t.vault.get_user_shares(&t.platform), 0);
+
+    // A year at 0.5% hands the platform 0.5% of the supply, and so of the
+    // vault, without moving any USDC
+    t.advance_time(365 * 24 * 60 * 60);
+    let preview = t.vault.preview_withdraw(&t.vault.get_user_shares(&alice));
+    let fee_shares = t.vault.accrue_fees();
+    assert_eq!(fee_shares, total_shares * 50 / 9_950);
+    assert_eq!(t.vault.get_user_shares(&t.platform), fee_shares);
+    assert_eq!(t.vault.get_total_shares(), total_shares + fee_shares);
+    assert_eq!(t.vault.get_total_assets(), 1_000 * UNIT);
+    assert_eq!(t.vault.get_share_value(), 995 * UNIT / 1_000);
+    assert_eq!(t.vault.preview_withdraw(&fee_shares), 5 * UNIT - 1);
+    // Previews already counted the pending fee
+    assert_eq!(t.vault.preview_withdraw(&t.vault.get_user_shares(&alice)), preview);
+
+    // Accruing twice in one ledger mints nothing more
+    assert_eq!(t.vault.accrue_fees(), 0);
+    assert_eq!(t.vault.get_user_shares(&t.platform), fee_shares);
+
+    // Deposits and withdrawals accrue on the way in
+    t.advance_time(30 * 24 * 60 * 60);
+    let shares = t.vault.get_user_shares(&alice);
+    t.vault.withdraw(&alice, &shares, &0);
+    assert!(t.vault.get_user_shares(&t.platform) > fee_shares);
+    assert_eq!(t.vault.get_last_accrual(), Some(t.env.ledger().timestamp()));
+}
+
+#[test]
+fn test_deposit_fee_discounted_by_tux_tier() {
+    let t = TuxedoFixture::new(&Env::default());
+    assert_eq!(t.vault.get_deposit_fee_bps(), 0);
+    // Capped at 1% in code
+    assert_eq!(
+        t.vault.try_set_deposit_fee_bps(&101),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.set_deposit_fee_bps(&100);
+
+    let tux = MockTierOracleClient::new(&t.env, &t.env.register(MockTierOracle, ()));
+    let users: Vec<Address> = (0..5).map(|_| t.user_with_usdc(1_000 * UNIT)).collect();
+    for (tier, user) in users.iter().enumerate() {
+        tux.set_tier(user, &(tier as u32 * 2).min(5));
+    }
+
+    // Without a TUX token, or with one that can't rank users, no one gets a
+    // discount
+    assert_eq!(t.vault.get_tux_token(), None);
+    for user in &users {
+        assert_eq!(t.vault.get_user_deposit_fee_bps(user), 100);
+    }
+    t.vault.set_tux_token(&t.usdc.address);
+    assert_eq!(t.vault.get_user_deposit_fee_bps(&users[4]), 100);
+
+    // Tiers 0, 2, 4 and 5: no discount, Silver 25%, then Gold's 50% for
+    // every tier above
+    t.vault.set_tux_token(&tux.address);
+    assert_eq!(t.vault.get_tux_token(), Some(tux.address.clone()));
+    for (user, fee_bps) in users.iter().zip([100, 75, 50, 50, 50]) {
+        assert_eq!(t.vault.get_user_deposit_fee_bps(user), fee_bps);
+
+        let platform_before = t.usdc.balance(&t.platform);
+        let deposits_before = t.vault.get_vault_stats().initial_deposits;
+        let preview = t.vault.preview_deposit(&(1_000 * UNIT));
+        let shares = t.vault.deposit(user, &t.usdc.address, &(1_000 * UNIT), &0);
+
+        // The fee goes to the platform and buys no shares
+        let fee = 1_000 * UNIT * fee_bps as i128 / 10_000;
+        assert_eq!(t.usdc.balance(&t.platform) - platform_before, fee);
+        assert_eq!(
+            t.vault.get_vault_stats().initial_deposits - deposits_before,
+            1_000 * UNIT - fee
+        );
+        assert_eq!(t.vault.get_total_assets(), t.vault.get_vault_stats().initial_deposits);
+        // Previews charge the full fee, so holders get at least as much
+        assert!(shares >= preview);
+    }
+
+    // Tier 1 is Bronze's 10% off
+    tux.set_tier(&users[0], &1);
+    assert_eq!(t.vault.get_user_deposit_fee_bps(&users[0]), 90);
+}
+
+#[test]
+fn test_early_withdrawal_penalty_during_lockup() {
+    let t = TuxedoFixture::new(&Env::default());
+    assert_eq!(t.vault.get_lockup(), (0, 0));
+    // Capped at 5% in code
+    assert_eq!(
+        t.vault.try_set_lockup(&86_400, &501),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // Shares deposited before the lock-up are never locked
+    let bob = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&bob, &t.usdc.address, &(1_000 * UNIT), &0);
+    let week = 7 * 86_400;
+    t.vault.set_lockup(&week, &200);
+    assert_eq!(t.vault.get_lockup(), (week, 200));
+    assert_eq!(t.vault.get_unlock_time(&bob), 0);
+
+    let alice = t.user_with_usdc(2_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    let unlock = t.env.ledger().timestamp() + week;
+    assert_eq!(t.vault.get_unlock_time(&alice), unlock);
+
+    // Leaving early costs 2%, which stays with the remaining holders without
+    // counting as yield
+    assert_eq!(t.vault.max_withdraw(&alice), 980 * UNIT);
+    assert_eq!(t.vault.withdraw(&alice, &(100 * UNIT), &0), 98 * UNIT);
+    let (total_assets, total_shares) = (t.vault.get_total_assets(), t.vault.get_total_shares());
+    assert!(t.vault.get_share_value() > UNIT);
+    assert_eq!(
+        t.vault.try_distribute_yield(&t.keeper),
+        Err(Ok(VaultError::NoYieldToDistribute))
+    );
+
+    // Exact-asset withdrawals burn shares for the penalty on top
+    let shares = t.vault.withdraw_assets(&alice, &(98 * UNIT));
+    assert_eq!(
+        shares,
+        (100 * UNIT * total_shares + total_assets - 1) / total_assets
+    );
+
+    // Locked shares stay locked when transferred
+    let carol = Address::generate(&t.env);
+    t.vault.transfer(&alice, &carol, &(10 * UNIT));
+    assert_eq!(t.vault.get_unlock_time(&carol), unlock);
+
+    // Once unlocked, withdrawals pay in full
+    t.advance_time(week);
+    assert_eq!(t.vault.get_unlock_time(&alice), t.env.ledger().timestamp());
+    let assets = t.vault.preview_withdraw(&(10 * UNIT));
+    assert_eq!(t.vault.withdraw(&alice, &(10 * UNIT), &0), assets);
+
+    // Topping up locks the new shares without restarting the lock on the
+    // unlocked ones: the unlock time moves forward by the new shares' weight
+    let held = t.vault.get_user_shares(&alice);
+    let added = t.vault.deposit(&alice, &t.usdc.address, &(500 * UNIT), &0);
+    let now = t.env.ledger().timestamp();
+    let age = (week as i128 * added / (held + added)) as u64;
+    assert_eq!(t.vault.get_unlock_time(&alice), now + age);
+    assert!(age < week / 2);
+
+    // Lifting the lock-up frees every share
+    t.vault.set_lockup(&0, &0);
+    assert_eq!(t.vault.get_unlock_time(&alice), 0);
+    let assets = t.vault.preview_withdraw(&held);
+    assert_eq!(t.vault.withdraw(&alice, &held, &0), assets);
+}
+
+#[cfg(feature = "history")]
+#[test]
+fn test_user_history_keeps_the_last_operations() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(10_000 * UNIT);
+    assert_eq!(t.vault.get_user_history(&alice).len(), 0);
+
+    // 21 operations: deposits of 100, 101, ... USDC, each but the last
+    // followed by half of it withdrawn, a minute apart
+    let mut records = std::vec::Vec::new();
+    for i in 0..11 {
+        let amount = (100 + i) * UNIT;
+        let share_value = t.vault.get_share_value();
+        let shares = t.vault.deposit(&alice, &t.usdc.address, &amount, &0);
+        records.push(DepositRecord {
+            amount,
+            shares,
+            share_value,
+            timestamp: t.env.ledger().timestamp(),
+        });
+        t.advance_time(60);
+        if i == 10 {
+            break;
+        }
+
+        let share_value = t.vault.get_share_value();
+        let assets = t.vault.withdraw(&alice, &(shares / 2), &0);
+        records.push(DepositRecord {
+            amount: -assets,
+            shares: -(shares / 2),
+            share_value,
+            timestamp: t.env.ledger().timestamp(),
+        });
+        t.advance_time(60);
+    }
+    assert_eq!(records.len(), 21);
+
+    // The oldest record wrapped out and the rest are kept oldest first
+    let history = t.vault.get_user_history(&alice);
+    assert_eq!(history.len(), HISTORY_LEN);
+    for (record, expected) in history.iter().zip(&records[1..]) {
+        assert_eq!(&record, expected);
+    }
+
+    // A full history stays around 3 KB, and lives as long as share balances
+    assert!(history.clone().to_xdr(&t.env).len() <= 3_200);
+    t.env.as_contract(&t.vault.address, || {
+        let key = (symbol_short!("history"), alice.clone());
+        assert_eq!(t.env.storage().persistent().get_ttl(&key), 120 * 17_280);
+    });
+}
+
+#[test]
+fn test_holder_count_and_user_assets() {
+    let t = TuxedoFixture::new(&Env::default());
+    let stats = t.vault.get_vault_stats();
+    assert_eq!((stats.holder_count, stats.idle_assets), (0, 0));
+
+    // The dead shares minted with the first deposit don't count as a holder
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let bob = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    assert_eq!(t.vault.get_holder_count(), 1);
+    t.vault.deposit(&bob, &t.usdc.address, &(500 * UNIT), &0);
+    t.vault.deposit(&bob, &t.usdc.address, &(500 * UNIT), &0);
+    assert_eq!(t.vault.get_holder_count(), 2);
+
+    // Yield makes shares worth an uneven amount, rounded down like a withdrawal
+    t.simulate_yield(333 * UNIT);
+    let stats = t.vault.get_vault_stats();
+    assert_eq!(stats.idle_assets, stats.total_assets);
+    assert_eq!(stats.deployed_assets, 0);
+    assert_eq!(stats.platform_fee_bps, 200);
+    let shares = t.vault.get_user_shares(&alice);
+    assert_eq!(
+        t.vault.get_user_assets(&alice),
+        shares * stats.share_value / 10_000_000
+    );
+    assert_eq!(t.vault.get_user_assets(&alice), t.vault.preview_withdraw(&shares));
+
+    // A partial withdrawal keeps the holder, a full one drops them
+    t.vault.withdraw(&alice, &(shares / 2), &0);
+    assert_eq!(t.vault.get_holder_count(), 2);
+    let assets = t.vault.get_user_assets(&alice);
+    assert_eq!(t.vault.withdraw_all(&alice), assets);
+    assert_eq!(t.vault.get_user_assets(&alice), 0);
+    assert_eq!(t.vault.get_vault_stats().holder_count, 1);
+
+    // Transfers count a new holder in and an emptied one out
+    let carol = Address::generate(&t.env);
+    t.vault.transfer(&bob, &carol, &(100 * UNIT));
+    assert_eq!(t.vault.get_holder_count(), 2);
+    let rest = t.vault.get_user_shares(&bob);
+    t.vault.transfer(&bob, &carol, &rest);
+    assert_eq!(t.vault.get_holder_count(), 1);
+}
+
+#[cfg(feature = "history")]
+#[test]
+fn test_estimated_apy_from_share_value_history() {
+    let t = TuxedoFixture::new(&Env::default());
+    assert_eq!(t.vault.get_share_value_history().len(), 0);
+    assert_eq!(t.vault.get_estimated_apy(), 0);
+
+    let alice = t.user_with_usdc(2_000 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    let start = t.env.ledger().timestamp();
+    assert_eq!(t.vault.get_share_value_history(), vec![&t.env, (start, UNIT)]);
+    assert_eq!(t.vault.get_estimated_apy(), 0);
+
+    // Snapshots are at most 6 hours apart
+    t.advance_time(6 * 60 * 60 - 1);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+    assert_eq!(t.vault.get_share_value_history().len(), 1);
+    t.vault.withdraw(&alice, &(100 * UNIT), &0);
+    assert_eq!(t.vault.get_share_value_history().len(), 1);
+
+    // 0.1% a day for a week is 36.5% a year without compounding, less the
+    // keeper reward and platform fee taken out of each day's yield
+    t.advance_time(18 * 60 * 60 + 1);
+    for day in 1..=7 {
+        t.simulate_yield(t.vault.get_total_assets() / 1_000);
+        t.vault.distribute_yield(&t.keeper);
+        let history = t.vault.get_share_value_history();
+        assert_eq!(history.last().unwrap().0, start + day * 24 * 60 * 60);
+        t.advance_time(24 * 60 * 60);
+    }
+    let history = t.vault.get_share_value_history();
+    assert_eq!(history.len(), 8);
+    let last = history.last().unwrap();
+    let apy = t.vault.get_estimated_apy();
+    assert_eq!(apy, (last.1 - UNIT) * 10_000 * 365 / (7 * UNIT));
+    assert!(apy > 3_650 * 97 / 100 && apy < 3_650, "apy {apy}");
+
+    // The oldest snapshots drop out once a week's worth is kept
+    for _ in 0..30 {
+        t.advance_time(6 * 60 * 60);
+        t.vault.deposit(&alice, &t.usdc.address, &(10 * UNIT), &0);
+    }
+    let history = t.vault.get_share_value_history();
+    assert_eq!(history.len(), 29);
+    assert_eq!(history.first().unwrap().0, t.env.ledger().timestamp() - 7 * 24 * 60 * 60);
+    // No yield in the window, so no return
+    assert_eq!(t.vault.get_estimated_apy(), 0);
+}
+
+#[test]
+fn test_blacklisted_user_can_only_withdraw() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let shares = t.vault.deposit(&alice, &t.usdc.address, &(500 * UNIT), &0);
+
+    t.vault.add_to_blacklist(&alice);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert!(t.vault.is_blacklisted(&alice));
+
+    // No deposits from the address, to it, or on its behalf
+    let friend = t.user_with_usdc(100 * UNIT);
+    assert_eq!(
+        t.vault.try_deposit(&alice, &t.usdc.address, &(100 * UNIT), &0),
+        Err(Ok(VaultError::Blacklisted))
+    );
+    assert_eq!(
+        t.vault.try_deposit_for(&friend, &alice, &t.usdc.address, &(100 * UNIT)),
+        Err(Ok(VaultError::Blacklisted))
+    );
+    assert_eq!(
+        t.vault.try_deposit_for(&alice, &friend, &t.usdc.address, &(100 * UNIT)),
+        Err(Ok(VaultError::Blacklisted))
+    );
+
+    // Shares it already holds can always be withdrawn
+    let assets = t.vault.preview_withdraw(&(shares / 2));
+    assert_eq!(t.vault.withdraw(&alice, &(shares / 2), &0), assets);
+    t.vault.withdraw_all(&alice);
+    assert_eq!(t.vault.get_user_shares(&alice), 0);
+    assert_eq!(t.usdc.balance(&alice), 1_000 * UNIT - DEAD_SHARES);
+
+    t.vault.remove_from_blacklist(&alice);
+    assert!(!t.vault.is_blacklisted(&alice));
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+}
+
+#[test]
+fn test_whitelist_mode_limits_deposits() {
+    let t = TuxedoFixture::new(&Env::default());
+    let tester = t.user_with_usdc(1_000 * UNIT);
+    let outsider = t.user_with_usdc(1_000 * UNIT);
+    assert!(!t.vault.is_whitelist_mode());
+
+    // The list is ignored while the mode is off
+    t.vault.add_to_whitelist(&vec![&t.env, tester.clone()]);
+    assert!(t.vault.is_whitelisted(&tester));
+    assert!(!t.vault.is_whitelisted(&outsider));
+    t.vault.deposit(&outsider, &t.usdc.address, &(100 * UNIT), &0);
+
+    // With it on, only whitelisted addresses receive shares, however funded
+    t.vault.set_whitelist_mode(&true);
+    assert!(t.vault.is_whitelist_mode());
+    t.vault.deposit(&tester, &t.usdc.address, &(100 * UNIT), &0);
+    assert_eq!(
+        t.vault.try_deposit(&outsider, &t.usdc.address, &(100 * UNIT), &0),
+        Err(Ok(VaultError::NotWhitelisted))
+    );
+    assert_eq!(
+        t.vault.try_deposit_for(&tester, &outsider, &t.usdc.address, &(100 * UNIT)),
+        Err(Ok(VaultError::NotWhitelisted))
+    );
+    t.vault.deposit_for(&outsider, &tester, &t.usdc.address, &(100 * UNIT));
+
+    // Withdrawals never depend on the list
+    let shares = t.vault.get_user_shares(&outsider);
+    t.vault.withdraw(&outsider, &shares, &0);
+
+    t.vault.remove_from_whitelist(&vec![&t.env, tester.clone()]);
+    assert!(!t.vault.is_whitelisted(&tester));
+    assert_eq!(
+        t.vault.try_deposit(&tester, &t.usdc.address, &(100 * UNIT), &0),
+        Err(Ok(VaultError::NotWhitelisted))
+    );
+
+    // Back to public deposits
+    t.vault.set_whitelist_mode(&false);
+    t.vault.deposit(&outsider, &t.usdc.address, &(100 * UNIT), &0);
+}
+
+#[test]
+fn test_strategy_history_wraps_around() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(10_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(10_000 * UNIT), &0);
+    assert_eq!(t.vault.get_strategy_history().len(), 0);
+
+    // Supply 1..=60 USDC in turn, one strategy per ledger
+    let total = STRATEGY_HISTORY_LEN + 10;
+    for i in 1..=total {
+        t.env.ledger().with_mut(|l| {
+            l.sequence_number += 1;
+            l.timestamp += 5;
+        });
+        t.vault.agent_execute(&t.agent, &supply_strategy(&t, i as i128 * UNIT));
+    }
+    assert_eq!(t.vault.get_strategy_count(), total as u64);
+
+    // Only the last STRATEGY_HISTORY_LEN remain, oldest first
+    let history = t.vault.get_strategy_history();
+    assert_eq!(history.len(), STRATEGY_HISTORY_LEN);
+    let oldest = history.first().unwrap();
+    assert_eq!(oldest.amount, 11 * UNIT);
+    assert_eq!(oldest.action, symbol_short!("supply"));
+    assert_eq!(oldest.pool, t.pool);
+    assert_eq!(oldest.asset, t.usdc.address);
+    let newest = history.last().unwrap();
+    assert_eq!(newest.amount, total as i128 * UNIT);
+    assert_eq!(newest.timestamp, t.env.ledger().timestamp());
+    assert_eq!(newest.ledger, t.env.ledger().sequence());
+    assert_eq!(newest.ledger - oldest.ledger, STRATEGY_HISTORY_LEN - 1);
+
+    // Batched strategies are recorded one by one
+    let withdraw = Strategy {
+        action: symbol_short!("withdraw"),
+        request_type: RequestType::WithdrawCollateral as u32,
+        ..supply_strategy(&t, 5 * UNIT)
+    };
+    t.vault
+        .agent_execute_many(&t.agent, &vec![&t.env, withdraw.clone(), withdraw]);
+    assert_eq!(t.vault.get_strategy_count(), total as u64 + 2);
+    let history = t.vault.get_strategy_history();
+    assert_eq!(history.len(), STRATEGY_HISTORY_LEN);
+    assert_eq!(history.first().unwrap().amount, 13 * UNIT);
+    assert_eq!(history.last().unwrap().action, symbol_short!("withdraw"));
+}
+
+#[cfg(feature = "withdrawal-queue")]
+#[test]
+fn test_withdraw_queue_partial_fulfillment() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let bob = t.user_with_usdc(1_000 * UNIT);
+    let carol = t.user_with_usdc(1_000 * UNIT);
+    for user in [&alice, &bob, &carol] {
+        t.vault.deposit(user, &t.usdc.address, &(1_000 * UNIT), &0);
+    }
+
+    // Most of the USDC goes to the pool, and 150 of yield accrues there
+    t.vault.set_reserve_bps(&0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 2_000 * UNIT));
+    MockBlendPoolClient::new(&t.env, &t.pool).accrue(&750);
+    let withdraw = |amount: i128| Strategy {
+        action: symbol_short!("withdraw"),
+        request_type: RequestType::WithdrawCollateral as u32,
+        ..supply_strategy(&t, amount)
+    };
+    t.vault.agent_execute(&t.agent, &withdraw(UNIT));
+    assert_eq!(t.vault.get_deployed_assets(), 2_149 * UNIT);
+
+    // Requests are valued with the pool position, at 1.05 USDC a share
+    let alice_shares = t.vault.get_user_shares(&alice);
+    let bob_shares = t.vault.get_user_shares(&bob) / 2;
+    let alice_assets = t.vault.request_withdraw(&alice, &alice_shares);
+    assert_eq!(alice_assets, alice_shares * 105 / 100);
+    let bob_assets = t.vault.request_withdraw(&bob, &bob_shares);
+    assert_eq!(bob_assets, bob_shares * 105 / 100);
+    // Dust requests can't crowd the queue
+    assert_eq!(
+        t.vault.try_request_withdraw(&carol, &(9 * UNIT)),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.request_withdraw(&carol, &(100 * UNIT));
+    assert_eq!(
+        t.vault.get_withdraw_queue(),
+        vec![&t.env, alice.clone(), bob.clone(), carol.clone()]
+    );
+    // The queue lives as long as the requests in it
+    t.env.as_contract(&t.vault.address, || {
+        let ttl = t.env.storage().persistent().get_ttl(&symbol_short!("WD_QUEUE"));
+        assert_eq!(ttl, 120 * 17_280);
+    });
+    assert_eq!(t.vault.get_user_shares(&alice), 0);
+    assert_eq!(
+        t.vault.try_request_withdraw(&bob, &UNIT),
+        Err(Ok(VaultError::WithdrawPending))
+    );
+
+    // Carol changes her mind and gets her shares back
+    let carol_shares = t.vault.get_user_shares(&carol);
+    t.vault.cancel_withdraw(&carol);
+    assert_eq!(t.vault.get_user_shares(&carol), carol_shares + 100 * UNIT);
+    assert_eq!(t.vault.get_withdraw_request(&carol), None);
+    assert_eq!(
+        t.vault.try_cancel_withdraw(&carol),
+        Err(Ok(VaultError::NoWithdrawRequest))
+    );
+
+    // Idle USDC covers nobody yet
+    assert_eq!(t.vault.fulfill_withdrawals(&t.agent, &10), 0);
+    assert_eq!(
+        t.vault.try_claim_withdraw(&alice),
+        Err(Ok(VaultError::NotClaimable))
+    );
+
+    // Enough for Alice but not for Bob after her
+    t.vault.agent_execute(&t.agent, &withdraw(500 * UNIT));
+    let total_shares = t.vault.get_total_shares();
+    assert_eq!(t.vault.fulfill_withdrawals(&t.agent, &10), 1);
+    assert_eq!(t.vault.get_withdraw_queue(), vec![&t.env, bob.clone()]);
+    assert_eq!(t.vault.get_total_shares(), total_shares - alice_shares);
+    assert_eq!(t.vault.get_claimable_withdrawals(), alice_assets);
+    assert_eq!(t.vault.get_total_assets(), 1_501 * UNIT - alice_assets);
+    assert_eq!(
+        t.vault.try_cancel_withdraw(&alice),
+        Err(Ok(VaultError::AlreadyFulfilled))
+    );
+
+    // Set-aside USDC is the user's whenever they claim it
+    assert_eq!(t.vault.claim_withdraw(&alice), alice_assets);
+    assert_eq!(t.usdc.balance(&alice), alice_assets);
+    assert_eq!(t.vault.get_claimable_withdrawals(), 0);
+    assert_eq!(
+        t.vault.try_claim_withdraw(&alice),
+        Err(Ok(VaultError::NoWithdrawRequest))
+    );
+
+    // The rest of the queue once more liquidity comes back, which the admin
+    // can fulfill too, though nobody else can
+    t.vault.agent_execute(&t.agent, &withdraw(100 * UNIT));
+    assert_eq!(
+        t.vault.try_fulfill_withdrawals(&carol, &10),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+    assert_eq!(t.vault.fulfill_withdrawals(&t.admin, &10), 1);
+    assert_eq!(t.vault.get_withdraw_queue().len(), 0);
+    assert_eq!(t.vault.claim_withdraw(&bob), bob_assets);
+    assert_eq!(t.usdc.balance(&bob), bob_assets);
+    assert_eq!(t.vault.get_withdraw_request(&bob), None);
+}
+
+#[test]
+fn test_pool_loss_is_shared_by_every_withdrawal() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let bob = t.user_with_usdc(1_000 * UNIT);
+    let carol = t.user_with_usdc(1_000 * UNIT);
+    for user in [&alice, &bob, &carol] {
+        t.vault.deposit(user, &t.usdc.address, &(1_000 * UNIT), &0);
+    }
+
+    // With every USDC in the pool there is nothing to pay out. The admin is
+    // already handling the loss, so the breaker stays out of the way
+    t.vault.set_reserve_bps(&0);
+    t.vault.set_breaker_bps(&0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 3_000 * UNIT));
+    assert_eq!(
+        t.vault.try_withdraw_all(&alice),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+    assert_eq!(
+        t.vault.try_withdraw_assets(&alice, &UNIT),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+
+    // The pool writes off 10% and the admin brings back what is left
+    MockBlendPoolClient::new(&t.env, &t.pool).write_down(&1_000);
+    assert_eq!(t.vault.emergency_recall(&t.pool), 2_700 * UNIT);
+    assert_eq!(t.vault.get_share_value(), 9 * UNIT / 10);
+
+    // Everyone takes the same 10% haircut, the last one out included
+    assert_eq!(
+        t.vault.withdraw_all(&alice),
+        (1_000 * UNIT - DEAD_SHARES) * 9 / 10
+    );
+    assert_eq!(t.vault.withdraw_all(&bob), 900 * UNIT);
+    assert_eq!(t.vault.withdraw_all(&carol), 900 * UNIT);
+    assert_eq!(t.vault.get_total_shares(), DEAD_SHARES);
+    assert_eq!(t.vault.get_total_assets(), DEAD_SHARES * 9 / 10);
+}
+
+#[test]
+fn test_breaker_trips_on_share_value_drop() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 500 * UNIT));
+    let pool = MockBlendPoolClient::new(&t.env, &t.pool);
+
+    assert_eq!(t.vault.get_breaker_bps(), 500);
+    assert_eq!(
+        t.vault.try_set_breaker_bps(&10_001),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // A 1% dip stays under the 5% default
+    pool.write_down(&200);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, UNIT));
+    assert!(!t.vault.is_breaker_tripped());
+    assert_eq!(t.vault.get_pause_state(), PauseState::Active);
+
+    // A 10% drop trips it: the call that saw it completes, then the vault halts
+    pool.write_down(&2_000);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, UNIT));
+    assert!(t.vault.is_breaker_tripped());
+    assert_eq!(t.vault.get_pause_state(), PauseState::FullyPaused);
+    assert_eq!(
+        t.vault.try_withdraw(&user, &UNIT, &0),
+        Err(Ok(VaultError::Paused))
+    );
+    assert_eq!(
+        t.vault.try_deposit(&user, &t.usdc.address, &UNIT, &0),
+        Err(Ok(VaultError::Paused))
+    );
+
+    // A plain unpause can't get around it; only clearing the breaker can
+    assert_eq!(t.vault.try_unpause(), Err(Ok(VaultError::BreakerTripped)));
+    t.vault.clear_breaker();
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert!(!t.vault.is_breaker_tripped());
+    assert_eq!(t.vault.get_pause_state(), PauseState::Active);
+
+    // The written-down value is the new reference
+    t.vault.withdraw(&user, &(100 * UNIT), &0);
+    assert!(!t.vault.is_breaker_tripped());
+
+    // With the threshold at zero the breaker is off
+    t.vault.set_breaker_bps(&0);
+    pool.write_down(&5_000);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, UNIT));
+    assert!(!t.vault.is_breaker_tripped());
+}
+
+#[test]
+fn test_claim_emissions_follows_route() {
+    let t = TuxedoFixture::new(&Env::default());
+    let blnd = t.env.register_stellar_asset_contract_v2(t.admin.clone()).address();
+    let blnd_balance = |address: &Address| TokenClient::new(&t.env, &blnd).balance(address);
+    let pool = MockBlendPoolClient::new(&t.env, &t.pool);
+    StellarAssetClient::new(&t.env, &blnd).mint(&t.pool, &(100 * UNIT));
+    let token_ids = vec![&t.env, 1u32];
+
+    let outsider = t.env.register(MockBlendPool, ());
+    assert_eq!(
+        t.vault.try_claim_emissions(&t.agent, &outsider, &token_ids),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+    assert_eq!(
+        t.vault.try_claim_emissions(&t.agent, &t.pool, &vec![&t.env]),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // An approved pool the agent isn't assigned is off limits too
+    let other_agent = Address::generate(&t.env);
+    t.vault.add_agent(&other_agent);
+    assert_eq!(
+        t.vault.try_claim_emissions(&other_agent, &t.pool, &token_ids),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+
+    // By default claimed emissions stay in the vault for the agent to swap
+    assert_eq!(t.vault.get_emissions_route(), EmissionsRoute::Hold);
+    pool.set_emissions(&blnd, &(40 * UNIT));
+    assert_eq!(t.vault.claim_emissions(&t.agent, &t.pool, &token_ids), 40 * UNIT);
+    assert_eq!(t.env.auths()[0].0, t.agent);
+    assert_eq!(blnd_balance(&t.vault.address), 40 * UNIT);
+
+    // Emissions can't be routed as if they were the vault's own assets
+    assert_eq!(
+        t.vault.try_set_emissions_route(&t.usdc.address, &EmissionsRoute::Platform),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+    assert_eq!(
+        t.vault.try_set_emissions_route(&t.vault.address, &EmissionsRoute::Platform),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+
+    // Routed to the platform, which converts them and donates the USDC back
+    t.vault.set_emissions_route(&blnd, &EmissionsRoute::Platform);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_emissions_token(), Some(blnd.clone()));
+    pool.set_emissions(&blnd, &(60 * UNIT));
+    assert_eq!(t.vault.claim_emissions(&t.agent, &t.pool, &token_ids), 60 * UNIT);
+    assert_eq!(blnd_balance(&t.platform), 60 * UNIT);
+    assert_eq!(blnd_balance(&t.vault.address), 40 * UNIT);
+
+    // Nothing left to claim
+    assert_eq!(t.vault.claim_emissions(&t.agent, &t.pool, &token_ids), 0);
+
+    let user = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(100 * UNIT), &0);
+    t.usdc_admin.mint(&t.platform, &(15 * UNIT));
+    assert_eq!(
+        t.vault.try_donate_yield(&0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.donate_yield(&(15 * UNIT));
+    assert_eq!(t.env.auths()[0].0, t.platform);
+    assert_eq!(t.vault.get_total_assets(), 115 * UNIT);
+    assert_eq!(t.vault.get_total_shares(), 100 * UNIT);
+}
+
+#[test]
+fn test_migrate_moves_position_into_target_vault() {
+    let t = TuxedoFixture::new(&Env::default());
+    let target = register_vault(
+        &t.env,
+        [&t.admin, &t.agent, &t.platform],
+        Some(&t.usdc.address),
+        None,
+    );
+    let alice = t.user_with_usdc(1_000 * UNIT);
+    let carol = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(1_000 * UNIT), &0);
+    target.deposit(&carol, &t.usdc.address, &(100 * UNIT), &0);
+    // Yield leaves both share values uneven
+    t.simulate_yield(1_000 * UNIT / 3);
+    t.usdc_admin.mint(&target.address, &(100 * UNIT / 7));
+
+    // Only the admin-approved target is accepted
+    assert_eq!(
+        t.vault.try_migrate(&alice, &target.address),
+        Err(Ok(VaultError::MigrationNotApproved))
+    );
+    assert_eq!(
+        t.vault.try_set_migration_target(&t.vault.address),
+        Err(Ok(VaultError::InvalidAddress))
+    );
+    t.vault.set_migration_target(&target.address);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_migration_target(), Some(target.address.clone()));
+
+    let value = t.vault.preview_withdraw(&t.vault.get_user_shares(&alice));
+    let assets_before = t.vault.get_total_assets() + target.get_total_assets();
+    let shares = t.vault.migrate(&alice, &target.address);
+    assert_eq!(t.env.auths()[0].0, alice);
+
+    // The USDC went straight across, never through alice's wallet
+    assert_eq!(t.vault.get_user_shares(&alice), 0);
+    assert_eq!(target.get_user_shares(&alice), shares);
+    assert_eq!(t.usdc.balance(&alice), 0);
+    let assets_after = t.vault.get_total_assets() + target.get_total_assets();
+    assert!((assets_before - assets_after).abs() <= 1);
+    assert!((target.preview_withdraw(&shares) - value).abs() <= 1);
+
+    // Nothing left to migrate
+    assert_eq!(
+        t.vault.try_migrate(&alice, &target.address),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_uninitialized_vault_returns_typed_error() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(10 * UNIT);
+
+    // The constructor always initializes, so wipe what it wrote to get a vault
+    // in the state an uninitialized deployment would be in
+    t.env.as_contract(&t.vault.address, || {
+        let storage = t.env.storage().instance();
+        for key in ["ADMIN", "PLATFORM", "SHR_TKN"] {
+            storage.remove(&Symbol::new(&t.env, key));
+        }
+        t.env.storage().persistent().remove(&Symbol::new(&t.env, "AGENTS"));
+    });
+    let vault = &t.vault;
+
+    assert_eq!(
+        vault.try_deposit(&user, &t.usdc.address, &UNIT, &0),
+        Err(Ok(VaultError::NotInitialized))
+    );
+    // Plain-valued views fail with the same contract error
+    let not_initialized = Err(Ok(VaultError::NotInitialized.into()));
+    assert_eq!(vault.try_get_admin().map(|_| ()), not_initialized);
+    assert_eq!(vault.try_get_total_assets().map(|_| ()), not_initialized);
+    assert_eq!(vault.try_set_platform_fee_bps(&300), Err(Ok(VaultError::NotInitialized)));
+}
+
+#[test]
+fn test_operator_withdraws_with_live_approval() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    let manager = Address::generate(&t.env);
+    let payout = Address::generate(&t.env);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+    let held = t.vault.get_user_shares(&alice);
+
+    // Without an approval the operator can't touch alice's shares
+    assert!(!t.vault.is_operator(&alice, &manager));
+    assert_eq!(
+        t.vault.try_withdraw_from(&manager, &alice, &UNIT, &payout),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+
+    let expiry = t.env.ledger().sequence() + 100;
+    t.vault.approve_operator(&alice, &manager, &expiry);
+    assert!(t.vault.is_operator(&alice, &manager));
+    assert_eq!(t.vault.withdraw_from(&manager, &alice, &(40 * UNIT), &payout), 40 * UNIT);
+    assert_eq!(t.usdc.balance(&payout), 40 * UNIT);
+    assert_eq!(t.vault.get_user_shares(&alice), held - 40 * UNIT);
+    assert_eq!(t.vault.get_user_shares(&manager), 0);
+
+    // The approval covers alice's shares, not more than she holds
+    assert_eq!(
+        t.vault.try_withdraw_from(&manager, &alice, &(held - 40 * UNIT + 1), &payout),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+
+    // Revoked approvals stop the operator at once
+    t.vault.revoke_operator(&alice, &manager);
+    assert!(!t.vault.is_operator(&alice, &manager));
+    assert_eq!(
+        t.vault.try_withdraw_from(&manager, &alice, &UNIT, &payout),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+
+    // Expired approvals read as absent
+    t.vault.approve_operator(&alice, &manager, &expiry);
+    t.env.ledger().with_mut(|li| li.sequence_number = expiry);
+    t.vault.withdraw_from(&manager, &alice, &UNIT, &payout);
+    t.env.ledger().with_mut(|li| li.sequence_number = expiry + 1);
+    assert!(!t.vault.is_operator(&alice, &manager));
+    assert_eq!(
+        t.vault.try_withdraw_from(&manager, &alice, &UNIT, &payout),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+    assert_eq!(
+        t.vault.try_approve_operator(&alice, &manager, &expiry),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    assert_eq!(t.vault.get_user_shares(&alice), held - 41 * UNIT);
+}
+
+#[cfg(feature = "referrals")]
+#[test]
+fn test_referrers_share_platform_fee_by_referred_shares() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(300 * UNIT);
+    let bob = t.user_with_usdc(100 * UNIT);
+    let carol = t.user_with_usdc(100 * UNIT);
+    let big = Address::generate(&t.env);
+    let small = Address::generate(&t.env);
+    assert_eq!(
+        t.vault.try_set_referral_bps(&5_001),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    t.vault.set_referral_bps(&5_000);
+    assert_eq!(t.vault.get_referral_bps(), 5_000);
+
+    // Nobody refers themselves
+    assert_eq!(
+        t.vault.try_deposit_with_referral(&alice, &t.usdc.address, &(10 * UNIT), &0, &alice),
+        Err(Ok(VaultError::InvalidAddress))
+    );
+
+    t.vault.deposit(&carol, &t.usdc.address, &(100 * UNIT), &0);
+    t.vault.deposit_with_referral(&alice, &t.usdc.address, &(200 * UNIT), &0, &big);
+    t.vault.deposit_with_referral(&alice, &t.usdc.address, &(100 * UNIT), &0, &big);
+    t.vault.deposit_with_referral(&bob, &t.usdc.address, &(100 * UNIT), &0, &small);
+    let stats = t.vault.get_referral_stats(&big);
+    assert_eq!((stats.volume, stats.shares), (300 * UNIT, 300 * UNIT));
+    assert_eq!(t.vault.get_referral_stats(&small).shares, 100 * UNIT);
+    assert_eq!(t.vault.get_referral_stats(&carol), ReferralStats::default());
+
+    // Referrers accrue the referral half of the platform fee in proportion to
+    // the 500 shares their referrals hold; the platform takes the rest
+    t.simulate_yield(50 * UNIT);
+    t.vault.distribute_yield(&t.keeper);
+    let keeper_reward = 50 * UNIT * 10 / 10_000;
+    let platform_fee = (50 * UNIT - keeper_reward) * 200 / 10_000;
+    let pot = platform_fee / 2;
+    let big_payout = pot * 300 / 500;
+    let small_payout = pot * 100 / 500;
+    assert_eq!(t.vault.get_referral_stats(&big).accrued, big_payout);
+    assert_eq!(t.vault.get_referral_stats(&small).accrued, small_payout);
+    assert_eq!(t.usdc.balance(&t.platform), platform_fee - big_payout - small_payout);
+    assert_eq!(t.vault.get_referral_owed(), big_payout + small_payout);
+
+    // Each referrer claims what they accrued; the set-aside USDC never counted
+    // as the vault's
+    let total_assets = t.vault.get_total_assets();
+    assert_eq!(t.vault.claim_referral(&big), big_payout);
+    assert_eq!(t.usdc.balance(&big), big_payout);
+    assert_eq!(t.vault.get_total_assets(), total_assets);
+    let stats = t.vault.get_referral_stats(&big);
+    assert_eq!((stats.accrued, stats.paid), (0, big_payout));
+    assert_eq!(t.vault.try_claim_referral(&big), Err(Ok(VaultError::NotClaimable)));
+
+    // Shares withdrawn or moved away stop counting, so the referrers earn
+    // nothing for Alice's exit and half as much for Bob's half-gone position
+    t.vault.withdraw_all(&alice);
+    t.vault.transfer(&bob, &carol, &(50 * UNIT));
+    assert_eq!(t.vault.get_referral_stats(&big).shares, 0);
+    assert_eq!(t.vault.get_referral_stats(&small).shares, 50 * UNIT);
+    assert_eq!(
+        t.vault.get_referral(&bob),
+        Some(Referral { referrer: small.clone(), shares: 50 * UNIT })
+    );
+    assert_eq!(t.vault.get_referral(&alice), None);
+    t.simulate_yield(10 * UNIT);
+    t.advance_time(3_600);
+    let platform_before = t.usdc.balance(&t.platform);
+    t.vault.distribute_yield(&t.keeper);
+    let platform_fee = t.usdc.balance(&t.platform) - platform_before;
+    assert_eq!(t.vault.get_referral_stats(&big).accrued, 0);
+    let small_accrued = t.vault.get_referral_stats(&small).accrued - small_payout;
+    assert!(small_accrued > 0);
+    assert!(small_accrued <= (platform_fee + small_accrued) / 2 * 50 / 200);
+}
+
+#[cfg(feature = "referrals")]
+#[test]
+fn test_referrer_moves_with_the_latest_referral() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(100 * UNIT);
+    let (first, second) = (Address::generate(&t.env), Address::generate(&t.env));
+    t.vault.deposit(&user, &t.usdc.address, &(10 * UNIT), &0);
+
+    t.vault.deposit_with_referral(&user, &t.usdc.address, &(20 * UNIT), &0, &first);
+    t.vault.deposit_with_referral(&user, &t.usdc.address, &(30 * UNIT), &0, &second);
+    assert_eq!(t.vault.get_referral_stats(&first).shares, 0);
+    assert_eq!(t.vault.get_referral_stats(&first).volume, 20 * UNIT);
+    assert_eq!(t.vault.get_referral_stats(&second).shares, 50 * UNIT);
+}
+
+#[cfg(feature = "referrals")]
+#[test]
+fn test_referrers_are_not_capped() {
+    let t = TuxedoFixture::new(&Env::default());
+    let seed = t.user_with_usdc(10 * UNIT);
+    t.vault.deposit(&seed, &t.usdc.address, &(10 * UNIT), &0);
+    t.vault.set_referral_bps(&5_000);
+
+    // Distributing costs the same however many referrers there are
+    let referrers: std::vec::Vec<Address> =
+        (0..30).map(|_| Address::generate(&t.env)).collect();
+    for referrer in &referrers {
+        let user = t.user_with_usdc(UNIT);
+        t.vault.deposit_with_referral(&user, &t.usdc.address, &UNIT, &0, referrer);
+    }
+    t.simulate_yield(40 * UNIT);
+    t.vault.distribute_yield(&t.keeper);
+    let accrued = t.vault.get_referral_stats(&referrers[0]).accrued;
+    assert!(accrued > 0);
+    for referrer in &referrers {
+        assert_eq!(t.vault.get_referral_stats(referrer).accrued, accrued);
+    }
+}
+
+#[test]
+fn test_withdraw_sweeps_remainder_below_dust_threshold() {
+    let t = TuxedoFixture::new(&Env::default());
+    let seed = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&seed, &t.usdc.address, &(100 * UNIT), &0);
+    assert_eq!(t.vault.get_dust_threshold(), 0);
+    assert_eq!(
+        t.vault.try_set_dust_threshold(&(UNIT + 1)),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    assert_eq!(
+        t.vault.try_set_dust_threshold(&-1),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    let threshold = UNIT / 10;
+    t.vault.set_dust_threshold(&threshold);
+    assert_eq!(t.vault.get_dust_threshold(), threshold);
+
+    // Leaving exactly the threshold, or just above it, keeps the remainder
+    for left in [threshold, threshold + 1] {
+        let user = t.user_with_usdc(10 * UNIT);
+        t.vault.deposit(&user, &t.usdc.address, &(10 * UNIT), &0);
+        assert_eq!(t.vault.withdraw(&user, &(10 * UNIT - left), &0), 10 * UNIT - left);
+        assert_eq!(t.vault.get_user_shares(&user), left);
+        assert_eq!(t.usdc.balance(&user), 10 * UNIT - left);
+    }
+
+    // Just below it, the remainder is burned and paid out with the withdrawal
+    let user = t.user_with_usdc(10 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(10 * UNIT), &0);
+    let holders = t.vault.get_holder_count();
+    let left = threshold - 1;
+    assert_eq!(t.vault.withdraw(&user, &(10 * UNIT - left), &0), 10 * UNIT);
+    assert_eq!(t.vault.get_user_shares(&user), 0);
+    assert_eq!(t.usdc.balance(&user), 10 * UNIT);
+    assert_eq!(t.vault.get_holder_count(), holders - 1);
+    t.env.as_contract(&t.vault.address, || {
+        assert!(!t.env.storage().persistent().has(&(symbol_short!("shares"), user.clone())));
+    });
+
+    // Off again, dust is left where it is
+    t.vault.set_dust_threshold(&0);
+    let user = t.user_with_usdc(10 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(10 * UNIT), &0);
+    t.vault.withdraw(&user, &(10 * UNIT - 1), &0);
+    assert_eq!(t.vault.get_user_shares(&user), 1);
+}
+
+#[test]
+fn test_rescue_tokens_sent_by_mistake() {
+    let t = TuxedoFixture::new(&Env::default());
+    let alice = t.user_with_usdc(100 * UNIT);
+    t.vault.deposit(&alice, &t.usdc.address, &(100 * UNIT), &0);
+
+    // TUX sent to the vault address goes back to whoever sent it
+    let sender = Address::generate(&t.env);
+    t.tux.transfer(&t.admin, &t.vault.address, &(25 * UNIT));
+    t.vault.rescue_tokens(&t.tux.address, &sender, &(25 * UNIT));
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.tux.balance(&sender), 25 * UNIT);
+    assert_eq!(t.tux.balance(&t.vault.address), 0);
+    assert_eq!(
+        t.vault.try_rescue_tokens(&t.tux.address, &sender, &0),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+
+    // Depositor funds never leave this way
+    for token in [&t.usdc.address, &t.vault.address] {
+        assert_eq!(
+            t.vault.try_rescue_tokens(token, &sender, &UNIT),
+            Err(Ok(VaultError::InvalidAsset))
+        );
+    }
+    let blnd = Address::generate(&t.env);
+    t.vault.set_emissions_route(&blnd, &EmissionsRoute::Hold);
+    assert_eq!(
+        t.vault.try_rescue_tokens(&blnd, &sender, &UNIT),
+        Err(Ok(VaultError::InvalidAsset))
+    );
+    assert_eq!(t.usdc.balance(&t.vault.address), 100 * UNIT);
+}
+
+#[test]
+fn test_agents_are_added_and_removed() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    assert_eq!(t.vault.get_agents(), vec![&t.env, t.agent.clone()]);
+
+    // A second agent runs strategies alongside the primary one
+    let rebalancer = Address::generate(&t.env);
+    t.vault.add_agent(&rebalancer);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_agents(), vec![&t.env, t.agent.clone(), rebalancer.clone()]);
+    assert_eq!(t.vault.get_agent(), t.agent);
+    for agent in [&t.admin, &t.platform, &rebalancer] {
+        assert_eq!(t.vault.try_add_agent(agent), Err(Ok(VaultError::InvalidAddress)));
+    }
+    t.vault.assign_pool(&rebalancer, &t.pool);
+
+    t.vault.agent_execute(&rebalancer, &supply_strategy(&t, 100 * UNIT));
+    assert_eq!(t.env.auths()[0].0, rebalancer);
+    t.vault.agent_execute(&t.agent, &supply_strategy(&t, 100 * UNIT));
+    assert_eq!(t.env.auths()[0].0, t.agent);
+
+    // Naming an address outside the set fails even with its signature
+    let outsider = Address::generate(&t.env);
+    assert_eq!(
+        t.vault.try_agent_execute(&outsider, &supply_strategy(&t, UNIT)),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+
+    // The other agent-only calls name their agent the same way
+    let token_ids = vec![&t.env, 1u32];
+    assert_eq!(t.vault.claim_emissions(&rebalancer, &t.pool, &token_ids), 0);
+    assert_eq!(t.env.auths()[0].0, rebalancer);
+    assert_eq!(
+        t.vault.try_claim_emissions(&outsider, &t.pool, &token_ids),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+
+    // A removed agent is rejected from the next call on
+    t.vault.remove_agent(&rebalancer);
+    assert_eq!(t.vault.get_agents(), vec![&t.env, t.agent.clone()]);
+    assert_eq!(
+        t.vault.try_agent_execute(&rebalancer, &supply_strategy(&t, UNIT)),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+    assert_eq!(
+        t.vault.try_agent_execute_many(&rebalancer, &vec![&t.env, supply_strategy(&t, UNIT)]),
+        Err(Ok(VaultError::NotAuthorized))
+    );
+    assert_eq!(t.vault.try_remove_agent(&rebalancer), Err(Ok(VaultError::InvalidAddress)));
+
+    // The last agent can only be replaced
+    assert_eq!(t.vault.try_remove_agent(&t.agent), Err(Ok(VaultError::InvalidAddress)));
+
+    // Promoting a listed agent moves it to primary in place of the old one
+    t.vault.add_agent(&rebalancer);
+    t.vault.set_agent(&rebalancer);
+    assert_eq!(t.vault.get_agents(), vec![&t.env, rebalancer.clone()]);
+    assert_eq!(t.vault.get_agent(), rebalancer);
+    assert_eq!(t.usdc.balance(&t.pool), 200 * UNIT);
+}
+
+#[test]
+fn test_agents_are_capped() {
+    let t = TuxedoFixture::new(&Env::default());
+    for _ in 1..10 {
+        t.vault.add_agent(&Address::generate(&t.env));
+    }
+    assert_eq!(t.vault.get_agents().len(), 10);
+    assert_eq!(
+        t.vault.try_add_agent(&Address::generate(&t.env)),
+        Err(Ok(VaultError::TooManyAgents))
+    );
+}
+
+#[test]
+fn test_agents_only_use_their_assigned_pools() {
+    let t = TuxedoFixture::new(&Env::default());
+    let user = t.user_with_usdc(1_000 * UNIT);
+    t.vault.deposit(&user, &t.usdc.address, &(1_000 * UNIT), &0);
+    let supplier = t.agent.clone();
+    let rebalancer = Address::generate(&t.env);
+    let other_pool = t.env.register(MockBlendPool, ());
+    t.vault.add_agent(&rebalancer);
+    t.vault.add_approved_pool(&other_pool);
+    assert_eq!(t.vault.get_agent_pools(&supplier), vec![&t.env, t.pool.clone()]);
+    assert_eq!(t.vault.get_agent_pools(&rebalancer), vec![&t.env]);
+
+    // Pools go to listed agents, and only once approved
+    let outsider = Address::generate(&t.env);
+    assert_eq!(
+        t.vault.try_assign_pool(&outsider, &other_pool),
+        Err(Ok(VaultError::InvalidAddress))
+    );
+    assert_eq!(
+        t.vault.try_assign_pool(&rebalancer, &Address::generate(&t.env)),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+    t.vault.assign_pool(&rebalancer, &other_pool);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_agent_pools(&rebalancer), vec![&t.env, other_pool.clone()]);
+
+    // Each agent works its own pool and can't touch the other's, even though
+    // both are approved
+    let mut other_supply = supply_strategy(&t, 100 * UNIT);
+    other_supply.pool = other_pool.clone();
+    t.vault.agent_execute(&supplier, &supply_strategy(&t, 100 * UNIT));
+    t.vault.agent_execute(&rebalancer, &other_supply);
+    assert_eq!(
+        t.vault.try_agent_execute(&supplier, &other_supply),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+    assert_eq!(
+        t.vault.try_agent_execute(&rebalancer, &supply_strategy(&t, 100 * UNIT)),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+    assert_eq!(
+        t.vault.try_agent_execute_many(
+            &rebalancer,
+            &vec![&t.env, other_supply.clone(), supply_strategy(&t, UNIT)]
+        ),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+    assert_eq!(t.usdc.balance(&t.pool), 100 * UNIT);
+    assert_eq!(t.usdc.balance(&other_pool), 100 * UNIT);
+
+    // Unassigned, the pool is off limits again
+    t.vault.unassign_pool(&rebalancer, &other_pool);
+    assert_eq!(t.vault.get_agent_pools(&rebalancer), vec![&t.env]);
+    assert_eq!(
+        t.vault.try_agent_execute(&rebalancer, &other_supply),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+    assert_eq!(
+        t.vault.try_unassign_pool(&rebalancer, &other_pool),
+        Err(Ok(VaultError::PoolNotApproved))
+    );
+
+    // A replacement primary agent takes over the pools of the one it replaces
+    let new_supplier = Address::generate(&t.env);
+    t.vault.set_agent(&new_supplier);
+    assert_eq!(t.vault.get_agent_pools(&new_supplier), vec![&t.env, t.pool.clone()]);
+    assert_eq!(t.vault.get_agent_pools(&supplier), vec![&t.env]);
+}
+
+#[cfg(feature = "strategy-timelock")]
+#[test]
+fn test_timelocked_changes_wait_out_the_delay() {
+    let t = TuxedoFixture::new(&Env::default());
+    t.vault.set_change_delay(&(2 * 86_400));
+    assert_eq!(t.vault.get_change_delay(), 2 * 86_400);
+
+    // Once the delay is set, the guarded setters only work through the queue
+    let new_agent = Address::generate(&t.env);
+    assert_eq!(t.vault.try_set_platform_fee_bps(&300), Err(Ok(VaultError::TooSoon)));
+    assert_eq!(t.vault.try_set_agent(&new_agent), Err(Ok(VaultError::TooSoon)));
+    assert_eq!(t.vault.try_set_platform(&new_agent), Err(Ok(VaultError::TooSoon)));
+    assert_eq!(t.vault.try_set_reserve_bps(&1_000), Err(Ok(VaultError::TooSoon)));
+    assert_eq!(t.vault.try_set_change_delay(&0), Err(Ok(VaultError::TooSoon)));
+
+    let fee = t.vault.queue_change(&ConfigChange::PlatformFeeBps(300));
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    let agent = t.vault.queue_change(&ConfigChange::Agent(new_agent.clone()));
+    let eta = t.env.ledger().timestamp() + 2 * 86_400;
+    assert_eq!(
+        t.vault.get_pending_change(&fee),
+        Some(PendingChange {
+            change: ConfigChange::PlatformFeeBps(300),
+            eta,
+        })
+    );
+    assert_eq!(
+        t.vault.try_queue_change(&ConfigChange::PlatformFeeBps(5_000)),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    assert_eq!(
+        t.vault.try_queue_change(&ConfigChange::Agent(t.admin.clone())),
+        Err(Ok(VaultError::InvalidAddress))
+    );
+
+    // Executing before the delay has passed fails
+    t.advance_time(86_400);
+    assert_eq!(t.vault.try_execute_change(&fee), Err(Ok(VaultError::TooSoon)));
+    assert_eq!(t.vault.get_platform_fee_bps(), 200);
+
+    // A cancelled change can't be executed later
+    t.vault.cancel_change(&agent);
+    assert_eq!(t.vault.get_pending_change(&agent), None);
+    t.advance_time(86_400);
+    assert_eq!(t.vault.try_execute_change(&agent), Err(Ok(VaultError::ChangeNotQueued)));
+    assert_eq!(t.vault.try_cancel_change(&agent), Err(Ok(VaultError::ChangeNotQueued)));
+
+    t.vault.execute_change(&fee);
+    assert_eq!(t.env.auths()[0].0, t.admin);
+    assert_eq!(t.vault.get_platform_fee_bps(), 300);
+    assert_eq!(t.vault.get_agent(), t.agent);
+    assert_eq!(t.vault.try_execute_change(&fee), Err(Ok(VaultError::ChangeNotQueued)));
+
+    // Lowering the delay waits out the current one; after that setters apply directly
+    let delay = t.vault.queue_change(&ConfigChange::ChangeDelay(0));
+    t.advance_time(2 * 86_400);
+    t.vault.execute_change(&delay);
+    assert_eq!(t.vault.get_change_delay(), 0);
+    t.vault.set_reserve_bps(&1_000);
+    assert_eq!(t.vault.get_reserve_ratio(), 1_000);
+    assert_eq!(
+        t.vault.try_set_change_delay(&(31 * 86_400)),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+}
@@ -0,0 +1,59 @@
+//! Wasm size check for the vault's feature sets.
+//!
+//! Builds the contract for `wasm32v1-none` with the default features, as
+//! `minimal` and with each optional module alone, each into its own target
+//! directory, and checks that every module the features gate is left out of
+//! the artifact without it. It needs the wasm target and release builds, so it
+//! only runs when asked for:
+//! `cargo test -p tuxedo-vault --test wasm_size -- --ignored --nocapture`
+
+use std::{path::PathBuf, process::Command};
+
+const TARGET: &str = "wasm32v1-none";
+
+/// Build the vault with `features` (no defaults unless listed) and return the
+/// size of the wasm artifact in bytes
+fn wasm_size(name: &str, features: &[&str]) -> u64 {
+    let target_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+    let mut cmd = Command::new(env!("CARGO"));
+    cmd.arg("build")
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .args(["--release", "--target", TARGET, "--no-default-features"])
+        .arg("--target-dir")
+        .arg(&target_dir);
+    if !features.is_empty() {
+        cmd.args(["--features", &features.join(",")]);
+    }
+    let status = cmd.status().expect("failed to run cargo");
+    assert!(status.success(), "{name} build failed");
+
+    let wasm = target_dir.join(TARGET).join("release/tuxedo_vault.wasm");
+    std::fs::metadata(&wasm)
+        .unwrap_or_else(|_| panic!("missing {}", wasm.display()))
+        .len()
+}
+
+#[test]
+#[ignore = "builds the vault wasm six times; needs the wasm32v1-none target"]
+fn test_minimal_wasm_smaller_than_default() {
+    let default = wasm_size("default", &["default"]);
+    let minimal = wasm_size("minimal", &["minimal"]);
+    println!("vault wasm: default {default} bytes, minimal {minimal} bytes");
+    assert!(
+        minimal < default,
+        "minimal build ({minimal} bytes) isn't smaller than default ({default} bytes)"
+    );
+
+    // Each module adds to the minimal build on its own
+    for feature in ["history", "referrals", "withdrawal-queue", "strategy-timelock"] {
+        let size = wasm_size(feature, &[feature]);
+        println!("vault wasm: {feature} alone {size} bytes");
+        assert!(
+            minimal < size && size < default,
+            "{feature} build ({size} bytes) isn't between minimal and default"
+        );
+    }
+}
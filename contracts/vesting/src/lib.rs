@@ -0,0 +1,306 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
+    Vec,
+};
+use tuxedo_common::math;
+
+// ============ Constants ============
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const TOKEN: Symbol = symbol_short!("TOKEN");
+const TREASURY: Symbol = symbol_short!("TREASURY");
+const GRANT_COUNT: Symbol = symbol_short!("GRANTS");
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VestingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InvalidSchedule = 4,
+    GrantNotFound = 5,
+    NotRevocable = 6,
+    AlreadyRevoked = 7,
+    NothingToRelease = 8,
+    MathOverflow = 9,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grant {
+    pub beneficiary: Address,
+    pub total: i128,
+    pub released: i128,
+    pub start: u64,
+    /// Seconds after `start` before anything vests
+    pub cliff: u64,
+    /// Seconds after `start` until the grant is fully vested
+    pub duration: u64,
+    pub revocable: bool,
+    /// Vesting stops at this timestamp once the grant is revoked
+    pub revoked_at: Option<u64>,
+}
+
+// ============ Vesting Contract ============
+#[contract]
+pub struct TuxVesting;
+
+#[contractimpl]
+impl TuxVesting {
+    /// Initialize with the foundation admin, the vested token and the treasury
+    /// that receives unvested tokens from revoked grants
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        treasury: Address,
+    ) -> Result<(), VestingError> {
+        // Check if already initialized
+        if env.storage().instance().has(&ADMIN) {
+            return Err(VestingError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&ADMIN, &admin);
+        env.storage().instance().set(&TOKEN, &token);
+        env.storage().instance().set(&TREASURY, &treasury);
+        env.storage().instance().set(&GRANT_COUNT, &0u32);
+
+        // Emit initialization event
+        env.events().publish(
+            (symbol_short!("vest"), symbol_short!("init")),
+            (admin, token, treasury),
+        );
+
+        Ok(())
+    }
+
+    /// Create a grant (admin only). The full `total` is transferred in from the admin
+    /// so every grant is funded up front. Returns the new grant id.
+    pub fn create_grant(
+        env: Env,
+        beneficiary: Address,
+        total: i128,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+        revocable: bool,
+    ) -> Result<u32, VestingError> {
+        let admin = Self::require_admin(&env)?;
+
+        if total <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+        if duration == 0 || cliff > duration {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        // Fund the grant
+        let token: Address = env.storage().instance().get(&TOKEN).unwrap();
+        token::TokenClient::new(&env, &token).transfer(
+            &admin,
+            &env.current_contract_address(),
+            &total,
+        );
+
+        let grant_id: u32 = env.storage().instance().get(&GRANT_COUNT).unwrap_or(0);
+        let grant = Grant {
+            beneficiary: beneficiary.clone(),
+            total,
+            released: 0,
+            start,
+            cliff,
+            duration,
+            revocable,
+            revoked_at: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&Self::grant_key(grant_id), &grant);
+        env.storage().instance().set(&GRANT_COUNT, &(grant_id + 1));
+
+        // Index the grant under its beneficiary
+        let mut grants = Self::get_grants(env.clone(), beneficiary.clone());
+        grants.push_back(grant_id);
+        env.storage()
+            .persistent()
+            .set(&Self::beneficiary_key(&beneficiary), &grants);
+
+        // Emit grant event
+        env.events().publish(
+            (symbol_short!("vest"), symbol_short!("grant")),
+            (grant_id, beneficiary, total),
+        );
+
+        Ok(grant_id)
+    }
+
+    /// Pay out everything vested and not yet released across the beneficiary's grants
+    pub fn release(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        beneficiary.require_auth();
+
+        let now = env.ledger().timestamp();
+        let mut payout: i128 = 0;
+        for grant_id in Self::get_grants(env.clone(), beneficiary.clone()).iter() {
+            let key = Self::grant_key(grant_id);
+            let mut grant: Grant = env.storage().persistent().get(&key).unwrap();
+
+            let releasable = Self::vested_at(&grant, now)? - grant.released;
+            if releasable > 0 {
+                grant.released += releasable;
+                env.storage().persistent().set(&key, &grant);
+                payout += releasable;
+            }
+        }
+
+        if payout <= 0 {
+            return Err(VestingError::NothingToRelease);
+        }
+
+        let token: Address = env.storage().instance().get(&TOKEN).unwrap();
+        token::TokenClient::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &beneficiary,
+            &payout,
+        );
+
+        // Emit release event
+        env.events().publish(
+            (symbol_short!("vest"), symbol_short!("release")),
+            (beneficiary, payout),
+        );
+
+        Ok(payout)
+    }
+
+    /// Revoke a grant (admin only). The unvested remainder goes to the treasury;
+    /// whatever had vested stays claimable by the beneficiary. Returns the amount returned.
+    pub fn revoke(env: Env, grant_id: u32) -> Result<i128, VestingError> {
+        Self::require_admin(&env)?;
+
+        let key = Self::grant_key(grant_id);
+        let mut grant: Grant = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(VestingError::GrantNotFound)?;
+
+        if !grant.revocable {
+            return Err(VestingError::NotRevocable);
+        }
+        if grant.revoked_at.is_some() {
+            return Err(VestingError::AlreadyRevoked);
+        }
+
+        let now = env.ledger().timestamp();
+        let unvested = grant.total - Self::vested_at(&grant, now)?;
+        grant.revoked_at = Some(now);
+        env.storage().persistent().set(&key, &grant);
+
+        if unvested > 0 {
+            let token: Address = env.storage().instance().get(&TOKEN).unwrap();
+            let treasury: Address = env.storage().instance().get(&TREASURY).unwrap();
+            token::TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &treasury,
+                &unvested,
+            );
+        }
+
+        // Emit revoke event
+        env.events().publish(
+            (symbol_short!("vest"), symbol_short!("revoke")),
+            (grant_id, unvested),
+        );
+
+        Ok(unvested)
+    }
+
+    /// Get grant details
+    pub fn get_grant(env: Env, grant_id: u32) -> Result<Grant, VestingError> {
+        env.storage()
+            .persistent()
+            .get(&Self::grant_key(grant_id))
+            .ok_or(VestingError::GrantNotFound)
+    }
+
+    /// Get the amount vested to date, including anything already released
+    pub fn get_vested(env: Env, grant_id: u32) -> Result<i128, VestingError> {
+        let grant = Self::get_grant(env.clone(), grant_id)?;
+        Self::vested_at(&grant, env.ledger().timestamp())
+    }
+
+    /// Get the amount that `release` would pay out for this grant right now
+    pub fn get_releasable(env: Env, grant_id: u32) -> Result<i128, VestingError> {
+        let grant = Self::get_grant(env.clone(), grant_id)?;
+        Ok(Self::vested_at(&grant, env.ledger().timestamp())? - grant.released)
+    }
+
+    /// Get the ids of every grant held by `beneficiary`
+    pub fn get_grants(env: Env, beneficiary: Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&Self::beneficiary_key(&beneficiary))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the number of grants created
+    pub fn get_grant_count(env: Env) -> u32 {
+        env.storage().instance().get(&GRANT_COUNT).unwrap_or(0)
+    }
+
+    /// Get the treasury receiving revoked tokens
+    pub fn get_treasury(env: Env) -> Address {
+        env.storage().instance().get(&TREASURY).unwrap()
+    }
+
+    /// Get contract admin
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn require_admin(env: &Env) -> Result<Address, VestingError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(VestingError::NotInitialized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Linear vesting after the cliff, frozen at the revocation time
+    fn vested_at(grant: &Grant, timestamp: u64) -> Result<i128, VestingError> {
+        let now = match grant.revoked_at {
+            Some(revoked_at) => timestamp.min(revoked_at),
+            None => timestamp,
+        };
+
+        if now < grant.start.saturating_add(grant.cliff) {
+            return Ok(0);
+        }
+
+        let elapsed = now - grant.start;
+        if elapsed >= grant.duration {
+            return Ok(grant.total);
+        }
+
+        math::muldiv(grant.total, elapsed as i128, grant.duration as i128)
+            .ok_or(VestingError::MathOverflow)
+    }
+
+    fn grant_key(grant_id: u32) -> (Symbol, u32) {
+        (symbol_short!("grant"), grant_id)
+    }
+
+    fn beneficiary_key(beneficiary: &Address) -> (Symbol, Address) {
+        (symbol_short!("benef"), beneficiary.clone())
+    }
+}
+
+mod test;
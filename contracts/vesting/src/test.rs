@@ -0,0 +1,151 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+
+const UNIT: i128 = 10_000_000;
+const YEAR: u64 = 365 * 86_400;
+const START: u64 = 1_000;
+
+struct VestingTest<'a> {
+    env: Env,
+    vesting: TuxVestingClient<'a>,
+    tux: TuxTokenClient<'a>,
+    treasury: Address,
+    beneficiary: Address,
+}
+
+impl<'a> VestingTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| li.timestamp = START);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let beneficiary = Address::generate(&env);
+
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &(1_000_000 * UNIT));
+
+        let vesting = TuxVestingClient::new(&env, &env.register(TuxVesting, ()));
+        vesting.initialize(&admin, &tux.address, &treasury);
+
+        VestingTest {
+            vesting,
+            tux,
+            treasury,
+            beneficiary,
+            env,
+        }
+    }
+
+    /// 4-year grant with a 1-year cliff
+    fn create_grant(&self, revocable: bool) -> u32 {
+        self.vesting.create_grant(
+            &self.beneficiary,
+            &(4_000 * UNIT),
+            &START,
+            &YEAR,
+            &(4 * YEAR),
+            &revocable,
+        )
+    }
+
+    fn jump_to(&self, timestamp: u64) {
+        self.env.ledger().with_mut(|li| li.timestamp = timestamp);
+    }
+}
+
+#[test]
+fn test_nothing_vests_before_cliff() {
+    let t = VestingTest::setup();
+    let id = t.create_grant(false);
+    assert_eq!(t.tux.balance(&t.vesting.address), 4_000 * UNIT);
+
+    t.jump_to(START + YEAR - 1);
+    assert_eq!(t.vesting.get_vested(&id), 0);
+    assert_eq!(
+        t.vesting.try_release(&t.beneficiary),
+        Err(Ok(VestingError::NothingToRelease))
+    );
+
+    // The cliff releases the first year at once
+    t.jump_to(START + YEAR);
+    assert_eq!(t.vesting.release(&t.beneficiary), 1_000 * UNIT);
+    assert_eq!(t.tux.balance(&t.beneficiary), 1_000 * UNIT);
+
+    // Fully vested at the end, minus what was already released
+    t.jump_to(START + 5 * YEAR);
+    assert_eq!(t.vesting.get_releasable(&id), 3_000 * UNIT);
+    assert_eq!(t.vesting.release(&t.beneficiary), 3_000 * UNIT);
+    assert_eq!(t.vesting.get_grant(&id).released, 4_000 * UNIT);
+}
+
+#[test]
+fn test_revoke_mid_vest_returns_unvested() {
+    let t = VestingTest::setup();
+    let id = t.create_grant(true);
+
+    t.jump_to(START + YEAR);
+    t.vesting.release(&t.beneficiary);
+
+    // Halfway through: 2,000 vested, 1,000 of it still unreleased
+    t.jump_to(START + 2 * YEAR);
+    assert_eq!(t.vesting.revoke(&id), 2_000 * UNIT);
+    assert_eq!(t.tux.balance(&t.treasury), 2_000 * UNIT);
+    assert_eq!(t.vesting.get_grant(&id).revoked_at, Some(START + 2 * YEAR));
+
+    assert_eq!(
+        t.vesting.try_revoke(&id),
+        Err(Ok(VestingError::AlreadyRevoked))
+    );
+}
+
+#[test]
+fn test_release_after_revoke_pays_vested_only() {
+    let t = VestingTest::setup();
+    let id = t.create_grant(true);
+
+    t.jump_to(START + 2 * YEAR);
+    t.vesting.revoke(&id);
+
+    // Vesting is frozen at the revocation time
+    t.jump_to(START + 4 * YEAR);
+    assert_eq!(t.vesting.get_vested(&id), 2_000 * UNIT);
+    assert_eq!(t.vesting.release(&t.beneficiary), 2_000 * UNIT);
+    assert_eq!(t.tux.balance(&t.vesting.address), 0);
+    assert_eq!(
+        t.vesting.try_release(&t.beneficiary),
+        Err(Ok(VestingError::NothingToRelease))
+    );
+}
+
+#[test]
+fn test_grant_validation() {
+    let t = VestingTest::setup();
+    let id = t.create_grant(false);
+
+    assert_eq!(
+        t.vesting.try_revoke(&id),
+        Err(Ok(VestingError::NotRevocable))
+    );
+    assert_eq!(
+        t.vesting
+            .try_create_grant(&t.beneficiary, &UNIT, &START, &(2 * YEAR), &YEAR, &true),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(
+        t.vesting
+            .try_create_grant(&t.beneficiary, &0, &START, &0, &YEAR, &true),
+        Err(Ok(VestingError::InvalidAmount))
+    );
+    assert_eq!(
+        t.vesting.try_get_grant(&7),
+        Err(Ok(VestingError::GrantNotFound))
+    );
+}
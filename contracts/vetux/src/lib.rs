@@ -0,0 +1,326 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Map,
+    Symbol,
+};
+
+// ============ Constants ============
+const TUX_TOKEN: Symbol = symbol_short!("TUX_TKN");
+const GLOBAL: Symbol = symbol_short!("GLOBAL");
+const SLOPE_CHANGES: Symbol = symbol_short!("DSLOPES");
+
+/// Lock ends are rounded down to whole weeks so expiries share a few slope changes
+pub const WEEK: u64 = 7 * 86_400;
+
+/// Longest possible lock; a max-length lock of N TUX starts with N voting power
+pub const MAX_LOCK: u64 = 104 * WEEK;
+
+// ============ Errors ============
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VeTuxError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InvalidDuration = 4,
+    LockExists = 5,
+    NoLock = 6,
+    LockExpired = 7,
+    LockNotExpired = 8,
+}
+
+// ============ Data Structures ============
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lock {
+    pub amount: i128,
+    pub end: u64,
+}
+
+/// Sum of all active locks, as `amount × seconds remaining`, at time `ts`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GlobalPoint {
+    pub bias: i128,
+    pub slope: i128, // TUX locked and not yet expired
+    pub ts: u64,
+}
+
+// ============ veTUX Contract ============
+/// Vote-escrowed TUX. Locking `amount` until `end` gives non-transferable voting
+/// power `amount × (end - now) / MAX_LOCK`, decaying linearly to zero at `end`.
+#[contract]
+pub struct VeTux;
+
+#[contractimpl]
+impl VeTux {
+    /// Initialize with the TUX token that gets locked
+    pub fn initialize(env: Env, tux_token: Address) -> Result<(), VeTuxError> {
+        // Check if already initialized
+        if env.storage().instance().has(&TUX_TOKEN) {
+            return Err(VeTuxError::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&TUX_TOKEN, &tux_token);
+        env.storage().instance().set(
+            &GLOBAL,
+            &GlobalPoint {
+                bias: 0,
+                slope: 0,
+                ts: env.ledger().timestamp(),
+            },
+        );
+
+        // Emit initialization event
+        env.events()
+            .publish((symbol_short!("vetux"), symbol_short!("init")), tux_token);
+
+        Ok(())
+    }
+
+    /// Lock `amount` TUX for `duration` seconds, rounded down to a whole week.
+    /// Returns the unlock time.
+    pub fn lock(env: Env, user: Address, amount: i128, duration: u64) -> Result<u64, VeTuxError> {
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(VeTuxError::InvalidAmount);
+        }
+        if Self::get_lock(env.clone(), user.clone()).is_some() {
+            return Err(VeTuxError::LockExists);
+        }
+
+        let end = Self::unlock_time(&env, duration)?;
+        Self::pull_tux(&env, &user, amount)?;
+        Self::write_lock(&env, &user, None, Lock { amount, end });
+
+        // Emit lock event
+        env.events().publish(
+            (symbol_short!("vetux"), symbol_short!("lock")),
+            (user, amount, end),
+        );
+
+        Ok(end)
+    }
+
+    /// Add TUX to an active lock without changing its end
+    pub fn increase_amount(env: Env, user: Address, amount: i128) -> Result<(), VeTuxError> {
+        user.require_auth();
+
+        if amount <= 0 {
+            return Err(VeTuxError::InvalidAmount);
+        }
+        let old = Self::active_lock(&env, &user)?;
+
+        Self::pull_tux(&env, &user, amount)?;
+        let new = Lock {
+            amount: old.amount + amount,
+            end: old.end,
+        };
+        Self::write_lock(&env, &user, Some(old), new.clone());
+
+        // Emit increase event
+        env.events().publish(
+            (symbol_short!("vetux"), symbol_short!("increase")),
+            (user, new.amount, new.end),
+        );
+
+        Ok(())
+    }
+
+    /// Move an active lock's end to `duration` seconds from now, rounded down to a
+    /// whole week. The new end must be later than the current one. Returns it.
+    pub fn extend_lock(env: Env, user: Address, duration: u64) -> Result<u64, VeTuxError> {
+        user.require_auth();
+
+        let old = Self::active_lock(&env, &user)?;
+        let end = Self::unlock_time(&env, duration)?;
+        if end <= old.end {
+            return Err(VeTuxError::InvalidDuration);
+        }
+
+        let new = Lock {
+            amount: old.amount,
+            end,
+        };
+        Self::write_lock(&env, &user, Some(old), new.clone());
+
+        // Emit extend event
+        env.events().publish(
+            (symbol_short!("vetux"), symbol_short!("extend")),
+            (user, new.amount, end),
+        );
+
+        Ok(end)
+    }
+
+    /// Return the locked TUX once the lock has expired
+    pub fn withdraw(env: Env, user: Address) -> Result<i128, VeTuxError> {
+        user.require_auth();
+
+        let lock = Self::get_lock(env.clone(), user.clone()).ok_or(VeTuxError::NoLock)?;
+        if env.ledger().timestamp() < lock.end {
+            return Err(VeTuxError::LockNotExpired);
+        }
+
+        env.storage().persistent().remove(&Self::lock_key(&user));
+
+        let tux_token: Address = env.storage().instance().get(&TUX_TOKEN).unwrap();
+        token::TokenClient::new(&env, &tux_token).transfer(
+            &env.current_contract_address(),
+            &user,
+            &lock.amount,
+        );
+
+        // Emit withdraw event
+        env.events().publish(
+            (symbol_short!("vetux"), symbol_short!("withdraw")),
+            (user, lock.amount),
+        );
+
+        Ok(lock.amount)
+    }
+
+    /// Get a user's current voting power
+    pub fn voting_power(env: Env, user: Address) -> i128 {
+        let now = env.ledger().timestamp();
+        match Self::get_lock(env, user) {
+            Some(lock) if lock.end > now => {
+                lock.amount * (lock.end - now) as i128 / MAX_LOCK as i128
+            }
+            _ => 0,
+        }
+    }
+
+    /// Get the combined voting power of all locks
+    pub fn total_voting_power(env: Env) -> i128 {
+        match env.storage().instance().get::<_, GlobalPoint>(&GLOBAL) {
+            Some(point) => {
+                let slope_changes = Self::slope_changes(&env);
+                let point = Self::roll_forward(point, &slope_changes, env.ledger().timestamp());
+                point.bias / MAX_LOCK as i128
+            }
+            None => 0,
+        }
+    }
+
+    /// Get a user's lock, including an expired one not yet withdrawn
+    pub fn get_lock(env: Env, user: Address) -> Option<Lock> {
+        env.storage().persistent().get(&Self::lock_key(&user))
+    }
+
+    /// Get the locked token
+    pub fn get_tux_token(env: Env) -> Address {
+        env.storage().instance().get(&TUX_TOKEN).unwrap()
+    }
+
+    // ============ Internal Helper Functions ============
+
+    fn unlock_time(env: &Env, duration: u64) -> Result<u64, VeTuxError> {
+        let now = env.ledger().timestamp();
+        let end = (now + duration) / WEEK * WEEK;
+        if end <= now || end > now + MAX_LOCK {
+            return Err(VeTuxError::InvalidDuration);
+        }
+        Ok(end)
+    }
+
+    fn active_lock(env: &Env, user: &Address) -> Result<Lock, VeTuxError> {
+        let lock = Self::get_lock(env.clone(), user.clone()).ok_or(VeTuxError::NoLock)?;
+        if lock.end <= env.ledger().timestamp() {
+            return Err(VeTuxError::LockExpired);
+        }
+        Ok(lock)
+    }
+
+    fn pull_tux(env: &Env, user: &Address, amount: i128) -> Result<(), VeTuxError> {
+        let tux_token: Address = env
+            .storage()
+            .instance()
+            .get(&TUX_TOKEN)
+            .ok_or(VeTuxError::NotInitialized)?;
+        token::TokenClient::new(env, &tux_token).transfer(
+            user,
+            &env.current_contract_address(),
+            &amount,
+        );
+        Ok(())
+    }
+
+    /// Store a user's lock and move its contribution to the global point from `old` to `new`
+    fn write_lock(env: &Env, user: &Address, old: Option<Lock>, new: Lock) {
+        let now = env.ledger().timestamp();
+        let point: GlobalPoint = env.storage().instance().get(&GLOBAL).unwrap();
+        let mut slope_changes = Self::slope_changes(env);
+        let mut point = Self::roll_forward(point, &slope_changes, now);
+
+        // Changes up to now are folded into the point
+        for week in slope_changes.keys().iter() {
+            if week > now {
+                break;
+            }
+            slope_changes.remove(week);
+        }
+
+        if let Some(old) = old {
+            point.bias -= old.amount * (old.end - now) as i128;
+            point.slope -= old.amount;
+            Self::add_slope_change(&mut slope_changes, old.end, -old.amount);
+        }
+        point.bias += new.amount * (new.end - now) as i128;
+        point.slope += new.amount;
+        Self::add_slope_change(&mut slope_changes, new.end, new.amount);
+
+        env.storage().instance().set(&GLOBAL, &point);
+        env.storage().instance().set(&SLOPE_CHANGES, &slope_changes);
+        env.storage().persistent().set(&Self::lock_key(user), &new);
+    }
+
+    /// Decay the global point up to `now`, dropping locks as they expire
+    fn roll_forward(
+        mut point: GlobalPoint,
+        slope_changes: &Map<u64, i128>,
+        now: u64,
+    ) -> GlobalPoint {
+        for (week, change) in slope_changes.iter() {
+            if week <= point.ts {
+                continue;
+            }
+            if week > now {
+                break;
+            }
+            point.bias -= point.slope * (week - point.ts) as i128;
+            point.slope -= change;
+            point.ts = week;
+        }
+        point.bias -= point.slope * (now - point.ts) as i128;
+        point.ts = now;
+        point
+    }
+
+    /// TUX unlocking at each week boundary. Kept in one entry: at most
+    /// `MAX_LOCK / WEEK` future weeks can be pending at once.
+    fn slope_changes(env: &Env) -> Map<u64, i128> {
+        env.storage()
+            .instance()
+            .get(&SLOPE_CHANGES)
+            .unwrap_or(Map::new(env))
+    }
+
+    fn add_slope_change(slope_changes: &mut Map<u64, i128>, week: u64, delta: i128) {
+        let change = slope_changes.get(week).unwrap_or(0) + delta;
+        if change == 0 {
+            slope_changes.remove(week);
+        } else {
+            slope_changes.set(week, change);
+        }
+    }
+
+    fn lock_key(user: &Address) -> (Symbol, Address) {
+        (symbol_short!("lock"), user.clone())
+    }
+}
+
+mod test;
@@ -0,0 +1,145 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+use tux_token::{TuxToken, TuxTokenClient};
+
+const UNIT: i128 = 10_000_000;
+
+struct VeTuxTest<'a> {
+    env: Env,
+    vetux: VeTuxClient<'a>,
+    tux: TuxTokenClient<'a>,
+    alice: Address,
+    bob: Address,
+}
+
+impl<'a> VeTuxTest<'a> {
+    fn setup() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        // Start on a week boundary so lock ends land exactly
+        env.ledger().set_timestamp(100 * WEEK);
+
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        let tux = TuxTokenClient::new(&env, &env.register(TuxToken, ()));
+        tux.initialize(&admin, &(1_000_000 * UNIT));
+        tux.transfer(&admin, &alice, &(10_000 * UNIT));
+        tux.transfer(&admin, &bob, &(10_000 * UNIT));
+
+        let vetux = VeTuxClient::new(&env, &env.register(VeTux, ()));
+        vetux.initialize(&tux.address);
+
+        VeTuxTest {
+            env,
+            vetux,
+            tux,
+            alice,
+            bob,
+        }
+    }
+
+    fn advance(&self, seconds: u64) {
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + seconds);
+    }
+}
+
+#[test]
+fn test_voting_power_decays_linearly() {
+    let t = VeTuxTest::setup();
+
+    // A max lock starts at full weight, a half lock at half
+    t.vetux.lock(&t.alice, &(1_040 * UNIT), &MAX_LOCK);
+    t.vetux.lock(&t.bob, &(1_040 * UNIT), &(MAX_LOCK / 2));
+    assert_eq!(t.vetux.voting_power(&t.alice), 1_040 * UNIT);
+    assert_eq!(t.vetux.voting_power(&t.bob), 520 * UNIT);
+    assert_eq!(t.vetux.total_voting_power(), 1_560 * UNIT);
+
+    t.advance(26 * WEEK);
+    assert_eq!(t.vetux.voting_power(&t.alice), 780 * UNIT);
+    assert_eq!(t.vetux.voting_power(&t.bob), 260 * UNIT);
+    assert_eq!(t.vetux.total_voting_power(), 1_040 * UNIT);
+
+    // Bob's lock has run out; only Alice's counts from here
+    t.advance(30 * WEEK);
+    assert_eq!(t.vetux.voting_power(&t.bob), 0);
+    assert_eq!(t.vetux.voting_power(&t.alice), 480 * UNIT);
+    assert_eq!(t.vetux.total_voting_power(), 480 * UNIT);
+
+    t.advance(MAX_LOCK);
+    assert_eq!(t.vetux.total_voting_power(), 0);
+}
+
+#[test]
+fn test_early_withdrawal_impossible() {
+    let t = VeTuxTest::setup();
+    let end = t.vetux.lock(&t.alice, &(100 * UNIT), &(4 * WEEK));
+    assert_eq!(t.tux.balance(&t.alice), 9_900 * UNIT);
+
+    t.advance(4 * WEEK - 1);
+    assert_eq!(
+        t.vetux.try_withdraw(&t.alice),
+        Err(Ok(VeTuxError::LockNotExpired))
+    );
+
+    t.env.ledger().set_timestamp(end);
+    assert_eq!(t.vetux.withdraw(&t.alice), 100 * UNIT);
+    assert_eq!(t.tux.balance(&t.alice), 10_000 * UNIT);
+    assert_eq!(t.vetux.get_lock(&t.alice), None);
+}
+
+#[test]
+fn test_increase_and_extend() {
+    let t = VeTuxTest::setup();
+    t.vetux.lock(&t.alice, &(104 * UNIT), &(52 * WEEK));
+    assert_eq!(t.vetux.voting_power(&t.alice), 52 * UNIT);
+
+    t.vetux.increase_amount(&t.alice, &(104 * UNIT));
+    assert_eq!(t.vetux.voting_power(&t.alice), 104 * UNIT);
+
+    t.vetux.extend_lock(&t.alice, &MAX_LOCK);
+    assert_eq!(t.vetux.voting_power(&t.alice), 208 * UNIT);
+    assert_eq!(t.vetux.total_voting_power(), 208 * UNIT);
+
+    // Extending can't shorten a lock, and nothing can exceed the max
+    assert_eq!(
+        t.vetux.try_extend_lock(&t.alice, &(10 * WEEK)),
+        Err(Ok(VeTuxError::InvalidDuration))
+    );
+    assert_eq!(
+        t.vetux.try_lock(&t.bob, &UNIT, &(MAX_LOCK + WEEK)),
+        Err(Ok(VeTuxError::InvalidDuration))
+    );
+    assert_eq!(
+        t.vetux.try_lock(&t.alice, &UNIT, &WEEK),
+        Err(Ok(VeTuxError::LockExists))
+    );
+
+    // The old end no longer drops Alice's power from the total
+    t.advance(60 * WEEK);
+    assert_eq!(t.vetux.total_voting_power(), 88 * UNIT);
+    assert_eq!(t.vetux.voting_power(&t.alice), 88 * UNIT);
+}
+
+#[test]
+fn test_expired_lock_cannot_be_topped_up() {
+    let t = VeTuxTest::setup();
+    t.vetux.lock(&t.alice, &(10 * UNIT), &WEEK);
+    t.advance(WEEK);
+
+    assert_eq!(
+        t.vetux.try_increase_amount(&t.alice, &UNIT),
+        Err(Ok(VeTuxError::LockExpired))
+    );
+    assert_eq!(
+        t.vetux.try_increase_amount(&t.bob, &UNIT),
+        Err(Ok(VeTuxError::NoLock))
+    );
+}
@@ -0,0 +1,90 @@
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _, MockAuth, MockAuthInvoke},
+    vec, Address, Env, IntoVal,
+};
+use tuxedo_blend_adapter::RequestType;
+use tuxedo_fixtures::{TuxedoFixture, UNIT};
+use tuxedo_vault::{Strategy, DEAD_SHARES};
+
+#[test]
+fn test_admin_rotation_mid_flow() {
+    let p = TuxedoFixture::new(&Env::default());
+    let user = p.user_with_usdc(1_000 * UNIT);
+    p.vault.deposit(&user, &p.usdc.address, &(1_000 * UNIT), &0);
+
+    let new_admin = Address::generate(&p.env);
+    p.vault.set_admin(&new_admin);
+    assert_eq!(
+        p.env.events().all(),
+        vec![
+            &p.env,
+            (
+                p.vault.address.clone(),
+                // Third vault event after init and the deposit
+                (symbol_short!("vault"), symbol_short!("admin"), 5u64).into_val(&p.env),
+                (p.admin.clone(), new_admin.clone()).into_val(&p.env),
+            ),
+        ]
+    );
+    assert_eq!(p.vault.get_admin(), new_admin);
+
+    // The old admin's signature no longer works
+    let fee_invoke = MockAuthInvoke {
+        contract: &p.vault.address,
+        fn_name: "set_platform_fee_bps",
+        args: (500u32,).into_val(&p.env),
+        sub_invokes: &[],
+    };
+    assert!(p
+        .vault
+        .mock_auths(&[MockAuth {
+            address: &p.admin,
+            invoke: &fee_invoke,
+        }])
+        .try_set_platform_fee_bps(&500)
+        .is_err());
+    p.vault
+        .mock_auths(&[MockAuth {
+            address: &new_admin,
+            invoke: &fee_invoke,
+        }])
+        .set_platform_fee_bps(&500);
+
+    // Deposits made before the rotation keep working under the new fee
+    p.env.mock_all_auths();
+    p.simulate_yield(100 * UNIT);
+    p.vault.distribute_yield(&p.keeper);
+    assert_eq!(p.usdc.balance(&p.keeper), UNIT / 10);
+    assert_eq!(p.usdc.balance(&p.platform), 4_995 * UNIT / 1_000);
+    assert_eq!(
+        p.vault.withdraw(&user, &p.vault.get_user_shares(&user), &0),
+        (1_000 * UNIT - DEAD_SHARES) * 1_094_905 / 1_000_000
+    );
+}
+
+#[test]
+fn test_agent_rotation_mid_flow() {
+    let p = TuxedoFixture::new(&Env::default());
+    let user = p.user_with_usdc(1_000 * UNIT);
+    p.vault.deposit(&user, &p.usdc.address, &(1_000 * UNIT), &0);
+
+    let supply = Strategy {
+        action: symbol_short!("supply"),
+        pool: p.pool.clone(),
+        asset: p.usdc.address.clone(),
+        amount: 400 * UNIT,
+        request_type: RequestType::SupplyCollateral as u32,
+    };
+    p.vault.agent_execute(&p.agent, &supply);
+    assert_eq!(p.env.auths()[0].0, p.agent);
+
+    // After rotation the new agent signs strategy calls
+    let new_agent = Address::generate(&p.env);
+    p.vault.set_agent(&new_agent);
+    assert_eq!(p.vault.get_agent(), new_agent);
+
+    p.vault.agent_execute(&new_agent, &supply);
+    assert_eq!(p.env.auths()[0].0, new_agent);
+    assert_eq!(p.usdc.balance(&p.pool), 800 * UNIT);
+}
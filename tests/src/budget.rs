@@ -0,0 +1,131 @@
+//! Resource budgets for the hot entrypoints.
+//!
+//! Each limit is the measured cost plus ~20% headroom, so a refactor that
+//! makes an entrypoint 25% more expensive fails here. After an intentional
+//! change, re-baseline from the numbers printed with `--nocapture`.
+//!
+//! Contracts run natively here, so the numbers exclude Wasm VM costs and only
+//! track relative changes in the contract logic and storage access.
+//!
+//! A contract's instance storage is a single ledger entry, so loading the
+//! vault's instance state once per call (`VaultState`) shows up in the
+//! instruction and memory numbers, not in the entry counts.
+
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Vec};
+use tuxedo_blend_adapter::RequestType;
+use tuxedo_fixtures::{cost::Cost, TuxedoFixture, TUX0_POOL, UNIT};
+use tuxedo_vault::{Strategy, VaultError};
+
+const DEPOSIT: Cost = Cost {
+    instructions: 847_000,
+    mem_bytes: 165_000,
+    read_entries: 12,
+    write_entries: 8,
+};
+
+const WITHDRAW: Cost = Cost {
+    instructions: 917_000,
+    mem_bytes: 165_000,
+    read_entries: 12,
+    write_entries: 8,
+};
+
+const AGENT_EXECUTE: Cost = Cost {
+    instructions: 904_000,
+    mem_bytes: 181_000,
+    read_entries: 15,
+    write_entries: 8,
+};
+
+const STAKE: Cost = Cost {
+    instructions: 415_000,
+    mem_bytes: 85_000,
+    read_entries: 11,
+    write_entries: 10,
+};
+
+const CLAIM_REWARDS: Cost = Cost {
+    instructions: 608_000,
+    mem_bytes: 118_000,
+    read_entries: 18,
+    write_entries: 10,
+};
+
+const WHITELIST_40: Cost = Cost {
+    instructions: 1_591_000,
+    mem_bytes: 414_000,
+    read_entries: 44,
+    write_entries: 42,
+};
+
+/// Measure the invocation `call` makes against its budget
+fn check(p: &TuxedoFixture, label: &str, limit: &Cost, call: impl FnOnce()) {
+    call();
+    Cost::last(&p.env, label).assert_within(label, limit);
+}
+
+#[test]
+fn test_vault_entrypoints_within_budget() {
+    let p = TuxedoFixture::new(&Env::default());
+    let user = p.users[0].clone();
+
+    // Measure steady state, not first-deposit initialization
+    p.vault.deposit(&p.users[1], &p.usdc.address, &(1_000 * UNIT), &0);
+
+    check(&p, "deposit", &DEPOSIT, || {
+        p.vault.deposit(&user, &p.usdc.address, &(1_000 * UNIT), &0);
+    });
+    check(&p, "withdraw", &WITHDRAW, || {
+        p.vault.withdraw(&user, &(400 * UNIT), &0);
+    });
+    check(&p, "agent_execute", &AGENT_EXECUTE, || {
+        p.vault.agent_execute(&p.agent, &Strategy {
+            action: symbol_short!("supply"),
+            pool: p.pool.clone(),
+            asset: p.usdc.address.clone(),
+            amount: 500 * UNIT,
+            request_type: RequestType::SupplyCollateral as u32,
+        });
+    });
+}
+
+#[test]
+fn test_whitelist_batch_within_budget() {
+    let p = TuxedoFixture::new(&Env::default());
+    let mut testers = Vec::new(&p.env);
+    for _ in 0..50 {
+        testers.push_back(Address::generate(&p.env));
+    }
+
+    // 50 addresses plus the vault's own entries would write more ledger
+    // entries than a transaction may, so batches stop at 40
+    assert_eq!(
+        p.vault.try_add_to_whitelist(&testers),
+        Err(Ok(VaultError::InvalidAmount))
+    );
+    let batch = testers.slice(0..40);
+    check(&p, "add_to_whitelist(40)", &WHITELIST_40, || {
+        p.vault.add_to_whitelist(&batch);
+    });
+    p.vault.add_to_whitelist(&testers.slice(40..));
+    assert!(testers.iter().all(|tester| p.vault.is_whitelisted(&tester)));
+}
+
+#[test]
+fn test_farming_entrypoints_within_budget() {
+    let p = TuxedoFixture::new(&Env::default());
+    let user = p.users[0].clone();
+    p.fund_rewards(10_000 * UNIT);
+    p.farming.set_reward_rate(&p.admin, &TUX0_POOL, &UNIT);
+    p.vault.deposit(&user, &p.usdc.address, &(1_000 * UNIT), &0);
+    p.farming.stake(&user, &TUX0_POOL, &(100 * UNIT));
+    p.advance_time(100);
+
+    check(&p, "stake", &STAKE, || {
+        p.farming.stake(&user, &TUX0_POOL, &(100 * UNIT));
+    });
+    p.advance_time(100);
+    check(&p, "claim_rewards", &CLAIM_REWARDS, || {
+        p.farming.claim_rewards(&user, &TUX0_POOL);
+    });
+}
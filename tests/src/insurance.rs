@@ -0,0 +1,60 @@
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, Env};
+use tuxedo_blend_adapter::RequestType;
+use tuxedo_fee_splitter::{FeeSplitter, FeeSplitterClient};
+use tuxedo_fixtures::{TuxedoFixture, UNIT};
+use tuxedo_insurance::{ClaimStatus, InsuranceFund, InsuranceFundClient};
+use tuxedo_vault::{Strategy, DEAD_SHARES};
+
+#[test]
+fn test_insurance_covers_strategy_loss() {
+    let p = TuxedoFixture::new(&Env::default());
+    let treasury = Address::generate(&p.env);
+
+    // Vault fees -> splitter -> half to the treasury, half to the insurance fund
+    let splitter = FeeSplitterClient::new(&p.env, &p.env.register(FeeSplitter, ()));
+    let fund = InsuranceFundClient::new(&p.env, &p.env.register(InsuranceFund, ()));
+    splitter.initialize(&p.admin);
+    splitter.set_recipients(&vec![
+        &p.env,
+        (treasury.clone(), 5_000),
+        (fund.address.clone(), 5_000),
+    ]);
+    splitter.set_notify(&fund.address, &true);
+    fund.initialize(&p.admin, &p.usdc.address, &splitter.address);
+    p.vault.set_platform(&splitter.address);
+
+    let depositor = p.user_with_usdc(1_000 * UNIT);
+    p.vault.deposit(&depositor, &p.usdc.address, &(1_000 * UNIT), &0);
+    p.simulate_yield(1_000 * UNIT);
+    p.vault.distribute_yield(&p.keeper);
+    splitter.distribute(&p.usdc.address);
+    let contributed = 999 * UNIT / 100;
+    assert_eq!(fund.get_fund_balance(), contributed);
+    assert_eq!(fund.get_total_contributed(), contributed);
+    let share_value = p.vault.get_share_value();
+
+    // The agent's strategy loses as much as the fund holds, half of what it
+    // supplied, and the admin recalls the rest
+    p.vault.agent_execute(&p.agent, &Strategy {
+        action: symbol_short!("supply"),
+        pool: p.pool.clone(),
+        asset: p.usdc.address.clone(),
+        amount: 2 * contributed,
+        request_type: RequestType::SupplyCollateral as u32,
+    });
+    p.simulate_loss(5_000);
+    assert_eq!(p.vault.emergency_recall(&p.pool), contributed);
+    assert!(p.vault.get_share_value() < share_value);
+
+    // The vault admin claims and the insurance admin pays it back in
+    let id = fund.request_coverage(&p.vault.address, &contributed);
+    fund.approve_claim(&id);
+
+    assert_eq!(p.vault.get_share_value(), share_value);
+    assert_eq!(fund.get_fund_balance(), 0);
+    assert_eq!(fund.get_claim(&id).status, ClaimStatus::Approved);
+    assert_eq!(
+        p.vault.withdraw(&depositor, &p.vault.get_user_shares(&depositor), &0),
+        (1_000 * UNIT - DEAD_SHARES) * 197_902 / 100_000
+    );
+}
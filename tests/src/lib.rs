@@ -0,0 +1,17 @@
+#![cfg(test)]
+
+//! End-to-end scenarios running the vault, TUX token, farming and admin
+//! contracts together in one `Env`.
+
+mod admin_rotation;
+mod budget;
+mod insurance;
+mod multisig;
+mod revenue_share;
+mod router;
+mod scenarios;
+mod spec;
+mod staking;
+mod stats;
+mod tiers;
+mod yield_cycle;
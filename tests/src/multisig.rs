@@ -0,0 +1,63 @@
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    vec, Address, Env, IntoVal, Symbol,
+};
+use tuxedo_fixtures::TuxedoFixture;
+use tuxedo_multisig::{Multisig, MultisigClient, MultisigError};
+
+#[test]
+fn test_two_of_three_multisig_as_vault_admin() {
+    let p = TuxedoFixture::new(&Env::default());
+    let members = [
+        Address::generate(&p.env),
+        Address::generate(&p.env),
+        Address::generate(&p.env),
+    ];
+
+    let multisig = MultisigClient::new(&p.env, &p.env.register(Multisig, ()));
+    multisig.initialize(
+        &vec![
+            &p.env,
+            members[0].clone(),
+            members[1].clone(),
+            members[2].clone(),
+        ],
+        &2,
+        &(7 * 86_400),
+    );
+    p.vault.set_admin(&multisig.address);
+
+    let id = multisig.propose(
+        &members[0],
+        &p.vault.address,
+        &Symbol::new(&p.env, "set_platform_fee_bps"),
+        &vec![&p.env, 500u32.into_val(&p.env)],
+    );
+
+    // One confirmation isn't enough
+    assert_eq!(
+        multisig.try_execute(&id),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+
+    // Nor can a single member go around the multisig
+    assert!(p
+        .vault
+        .mock_auths(&[MockAuth {
+            address: &members[0],
+            invoke: &MockAuthInvoke {
+                contract: &p.vault.address,
+                fn_name: "set_platform_fee_bps",
+                args: (500u32,).into_val(&p.env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_set_platform_fee_bps(&500)
+        .is_err());
+    assert_eq!(p.vault.get_platform_fee_bps(), 200);
+
+    p.env.mock_all_auths();
+    multisig.confirm(&members[1], &id);
+    multisig.execute(&id);
+    assert_eq!(p.vault.get_platform_fee_bps(), 500);
+}
@@ -0,0 +1,39 @@
+use soroban_sdk::{testutils::Address as _, vec, Address, Env};
+use tuxedo_fee_splitter::{FeeSplitter, FeeSplitterClient};
+use tuxedo_fixtures::{TuxedoFixture, UNIT};
+use tuxedo_revenue_share::{TuxRevenueShare, TuxRevenueShareClient};
+
+#[test]
+fn test_platform_fees_reach_tux_stakers() {
+    let p = TuxedoFixture::new(&Env::default());
+    let treasury = Address::generate(&p.env);
+
+    // Vault fees -> splitter -> half to the treasury, half to TUX stakers
+    let splitter = FeeSplitterClient::new(&p.env, &p.env.register(FeeSplitter, ()));
+    let revshare = TuxRevenueShareClient::new(&p.env, &p.env.register(TuxRevenueShare, ()));
+    splitter.initialize(&p.admin);
+    splitter.set_recipients(&vec![
+        &p.env,
+        (treasury.clone(), 5_000),
+        (revshare.address.clone(), 5_000),
+    ]);
+    splitter.set_notify(&revshare.address, &true);
+    revshare.initialize(&p.admin, &p.tux.address, &p.usdc.address, &splitter.address);
+    p.vault.set_platform(&splitter.address);
+
+    let staker = Address::generate(&p.env);
+    p.tux.transfer(&p.admin, &staker, &(500 * UNIT));
+    revshare.stake(&staker, &(500 * UNIT));
+
+    let depositor = p.user_with_usdc(1_000 * UNIT);
+    p.vault.deposit(&depositor, &p.usdc.address, &(1_000 * UNIT), &0);
+    p.simulate_yield(100 * UNIT);
+    p.vault.distribute_yield(&p.keeper);
+    assert_eq!(splitter.distribute(&p.usdc.address), 1_998 * UNIT / 1_000);
+
+    let half = 999 * UNIT / 1_000;
+    assert_eq!(p.usdc.balance(&treasury), half);
+    assert_eq!(revshare.get_pending_rewards(&staker), half);
+    assert_eq!(revshare.claim(&staker), half);
+    assert_eq!(p.usdc.balance(&staker), half);
+}
@@ -0,0 +1,66 @@
+use soroban_sdk::{testutils::AuthorizedFunction, Env, IntoVal, Symbol};
+use tuxedo_fixtures::{TuxedoFixture, TUX0_POOL, UNIT};
+use tuxedo_registry::{TuxedoRegistry, TuxedoRegistryClient};
+use tuxedo_router::{TuxedoRouter, TuxedoRouterClient, FARMING_KEY, USDC_KEY, VAULT_KEY};
+use tuxedo_vault::DEAD_SHARES;
+
+/// Router resolving the fixture's vault, farming contract and USDC through a
+/// registry
+fn deploy_router<'a>(p: &TuxedoFixture<'a>) -> TuxedoRouterClient<'a> {
+    let registry = TuxedoRegistryClient::new(&p.env, &p.env.register(TuxedoRegistry, ()));
+    registry.initialize(&p.admin);
+    registry.set(&VAULT_KEY, &p.vault.address);
+    registry.set(&FARMING_KEY, &p.farming.address);
+    registry.set(&USDC_KEY, &p.usdc.address);
+
+    let router = TuxedoRouterClient::new(&p.env, &p.env.register(TuxedoRouter, ()));
+    router.initialize(&p.admin, &registry.address);
+    router
+}
+
+#[test]
+fn test_router_round_trip_with_one_signature_each_way() {
+    let p = TuxedoFixture::new(&Env::default());
+    let router = deploy_router(&p);
+    p.fund_rewards(100_000 * UNIT);
+    p.farming.set_reward_rate(&p.admin, &TUX0_POOL, &UNIT); // 1 TUX per second
+
+    // A first depositor takes the dead shares
+    let seed = p.user_with_usdc(10 * UNIT + DEAD_SHARES);
+    p.vault.deposit(&seed, &p.usdc.address, &(10 * UNIT + DEAD_SHARES), &0);
+
+    // The user's one authorization covers the deposit and the stake
+    let user = p.user_with_usdc(1_000 * UNIT);
+    p.usdc.approve(&user, &router.address, &(1_000 * UNIT), &1_000);
+    let shares = router.deposit_and_stake(&user, &(1_000 * UNIT), &TUX0_POOL);
+    let auths = p.env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, user);
+    assert_eq!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract((
+            router.address.clone(),
+            Symbol::new(&p.env, "deposit_and_stake"),
+            (user.clone(), 1_000 * UNIT, TUX0_POOL).into_val(&p.env),
+        ))
+    );
+    assert_eq!(shares, 1_000 * UNIT);
+    assert_eq!(p.vault.balance(&user), 0);
+    assert_eq!(p.farming.get_user_stake(&user, &TUX0_POOL), shares);
+
+    // Staked shares earn farming rewards and vault yield alike: 10% on
+    // everything in the vault
+    p.advance_time(1_000);
+    p.simulate_yield(101 * UNIT + DEAD_SHARES / 10);
+    assert!(p.farming.claim_rewards(&user, &TUX0_POOL) > 0);
+
+    let assets = router.unstake_and_withdraw(&user, &shares, &TUX0_POOL);
+    let auths = p.env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, user);
+    assert_eq!(assets, 1_100 * UNIT);
+    assert_eq!(p.usdc.balance(&user), 1_100 * UNIT);
+    assert_eq!(p.farming.get_user_stake(&user, &TUX0_POOL), 0);
+    assert_eq!(p.vault.balance(&router.address), 0);
+    assert_eq!(p.usdc.balance(&router.address), 0);
+}
@@ -0,0 +1,162 @@
+use tuxedo_fixtures::{
+    scenario::{half, pct, Scenario},
+    TuxedoFixture, UNIT,
+};
+use tuxedo_vault::{DepositEvent, VaultError, YieldEvent, DEAD_SHARES};
+
+fn no_yield(p: &TuxedoFixture) {
+    assert_eq!(
+        p.vault.try_distribute_yield(&p.keeper),
+        Err(Ok(VaultError::NoYieldToDistribute))
+    );
+}
+
+#[test]
+fn test_strategy_loss_shared_pro_rata() {
+    let mut s = Scenario::new()
+        .deposit("alice", 1_000 * UNIT)
+        .deposit("bob", 1_000 * UNIT)
+        .agent_supply(pct(80))
+        .advance_days(2)
+        // A quarter of the 1,600 USDC position goes bad
+        .write_down(pct(25))
+        .agent_withdraw(pct(100))
+        // Realizing the loss trips the breaker until the admin reviews it
+        .clear_breaker()
+        .expect_total_assets(1_600 * UNIT)
+        .expect_share_value(8 * UNIT / 10)
+        // Losses carry no fee
+        .then(no_yield)
+        .withdraw_shares("alice", pct(100))
+        .expect_usdc("alice", (1_000 * UNIT - DEAD_SHARES) * 8 / 10);
+
+    // New money buys in at the written-down share value
+    let bob = s.user("bob");
+    s.deposit("bob", 400 * UNIT)
+        .expect_event(
+            "deposit",
+            DepositEvent {
+                user: bob,
+                amount: 400 * UNIT,
+                shares: 500 * UNIT,
+                share_value_after: 8 * UNIT / 10,
+                total_assets_after: 1_200 * UNIT + DEAD_SHARES * 8 / 10,
+                total_shares_after: 1_500 * UNIT + DEAD_SHARES,
+            },
+        )
+        .expect_shares("bob", 1_500 * UNIT)
+        .withdraw_shares("bob", pct(100))
+        .expect_usdc("bob", 1_200 * UNIT)
+        .expect_platform_fees(0);
+}
+
+#[test]
+fn test_loss_recovered_before_fees_resume() {
+    let s = Scenario::new();
+    let keeper = s.t.keeper.clone();
+    s.deposit("alice", 1_000 * UNIT)
+        .agent_supply(half())
+        .write_down(pct(20))
+        .agent_withdraw(pct(100))
+        .clear_breaker()
+        .expect_share_value(9 * UNIT / 10)
+        // Insurance covers half the loss; recovered principal isn't yield
+        .recover(50 * UNIT)
+        .expect_share_value(95 * UNIT / 100)
+        .then(no_yield)
+        // With no reserve held back, a month of 10% interest on everything
+        // lifts the vault past its deposits, and only the gain above them pays
+        // the keeper and the platform
+        .then(|t| t.vault.set_reserve_bps(&0))
+        .agent_supply(pct(100))
+        .advance_days(30)
+        .accrue_yield(pct(10))
+        .agent_withdraw(pct(100))
+        .expect_total_assets(1_045 * UNIT)
+        .distribute()
+        .expect_event(
+            "yield",
+            YieldEvent {
+                yield_earned: 45 * UNIT,
+                platform_fee: 8_991 * UNIT / 10_000,
+                caller: keeper,
+                keeper_reward: 45 * UNIT / 1_000,
+                share_value_after: 10_440_559 * UNIT / 10_000_000,
+                total_assets_after: 10_440_559 * UNIT / 10_000,
+                total_shares_after: 1_000 * UNIT,
+            },
+        )
+        .expect_platform_fees(8_991 * UNIT / 10_000)
+        .expect_share_value(10_440_559 * UNIT / 10_000_000)
+        .withdraw_shares("alice", pct(100))
+        .expect_usdc("alice", (1_000 * UNIT - DEAD_SHARES) * 10_440_559 / 10_000_000);
+}
+
+#[test]
+fn test_donated_emissions_count_as_yield() {
+    let s = Scenario::new();
+    let keeper = s.t.keeper.clone();
+    s.deposit("alice", 1_000 * UNIT)
+        // The platform sold the vault's emissions and pays the USDC back in
+        .donate_yield(100 * UNIT)
+        .expect_total_assets(1_100 * UNIT)
+        .then(|t| assert_eq!(t.vault.get_vault_stats().initial_deposits, 1_000 * UNIT))
+        .distribute()
+        .expect_event(
+            "yield",
+            YieldEvent {
+                yield_earned: 100 * UNIT,
+                platform_fee: 1_998 * UNIT / 1_000,
+                caller: keeper,
+                keeper_reward: UNIT / 10,
+                share_value_after: 10_979_020,
+                total_assets_after: 1_097_902 * UNIT / 1_000,
+                total_shares_after: 1_000 * UNIT,
+            },
+        )
+        .expect_platform_fees(1_998 * UNIT / 1_000)
+        .expect_share_value(10_979_020);
+}
+
+#[test]
+fn test_hwm_fees_charge_only_above_the_peak() {
+    let s = Scenario::new()
+        .then(|t| t.vault.set_hwm_fees(&true))
+        .deposit("alice", 1_000 * UNIT)
+        .simulate_yield(100 * UNIT)
+        .distribute();
+    let peak = s.t.vault.get_share_value();
+    let fees = s.t.usdc.balance(&s.t.platform);
+    assert_eq!(s.t.vault.get_fee_high_water_mark(), peak);
+
+    // A strategy loss, then bob buys in at the written-down share value
+    let s = s
+        .agent_supply(half())
+        .write_down(pct(20))
+        .agent_withdraw(pct(100))
+        .clear_breaker()
+        .deposit("bob", 1_000 * UNIT);
+    assert!(s.t.vault.get_share_value() < peak);
+
+    // Back exactly to the old peak: bob is up, but no share has gained on the
+    // peak, so there is nothing to charge
+    let t = &s.t;
+    let shares = t.vault.get_total_shares();
+    let assets_at_peak = (peak * shares + UNIT - 1) / UNIT;
+    t.simulate_yield(assets_at_peak - t.vault.get_total_assets());
+    assert_eq!(t.vault.get_share_value(), peak);
+    assert!(t.vault.get_total_assets() > t.vault.get_vault_stats().initial_deposits);
+    no_yield(t);
+    assert_eq!(t.usdc.balance(&t.platform), fees);
+
+    // Past the peak, only the gain above it is charged
+    t.simulate_yield(50 * UNIT);
+    let gain = (t.vault.get_share_value() - peak) * shares / UNIT;
+    let keeper_reward = gain * 10 / 10_000;
+    t.vault.distribute_yield(&t.keeper);
+    assert_eq!(
+        t.usdc.balance(&t.platform) - fees,
+        (gain - keeper_reward) * 200 / 10_000
+    );
+    assert!(t.vault.get_fee_high_water_mark() > peak);
+}
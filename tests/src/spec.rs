@@ -0,0 +1,243 @@
+//! Contract spec snapshots guarding the public ABI of the vault, farming and
+//! TUX token contracts.
+//!
+//! Each listed entrypoint and type is rendered from the spec entry the
+//! contract macros generate and compared against
+//! `tests/snapshots/spec/<contract>.txt`. A changed signature, field or error
+//! code breaks deployed clients, so the test fails with a diff; a removed one
+//! fails to compile. Add new entrypoints and types to the lists below.
+//!
+//! After an intentional ABI change, regenerate and commit the snapshots:
+//! `UPDATE_SNAPSHOTS=1 cargo test -p tuxedo-integration-tests spec`
+
+use soroban_sdk::xdr::{Limits, ReadXdr, ScSpecEntry, ScSpecTypeDef, ScSpecUdtUnionCaseV0};
+use std::{fs, path::PathBuf};
+use tux_farming::{ClaimEvent, FarmingError, PoolRewards, TuxFarming, UserRewards};
+use tux_token::{Checkpoint, TokenError, TuxToken};
+use tuxedo_vault::{
+    ConfigChange, DepositAsset, DepositEvent, DepositRecord, EmissionsRoute, PauseState,
+    PendingChange, Referral, ReferralStats, Strategy, StrategyEvent, StrategyPreview,
+    StrategyRecord, Swap, TuxedoVault, VaultError, VaultStats, WithdrawEvent, WithdrawRequest,
+    YieldEvent,
+};
+
+/// Spec XDR of a contract's entrypoints followed by its types
+macro_rules! spec {
+    ($contract:ident { $($func:ident),* $(,)? } types { $($ty:ty),* $(,)? }) => {
+        paste::paste! {
+            [
+                $(&$contract::[<spec_xdr_ $func>]()[..],)*
+                $(&<$ty>::spec_xdr()[..],)*
+            ]
+        }
+    };
+}
+
+fn type_name(ty: &ScSpecTypeDef) -> String {
+    match ty {
+        ScSpecTypeDef::Option(o) => format!("Option<{}>", type_name(&o.value_type)),
+        ScSpecTypeDef::Result(r) => format!(
+            "Result<{}, {}>",
+            type_name(&r.ok_type),
+            type_name(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("Vec<{}>", type_name(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "Map<{}, {}>",
+            type_name(&m.key_type),
+            type_name(&m.value_type)
+        ),
+        ScSpecTypeDef::Tuple(t) => {
+            let types: Vec<String> = t.value_types.iter().map(type_name).collect();
+            format!("({})", types.join(", "))
+        }
+        ScSpecTypeDef::BytesN(b) => format!("BytesN<{}>", b.n),
+        ScSpecTypeDef::Udt(u) => u.name.to_utf8_string_lossy(),
+        primitive => format!("{primitive:?}"),
+    }
+}
+
+/// One spec entry as text, without docs since they don't affect the ABI
+fn render(xdr: &[u8]) -> String {
+    match ScSpecEntry::from_xdr(xdr, Limits::none()).expect("invalid spec XDR") {
+        ScSpecEntry::FunctionV0(f) => {
+            let inputs: Vec<String> = f
+                .inputs
+                .iter()
+                .map(|i| format!("{}: {}", i.name.to_utf8_string_lossy(), type_name(&i.type_)))
+                .collect();
+            let output = match f.outputs.first() {
+                Some(ty) => format!(" -> {}", type_name(ty)),
+                None => String::new(),
+            };
+            format!(
+                "fn {}({}){output}\n",
+                f.name.0.to_utf8_string_lossy(),
+                inputs.join(", ")
+            )
+        }
+        ScSpecEntry::UdtStructV0(s) => {
+            let mut out = format!("struct {} {{\n", s.name.to_utf8_string_lossy());
+            for field in s.fields.iter() {
+                out += &format!(
+                    "    {}: {}\n",
+                    field.name.to_utf8_string_lossy(),
+                    type_name(&field.type_)
+                );
+            }
+            out + "}\n"
+        }
+        ScSpecEntry::UdtErrorEnumV0(e) => {
+            let mut out = format!("error {} {{\n", e.name.to_utf8_string_lossy());
+            for case in e.cases.iter() {
+                out += &format!(
+                    "    {} = {}\n",
+                    case.name.to_utf8_string_lossy(),
+                    case.value
+                );
+            }
+            out + "}\n"
+        }
+        ScSpecEntry::UdtEnumV0(e) => {
+            let mut out = format!("enum {} {{\n", e.name.to_utf8_string_lossy());
+            for case in e.cases.iter() {
+                out += &format!(
+                    "    {} = {}\n",
+                    case.name.to_utf8_string_lossy(),
+                    case.value
+                );
+            }
+            out + "}\n"
+        }
+        ScSpecEntry::UdtUnionV0(u) => {
+            let mut out = format!("union {} {{\n", u.name.to_utf8_string_lossy());
+            for case in u.cases.iter() {
+                out += &match case {
+                    ScSpecUdtUnionCaseV0::VoidV0(c) => {
+                        format!("    {}\n", c.name.to_utf8_string_lossy())
+                    }
+                    ScSpecUdtUnionCaseV0::TupleV0(c) => {
+                        let types: Vec<String> = c.type_.iter().map(type_name).collect();
+                        format!(
+                            "    {}({})\n",
+                            c.name.to_utf8_string_lossy(),
+                            types.join(", ")
+                        )
+                    }
+                };
+            }
+            out + "}\n"
+        }
+        other => format!("{other:?}\n"),
+    }
+}
+
+/// Compare the rendered spec with its snapshot, or rewrite the snapshot when
+/// `UPDATE_SNAPSHOTS` is set
+fn check_snapshot(contract: &str, entries: &[&[u8]]) {
+    let actual: String = entries.iter().map(|xdr| render(xdr)).collect();
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots/spec")
+        .join(format!("{contract}.txt"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_default();
+    if actual != expected {
+        let removed = expected
+            .lines()
+            .filter(|line| !actual.lines().any(|l| l == *line));
+        let added = actual
+            .lines()
+            .filter(|line| !expected.lines().any(|l| l == *line));
+        let diff: Vec<String> = removed
+            .map(|line| format!("- {line}"))
+            .chain(added.map(|line| format!("+ {line}")))
+            .collect();
+        panic!(
+            "{contract} ABI changed ({}):\n{}\n\
+             If intentional, rerun with UPDATE_SNAPSHOTS=1 and commit the snapshot.",
+            path.display(),
+            diff.join("\n")
+        );
+    }
+}
+
+#[test]
+fn test_vault_spec_snapshot() {
+    let entries = spec!(TuxedoVault {
+        __constructor, initialize, deposit, deposit_with_referral, claim_referral,
+        sync_referral, deposit_for, receive_bridged_deposit, withdraw, withdraw_to,
+        withdraw_from, approve_operator, revoke_operator, is_operator, withdraw_all,
+        withdraw_assets, migrate, request_withdraw, fulfill_withdrawals, claim_withdraw,
+        cancel_withdraw, transfer, transfer_shares, transfer_from, approve, allowance, burn,
+        burn_from, bump_user_shares, decimals, name, symbol, agent_execute, simulate_strategy,
+        agent_execute_many, agent_execute_signed, strategy_payload, claim_emissions,
+        agent_swap_rewards, agent_execute_swap, distribute_yield, keeper_distribute_yield,
+        recover_assets, rescue_tokens, donate_yield, accrue_fees, set_admin, upgrade,
+        finish_upgrade, set_agent, add_agent, remove_agent, set_platform, set_stop_loss,
+        clear_stop_loss, set_breaker_bps, clear_breaker, pause, unpause, emergency_recall, sync,
+        rescue_excess, check_stop_loss, set_keeper, set_policy_key, set_swap_router,
+        set_migration_target, set_emissions_route, set_supported_asset, add_deposit_asset,
+        remove_deposit_asset, add_approved_pool, remove_approved_pool, assign_pool,
+        unassign_pool, add_to_blacklist, set_whitelist_mode, add_to_whitelist,
+        remove_from_whitelist, remove_from_blacklist, set_bridge, set_swap_limit,
+        set_platform_fee_bps, set_keeper_reward_bps, set_min_distribution_interval,
+        set_hwm_fees, set_hurdle_bps, set_management_fee_bps, set_deposit_fee_bps,
+        set_referral_bps, set_tux_token, set_lockup, set_deposit_cap, set_user_deposit_limit,
+        set_dust_threshold, set_change_delay, queue_change, execute_change, cancel_change,
+        set_oracle, set_fallback_price, get_asset_price, set_share_token, preview_deposit,
+        preview_withdraw, max_deposit, max_withdraw, get_share_value, get_total_assets,
+        get_total_shares, get_user_shares, get_user_assets, get_holder_count,
+        get_share_value_history, get_estimated_apy, balance, get_vault_stats, get_agent,
+        get_agents, get_platform, get_admin, get_version, get_share_token, get_oracle,
+        get_swap_router, get_migration_target, get_emissions_token, get_emissions_route,
+        is_supported_asset, get_deposit_assets, get_deposit_asset, is_pool_approved,
+        get_agent_pools, is_blacklisted, is_whitelist_mode, is_whitelisted, is_bridge,
+        get_swap_limit, get_swap_volume, get_reserve_ratio, get_deployed_assets,
+        get_pool_deployed, get_strategy_limits, get_strategy_volume, get_strategy_history,
+        get_strategy_count, get_policy_key, get_stop_loss, get_high_water_mark, is_stopped,
+        get_breaker_bps, is_breaker_tripped, get_keeper, get_policy_nonce, get_platform_fee_bps,
+        get_keeper_reward_bps, get_min_distribution_interval, get_hurdle_bps, is_hwm_fees,
+        get_management_fee_bps, get_last_accrual, get_deposit_fee_bps, get_referral_bps,
+        get_referral_stats, get_referral, get_referral_owed, get_user_deposit_fee_bps,
+        get_tux_token, get_lockup, get_unlock_time, get_fee_high_water_mark, get_excess,
+        get_withdraw_request, get_withdraw_queue, get_claimable_withdrawals, get_deposit_cap,
+        get_min_deposit, get_dust_threshold, get_change_delay, get_pending_change,
+        get_user_deposit_limit, get_user_history, get_user_deposited, get_user_pnl,
+        get_pause_state,
+    } types {
+        VaultError, VaultStats, Strategy, PauseState, EmissionsRoute, Swap, DepositEvent,
+        WithdrawEvent, DepositRecord, StrategyRecord, WithdrawRequest, ReferralStats, Referral,
+        ConfigChange, PendingChange, DepositAsset, StrategyPreview, StrategyEvent, YieldEvent,
+    });
+    check_snapshot("tuxedo_vault", &entries);
+}
+
+#[test]
+fn test_farming_spec_snapshot() {
+    let entries = spec!(TuxFarming {
+        initialize, add_pool, stake, unstake, set_reward_rate, set_vault, set_tier_oracle,
+        claim_rewards, mint_rewards, get_pool_token, get_user_stake, get_pending_rewards,
+        get_boost_bps, get_pool_rewards, get_vault, get_tier_oracle, get_admin, get_tux_token,
+    } types {
+        FarmingError, PoolRewards, UserRewards, ClaimEvent,
+    });
+    check_snapshot("tux_farming", &entries);
+}
+
+#[test]
+fn test_token_spec_snapshot() {
+    let entries = spec!(TuxToken {
+        initialize, mint, total_supply, total_burned, get_past_votes, get_past_total_supply,
+        set_tier_thresholds, get_tier_thresholds, get_admin, allowance, approve, balance,
+        transfer, transfer_from, burn, burn_from, decimals, name, symbol, tier_of, meets_tier,
+    } types {
+        TokenError, Checkpoint,
+    });
+    check_snapshot("tux_token", &entries);
+}
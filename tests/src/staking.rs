@@ -0,0 +1,55 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use tux_farming::FarmingError;
+use tuxedo_fixtures::{TuxedoFixture, TUX0_POOL, UNIT};
+use tuxedo_vault::DEAD_SHARES;
+
+#[test]
+fn test_deposit_stake_shares_claim_tux() {
+    let p = TuxedoFixture::new(&Env::default());
+    p.fund_rewards(100_000 * UNIT);
+    p.farming.set_reward_rate(&p.admin, &TUX0_POOL, &UNIT); // 1 TUX per second
+
+    // 20,000 USDC deposited on top of the first deposit's dead shares; half
+    // the shares are staked, half kept for the boost
+    let user = p.user_with_usdc(20_000 * UNIT + DEAD_SHARES);
+    p.vault.deposit(&user, &p.usdc.address, &(20_000 * UNIT + DEAD_SHARES), &0);
+    p.farming.stake(&user, &TUX0_POOL, &(10_000 * UNIT));
+
+    assert_eq!(p.vault.balance(&user), 10_000 * UNIT);
+    assert_eq!(p.vault.balance(&p.farming.address), 10_000 * UNIT);
+    assert_eq!(p.farming.get_user_stake(&user, &TUX0_POOL), 10_000 * UNIT);
+
+    // 1,000 seconds of emissions with a +10% boost from 10,000 USDC of held shares
+    p.advance_time(1_000);
+    assert_eq!(p.farming.get_boost_bps(&user), 1_000);
+    assert_eq!(p.farming.claim_rewards(&user, &TUX0_POOL), 1_100 * UNIT);
+    assert_eq!(p.tux.balance(&user), 1_100 * UNIT);
+
+    // Unstake and redeem the full position
+    p.farming.unstake(&user, &TUX0_POOL, &(10_000 * UNIT));
+    p.vault.withdraw(&user, &(20_000 * UNIT), &0);
+    assert_eq!(p.usdc.balance(&user), 20_000 * UNIT);
+    assert_eq!(p.vault.balance(&p.farming.address), 0);
+}
+
+#[test]
+fn test_staking_error_paths() {
+    let p = TuxedoFixture::new(&Env::default());
+    let user = p.user_with_usdc(100 * UNIT);
+    let stranger = Address::generate(&p.env);
+    let shares = p.vault.deposit(&user, &p.usdc.address, &(100 * UNIT), &0);
+    p.farming.stake(&user, &TUX0_POOL, &shares);
+
+    assert_eq!(
+        p.farming.try_unstake(&user, &TUX0_POOL, &(shares + 1)),
+        Err(Ok(FarmingError::InsufficientBalance))
+    );
+    assert_eq!(
+        p.farming.try_claim_rewards(&user, &TUX0_POOL),
+        Err(Ok(FarmingError::NothingToClaim))
+    );
+    assert_eq!(
+        p.farming.try_set_reward_rate(&stranger, &TUX0_POOL, &UNIT),
+        Err(Ok(FarmingError::NotAuthorized))
+    );
+}
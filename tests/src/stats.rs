@@ -0,0 +1,49 @@
+use soroban_sdk::{testutils::Address as _, vec, Address, Env};
+use tuxedo_fixtures::{TuxedoFixture, TUX0_POOL, UNIT};
+use tuxedo_registry::{TuxedoRegistry, TuxedoRegistryClient};
+use tuxedo_stats::{
+    StatsAggregator, StatsAggregatorClient, FARMING_KEY, OK_POOLS, OK_TUX_BURNED, OK_TUX_SUPPLY,
+    OK_VAULTS, TOKEN_KEY, VAULT_KEY,
+};
+
+#[test]
+fn test_protocol_stats_match_individual_reads() {
+    let p = TuxedoFixture::new(&Env::default());
+    let registry = TuxedoRegistryClient::new(&p.env, &p.env.register(TuxedoRegistry, ()));
+    registry.initialize(&p.admin);
+    registry.set(&VAULT_KEY, &p.vault.address);
+    registry.set(&FARMING_KEY, &p.farming.address);
+    registry.set(&TOKEN_KEY, &p.tux.address);
+
+    let stats = StatsAggregatorClient::new(&p.env, &p.env.register(StatsAggregator, ()));
+    stats.initialize(&p.admin, &registry.address);
+    stats.set_pools(&vec![&p.env, TUX0_POOL]);
+
+    // Some TVL, some of it staked, and some TUX burned
+    let user = p.user_with_usdc(500 * UNIT);
+    p.vault.deposit(&user, &p.usdc.address, &(500 * UNIT), &0);
+    p.farming.stake(&user, &TUX0_POOL, &(200 * UNIT));
+    p.tux.burn(&p.admin, &(1_000 * UNIT));
+
+    let totals = stats.get_protocol_stats();
+    assert_eq!(
+        totals.ok,
+        OK_VAULTS | OK_POOLS | OK_TUX_SUPPLY | OK_TUX_BURNED
+    );
+    assert_eq!(totals.total_value_locked, p.vault.get_total_assets());
+    assert_eq!(totals.vaults.get(0).unwrap().total_assets, 500 * UNIT);
+    assert_eq!(
+        totals.pools.get(0).unwrap().total_staked,
+        p.farming.get_pool_rewards(&TUX0_POOL).total_staked
+    );
+    assert_eq!(totals.tux_total_supply, p.tux.total_supply());
+    assert_eq!(totals.tux_burned, p.tux.total_burned());
+
+    // An unreachable farming contract zeroes its stakes without hiding the rest
+    registry.set(&FARMING_KEY, &Address::generate(&p.env));
+    let totals = stats.get_protocol_stats();
+    assert_eq!(totals.ok, OK_VAULTS | OK_TUX_SUPPLY | OK_TUX_BURNED);
+    assert!(!totals.pools.get(0).unwrap().ok);
+    assert_eq!(totals.pools.get(0).unwrap().total_staked, 0);
+    assert_eq!(totals.total_value_locked, 500 * UNIT);
+}
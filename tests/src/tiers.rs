@@ -0,0 +1,52 @@
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, Env};
+use tux_token::{TuxToken, TuxTokenClient};
+use tuxedo_fixtures::{TuxedoFixture, UNIT};
+use tuxedo_subscriptions::{SubscriptionError, TuxSubscriptions, TuxSubscriptionsClient};
+
+#[test]
+fn test_consumers_follow_tier_oracle_swaps() {
+    let p = TuxedoFixture::new(&Env::default());
+    let treasury = Address::generate(&p.env);
+    let pro = symbol_short!("pro");
+
+    let subs = TuxSubscriptionsClient::new(&p.env, &p.env.register(TuxSubscriptions, ()));
+    subs.initialize(&p.admin, &p.tux.address, &treasury, &0);
+    subs.set_plan(&pro, &(10 * UNIT), &86_400, &false);
+    subs.set_plan_min_tier(&pro, &2);
+
+    // A TUX holder at tier 1 under the live token thresholds
+    let user = Address::generate(&p.env);
+    p.tux.transfer(&p.admin, &user, &(1_000 * UNIT));
+    p.tux
+        .set_tier_thresholds(&p.admin, &vec![&p.env, 1_000 * UNIT, 10_000 * UNIT]);
+    p.farming.set_tier_oracle(&p.admin, &p.tux.address);
+    subs.set_tier_oracle(&p.tux.address);
+
+    assert_eq!(p.farming.get_boost_bps(&user), 500);
+    assert_eq!(
+        subs.try_subscribe(&user, &pro),
+        Err(Ok(SubscriptionError::TierTooLow))
+    );
+
+    // A replacement oracle with lower thresholds puts the same user at tier 2
+    let oracle = TuxTokenClient::new(&p.env, &p.env.register(TuxToken, ()));
+    oracle.initialize(&p.admin, &0);
+    oracle.set_tier_thresholds(&p.admin, &vec![&p.env, 1, 2]);
+    oracle.mint(&p.admin, &user, &2);
+    p.farming.set_tier_oracle(&p.admin, &oracle.address);
+    subs.set_tier_oracle(&oracle.address);
+
+    assert_eq!(p.farming.get_boost_bps(&user), 1_000);
+    subs.subscribe(&user, &pro);
+    assert!(subs.is_active(&user, &pro));
+
+    // An address that can't answer tier queries ranks everyone at tier 0
+    p.farming.set_tier_oracle(&p.admin, &p.vault.address);
+    subs.set_tier_oracle(&p.vault.address);
+
+    assert_eq!(p.farming.get_boost_bps(&user), 0);
+    assert_eq!(
+        subs.try_subscribe(&user, &pro),
+        Err(Ok(SubscriptionError::TierTooLow))
+    );
+}
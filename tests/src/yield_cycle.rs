@@ -0,0 +1,86 @@
+use soroban_sdk::{symbol_short, testutils::Events as _, vec, Env, IntoVal};
+use tuxedo_blend_adapter::RequestType;
+use tuxedo_fixtures::{
+    scenario::{pct, Scenario},
+    TuxedoFixture, UNIT,
+};
+use tuxedo_vault::{Strategy, VaultError, DEAD_SHARES};
+
+#[test]
+fn test_deposit_supply_yield_distribute_withdraw() {
+    Scenario::new()
+        .deposit("user", 1_000 * UNIT)
+        .expect_shares("user", 1_000 * UNIT - DEAD_SHARES)
+        // Agent puts 600 USDC to work in the pool, then brings it back
+        .agent_supply(pct(60))
+        .expect_pool_position(600 * UNIT)
+        .agent_withdraw(pct(100))
+        .expect_total_assets(1_000 * UNIT)
+        // 100 USDC of yield arrives; the keeper takes 0.1% and the platform
+        // 2% of the rest
+        .simulate_yield(100 * UNIT)
+        .distribute()
+        .expect_platform_fees(1_998 * UNIT / 1_000)
+        .then(|p| {
+            assert_eq!(p.usdc.balance(&p.keeper), UNIT / 10);
+            assert_eq!(
+                p.vault.try_distribute_yield(&p.keeper),
+                Err(Ok(VaultError::NoYieldToDistribute))
+            );
+        })
+        // The depositor redeems everything, including the remaining yield;
+        // only the dead shares' slice stays behind, plus the dust the
+        // depositor's payout rounds down
+        .withdraw_shares("user", pct(100))
+        .expect_usdc("user", (1_000 * UNIT - DEAD_SHARES) * 1_097_902 / 1_000_000)
+        .expect_shares("user", 0)
+        .expect_total_assets(DEAD_SHARES * 1_097_902 / 1_000_000 + 1)
+        .then(|p| assert_eq!(p.vault.get_total_shares(), DEAD_SHARES));
+}
+
+#[test]
+fn test_withdraw_error_paths() {
+    let p = TuxedoFixture::new(&Env::default());
+    let user = p.user_with_usdc(100 * UNIT);
+    p.vault.deposit(&user, &p.usdc.address, &(100 * UNIT), &0);
+
+    assert_eq!(
+        p.vault.try_withdraw(&user, &(100 * UNIT + 1), &0),
+        Err(Ok(VaultError::InsufficientShares))
+    );
+    assert_eq!(p.vault.try_withdraw(&user, &0, &0), Err(Ok(VaultError::InvalidAmount)));
+
+    // With every USDC in the pool there is nothing to pay out
+    p.vault.set_reserve_bps(&0);
+    p.vault.agent_execute(&p.agent, &Strategy {
+        action: symbol_short!("supply"),
+        pool: p.pool.clone(),
+        asset: p.usdc.address.clone(),
+        amount: 100 * UNIT,
+        request_type: RequestType::SupplyCollateral as u32,
+    });
+    assert_eq!(
+        p.vault.try_withdraw(&user, &(100 * UNIT - DEAD_SHARES), &0),
+        Err(Ok(VaultError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_fee_update_emits_event() {
+    let p = TuxedoFixture::new(&Env::default());
+
+    p.vault.set_platform_fee_bps(&300);
+
+    assert_eq!(
+        p.env.events().all(),
+        vec![
+            &p.env,
+            (
+                p.vault.address.clone(),
+                // Second vault event, after init
+                (symbol_short!("vault"), symbol_short!("fee"), 4u64).into_val(&p.env),
+                (200u32, 300u32).into_val(&p.env),
+            ),
+        ]
+    );
+}